@@ -176,6 +176,8 @@ impl From<LitseaError> for CoreError {
             LitseaError::InvalidData(_) => ErrorKind::Parse,
             LitseaError::InvalidInput(_) => ErrorKind::InvalidArgument,
             LitseaError::Unsupported(_) => ErrorKind::Unsupported,
+            // The model is fine; the language the caller passed is not.
+            LitseaError::LanguageMismatch { .. } => ErrorKind::InvalidArgument,
             LitseaError::PosLearnerNotSet => {
                 return Self::new(
                     ErrorKind::PosUnavailable,
@@ -231,6 +233,12 @@ mod tests {
 
         let unsupported = CoreError::from(LitseaError::Unsupported("nope"));
         assert_eq!(unsupported.kind(), ErrorKind::Unsupported);
+
+        let mismatch = CoreError::from(LitseaError::LanguageMismatch {
+            model: litsea::Language::Korean,
+            requested: litsea::Language::Japanese,
+        });
+        assert_eq!(mismatch.kind(), ErrorKind::InvalidArgument);
     }
 
     #[test]
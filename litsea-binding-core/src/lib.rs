@@ -7,7 +7,8 @@
 //! would reimplement the same plumbing: parsing a language name, deciding
 //! whether a model file is a segmentation model or a two-stage POS model,
 //! keeping a reusable [`litsea::SegmentBuffer`], attaching byte offsets to
-//! tokens, driving the trainers with a cancellation flag, and turning
+//! tokens, driving the trainers with a cancellation flag, forwarding their
+//! progress reports to the host's thread, and turning
 //! [`litsea::LitseaError`] into something the host language can raise. That
 //! logic lives here as plain Rust, so it can be unit-tested without any FFI
 //! toolchain.
@@ -47,6 +48,8 @@ pub mod error;
 pub mod language;
 pub mod model;
 #[cfg(not(target_arch = "wasm32"))]
+mod progress;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod runtime;
 pub mod segmenter;
 pub mod token;
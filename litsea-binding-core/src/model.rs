@@ -27,8 +27,11 @@ pub struct BuiltSegmenter {
 /// Builds a [`Segmenter`] from raw model bytes, detecting the model kind.
 ///
 /// A two-stage model produces a POS-capable segmenter; an AdaBoost-format
-/// model produces a segmentation-only one. Legacy joint POS models (removed
-/// in #190) are rejected with an actionable message.
+/// model produces a segmentation-only one. Either may be in the text or the
+/// binary form (see [`litsea::ModelFormat`]); a binary model compiled for
+/// `language` also skips the packed-table compilation. A model whose
+/// metadata records a different language is rejected, and legacy joint POS
+/// models (removed in #190) are rejected with an actionable message.
 ///
 /// # Arguments
 /// * `language` - The language the model was trained for. Character type
@@ -40,23 +43,24 @@ pub struct BuiltSegmenter {
 ///
 /// # Errors
 /// Returns an [`ErrorKind::Parse`] error if the bytes are not valid UTF-8 or
-/// the model content is malformed, or an [`ErrorKind::Model`] error if the
-/// file is a legacy joint POS model.
+/// the model content is malformed, an [`ErrorKind::InvalidArgument`] error
+/// if the model was trained for a language other than `language`, or an
+/// [`ErrorKind::Model`] error if the file is a legacy joint POS model.
 pub fn build_segmenter(language: Language, bytes: &[u8]) -> CoreResult<BuiltSegmenter> {
-    // `ModelKind::detect` only inspects the first line, so decoding just
-    // that avoids validating megabytes of UTF-8 twice (the learner's parser
-    // does its own decoding).
+    // Kind detection only inspects the header lines and the first content
+    // line, so checking just the first line avoids validating megabytes of
+    // UTF-8 twice (the learner's parser does its own decoding).
     let head_end = bytes.iter().position(|&b| b == b'\n').unwrap_or(bytes.len());
-    let head = std::str::from_utf8(&bytes[..head_end]).map_err(|e| {
+    std::str::from_utf8(&bytes[..head_end]).map_err(|e| {
         CoreError::new(ErrorKind::Parse, format!("model is not valid UTF-8: {}", e))
     })?;
 
-    match ModelKind::detect(head) {
+    match ModelKind::detect_bytes(bytes) {
         ModelKind::TwoStage => {
             let mut learner = TwoStageLearner::new();
             learner.load_model_from_reader(bytes)?;
             Ok(BuiltSegmenter {
-                segmenter: Segmenter::with_two_stage_learner(language, learner),
+                segmenter: Segmenter::try_with_two_stage_learner(language, learner)?,
                 has_pos: true,
             })
         }
@@ -64,7 +68,7 @@ pub fn build_segmenter(language: Language, bytes: &[u8]) -> CoreResult<BuiltSegm
             let mut learner = AdaBoost::default();
             learner.load_model_from_reader(bytes)?;
             Ok(BuiltSegmenter {
-                segmenter: Segmenter::with_learner(language, learner),
+                segmenter: Segmenter::try_with_learner(language, learner)?,
                 has_pos: false,
             })
         }
@@ -148,6 +152,37 @@ mod tests {
         assert!(built.segmenter.segment_with_pos("すもも").is_ok());
     }
 
+    #[test]
+    fn test_build_binary_models() {
+        for (language, name, has_pos) in [
+            (Language::Japanese, "japanese.model", false),
+            (Language::Korean, "korean_pos.model", true),
+        ] {
+            let text = read_model_file(&model_path(name)).unwrap();
+            let from_text = build_segmenter(language, &text).unwrap();
+            let mut binary = Vec::new();
+            if has_pos {
+                let mut learner = litsea::TwoStageLearner::new();
+                learner.load_model_from_reader(text.as_slice()).unwrap();
+                learner.save_binary_model_to_writer(&mut binary, Some(language)).unwrap();
+            } else {
+                from_text
+                    .segmenter
+                    .learner()
+                    .save_binary_model_to_writer(&mut binary, Some(language))
+                    .unwrap();
+            }
+            let from_binary = build_segmenter(language, &binary).unwrap();
+            assert_eq!(from_binary.has_pos, has_pos);
+            for sentence in ["これはテストです。", "안녕하세요. 반갑습니다."] {
+                assert_eq!(
+                    from_binary.segmenter.segment(sentence),
+                    from_text.segmenter.segment(sentence)
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_joint_pos_model_is_rejected() {
         // A bare integer on the first line is the joint (Averaged
@@ -161,6 +196,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_language_mismatch_is_rejected() {
+        let bytes = b"#language\tkorean\nUW4:a\t10\n-5\n";
+        assert!(build_segmenter(Language::Korean, bytes).is_ok());
+        let error = build_segmenter(Language::Japanese, bytes).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+    }
+
     #[test]
     fn test_invalid_utf8_is_a_parse_error() {
         let error = build_segmenter(Language::Japanese, &[0xff, 0xfe, b'\n']).unwrap_err();
@@ -0,0 +1,144 @@
+//! Forwarding training progress to host-language callbacks.
+//!
+//! `litsea`'s trainers hand their [`TrainingProgress`] reports to a
+//! `Send + 'static` [`ProgressObserver`] on the training thread, but a host
+//! callback (a Python callable, a Ruby proc, a PHP closure) can only be
+//! called from a thread the host owns, often with its interpreter lock
+//! held. [`forward_progress`] therefore runs the training on a worker
+//! thread and hands every report to the calling thread, where the host
+//! callback runs; the callback needs to be neither `Send` nor `'static`.
+//!
+//! Not available on wasm32, which has no threads (and no trainers).
+
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use litsea::{ProgressObserver, TrainingProgress};
+
+use crate::cancel::CancelToken;
+
+/// How long the calling thread waits for a report before checking whether
+/// the worker has finished.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Runs `train` on a worker thread and calls `on_progress` on the calling
+/// thread for every report the training makes.
+///
+/// When `on_progress` returns `false` (typically because the host callback
+/// raised), `cancel` is cancelled, so training stops at its next check and
+/// writes the partially trained model as on any cancellation; later reports
+/// are dropped.
+///
+/// # Arguments
+/// * `cancel` - The token the training observes.
+/// * `train` - Runs the training, reporting to the observer it is given.
+/// * `on_progress` - Receives every report; returns whether to continue.
+///
+/// # Returns
+/// Whatever `train` returns.
+///
+/// # Panics
+/// Resumes the worker's panic, if it panicked.
+pub(crate) fn forward_progress<T, F>(
+    cancel: &CancelToken,
+    train: F,
+    on_progress: &mut dyn FnMut(&TrainingProgress) -> bool,
+) -> T
+where
+    T: Send,
+    F: FnOnce(Box<dyn ProgressObserver>) -> T + Send,
+{
+    let (sender, receiver) = mpsc::channel();
+    let observer = move |progress: &TrainingProgress| {
+        // The receiver is only dropped after the worker has finished.
+        let _ = sender.send(progress.clone());
+    };
+    thread::scope(|scope| {
+        let worker = scope.spawn(move || train(Box::new(observer)));
+        let mut listening = true;
+        let mut deliver = |progress: TrainingProgress| {
+            if listening && !on_progress(&progress) {
+                listening = false;
+                cancel.cancel();
+            }
+        };
+        // The trainer may keep the observer (and its sender) after training,
+        // so the channel does not necessarily disconnect: poll the worker.
+        loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(progress) => deliver(progress),
+                Err(RecvTimeoutError::Timeout) if !worker.is_finished() => {}
+                Err(_) => break,
+            }
+        }
+        receiver.try_iter().for_each(&mut deliver);
+        worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use litsea::TrainingStage;
+
+    use super::*;
+
+    fn report(iteration: usize) -> TrainingProgress {
+        TrainingProgress {
+            stage: TrainingStage::Perceptron,
+            iteration,
+            total: 3,
+            error_rate: 0.0,
+            updates: 0,
+            dev_accuracy: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_reports_arrive_on_the_calling_thread_in_order() {
+        let caller = thread::current().id();
+        let mut seen = Vec::new();
+        // The observer outlives the training, as a trainer would keep it.
+        let _observer = forward_progress(
+            &CancelToken::new(),
+            |mut observer| {
+                (1..=3).for_each(|i| observer.on_progress(&report(i)));
+                observer
+            },
+            &mut |progress| {
+                assert_eq!(thread::current().id(), caller);
+                seen.push(progress.iteration);
+                true
+            },
+        );
+        assert_eq!(seen, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_returning_false_cancels_training() {
+        let cancel = CancelToken::new();
+        let mut calls = 0;
+        let stopped = forward_progress(
+            &cancel,
+            |mut observer| {
+                observer.on_progress(&report(1));
+                // Wait for the cancellation, as a training loop would.
+                while cancel.running_flag().load(Ordering::SeqCst) {
+                    thread::yield_now();
+                }
+                observer.on_progress(&report(2));
+                true
+            },
+            &mut |_| {
+                calls += 1;
+                false
+            },
+        );
+        assert!(stopped);
+        assert!(cancel.is_cancelled());
+        assert_eq!(calls, 1, "reports after the cancellation are dropped");
+    }
+}
@@ -4,9 +4,9 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex, PoisonError};
 
-use litsea::{Language, SegmentBuffer, Segmenter};
+use litsea::{Language, PatternSet, SegmentBuffer, Segmenter, UserDictionary};
 
-use crate::error::CoreResult;
+use crate::error::{CoreError, CoreResult, ErrorKind};
 use crate::model::{BuiltSegmenter, build_segmenter, read_model_uri};
 use crate::token::TokenView;
 
@@ -131,6 +131,86 @@ impl CoreSegmenter {
         }
     }
 
+    /// Attaches a user dictionary whose words always come out as single
+    /// tokens (see [`Segmenter::with_user_dictionary`]).
+    ///
+    /// # Arguments
+    /// * `dictionary` - The words to keep whole; replaces any dictionary
+    ///   attached earlier.
+    ///
+    /// # Returns
+    /// The segmenter with the dictionary attached.
+    ///
+    /// # Errors
+    /// Returns an [`crate::ErrorKind::Runtime`] error if the underlying
+    /// [`Segmenter`] is already shared through [`segmenter`](Self::segmenter).
+    pub fn with_user_dictionary(self, dictionary: UserDictionary) -> CoreResult<Self> {
+        self.map_segmenter(|segmenter| segmenter.with_user_dictionary(dictionary))
+    }
+
+    /// Attaches a pattern set whose matches (URLs, e-mail addresses,
+    /// numbers, user regexes) always come out as single tokens (see
+    /// [`Segmenter::with_patterns`]).
+    ///
+    /// # Arguments
+    /// * `patterns` - The patterns to keep whole; replaces any set attached
+    ///   earlier.
+    ///
+    /// # Returns
+    /// The segmenter with the patterns attached.
+    ///
+    /// # Errors
+    /// Returns an [`crate::ErrorKind::Runtime`] error if the underlying
+    /// [`Segmenter`] is already shared through [`segmenter`](Self::segmenter).
+    pub fn with_patterns(self, patterns: PatternSet) -> CoreResult<Self> {
+        self.map_segmenter(|segmenter| segmenter.with_patterns(patterns))
+    }
+
+    /// Offsets the boundary decision threshold (see
+    /// [`Segmenter::with_threshold`]): positive values give coarser
+    /// tokens, negative values finer ones.
+    ///
+    /// # Arguments
+    /// * `threshold` - The offset; `0.0` is the model's own decision rule.
+    ///
+    /// # Returns
+    /// The segmenter with the offset applied.
+    ///
+    /// # Errors
+    /// Returns an [`crate::ErrorKind::Runtime`] error if the underlying
+    /// [`Segmenter`] is already shared through [`segmenter`](Self::segmenter).
+    pub fn with_threshold(self, threshold: f64) -> CoreResult<Self> {
+        self.map_segmenter(|segmenter| segmenter.with_threshold(threshold))
+    }
+
+    /// Rebuilds the wrapped [`Segmenter`] through one of its consuming
+    /// `with_*` configuration methods.
+    ///
+    /// Configuration happens before the segmenter is shared, so the `Arc`
+    /// normally has a single owner here; a clone handed out by
+    /// [`segmenter`](Self::segmenter) would make the change invisible to
+    /// its holder, which is reported instead of silently ignored.
+    ///
+    /// # Arguments
+    /// * `f` - The configuration to apply.
+    ///
+    /// # Returns
+    /// The reconfigured [`CoreSegmenter`].
+    ///
+    /// # Errors
+    /// Returns an [`crate::ErrorKind::Runtime`] error if the segmenter is
+    /// shared.
+    fn map_segmenter(self, f: impl FnOnce(Segmenter) -> Segmenter) -> CoreResult<Self> {
+        let segmenter = Arc::try_unwrap(self.segmenter).map_err(|_| {
+            CoreError::runtime("cannot reconfigure a segmenter that is already shared")
+        })?;
+        Ok(Self {
+            segmenter: Arc::new(f(segmenter)),
+            buffer: self.buffer,
+            has_pos: self.has_pos,
+        })
+    }
+
     /// Returns the language this segmenter was built for.
     ///
     /// # Returns
@@ -221,6 +301,28 @@ impl CoreSegmenter {
         })
     }
 
+    /// Splits a sentence into tokens carrying byte offsets and boundary
+    /// scores (see [`Segmenter::segment_with_scores`]).
+    ///
+    /// # Arguments
+    /// * `text` - The sentence to segment.
+    ///
+    /// # Returns
+    /// The tokens with `pos` set to `None` and `score` set.
+    #[must_use]
+    pub fn segment_with_scores(&self, text: &str) -> Vec<TokenView> {
+        self.with_buffer(|segmenter, buffer| {
+            let (ranges, scores) = segmenter.segment_with_scores_into(text, buffer);
+            ranges
+                .iter()
+                .zip(scores)
+                .map(|(&(start, end), &score)| {
+                    TokenView::new(&text[start..end], start, end, None).with_score(score)
+                })
+                .collect()
+        })
+    }
+
     /// Splits a sentence into tokens and tags each with a UPOS tag.
     ///
     /// # Arguments
@@ -279,6 +381,68 @@ impl CoreSegmenter {
             .collect()
     }
 
+    /// Learns from a corrected segmentation, so later calls segment the
+    /// sentence as corrected (see [`Segmenter::learn`]).
+    ///
+    /// # Arguments
+    /// * `sentence` - The sentence, as passed to [`segment`](Self::segment).
+    /// * `words` - Its corrected segmentation, spelling out `sentence`.
+    /// * `margin` - The score every position must reach, in the units of
+    ///   the model's weights.
+    ///
+    /// # Returns
+    /// The number of weight updates applied.
+    ///
+    /// # Errors
+    /// Returns an [`crate::ErrorKind::InvalidArgument`] error if the words
+    /// do not spell out the sentence or `margin` is not positive, or an
+    /// [`crate::ErrorKind::Runtime`] error if the underlying [`Segmenter`]
+    /// is shared through [`segmenter`](Self::segmenter).
+    pub fn learn<S: AsRef<str>>(
+        &mut self,
+        sentence: &str,
+        words: &[S],
+        margin: f64,
+    ) -> CoreResult<usize> {
+        let words: Vec<&str> = words.iter().map(AsRef::as_ref).collect();
+        let segmenter = Arc::get_mut(&mut self.segmenter).ok_or_else(|| {
+            CoreError::runtime("cannot update a segmenter that is already shared")
+        })?;
+        Ok(segmenter.learn(sentence, &words, margin)?)
+    }
+
+    /// Saves the segmentation model, with any [`learn`](Self::learn)
+    /// updates, so it can be loaded again.
+    ///
+    /// # Arguments
+    /// * `path` - The file to write.
+    /// * `binary` - Whether to write the binary model format (with packed
+    ///   tables compiled for this segmenter's language) instead of text.
+    ///
+    /// # Returns
+    /// Nothing; the model is on disk.
+    ///
+    /// # Errors
+    /// Returns an [`crate::ErrorKind::Unsupported`] error for a two-stage
+    /// POS model, whose tagging half is kept only in compiled form, or an
+    /// I/O error if the file cannot be written.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_model(&self, path: &Path, binary: bool) -> CoreResult<()> {
+        if self.has_pos {
+            return Err(CoreError::new(
+                ErrorKind::Unsupported,
+                "cannot save a two-stage POS model from a segmenter",
+            ));
+        }
+        let learner = self.segmenter.learner();
+        if binary {
+            learner.save_binary_model(path, Some(self.language()))?;
+        } else {
+            learner.save_model(path)?;
+        }
+        Ok(())
+    }
+
     /// Runs `f` with the segmenter and the locked scratch buffer.
     ///
     /// A panic in a previous call may have poisoned the mutex; the buffer
@@ -383,6 +547,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_segment_with_scores_matches_segment_tokens() {
+        for (language, model, sentence) in segmentation_cases() {
+            let core = CoreSegmenter::from_path(language, &model_path(model)).unwrap();
+            let scored = core.segment_with_scores(sentence);
+            let plain = core.segment_tokens(sentence);
+            assert_eq!(scored.len(), plain.len(), "{language}");
+            for (scored, plain) in scored.iter().zip(&plain) {
+                assert_eq!(
+                    (scored.byte_start, scored.byte_end),
+                    (plain.byte_start, plain.byte_end)
+                );
+                assert_eq!(plain.score, None);
+                assert!(scored.confidence().is_some_and(|c| c >= 0.0), "{language}: {scored:?}");
+            }
+            assert_eq!(scored[0].score.and_then(|score| score.boundary), None);
+        }
+    }
+
     #[test]
     fn test_pos_offsets_slice_the_input() {
         // Korean is the interesting case: its corpus preserves spaces, so a
@@ -465,6 +648,85 @@ mod tests {
         assert_eq!(from_path.segment(sentence), from_uri.segment(sentence));
     }
 
+    #[test]
+    fn test_user_dictionary_keeps_words_whole() {
+        let core =
+            CoreSegmenter::from_path(Language::Japanese, &model_path("japanese.model")).unwrap();
+        let sentence = "東京都に住んでいます。";
+        assert!(!core.segment(sentence).contains(&"住んでいます".to_string()));
+
+        let dictionary: UserDictionary = ["住んでいます"].into_iter().collect();
+        let core = core.with_user_dictionary(dictionary).unwrap();
+        let tokens = core.segment_tokens(sentence);
+        assert!(tokens.iter().any(|token| token.surface == "住んでいます"), "{tokens:?}");
+        assert_eq!(tokens.last().map(|token| token.byte_end), Some(sentence.len()));
+    }
+
+    #[test]
+    fn test_patterns_keep_matches_whole() {
+        let core =
+            CoreSegmenter::from_path(Language::Japanese, &model_path("japanese.model")).unwrap();
+        let sentence = "詳細はhttps://example.com/docsを参照。";
+        let core = core.with_patterns(PatternSet::builtin()).unwrap();
+        let tokens = core.segment_tokens(sentence);
+        assert!(
+            tokens.iter().any(|token| token.surface == "https://example.com/docs"),
+            "{tokens:?}"
+        );
+        assert_eq!(tokens.last().map(|token| token.byte_end), Some(sentence.len()));
+    }
+
+    #[test]
+    fn test_user_dictionary_rejects_shared_segmenter() {
+        let core =
+            CoreSegmenter::from_path(Language::Japanese, &model_path("japanese.model")).unwrap();
+        let _shared = Arc::clone(core.segmenter());
+        let error = core.with_user_dictionary(UserDictionary::new()).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Runtime);
+    }
+
+    #[test]
+    fn test_threshold_makes_segmentation_coarser() {
+        let core =
+            CoreSegmenter::from_path(Language::Japanese, &model_path("japanese.model")).unwrap();
+        let sentence = "東京都に住んでいます。";
+        assert!(core.segment(sentence).len() > 1);
+
+        let core = core.with_threshold(f64::INFINITY).unwrap();
+        assert_eq!(core.segmenter().threshold(), f64::INFINITY);
+        assert_eq!(core.segment(sentence), vec![sentence]);
+    }
+
+    #[test]
+    fn test_learn_and_save_model() {
+        let sentence = "きゃりーぱみゅぱみゅが歌う。";
+        let words = ["きゃりーぱみゅぱみゅ", "が", "歌う", "。"];
+        let mut core =
+            CoreSegmenter::from_path(Language::Japanese, &model_path("japanese.model")).unwrap();
+        assert_ne!(core.segment(sentence), words);
+        assert!(core.learn(sentence, &words, 1.0).unwrap() > 0);
+        assert_eq!(core.segment(sentence), words);
+
+        let dir = tempfile::tempdir().unwrap();
+        for binary in [false, true] {
+            let path = dir.path().join(if binary { "learned.bin" } else { "learned.model" });
+            core.save_model(&path, binary).unwrap();
+            let reloaded = CoreSegmenter::from_path(Language::Japanese, &path).unwrap();
+            assert_eq!(reloaded.segment(sentence), words);
+        }
+
+        let error = core.learn(sentence, &["きゃりー"], 1.0).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::InvalidArgument);
+        let _shared = Arc::clone(core.segmenter());
+        let error = core.learn(sentence, &words, 1.0).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Runtime);
+
+        let pos =
+            CoreSegmenter::from_path(Language::Korean, &model_path("korean_pos.model")).unwrap();
+        let error = pos.save_model(&dir.path().join("pos.model"), false).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Unsupported);
+    }
+
     #[test]
     fn test_shared_across_threads() {
         let core = Arc::new(
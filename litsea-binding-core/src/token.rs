@@ -1,6 +1,6 @@
 //! FFI-independent token representation.
 
-use litsea::Upos;
+use litsea::{TokenScore, Upos};
 
 /// A segmented token as the bindings expose it.
 ///
@@ -10,7 +10,7 @@ use litsea::Upos;
 /// (highlighting, span alignment), and they are recoverable because the
 /// tokens tile the input exactly, so [`TokenView`] carries them for both
 /// modes.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TokenView {
     /// The token's surface form.
     pub surface: String,
@@ -20,6 +20,9 @@ pub struct TokenView {
     pub byte_end: usize,
     /// The UPOS tag, or `None` when the segmenter has no POS model.
     pub pos: Option<Upos>,
+    /// The token's boundary scores, or `None` unless it came from
+    /// [`CoreSegmenter::segment_with_scores`](crate::CoreSegmenter::segment_with_scores).
+    pub score: Option<TokenScore>,
 }
 
 impl TokenView {
@@ -44,9 +47,33 @@ impl TokenView {
             byte_start,
             byte_end,
             pos,
+            score: None,
         }
     }
 
+    /// Attaches boundary scores to the token.
+    ///
+    /// # Arguments
+    /// * `score` - The token's [`TokenScore`].
+    ///
+    /// # Returns
+    /// The token with `score` set.
+    #[must_use]
+    pub fn with_score(mut self, score: TokenScore) -> Self {
+        self.score = Some(score);
+        self
+    }
+
+    /// Returns the token's confidence (see [`TokenScore::confidence`]).
+    ///
+    /// # Returns
+    /// The confidence, or `None` when the token carries no scores. This is
+    /// the single number the bindings expose as `score`.
+    #[must_use]
+    pub fn confidence(&self) -> Option<f64> {
+        self.score.map(|score| score.confidence())
+    }
+
     /// Returns the UPOS tag as its canonical uppercase name.
     ///
     /// # Returns
@@ -77,4 +104,16 @@ mod tests {
         let token = TokenView::new("すもも", 0, 9, None);
         assert_eq!(&sentence[token.byte_start..token.byte_end], token.surface);
     }
+
+    #[test]
+    fn test_confidence() {
+        let token = TokenView::new("すもも", 0, 9, None);
+        assert_eq!(token.confidence(), None);
+
+        let score = TokenScore {
+            boundary: Some(2.5),
+            inner: Some(-0.5),
+        };
+        assert_eq!(token.with_score(score).confidence(), Some(0.5));
+    }
 }
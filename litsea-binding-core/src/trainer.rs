@@ -13,11 +13,12 @@ use std::str::FromStr;
 
 use litsea::{
     BinaryMetrics, Extractor, Language, MulticlassMetrics, PerceptronTrainer, Trainer,
-    TwoStageFeatureSet, TwoStageMetrics, TwoStageTrainer,
+    TrainingProgress, TwoStageFeatureSet, TwoStageMetrics, TwoStageTrainer,
 };
 
 use crate::cancel::CancelToken;
 use crate::error::{CoreError, CoreResult};
+use crate::progress::forward_progress;
 
 /// How a training corpus is laid out.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -82,6 +83,21 @@ impl CoreExtractor {
         }
     }
 
+    /// Spreads extraction over worker threads.
+    ///
+    /// The output files are byte-identical to a single-threaded run.
+    ///
+    /// # Arguments
+    /// * `threads` - Number of worker threads (`0` is treated as `1`).
+    ///
+    /// # Returns
+    /// The extractor with the thread count set.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.inner = self.inner.with_threads(threads);
+        self
+    }
+
     /// Extracts boundary-classification features.
     ///
     /// # Arguments
@@ -224,6 +240,43 @@ impl CoreTrainer {
     pub fn train(&mut self, cancel: &CancelToken, model_path: &Path) -> CoreResult<BinaryMetrics> {
         self.inner.train(cancel.running_flag(), model_path).map_err(CoreError::from)
     }
+
+    /// Trains the model like [`CoreTrainer::train`], reporting every
+    /// boosting iteration to `on_progress`.
+    ///
+    /// Training runs on a worker thread; `on_progress` runs on the calling
+    /// thread, so it may call into the host language.
+    ///
+    /// # Arguments
+    /// * `cancel` - Cancellation token checked once per boosting iteration.
+    /// * `model_path` - Path of the model file to write.
+    /// * `on_progress` - Receives every report; returning `false` cancels
+    ///   `cancel`, stopping training as a cancellation does.
+    ///
+    /// # Returns
+    /// The training metrics.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the model cannot be written.
+    pub fn train_with_progress(
+        &mut self,
+        cancel: &CancelToken,
+        model_path: &Path,
+        on_progress: &mut dyn FnMut(&TrainingProgress) -> bool,
+    ) -> CoreResult<BinaryMetrics> {
+        let inner = &mut self.inner;
+        let trained = forward_progress(
+            cancel,
+            |observer| {
+                inner.set_progress(Some(observer));
+                let trained = inner.train(cancel.running_flag(), model_path);
+                inner.set_progress(None);
+                trained
+            },
+            on_progress,
+        );
+        trained.map_err(CoreError::from)
+    }
 }
 
 /// Trains a label-agnostic Averaged Perceptron model.
@@ -251,6 +304,20 @@ impl CorePerceptronTrainer {
         })
     }
 
+    /// Shuffles the training instances every epoch with a seeded generator.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the shuffle; the same seed and inputs produce a
+    ///   byte-identical model file.
+    ///
+    /// # Returns
+    /// The trainer with shuffling enabled.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.inner = self.inner.with_seed(seed);
+        self
+    }
+
     /// Loads an existing model to continue training from it.
     ///
     /// # Arguments
@@ -298,6 +365,43 @@ impl CorePerceptronTrainer {
     ) -> CoreResult<MulticlassMetrics> {
         self.inner.train(cancel.running_flag(), model_path).map_err(CoreError::from)
     }
+
+    /// Trains the model like [`CorePerceptronTrainer::train`], reporting
+    /// every epoch to `on_progress`.
+    ///
+    /// Training runs on a worker thread; `on_progress` runs on the calling
+    /// thread, so it may call into the host language.
+    ///
+    /// # Arguments
+    /// * `cancel` - Cancellation token checked per epoch and per instance.
+    /// * `model_path` - Path of the model file to write.
+    /// * `on_progress` - Receives every report; returning `false` cancels
+    ///   `cancel`, stopping training as a cancellation does.
+    ///
+    /// # Returns
+    /// The training metrics.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the model cannot be written.
+    pub fn train_with_progress(
+        &mut self,
+        cancel: &CancelToken,
+        model_path: &Path,
+        on_progress: &mut dyn FnMut(&TrainingProgress) -> bool,
+    ) -> CoreResult<MulticlassMetrics> {
+        let inner = &mut self.inner;
+        let trained = forward_progress(
+            cancel,
+            |observer| {
+                inner.set_progress(Some(observer));
+                let trained = inner.train(cancel.running_flag(), model_path);
+                inner.set_progress(None);
+                trained
+            },
+            on_progress,
+        );
+        trained.map_err(CoreError::from)
+    }
 }
 
 /// Trains a two-stage segmentation + POS model.
@@ -333,6 +437,21 @@ impl CoreTwoStageTrainer {
         })
     }
 
+    /// Shuffles the training instances of both stages every epoch with a
+    /// seeded generator.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the shuffle; the same seed and inputs produce a
+    ///   byte-identical model file.
+    ///
+    /// # Returns
+    /// The trainer with shuffling enabled.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.inner = self.inner.map(|inner| inner.with_seed(seed));
+        self
+    }
+
     /// Returns whether this trainer can still be used.
     ///
     /// # Returns
@@ -363,13 +482,58 @@ impl CoreTwoStageTrainer {
         cancel: &CancelToken,
         model_path: &Path,
     ) -> CoreResult<TwoStageMetrics> {
-        let trainer = self.inner.take().ok_or_else(|| {
+        let trainer = self.take()?;
+        trainer.train(cancel.running_flag(), model_path).map_err(CoreError::from)
+    }
+
+    /// Trains both stages like [`CoreTwoStageTrainer::train`], reporting
+    /// every epoch of either stage to `on_progress`.
+    ///
+    /// Training runs on a worker thread; `on_progress` runs on the calling
+    /// thread, so it may call into the host language.
+    ///
+    /// # Arguments
+    /// * `cancel` - Cancellation token, passed to both stages in turn.
+    /// * `model_path` - Path of the model file to write.
+    /// * `on_progress` - Receives every report; returning `false` cancels
+    ///   `cancel`, stopping training as a cancellation does.
+    ///
+    /// # Returns
+    /// The metrics of both stages.
+    ///
+    /// # Errors
+    /// Returns an [`crate::ErrorKind::InvalidArgument`] error if the trainer
+    /// has already been used, or an I/O error if the model cannot be
+    /// written.
+    pub fn train_with_progress(
+        &mut self,
+        cancel: &CancelToken,
+        model_path: &Path,
+        on_progress: &mut dyn FnMut(&TrainingProgress) -> bool,
+    ) -> CoreResult<TwoStageMetrics> {
+        let mut trainer = self.take()?;
+        let trained = forward_progress(
+            cancel,
+            |observer| {
+                trainer.set_progress(Some(observer));
+                trainer.train(cancel.running_flag(), model_path)
+            },
+            on_progress,
+        );
+        trained.map_err(CoreError::from)
+    }
+
+    /// Takes the wrapped trainer for a training run.
+    ///
+    /// # Errors
+    /// Returns an [`crate::ErrorKind::InvalidArgument`] error if the trainer
+    /// has already been used.
+    fn take(&mut self) -> CoreResult<TwoStageTrainer> {
+        self.inner.take().ok_or_else(|| {
             CoreError::invalid_argument(
                 "this two-stage trainer has already been used; create a new one to train again",
             )
-        })?;
-
-        trainer.train(cancel.running_flag(), model_path).map_err(CoreError::from)
+        })
     }
 }
 
@@ -377,6 +541,7 @@ impl CoreTwoStageTrainer {
 mod tests {
     use std::fs;
 
+    use litsea::TrainingStage;
     use tempfile::TempDir;
 
     use super::*;
@@ -463,6 +628,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_threaded_extraction_writes_identical_files() {
+        let dir = TempDir::new().unwrap();
+        let corpus = write_pos_corpus(&dir);
+
+        let sequential = dir.path().join("sequential");
+        let threaded = dir.path().join("threaded");
+        CoreExtractor::new(Language::Japanese)
+            .extract_two_stage(
+                &corpus,
+                &sequential,
+                TwoStageFeatureSet::Fast,
+                CorpusFormat::PlainText,
+            )
+            .unwrap();
+        CoreExtractor::new(Language::Japanese)
+            .with_threads(3)
+            .extract_two_stage(
+                &corpus,
+                &threaded,
+                TwoStageFeatureSet::Fast,
+                CorpusFormat::PlainText,
+            )
+            .unwrap();
+
+        for suffix in ["stage1", "stage2", "lexicon"] {
+            assert_eq!(
+                fs::read(sequential.with_extension(suffix)).unwrap(),
+                fs::read(threaded.with_extension(suffix)).unwrap(),
+                "{suffix} differs"
+            );
+        }
+    }
+
     #[test]
     fn test_cancelled_training_still_writes_a_model() {
         let dir = TempDir::new().unwrap();
@@ -549,6 +748,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_two_stage_training_reports_progress() {
+        let dir = TempDir::new().unwrap();
+        let corpus = write_pos_corpus(&dir);
+        let prefix = dir.path().join("features");
+        let model = dir.path().join("two_stage.model");
+
+        CoreExtractor::new(Language::Japanese)
+            .extract_two_stage(
+                &corpus,
+                &prefix,
+                TwoStageFeatureSet::default(),
+                CorpusFormat::PlainText,
+            )
+            .unwrap();
+
+        let mut seen = Vec::new();
+        CoreTwoStageTrainer::new(2, 0.99, &prefix)
+            .unwrap()
+            .train_with_progress(&CancelToken::new(), &model, &mut |progress| {
+                seen.push((progress.stage, progress.iteration));
+                true
+            })
+            .unwrap();
+
+        let (boundary, tagging) = (TrainingStage::Boundary, TrainingStage::Tagging);
+        assert_eq!(seen, [(boundary, 1), (boundary, 2), (tagging, 1), (tagging, 2)]);
+    }
+
+    #[test]
+    fn test_stopping_progress_cancels_training() {
+        let dir = TempDir::new().unwrap();
+        let corpus = write_corpus(&dir);
+        let features = dir.path().join("features.txt");
+        let model = dir.path().join("stopped.model");
+        CoreExtractor::new(Language::Japanese)
+            .extract(&corpus, &features, CorpusFormat::PlainText, false)
+            .unwrap();
+
+        let cancel = CancelToken::new();
+        let mut calls = 0;
+        let metrics = CoreTrainer::new(0.0, 1000, &features)
+            .unwrap()
+            .train_with_progress(&cancel, &model, &mut |_| {
+                calls += 1;
+                false
+            })
+            .unwrap();
+
+        // Like a cancellation, stopping still saves the partial model.
+        assert!(cancel.is_cancelled());
+        assert_eq!(calls, 1);
+        assert!(metrics.num_instances > 0);
+        assert!(model.exists());
+    }
+
     #[test]
     fn test_perceptron_trainer_trains_from_two_stage_features() {
         let dir = TempDir::new().unwrap();
@@ -575,6 +830,28 @@ mod tests {
         assert!(model.exists());
     }
 
+    #[test]
+    fn test_perceptron_trainer_seed_is_reproducible() {
+        let dir = TempDir::new().unwrap();
+        let corpus = write_corpus(&dir);
+        let features = dir.path().join("features.txt");
+        CoreExtractor::new(Language::Japanese)
+            .extract(&corpus, &features, CorpusFormat::PlainText, false)
+            .unwrap();
+
+        let first = dir.path().join("first.model");
+        let second = dir.path().join("second.model");
+        for model in [&first, &second] {
+            CorePerceptronTrainer::new(3, &features)
+                .unwrap()
+                .with_seed(7)
+                .train(&CancelToken::new(), model)
+                .unwrap();
+        }
+
+        assert_eq!(fs::read(&first).unwrap(), fs::read(&second).unwrap());
+    }
+
     #[test]
     fn test_parse_feature_set() {
         assert_eq!(parse_feature_set("FAST").unwrap(), TwoStageFeatureSet::Fast);
@@ -1,29 +1,40 @@
 //! Command-line interface for litsea.
 //!
-//! Provides four subcommands: `extract` (turn a corpus into training
+//! Provides nine subcommands: `extract` (turn a corpus into training
 //! features, or, with `--pos`, into the three feature files consumed
 //! by two-stage POS training), `train` (train an AdaBoost segmentation
-//! model, or, with `--pos`, a two-stage boundary+lexicon POS model, or,
-//! with `--perceptron`, a generic Averaged Perceptron over opaque labels —
-//! the training step of the bundled segmentation models' collapse recipe),
+//! model, or, with `--boundary-perceptron`, a boundary perceptron collapsed
+//! to the same format — how the bundled segmentation models are trained —
+//! or, with `--pos`, a two-stage boundary+lexicon POS model, or, with
+//! `--perceptron`, a generic Averaged Perceptron over opaque labels),
 //! `segment` (segment sentences from standard input with a trained model),
-//! and `evaluate` (measure held-out quality against a gold corpus).
+//! `evaluate` (measure held-out quality against a gold corpus), `convert`
+//! (rewrite a model between the text and binary formats), `inspect`
+//! (describe a model's contents), `prune` (drop low-weight features),
+//! `select` (pick the sentences most worth annotating from a raw corpus),
+//! and `distill` (train a fast tag-free model on a raw corpus labeled by an
+//! existing model).
 
 use std::error::Error;
 use std::fs::File;
-use std::io::{self, BufRead, Write};
-use std::path::PathBuf;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-use clap::{Args, Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand};
 
-use litsea::version;
 use litsea::{
-    AdaBoost, Extractor, Language, LitseaError, PerceptronTrainer, SegmentBuffer, Segmenter,
-    Trainer, TwoStageFeatureSet, TwoStageLearner, TwoStageTrainer, evaluation,
+    AdaBoost, AveragedPerceptron, BoundaryPerceptronTrainer, BuiltinPattern, Checkpoint,
+    CorpusFormat, Extractor, FeatureCutoff, Language, LitseaError, ModelFormat, ModelKind,
+    ModelMetadata, Normalization, PatternSet, PerceptronTrainer, ProgressObserver, PruneStrategy,
+    SegmentBuffer, Segmenter, SentenceSelector, TemplateCutoff, Trainer, TrainingProgress,
+    TrainingStage, TwoStageFeatureSet, TwoStageLearner, TwoStageTrainer, Uncertainty,
+    UserDictionary, evaluation, sentence,
 };
+use litsea::{metadata, version};
 
 /// Arguments for the extract command.
 #[derive(Debug, Args)]
@@ -33,9 +44,12 @@ struct ExtractArgs {
     #[arg(short, long, default_value = "japanese", value_parser = Language::from_str)]
     language: Language,
 
-    /// Corpus format: "space" (space-separated words) or "tsv" (tab-separated
-    /// tokens; a token may be a literal space, preserving original spacing)
-    #[arg(long, default_value = "space", value_parser = ["space", "tsv"])]
+    /// Corpus format: "space" (space-separated words), "tsv" (tab-separated
+    /// tokens; a token may be a literal space, preserving original spacing),
+    /// or "partial" (KyTea-style partial annotation: one delimiter between
+    /// every two characters, "|" a boundary, "-" none, " " unannotated; only
+    /// the annotated positions become training rows)
+    #[arg(long, default_value = "space", value_parser = ["space", "tsv", "partial"])]
     format: String,
 
     /// Extract two-stage POS training features (issue #147) from a
@@ -44,7 +58,10 @@ struct ExtractArgs {
     /// features), and .lexicon. With --format tsv the corpus is instead a
     /// space-preserving TSV of "word/POS" tokens (issue #198), so the model
     /// trains on the spacing its input actually has -- use this for
-    /// space-delimited languages such as Korean and English.
+    /// space-delimited languages such as Korean and English. With --format
+    /// partial, a fully annotated word may be tagged with a "/POS" suffix
+    /// after its last character (e.g. "こ-れ/PRON|は|テ ス ト"); only the
+    /// tagged words train stage 2.
     #[arg(long)]
     pos: bool,
 
@@ -60,6 +77,25 @@ struct ExtractArgs {
     #[arg(long)]
     tag_free: bool,
 
+    /// Unicode normalization applied to every corpus word before feature
+    /// extraction: "none" (default), "nfc", or "nfkc". Pass the same value
+    /// to `train --normalize` so the model declares it
+    #[arg(long, value_name = "FORM", default_value = "none", value_parser = Normalization::from_str)]
+    normalize: Normalization,
+
+    /// Word list (one word per line, as for `segment --user-dict`) whose
+    /// matches are extracted as dictionary features: whether a word ends
+    /// before, starts at, or spans each character, and its length. Pass
+    /// the same file to `train --dictionary` so the model stores it
+    #[arg(long, value_name = "FILE")]
+    dictionary: Option<PathBuf>,
+
+    /// Number of worker threads to extract with. The corpus lines are
+    /// split across the workers and the results written in corpus order,
+    /// so the output is byte-identical to the default single thread
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: u16,
+
     /// Path to the input corpus file (one pre-segmented sentence per line)
     corpus_file: PathBuf,
     /// Path to the output features file (with --pos, the prefix for
@@ -72,12 +108,12 @@ struct ExtractArgs {
 #[command(about = "Train a segmenter")]
 struct TrainArgs {
     /// Early-stopping threshold for AdaBoost training. Ignored with
-    /// `--perceptron` or `--pos`
+    /// `--boundary-perceptron`, `--perceptron`, or `--pos`
     #[arg(short, long, default_value = "0.01")]
     threshold: f64,
 
     /// Maximum number of AdaBoost boosting iterations. Ignored with
-    /// `--perceptron` or `--pos`
+    /// `--boundary-perceptron`, `--perceptron`, or `--pos`
     #[arg(short = 'i', long, default_value = "100")]
     num_iterations: usize,
 
@@ -85,15 +121,22 @@ struct TrainArgs {
     #[arg(short = 'm', long)]
     load_model_uri: Option<String>,
 
+    /// Train the segmentation model as a 2-class boundary Averaged
+    /// Perceptron and save it losslessly collapsed to the AdaBoost model
+    /// format, as the bundled segmentation models are trained. Reads
+    /// ordinary extract output. Cannot be combined with --perceptron,
+    /// --pos, or -m/--load-model-uri
+    #[arg(long, conflicts_with_all = ["perceptron", "pos", "load_model_uri"])]
+    boundary_perceptron: bool,
+
     /// Train a generic Averaged Perceptron model (labels are opaque
-    /// strings). This is the training step of the bundled segmentation
-    /// models' collapse recipe (see scripts/collapse_binary_perceptron.py)
+    /// strings). For a segmentation model, use --boundary-perceptron
     #[arg(long)]
     perceptron: bool,
 
-    /// Number of training epochs (applies to both `--perceptron` and
-    /// `--pos` training; for `--pos`, both stage 1 and stage 2
-    /// train for this many epochs)
+    /// Number of training epochs (applies to `--boundary-perceptron`,
+    /// `--perceptron`, and `--pos` training; for `--pos`, both stage 1 and
+    /// stage 2 train for this many epochs)
     #[arg(long, default_value = "10")]
     num_epochs: usize,
 
@@ -112,11 +155,191 @@ struct TrainArgs {
     #[arg(long, default_value = "0.99")]
     dominance: f64,
 
+    /// Unicode normalization the features were extracted with ("none",
+    /// "nfc", or "nfkc"; see `extract --normalize`), recorded in the model
+    /// so `segment` applies it to its input. Without it, the model declares
+    /// none (or, with -m, keeps the loaded model's declaration). Cannot be
+    /// combined with --perceptron
+    #[arg(long, value_name = "FORM", value_parser = Normalization::from_str, conflicts_with = "perceptron")]
+    normalize: Option<Normalization>,
+
+    /// Word list the features' dictionary features were extracted with
+    /// (see `extract --dictionary`), stored in the model so `segment`
+    /// matches it without a separate file. With --corpus, the in-process
+    /// extraction uses it too. Without it, the model stores none (or, with
+    /// -m, keeps the loaded model's). Cannot be combined with --perceptron
+    #[arg(long, value_name = "FILE", conflicts_with = "perceptron")]
+    dictionary: Option<PathBuf>,
+
+    /// Language of the training data (japanese, chinese, korean, or
+    /// english), recorded in the model's metadata so `segment` and
+    /// `evaluate` reject the model for any other language. Without it, no
+    /// language is recorded (or, with -m, the loaded model's is kept)
+    #[arg(short, long, value_parser = Language::from_str)]
+    language: Option<Language>,
+
+    /// Train straight from a segmented corpus (one sentence per line, as
+    /// read by extract) instead of a features file: the features are
+    /// extracted in process and fed to the learner, so no features file is
+    /// written. The only positional argument is then the model file.
+    /// Requires -l/--language; cannot be combined with --perceptron, --pos,
+    /// or -m/--load-model-uri
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "language",
+        conflicts_with_all = ["perceptron", "pos", "load_model_uri"]
+    )]
+    corpus: Option<PathBuf>,
+
+    /// Format of the --corpus file: "space" (space-separated words), "tsv"
+    /// (tab-separated tokens), or "partial" (partially annotated
+    /// characters); see extract --format
+    #[arg(
+        long,
+        default_value = "space",
+        value_parser = ["space", "tsv", "partial"],
+        requires = "corpus"
+    )]
+    format: String,
+
+    /// Exclude the 16 tag-dependent feature templates while extracting the
+    /// --corpus features (see extract --tag-free)
+    #[arg(long, requires = "corpus")]
+    tag_free: bool,
+
+    /// Drop features occurring in fewer than N training instances before
+    /// training. The default (1) keeps every feature
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    min_count: usize,
+
+    /// Comma-separated per-template overrides of --min-count, as
+    /// TEMPLATE=N (e.g. `UW4=3,BW2=2`); the template is the feature prefix
+    /// before the `:`
+    #[arg(
+        long,
+        value_name = "TEMPLATE=N",
+        value_delimiter = ',',
+        num_args = 1,
+        value_parser = TemplateCutoff::from_str
+    )]
+    min_count_template: Vec<TemplateCutoff>,
+
+    /// Held-out development set for --perceptron, --boundary-perceptron, or
+    /// --pos training, in the training input's format: a features file
+    /// (with --pos, the prefix passed to extract --pos for the development
+    /// corpus; with --corpus, a segmented gold corpus). The model is scored
+    /// on it after every epoch and the best epoch's weights are kept
+    #[arg(long, value_name = "FILE")]
+    dev: Option<PathBuf>,
+
+    /// Stop training after N epochs in a row without a better --dev
+    /// accuracy. Without it, all --num-epochs epochs run
+    #[arg(long, value_name = "N", requires = "dev")]
+    patience: Option<usize>,
+
+    /// Shuffle the training instances every epoch of --perceptron,
+    /// --boundary-perceptron, or --pos training, in an order drawn from
+    /// this seed. The same seed and inputs produce a byte-identical model;
+    /// without it, instances are visited in file order
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+
+    /// Number of threads to train --perceptron, --boundary-perceptron, or
+    /// --pos epochs on. With N > 1, each epoch is split into N shards
+    /// trained in parallel and their weights averaged (iterative parameter
+    /// mixing): the model is deterministic for a given N and --seed, but
+    /// differs from the default single-threaded one
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: u16,
+
+    /// Write a checkpoint of --perceptron, --boundary-perceptron, or --pos
+    /// training to FILE every --checkpoint-every epochs and when training
+    /// is interrupted with Ctrl-C; continue the run with --resume FILE
+    #[arg(long, value_name = "FILE")]
+    checkpoint: Option<PathBuf>,
+
+    /// Epochs between two checkpoints; 0 only writes one when training is
+    /// interrupted
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "checkpoint")]
+    checkpoint_every: usize,
+
+    /// Continue an interrupted run from a checkpoint written by
+    /// --checkpoint. Pass the same inputs and options as the interrupted
+    /// run; the resumed run produces the model it would have
+    #[arg(long, value_name = "CHECKPOINT")]
+    resume: Option<PathBuf>,
+
     /// Path to the features file produced by the extract command (with
-    /// --pos, the prefix passed to extract --pos)
+    /// --pos, the prefix passed to extract --pos). With --corpus, the path
+    /// to write the trained model to
     features_file: PathBuf,
-    /// Path to write the trained model to
-    model_file: PathBuf,
+    /// Path to write the trained model to (omitted with --corpus)
+    #[arg(required_unless_present = "corpus", conflicts_with = "corpus")]
+    model_file: Option<PathBuf>,
+}
+
+impl TrainArgs {
+    /// Returns the path to write the trained model to: the second
+    /// positional argument, or with `--corpus` the only one.
+    fn model_path(&self) -> &Path {
+        match (&self.corpus, &self.model_file) {
+            (None, Some(model_file)) => model_file,
+            // clap enforces exactly one of the two.
+            _ => &self.features_file,
+        }
+    }
+
+    /// Builds the extractor for `--corpus` training, in the language,
+    /// normalization, and dictionary the model is trained for.
+    fn corpus_extractor(&self) -> Result<Extractor, Box<dyn Error>> {
+        let language = self.language.ok_or("--corpus requires -l/--language")?;
+        let mut extractor =
+            Extractor::new(language).with_normalization(self.normalize.unwrap_or_default());
+        if let Some(dictionary) = self.dictionary()? {
+            extractor = extractor.with_dictionary(dictionary);
+        }
+        Ok(extractor)
+    }
+
+    /// Loads the `--dictionary` word list, if any.
+    fn dictionary(&self) -> Result<Option<UserDictionary>, Box<dyn Error>> {
+        Ok(self.dictionary.as_deref().map(UserDictionary::from_path).transpose()?)
+    }
+
+    /// The layout of the `--corpus` lines.
+    fn corpus_format(&self) -> CorpusFormat {
+        match self.format.as_str() {
+            "tsv" => CorpusFormat::Tsv,
+            "partial" => CorpusFormat::Partial,
+            _ => CorpusFormat::Space,
+        }
+    }
+
+    /// Loads the `--resume` checkpoint, if any.
+    fn resume_checkpoint(&self) -> Result<Option<Checkpoint>, Box<dyn Error>> {
+        let Some(path) = &self.resume else {
+            return Ok(None);
+        };
+        let checkpoint = Checkpoint::load(path)?;
+        eprintln!(
+            "Resuming from {} ({} epoch(s) of stage {} completed)",
+            path.display(),
+            checkpoint.epochs_completed(),
+            checkpoint.num_stages()
+        );
+        Ok(Some(checkpoint))
+    }
+
+    /// The feature frequency cutoff from `--min-count` and
+    /// `--min-count-template`.
+    fn cutoff(&self) -> FeatureCutoff {
+        self.min_count_template
+            .iter()
+            .fold(FeatureCutoff::new(self.min_count), |cutoff, t| {
+                cutoff.with_template(&t.template, t.min_count)
+            })
+    }
 }
 
 /// Arguments for the segment command.
@@ -139,6 +362,49 @@ struct SegmentArgs {
     #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
     threads: u16,
 
+    /// Path to a user dictionary (one word per line). Every
+    /// leftmost-longest match in the input is kept as a single token,
+    /// overriding the model's boundary decisions inside and at the edges
+    /// of the match
+    #[arg(long, value_name = "FILE")]
+    user_dict: Option<PathBuf>,
+
+    /// Comma-separated built-in patterns whose matches are kept as single
+    /// tokens: url, email, number, hashtag, mention (e.g.
+    /// `url,email,number`)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',', num_args = 1, value_parser = BuiltinPattern::from_str)]
+    patterns: Vec<BuiltinPattern>,
+
+    /// Path to a pattern file (one regular expression per line; blank
+    /// lines and lines starting with '#' are skipped) whose matches are
+    /// kept as single tokens, in addition to --patterns
+    #[arg(long, value_name = "FILE")]
+    pattern_file: Option<PathBuf>,
+
+    /// Offset added to the boundary decision threshold: a position is a
+    /// boundary when its score is at least this value. Positive values
+    /// give coarser tokens, negative values finer ones (pick one with
+    /// `evaluate --sweep-threshold`)
+    #[arg(
+        long,
+        value_name = "OFFSET",
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    threshold: f64,
+
+    /// Unicode normalization applied to the input ("none", "nfc", or
+    /// "nfkc"), overriding the one the model declares. Output tokens are
+    /// always slices of the original input
+    #[arg(long, value_name = "FORM", value_parser = Normalization::from_str)]
+    normalize: Option<Normalization>,
+
+    /// Split each input line into sentences with the language's rules
+    /// (terminators, brackets, abbreviations) and write one output line
+    /// per sentence, so whole paragraphs can be piped in
+    #[arg(long)]
+    split_sentences: bool,
+
     /// Model URI: a plain path, file:// path, or http(s):// URL
     model_uri: String,
 }
@@ -167,12 +433,227 @@ struct EvaluateArgs {
     #[arg(long, default_value = "space", value_parser = ["space", "tsv"])]
     format: String,
 
+    /// Comma-separated boundary threshold offsets to sweep (e.g.
+    /// `-1,-0.5,0,0.5,1`). Prints one tab-separated row of boundary
+    /// precision/recall/F1 and word F1 per offset to stdout, for picking
+    /// an operating point for `segment --threshold`
+    #[arg(
+        long,
+        value_name = "OFFSETS",
+        value_delimiter = ',',
+        allow_negative_numbers = true,
+        num_args = 1,
+        conflicts_with = "pos"
+    )]
+    sweep_threshold: Option<Vec<f64>>,
+
     /// URI of the model to evaluate (path, file://, or http(s):// with remote_model)
     model_uri: String,
     /// Path to the gold corpus file (one sentence per line)
     gold_file: PathBuf,
 }
 
+/// Arguments for the convert command.
+#[derive(Debug, Args)]
+#[command(about = "Convert a model between the text and binary formats")]
+struct ConvertArgs {
+    /// Language to compile the packed scoring tables for when writing a
+    /// binary model (japanese, chinese, korean, or english). Without it
+    /// only the weights are stored, and the tables are compiled at load
+    /// time as for a text model
+    #[arg(short, long, value_parser = Language::from_str)]
+    language: Option<Language>,
+
+    /// URI of the model to convert (path, file://, or http(s):// with
+    /// remote_model). A text model is written in the binary format and a
+    /// binary model in the text format
+    model_uri: String,
+    /// Path of the converted model file
+    output_file: PathBuf,
+}
+
+/// Arguments for the inspect command.
+#[derive(Debug, Args)]
+#[command(about = "Report the contents of a model")]
+struct InspectArgs {
+    /// Language to estimate the packed scoring tables for (japanese,
+    /// chinese, korean, or english). Defaults to the language recorded in
+    /// the model's metadata, or japanese
+    #[arg(short, long, value_parser = Language::from_str)]
+    language: Option<Language>,
+
+    /// Print the report as a JSON object instead of text
+    #[arg(long)]
+    json: bool,
+
+    /// URI of the model to inspect (path, file://, or http(s):// with
+    /// remote_model), in the text or binary format
+    model_uri: String,
+}
+
+/// Arguments for the prune command.
+#[derive(Debug, Args)]
+#[command(about = "Prune a segmentation model to fewer features")]
+#[command(group(
+    ArgGroup::new("strategy")
+        .args(["top_n", "min_weight", "per_template", "sweep"])
+        .required(true)
+))]
+struct PruneArgs {
+    /// Keep the N features with the largest absolute weight
+    #[arg(long, value_name = "N")]
+    top_n: Option<usize>,
+
+    /// Keep the features whose absolute weight is at least W
+    #[arg(long, value_name = "W")]
+    min_weight: Option<f64>,
+
+    /// Keep the N largest-|weight| features of each template (e.g. UW4)
+    #[arg(long, value_name = "N")]
+    per_template: Option<usize>,
+
+    /// Comma-separated candidate sizes to sweep (e.g.
+    /// `10000,20000,40000`). Each size is applied as --top-n and evaluated
+    /// against --gold, and one tab-separated row of feature count and
+    /// word/boundary F1 per size is printed to stdout; no model is written
+    #[arg(
+        long,
+        value_name = "SIZES",
+        value_delimiter = ',',
+        num_args = 1,
+        requires = "gold"
+    )]
+    sweep: Option<Vec<usize>>,
+
+    /// Gold corpus for --sweep (one sentence per line)
+    #[arg(long, value_name = "GOLD_FILE")]
+    gold: Option<PathBuf>,
+
+    /// Gold corpus format for --sweep: "space" (space-separated tokens) or
+    /// "tsv" (tab-separated tokens, a token may be a literal space)
+    #[arg(long, default_value = "space", value_parser = ["space", "tsv"])]
+    format: String,
+
+    /// Language of the gold corpus for --sweep (japanese, chinese, korean,
+    /// or english)
+    #[arg(short, long, default_value = "japanese", value_parser = Language::from_str)]
+    language: Language,
+
+    /// URI of the model to prune (path, file://, or http(s):// with
+    /// remote_model). A two-stage model has its stage-1 boundary classifier
+    /// pruned
+    model_uri: String,
+    /// Path of the pruned model file, written in the input's format
+    /// (required unless --sweep). A binary input's packed scoring tables
+    /// are recompiled for the language they were compiled for
+    #[arg(required_unless_present = "sweep", conflicts_with = "sweep")]
+    output_file: Option<PathBuf>,
+}
+
+/// Arguments for the select command.
+#[derive(Debug, Args)]
+#[command(about = "Select the sentences most worth annotating")]
+struct SelectArgs {
+    /// Language of the corpus (japanese, chinese, korean, or english)
+    #[arg(short, long, default_value = "japanese", value_parser = Language::from_str)]
+    language: Language,
+
+    /// Number of sentences to select
+    #[arg(short = 'k', long, value_name = "K", default_value_t = 100)]
+    top: usize,
+
+    /// How a sentence's boundary margins are aggregated: "margin" (the
+    /// smallest absolute margin) or "entropy" (the summed binary entropy
+    /// of the decisions)
+    #[arg(long, value_name = "MEASURE", default_value = "margin", value_parser = Uncertainty::from_str)]
+    uncertainty: Uncertainty,
+
+    /// Margin scale of the entropy measure; boosted models such as
+    /// RWCP.model want a value well below the default
+    #[arg(long, default_value_t = 1.0)]
+    scale: f64,
+
+    /// Skip sentences whose character N-grams are mostly covered by the
+    /// sentences already selected (see --max-overlap)
+    #[arg(long, value_name = "N")]
+    diversity: Option<usize>,
+
+    /// Largest fraction of a sentence's N-grams that may already be
+    /// covered for it to be selected, with --diversity
+    #[arg(long, value_name = "F", default_value_t = 0.5, requires = "diversity")]
+    max_overlap: f64,
+
+    /// Prefix each selected sentence with its line number and score,
+    /// tab-separated
+    #[arg(long)]
+    scores: bool,
+
+    /// URI of the model (path, file://, or http(s):// with remote_model)
+    model_uri: String,
+    /// Path to the raw corpus (one sentence per line)
+    corpus_file: PathBuf,
+}
+
+/// Arguments for the distill command.
+#[derive(Debug, Args)]
+#[command(about = "Distill a model into a tag-free student")]
+struct DistillArgs {
+    /// Language of the teacher model and the raw corpus (japanese,
+    /// chinese, korean, or english)
+    #[arg(short, long, default_value = "japanese", value_parser = Language::from_str)]
+    language: Language,
+
+    /// Train only on the positions the teacher decided by at least this
+    /// absolute margin, leaving its doubtful decisions out. The default
+    /// (0) trains on every position
+    #[arg(long, value_name = "MARGIN", default_value_t = 0.0)]
+    min_margin: f64,
+
+    /// Scale each position's training step by the teacher's margin there
+    /// divided by SCALE, capped at 1, so the student learns less from the
+    /// teacher's narrower decisions. By default every position counts fully
+    #[arg(long, value_name = "SCALE")]
+    weight_by_margin: Option<f64>,
+
+    /// Number of training epochs
+    #[arg(long, default_value = "10")]
+    num_epochs: usize,
+
+    /// Drop features occurring in fewer than N training instances before
+    /// training. The default (1) keeps every feature
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    min_count: usize,
+
+    /// Held-out gold corpus (space-separated words, one sentence per
+    /// line). The student is scored on it after every epoch and the best
+    /// epoch's weights are kept
+    #[arg(long, value_name = "FILE")]
+    dev: Option<PathBuf>,
+
+    /// Stop training after N epochs in a row without a better --dev
+    /// accuracy
+    #[arg(long, value_name = "N", requires = "dev")]
+    patience: Option<usize>,
+
+    /// Shuffle the training instances every epoch in an order drawn from
+    /// this seed
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+
+    /// Number of threads to label the corpus and train each epoch on (see
+    /// train --threads)
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: u16,
+
+    /// URI of the teacher model (path, file://, or http(s):// with
+    /// remote_model): a segmentation or two-stage model
+    teacher_uri: String,
+    /// Path to the raw corpus (one sentence per line)
+    corpus_file: PathBuf,
+    /// Path to write the student model to
+    model_file: PathBuf,
+}
+
 /// Subcommands for litsea CLI.
 #[derive(Debug, Subcommand)]
 enum Commands {
@@ -184,6 +665,16 @@ enum Commands {
     Segment(SegmentArgs),
     /// Evaluate a model against a held-out gold corpus
     Evaluate(EvaluateArgs),
+    /// Convert a model between the text and binary formats
+    Convert(ConvertArgs),
+    /// Report the contents of a model
+    Inspect(InspectArgs),
+    /// Prune a segmentation model to fewer features
+    Prune(PruneArgs),
+    /// Select the sentences most worth annotating
+    Select(SelectArgs),
+    /// Distill a model into a tag-free student
+    Distill(DistillArgs),
 }
 
 /// Arguments for the litsea command.
@@ -206,12 +697,13 @@ struct CommandArgs {
 /// features to the output file. With `--pos` the corpus is
 /// POS-tagged and three files (boundary, word-level, and lexicon) are
 /// written via `extract_two_stage` (issue #147), or via
-/// `extract_two_stage_tsv` when combined with `--format tsv` (issue #198);
-/// otherwise each line is space-separated words, or tab-separated tokens
-/// with `--format tsv` (a token may be a literal space, preserving the
-/// original spacing). `--tag-free` (boundary pipeline only, composable with
-/// `--format tsv`) drops the 16 tag-dependent templates so the trained model
-/// is pointwise (issue #183).
+/// `extract_two_stage_tsv` when combined with `--format tsv` (issue #198),
+/// or `extract_two_stage_partial` with `--format partial`; otherwise each
+/// line is space-separated words, tab-separated tokens with `--format tsv`
+/// (a token may be a literal space, preserving the original spacing), or
+/// partially annotated characters with `--format partial`. `--tag-free`
+/// (boundary pipeline only, composable with `--format`) drops the 16
+/// tag-dependent templates so the trained model is pointwise (issue #183).
 ///
 /// # Arguments
 /// * `args` - The arguments for the extract command [`ExtractArgs`].
@@ -219,7 +711,12 @@ struct CommandArgs {
 /// # Returns
 /// Returns a Result indicating success or failure.
 fn extract(args: ExtractArgs) -> Result<(), Box<dyn Error>> {
-    let extractor = Extractor::new(args.language);
+    let mut extractor = Extractor::new(args.language)
+        .with_normalization(args.normalize)
+        .with_threads(usize::from(args.threads));
+    if let Some(path) = &args.dictionary {
+        extractor = extractor.with_dictionary(UserDictionary::from_path(path)?);
+    }
 
     if args.tag_free && args.pos {
         // Tag-free extraction is a boundary-pipeline concept (#183); the
@@ -233,6 +730,12 @@ fn extract(args: ExtractArgs) -> Result<(), Box<dyn Error>> {
             args.features_file.as_path(),
             args.stage2_features,
         )?;
+    } else if args.pos && args.format == "partial" {
+        extractor.extract_two_stage_partial(
+            args.corpus_file.as_path(),
+            args.features_file.as_path(),
+            args.stage2_features,
+        )?;
     } else if args.pos {
         extractor.extract_two_stage(
             args.corpus_file.as_path(),
@@ -243,6 +746,11 @@ fn extract(args: ExtractArgs) -> Result<(), Box<dyn Error>> {
         extractor.extract_tsv_tag_free(args.corpus_file.as_path(), args.features_file.as_path())?;
     } else if args.format == "tsv" {
         extractor.extract_tsv(args.corpus_file.as_path(), args.features_file.as_path())?;
+    } else if args.format == "partial" && args.tag_free {
+        extractor
+            .extract_partial_tag_free(args.corpus_file.as_path(), args.features_file.as_path())?;
+    } else if args.format == "partial" {
+        extractor.extract_partial(args.corpus_file.as_path(), args.features_file.as_path())?;
     } else if args.tag_free {
         extractor.extract_tag_free(args.corpus_file.as_path(), args.features_file.as_path())?;
     } else {
@@ -253,6 +761,102 @@ fn extract(args: ExtractArgs) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Builds the provenance a trained model records: `base` (the metadata of
+/// a model loaded with -m, or empty) with the --language, if given, and the
+/// checksum of the training data.
+///
+/// # Arguments
+/// * `args` - The arguments for the train command [`TrainArgs`].
+/// * `base` - The metadata to start from.
+///
+/// # Returns
+/// The metadata to pass to the trainer.
+fn training_metadata(
+    args: &TrainArgs,
+    base: ModelMetadata,
+) -> Result<ModelMetadata, Box<dyn Error>> {
+    let checksum = if args.pos {
+        // The three files extract --pos writes, hashed as one stream.
+        let open = |suffix: &str| {
+            let mut path = args.features_file.clone().into_os_string();
+            path.push(suffix);
+            File::open(path)
+        };
+        metadata::checksum(open(".stage1")?.chain(open(".stage2")?).chain(open(".lexicon")?))?
+    } else if let Some(corpus) = &args.corpus {
+        metadata::checksum(File::open(corpus)?)?
+    } else {
+        metadata::checksum(File::open(&args.features_file)?)?
+    };
+
+    let mut metadata = base.with_corpus_checksum(checksum);
+    if let Some(language) = args.language {
+        metadata = metadata.with_language(language);
+    }
+    Ok(metadata)
+}
+
+/// Returns an observer drawing training progress as one line on stderr,
+/// rewritten in place, or `None` when stderr is not a terminal (a log only
+/// gets the final metrics).
+fn progress_line() -> Option<Box<dyn ProgressObserver>> {
+    if !io::stderr().is_terminal() {
+        return None;
+    }
+    let mut drawn: Option<Duration> = None;
+    let observer = move |progress: &TrainingProgress| {
+        // Boosting runs thousands of short iterations: redraw at most ten
+        // times a second, and always for the last one.
+        let recent = drawn.is_some_and(|at| progress.elapsed - at < Duration::from_millis(100));
+        if recent && progress.iteration < progress.total {
+            return;
+        }
+        drawn = Some(progress.elapsed);
+        let unit = if progress.stage == TrainingStage::Boosting { "iteration" } else { "epoch" };
+        let mut line = format!(
+            "{} {} {}/{}  error {:.2}%  updates {}",
+            progress.stage,
+            unit,
+            progress.iteration,
+            progress.total,
+            progress.error_rate,
+            progress.updates
+        );
+        if let Some(accuracy) = progress.dev_accuracy {
+            line.push_str(&format!("  dev {:.2}%", accuracy));
+        }
+        eprint!("\r\x1b[K{}  {:.1}s", line, progress.elapsed.as_secs_f64());
+    };
+    Some(Box::new(observer))
+}
+
+/// Clears the line drawn by [`progress_line`] before the metrics are
+/// printed.
+fn end_progress_line() {
+    if io::stderr().is_terminal() {
+        eprint!("\r\x1b[K");
+    }
+}
+
+/// Prints the per-epoch development accuracies of a perceptron run
+/// (nothing without `--dev`).
+///
+/// # Arguments
+/// * `prefix` - Prepended to each line's label, e.g. `"Stage 1 "`.
+/// * `curve` - The development accuracy after each epoch.
+fn print_dev_curve(prefix: &str, curve: &[f64]) {
+    let mut best: Option<(usize, f64)> = None;
+    for (epoch, &accuracy) in curve.iter().enumerate() {
+        eprintln!("  {}Dev Accuracy (epoch {}): {:.2}%", prefix, epoch + 1, accuracy);
+        if best.is_none_or(|(_, best_accuracy)| accuracy > best_accuracy) {
+            best = Some((epoch + 1, accuracy));
+        }
+    }
+    if let Some((epoch, accuracy)) = best {
+        eprintln!("  {}Best Dev Epoch: {} ( {:.2}% )", prefix, epoch, accuracy);
+    }
+}
+
 /// Train a segmenter using the provided arguments.
 /// This function initializes a Trainer with the specified parameters,
 /// loads a model if specified, and trains the model using the features file.
@@ -283,9 +887,35 @@ async fn train(args: TrainArgs) -> Result<(), Box<dyn Error>> {
         }
         // Train the two-stage model (issue #147): a binary boundary
         // classifier plus a word-level tagger, assembled with the lexicon.
-        let trainer =
-            TwoStageTrainer::new(args.num_epochs, args.dominance, args.features_file.as_path())?;
-        let metrics = trainer.train(&running, args.model_file.as_path())?;
+        let metadata = training_metadata(&args, ModelMetadata::new())?;
+        let mut trainer =
+            TwoStageTrainer::new(args.num_epochs, args.dominance, args.features_file.as_path())?
+                .with_normalization(args.normalize.unwrap_or_default())
+                .with_cutoff(args.cutoff())
+                .with_metadata(metadata);
+        if let Some(dictionary) = args.dictionary()? {
+            trainer = trainer.with_dictionary(dictionary);
+        }
+        if let Some(dev) = &args.dev {
+            trainer.load_dev(dev)?;
+        }
+        if let Some(patience) = args.patience {
+            trainer = trainer.with_patience(patience);
+        }
+        if let Some(seed) = args.seed {
+            trainer = trainer.with_seed(seed);
+        }
+        trainer = trainer.with_threads(usize::from(args.threads));
+        if let Some(path) = &args.checkpoint {
+            trainer = trainer.with_checkpoint(path, args.checkpoint_every);
+        }
+        if let Some(checkpoint) = args.resume_checkpoint()? {
+            trainer.resume(checkpoint)?;
+        }
+        trainer.set_progress(progress_line());
+        let metrics = trainer.train(&running, args.model_path());
+        end_progress_line();
+        let metrics = metrics?;
 
         eprintln!("Result Metrics (Two-Stage):");
         eprintln!(
@@ -300,6 +930,68 @@ async fn train(args: TrainArgs) -> Result<(), Box<dyn Error>> {
         );
         eprintln!("  Stage 2 Macro Precision: {:.2}%", metrics.stage2.macro_precision);
         eprintln!("  Stage 2 Macro Recall: {:.2}%", metrics.stage2.macro_recall);
+        eprintln!(
+            "  Features Removed: {} (stage 1), {} (stage 2)",
+            metrics.stage1.features_removed, metrics.stage2.features_removed
+        );
+        print_dev_curve("Stage 1 ", &metrics.stage1.dev_curve);
+        print_dev_curve("Stage 2 ", &metrics.stage2.dev_curve);
+    } else if args.boundary_perceptron {
+        // Train a 2-class boundary perceptron, saved collapsed to the
+        // AdaBoost model format
+        let metadata = training_metadata(&args, ModelMetadata::new())?;
+        let trainer = match &args.corpus {
+            Some(corpus) => BoundaryPerceptronTrainer::from_corpus(
+                args.num_epochs,
+                &args.corpus_extractor()?,
+                corpus,
+                args.corpus_format(),
+                args.tag_free,
+            )?,
+            None => BoundaryPerceptronTrainer::new(args.num_epochs, args.features_file.as_path())?,
+        };
+        let mut trainer = trainer
+            .with_normalization(args.normalize.unwrap_or_default())
+            .with_cutoff(args.cutoff())
+            .with_metadata(metadata);
+        if let Some(dictionary) = args.dictionary()? {
+            trainer = trainer.with_dictionary(dictionary);
+        }
+        match (&args.dev, &args.corpus) {
+            (Some(dev), Some(_)) => trainer.load_dev_corpus(
+                &args.corpus_extractor()?,
+                dev,
+                args.corpus_format(),
+                args.tag_free,
+            )?,
+            (Some(dev), None) => trainer.load_dev(dev)?,
+            (None, _) => {}
+        }
+        if let Some(patience) = args.patience {
+            trainer = trainer.with_patience(patience);
+        }
+        if let Some(seed) = args.seed {
+            trainer = trainer.with_seed(seed);
+        }
+        trainer = trainer.with_threads(usize::from(args.threads));
+        if let Some(path) = &args.checkpoint {
+            trainer = trainer.with_checkpoint(path, args.checkpoint_every);
+        }
+        if let Some(checkpoint) = args.resume_checkpoint()? {
+            trainer.resume(checkpoint)?;
+        }
+
+        trainer.set_progress(progress_line());
+        let metrics = trainer.train(&running, args.model_path());
+        end_progress_line();
+        let metrics = metrics?;
+
+        eprintln!("Result Metrics (Boundary Perceptron):");
+        eprintln!("  Accuracy: {:.2}% ( {} )", metrics.accuracy, metrics.num_instances);
+        eprintln!("  Macro Precision: {:.2}%", metrics.macro_precision);
+        eprintln!("  Macro Recall: {:.2}%", metrics.macro_recall);
+        eprintln!("  Features Removed: {}", metrics.features_removed);
+        print_dev_curve("", &metrics.dev_curve);
     } else if args.perceptron {
         // Train a generic Averaged Perceptron model (opaque string labels)
         let mut trainer = PerceptronTrainer::new(args.num_epochs, args.features_file.as_path())?;
@@ -307,23 +999,89 @@ async fn train(args: TrainArgs) -> Result<(), Box<dyn Error>> {
         if let Some(model_uri) = &args.load_model_uri {
             trainer.load_model(model_uri).await?;
         }
+        let metadata = training_metadata(&args, trainer.metadata().clone())?;
+        trainer = trainer.with_cutoff(args.cutoff()).with_metadata(metadata);
+        if let Some(dev) = &args.dev {
+            trainer.load_dev(dev)?;
+        }
+        if let Some(patience) = args.patience {
+            trainer = trainer.with_patience(patience);
+        }
+        if let Some(seed) = args.seed {
+            trainer = trainer.with_seed(seed);
+        }
+        trainer = trainer.with_threads(usize::from(args.threads));
+        if let Some(path) = &args.checkpoint {
+            trainer = trainer.with_checkpoint(path, args.checkpoint_every);
+        }
+        if let Some(checkpoint) = args.resume_checkpoint()? {
+            trainer.resume(checkpoint)?;
+        }
 
-        let metrics = trainer.train(&running, args.model_file.as_path())?;
+        trainer.set_progress(progress_line());
+        let metrics = trainer.train(&running, args.model_path());
+        end_progress_line();
+        let metrics = metrics?;
 
         eprintln!("Result Metrics (Perceptron):");
         eprintln!("  Accuracy: {:.2}% ( {} )", metrics.accuracy, metrics.num_instances);
         eprintln!("  Macro Precision: {:.2}%", metrics.macro_precision);
         eprintln!("  Macro Recall: {:.2}%", metrics.macro_recall);
+        eprintln!("  Features Removed: {}", metrics.features_removed);
+        print_dev_curve("", &metrics.dev_curve);
     } else {
         // Train the word segmentation model with AdaBoost
-        let mut trainer =
-            Trainer::new(args.threshold, args.num_iterations, args.features_file.as_path())?;
+        if args.dev.is_some() {
+            return Err(
+                "--dev requires --perceptron, --boundary-perceptron, or --pos (AdaBoost training has no epochs)"
+                    .into(),
+            );
+        }
+        if args.seed.is_some() {
+            return Err(
+                "--seed requires --perceptron, --boundary-perceptron, or --pos (AdaBoost training is not order-dependent)"
+                    .into(),
+            );
+        }
+        if args.threads > 1 {
+            return Err("--threads requires --perceptron, --boundary-perceptron, or --pos".into());
+        }
+        if args.checkpoint.is_some() || args.resume.is_some() {
+            return Err(
+                "--checkpoint and --resume require --perceptron, --boundary-perceptron, or --pos"
+                    .into(),
+            );
+        }
+        let mut trainer = match &args.corpus {
+            Some(corpus) => Trainer::from_corpus(
+                args.threshold,
+                args.num_iterations,
+                &args.corpus_extractor()?,
+                corpus,
+                args.corpus_format(),
+                args.tag_free,
+            )?,
+            None => {
+                Trainer::new(args.threshold, args.num_iterations, args.features_file.as_path())?
+            }
+        };
 
         if let Some(model_uri) = &args.load_model_uri {
             trainer.load_model(model_uri).await?;
         }
+        if let Some(normalization) = args.normalize {
+            trainer = trainer.with_normalization(normalization);
+        }
+        if let Some(dictionary) = args.dictionary()? {
+            trainer = trainer.with_dictionary(dictionary);
+        }
+        let metadata = training_metadata(&args, trainer.metadata().clone())?;
+        trainer = trainer.with_cutoff(args.cutoff()).with_metadata(metadata);
 
-        let metrics = trainer.train(&running, args.model_file.as_path())?;
+        trainer.set_progress(progress_line());
+        let metrics = trainer.train(&running, args.model_path());
+        end_progress_line();
+        let metrics = metrics?;
 
         eprintln!("Result Metrics:");
         eprintln!(
@@ -351,6 +1109,7 @@ async fn train(args: TrainArgs) -> Result<(), Box<dyn Error>> {
             metrics.false_negatives,
             metrics.true_negatives
         );
+        eprintln!("  Features Removed: {}", metrics.features_removed);
     }
 
     Ok(())
@@ -488,7 +1247,8 @@ where
 /// `file://` path, or an `http(s)://` URL with the `remote_model` feature):
 /// with `--pos`, a two-stage POS model (issue #147); otherwise an AdaBoost
 /// model (word segmentation only). The segmented sentences are written to
-/// standard output.
+/// standard output. With `--user-dict`, dictionary words are kept whole in
+/// either mode.
 ///
 /// A downstream consumer closing stdout early (broken pipe) terminates the
 /// command successfully instead of reporting an error.
@@ -506,13 +1266,38 @@ async fn segment(args: SegmentArgs) -> Result<(), Box<dyn Error>> {
     let mut writer = io::BufWriter::new(stdout.lock());
 
     let threads = usize::from(args.threads);
+    let user_dictionary = args.user_dict.as_deref().map(UserDictionary::from_path).transpose()?;
+    let mut patterns = match &args.pattern_file {
+        Some(path) => PatternSet::from_path(path)?,
+        None => PatternSet::new(),
+    };
+    for builtin in args.patterns {
+        patterns.insert_builtin(builtin);
+    }
+    let threshold = args.threshold;
+    let normalization = args.normalize;
+    let split = args.split_sentences.then_some(language);
+    // Applies the threshold, normalization, patterns, and user dictionary.
+    let configure = |segmenter: Segmenter| {
+        let mut segmenter = segmenter.with_threshold(threshold);
+        if let Some(normalization) = normalization {
+            segmenter = segmenter.with_normalization(normalization);
+        }
+        if !patterns.is_empty() {
+            segmenter = segmenter.with_patterns(patterns);
+        }
+        match user_dictionary {
+            Some(dictionary) => segmenter.with_user_dictionary(dictionary),
+            None => segmenter,
+        }
+    };
 
     if args.pos {
         // Two-stage segmentation + POS tagging (issue #147). The loader
         // rejects non-two-stage files with a precise error message.
         let mut learner = TwoStageLearner::new();
         learner.load_model(args.model_uri.as_str()).await?;
-        let segmenter = Segmenter::with_two_stage_learner(language, learner);
+        let segmenter = configure(Segmenter::try_with_two_stage_learner(language, learner)?);
 
         if threads > 1 {
             // Parallel path (#185): workers need no reusable scratch for
@@ -522,13 +1307,20 @@ async fn segment(args: SegmentArgs) -> Result<(), Box<dyn Error>> {
                 threads,
                 &mut states,
                 |line, (), out| {
-                    for (k, (word, pos)) in segmenter.segment_with_pos(line)?.iter().enumerate() {
-                        if k > 0 {
-                            out.push(' ');
+                    for (n, piece) in line_pieces(line, split).enumerate() {
+                        if n > 0 {
+                            out.push('\n');
+                        }
+                        for (k, (word, pos)) in
+                            segmenter.segment_with_pos(piece)?.iter().enumerate()
+                        {
+                            if k > 0 {
+                                out.push(' ');
+                            }
+                            out.push_str(word);
+                            out.push('/');
+                            out.push_str(&pos.to_string());
                         }
-                        out.push_str(word);
-                        out.push('/');
-                        out.push_str(&pos.to_string());
                     }
                     Ok(())
                 },
@@ -544,11 +1336,13 @@ async fn segment(args: SegmentArgs) -> Result<(), Box<dyn Error>> {
             if line.is_empty() {
                 continue;
             }
-            let tokens = segmenter.segment_with_pos(line)?;
-            let formatted: Vec<String> =
-                tokens.iter().map(|(word, pos)| format!("{}/{}", word, pos)).collect();
-            if !write_output_line(&mut writer, &formatted.join(" "))? {
-                return Ok(());
+            for piece in line_pieces(line, split) {
+                let tokens = segmenter.segment_with_pos(piece)?;
+                let formatted: Vec<String> =
+                    tokens.iter().map(|(word, pos)| format!("{}/{}", word, pos)).collect();
+                if !write_output_line(&mut writer, &formatted.join(" "))? {
+                    return Ok(());
+                }
             }
         }
     } else {
@@ -556,7 +1350,7 @@ async fn segment(args: SegmentArgs) -> Result<(), Box<dyn Error>> {
         let mut learner = AdaBoost::new(0.01, 100);
         learner.load_model(args.model_uri.as_str()).await?;
 
-        let segmenter = Segmenter::with_learner(language, learner);
+        let segmenter = configure(Segmenter::try_with_learner(language, learner)?);
 
         if threads > 1 {
             // Parallel path (#185): one reusable SegmentBuffer per worker
@@ -568,11 +1362,18 @@ async fn segment(args: SegmentArgs) -> Result<(), Box<dyn Error>> {
                 threads,
                 &mut states,
                 |line, buf, out| {
-                    for (k, &(start, end)) in segmenter.segment_into(line, buf).iter().enumerate() {
-                        if k > 0 {
-                            out.push(' ');
+                    for (n, piece) in line_pieces(line, split).enumerate() {
+                        if n > 0 {
+                            out.push('\n');
+                        }
+                        for (k, &(start, end)) in
+                            segmenter.segment_into(piece, buf).iter().enumerate()
+                        {
+                            if k > 0 {
+                                out.push(' ');
+                            }
+                            out.push_str(&piece[start..end]);
                         }
-                        out.push_str(&line[start..end]);
                     }
                     Ok(())
                 },
@@ -594,15 +1395,18 @@ async fn segment(args: SegmentArgs) -> Result<(), Box<dyn Error>> {
             if line.is_empty() {
                 continue;
             }
-            out.clear();
-            for (k, &(start, end)) in segmenter.segment_into(line, &mut buf).iter().enumerate() {
-                if k > 0 {
-                    out.push(' ');
+            for piece in line_pieces(line, split) {
+                out.clear();
+                for (k, &(start, end)) in segmenter.segment_into(piece, &mut buf).iter().enumerate()
+                {
+                    if k > 0 {
+                        out.push(' ');
+                    }
+                    out.push_str(&piece[start..end]);
+                }
+                if !write_output_line(&mut writer, &out)? {
+                    return Ok(());
                 }
-                out.push_str(&line[start..end]);
-            }
-            if !write_output_line(&mut writer, &out)? {
-                return Ok(());
             }
         }
     }
@@ -611,6 +1415,19 @@ async fn segment(args: SegmentArgs) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Returns the pieces of an input line that `segment` writes one output
+/// line each: the line's sentences with `--split-sentences` (`split` is
+/// the language to split with), otherwise the line itself.
+fn line_pieces(line: &str, split: Option<Language>) -> impl Iterator<Item = &str> {
+    let whole = split.is_none().then_some(line);
+    split
+        .map(|language| sentence::split(language, line))
+        .into_iter()
+        .flatten()
+        .map(move |(start, end)| &line[start..end])
+        .chain(whole)
+}
+
 /// Evaluate a model against a held-out gold corpus and print quality
 /// metrics.
 ///
@@ -633,7 +1450,7 @@ async fn evaluate(args: EvaluateArgs) -> Result<(), Box<dyn Error>> {
         // with a precise error message.
         let mut learner = TwoStageLearner::new();
         learner.load_model(args.model_uri.as_str()).await?;
-        let segmenter = Segmenter::with_two_stage_learner(args.language, learner);
+        let segmenter = Segmenter::try_with_two_stage_learner(args.language, learner)?;
 
         let tsv = args.format == "tsv";
         let gold = reader
@@ -655,7 +1472,7 @@ async fn evaluate(args: EvaluateArgs) -> Result<(), Box<dyn Error>> {
     } else {
         let mut learner = AdaBoost::new(0.01, 100);
         learner.load_model(args.model_uri.as_str()).await?;
-        let segmenter = Segmenter::with_learner(args.language, learner);
+        let mut segmenter = Segmenter::try_with_learner(args.language, learner)?;
 
         let tsv = args.format == "tsv";
         let gold = reader
@@ -663,6 +1480,35 @@ async fn evaluate(args: EvaluateArgs) -> Result<(), Box<dyn Error>> {
             .collect::<Result<Vec<String>, _>>()?
             .into_iter()
             .map(|line| evaluation::parse_gold_line(&line, tsv));
+
+        if let Some(offsets) = args.sweep_threshold {
+            // Threshold sweep: the gold corpus is parsed once and
+            // re-segmented at every offset.
+            let gold: Vec<Vec<String>> = gold.collect();
+            let stdout = io::stdout();
+            let mut writer = io::BufWriter::new(stdout.lock());
+            let header = "threshold\tboundary_precision\tboundary_recall\tboundary_f1\tword_f1";
+            if !write_output_line(&mut writer, header)? {
+                return Ok(());
+            }
+            for offset in offsets {
+                segmenter = segmenter.with_threshold(offset);
+                let metrics = evaluation::evaluate_segmentation(&segmenter, gold.iter().cloned());
+                let row = format!(
+                    "{}\t{:.2}\t{:.2}\t{:.2}\t{:.2}",
+                    offset,
+                    metrics.boundary_precision,
+                    metrics.boundary_recall,
+                    metrics.boundary_f1,
+                    metrics.word_f1
+                );
+                if !write_output_line(&mut writer, &row)? {
+                    return Ok(());
+                }
+            }
+            return flush_output(&mut writer).map_err(Into::into);
+        }
+
         let metrics = evaluation::evaluate_segmentation(&segmenter, gold);
 
         eprintln!("Evaluation Metrics:");
@@ -678,6 +1524,448 @@ async fn evaluate(args: EvaluateArgs) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Convert a model between the text and binary formats.
+///
+/// Reads the model once, detects its form ([`ModelFormat`]) and kind
+/// ([`ModelKind`]), loads it with the matching learner, and writes it in the
+/// other form: a text model becomes a binary one (with packed scoring tables
+/// for `--language`, when given), and a binary model becomes text.
+///
+/// # Arguments
+/// * `args` - The arguments for the convert command [`ConvertArgs`].
+///
+/// # Returns
+/// Returns a Result indicating success or failure.
+async fn convert(args: ConvertArgs) -> Result<(), Box<dyn Error>> {
+    let bytes = litsea::model_io::read_model_bytes(args.model_uri.as_str()).await?;
+    let to_binary = ModelFormat::detect(&bytes) == ModelFormat::Text;
+    if !to_binary && args.language.is_some() {
+        return Err("--language only applies when converting a text model to binary".into());
+    }
+    let kind = ModelKind::detect_bytes(&bytes);
+
+    let mut writer = io::BufWriter::new(File::create(args.output_file.as_path())?);
+    match kind {
+        ModelKind::AdaBoost => {
+            let mut learner = AdaBoost::new(0.01, 100);
+            learner.load_model_from_reader(bytes.as_slice())?;
+            if to_binary {
+                learner.save_binary_model_to_writer(&mut writer, args.language)?;
+            } else {
+                learner.save_model_to_writer(&mut writer)?;
+            }
+        }
+        ModelKind::AveragedPerceptron => {
+            let mut learner = AveragedPerceptron::new();
+            learner.load_model_from_reader(bytes.as_slice())?;
+            if to_binary {
+                learner.save_binary_model_to_writer(&mut writer)?;
+            } else {
+                learner.save_model_to_writer(&mut writer)?;
+            }
+        }
+        ModelKind::TwoStage => {
+            let mut learner = TwoStageLearner::new();
+            learner.load_model_from_reader(bytes.as_slice())?;
+            if to_binary {
+                learner.save_binary_model_to_writer(&mut writer, args.language)?;
+            } else {
+                learner.save_model_to_writer(&mut writer)?;
+            }
+        }
+    }
+    writer.flush()?;
+
+    eprintln!(
+        "Converted {:?} model to the {} format: {}",
+        kind,
+        if to_binary { "binary" } else { "text" },
+        args.output_file.display()
+    );
+    Ok(())
+}
+
+/// Report the contents of a model.
+/// This function detects the model kind, loads it with the matching
+/// learner, and prints its [`litsea::ModelInspection`] to stdout: the
+/// metadata, the feature count per template, whether the model is
+/// pointwise, the weight distribution, the classes, the lexicon, and the
+/// estimated packed memory.
+///
+/// # Arguments
+/// * `args` - The arguments for the inspect command [`InspectArgs`].
+///
+/// # Returns
+/// Returns a Result indicating success or failure.
+async fn inspect(args: InspectArgs) -> Result<(), Box<dyn Error>> {
+    let bytes = litsea::model_io::read_model_bytes(args.model_uri.as_str()).await?;
+    let language = |metadata: &ModelMetadata| {
+        args.language.or(metadata.language()).unwrap_or(Language::Japanese)
+    };
+    let report = match ModelKind::detect_bytes(&bytes) {
+        ModelKind::AdaBoost => {
+            let mut learner = AdaBoost::new(0.01, 100);
+            learner.load_model_from_reader(bytes.as_slice())?;
+            learner.inspect(language(learner.metadata()))
+        }
+        ModelKind::AveragedPerceptron => {
+            let mut learner = AveragedPerceptron::new();
+            learner.load_model_from_reader(bytes.as_slice())?;
+            learner.inspect()
+        }
+        ModelKind::TwoStage => {
+            let mut learner = TwoStageLearner::new();
+            learner.load_model_from_reader(bytes.as_slice())?;
+            learner.inspect(language(learner.metadata()))
+        }
+    };
+
+    if args.json {
+        println!("{}", report.to_json());
+        return Ok(());
+    }
+
+    println!("Kind: {:?}", report.kind);
+    let metadata = &report.metadata;
+    if let Some(language) = metadata.language() {
+        println!("Language: {}", language);
+    }
+    if let Some(templates) = metadata.templates() {
+        println!("Templates: {}", templates);
+    }
+    if let Some(checksum) = metadata.corpus_checksum() {
+        println!("Corpus: {}", checksum);
+    }
+    for (name, value) in metadata.params() {
+        println!("Parameter {}: {}", name, value);
+    }
+    for classifier in &report.classifiers {
+        println!("Classifier ({}):", classifier.name);
+        println!(
+            "  Features: {} ( {} outside the known templates )",
+            classifier.num_features, classifier.other_features
+        );
+        let templates: Vec<String> = classifier
+            .templates
+            .iter()
+            .map(|(prefix, count)| format!("{}={}", prefix, count))
+            .collect();
+        println!("  Per Template: {}", templates.join(" "));
+        if let Some(pointwise) = classifier.pointwise {
+            println!("  Pointwise: {}", if pointwise { "yes" } else { "no" });
+        }
+        if !classifier.classes.is_empty() {
+            println!("  Classes: {}", classifier.classes.join(" "));
+        }
+        let w = &classifier.weights;
+        println!(
+            "  Weights: {} ( {} non-zero ), min {}, max {}, mean {:.6}, mean |w| {:.6}, std dev {:.6}",
+            w.count, w.nonzero, w.min, w.max, w.mean, w.mean_abs, w.std_dev
+        );
+    }
+    if let Some(lexicon) = &report.lexicon {
+        println!(
+            "Lexicon: {} surfaces, {} dominance-fixed ( {:.2}% at dominance {} )",
+            lexicon.entries,
+            lexicon.fixed,
+            lexicon.fixed_ratio() * 100.0,
+            lexicon.dominance
+        );
+    }
+    if let Some(packed) = &report.packed {
+        println!(
+            "Packed Memory ({}): {:.2} MiB ( {} bytes, estimated )",
+            packed.language,
+            packed.bytes as f64 / (1024.0 * 1024.0),
+            packed.bytes
+        );
+    }
+    Ok(())
+}
+
+/// A segmentation or two-stage model loaded for pruning.
+enum PrunableModel {
+    Segmentation(Box<AdaBoost>),
+    TwoStage(Box<TwoStageLearner>),
+}
+
+impl PrunableModel {
+    /// Loads a model from its bytes, rejecting kinds that cannot be pruned.
+    fn load(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        match ModelKind::detect_bytes(bytes) {
+            ModelKind::AdaBoost => {
+                let mut learner = AdaBoost::new(0.01, 100);
+                learner.load_model_from_reader(bytes)?;
+                Ok(PrunableModel::Segmentation(Box::new(learner)))
+            }
+            ModelKind::TwoStage => {
+                let mut learner = TwoStageLearner::new();
+                learner.load_model_from_reader(bytes)?;
+                Ok(PrunableModel::TwoStage(Box::new(learner)))
+            }
+            ModelKind::AveragedPerceptron => {
+                Err("prune supports segmentation and two-stage models only".into())
+            }
+        }
+    }
+
+    /// Returns the language the input's packed tables were compiled for,
+    /// if it is a binary model that carries them.
+    fn compiled_language(&self) -> Option<Language> {
+        match self {
+            PrunableModel::Segmentation(learner) => learner.compiled_language(),
+            PrunableModel::TwoStage(learner) => learner.compiled_language(),
+        }
+    }
+
+    /// Prunes the (stage-1) features.
+    fn prune(&mut self, strategy: PruneStrategy) -> Result<usize, Box<dyn Error>> {
+        Ok(match self {
+            PrunableModel::Segmentation(learner) => learner.prune(strategy)?,
+            PrunableModel::TwoStage(learner) => learner.prune_stage1(strategy)?,
+        })
+    }
+}
+
+/// Prune a segmentation model to fewer features.
+/// This function removes the smallest-weight features of a segmentation
+/// model (or of a two-stage model's stage-1 boundary classifier) by top-N,
+/// by absolute weight threshold, or per template, keeping the bias
+/// unchanged. With `--sweep`, each candidate size is instead evaluated
+/// against a gold corpus and one row of feature count versus F1 is printed,
+/// for picking a size that trades quality against throughput.
+///
+/// # Arguments
+/// * `args` - The arguments for the prune command [`PruneArgs`].
+///
+/// # Returns
+/// Returns a Result indicating success or failure.
+async fn prune(args: PruneArgs) -> Result<(), Box<dyn Error>> {
+    let bytes = litsea::model_io::read_model_bytes(args.model_uri.as_str()).await?;
+
+    if let Some(sizes) = args.sweep {
+        let gold_path = args.gold.as_deref().ok_or("--sweep requires --gold")?;
+        let tsv = args.format == "tsv";
+        let gold: Vec<Vec<String>> = io::BufReader::new(File::open(gold_path)?)
+            .lines()
+            .map(|line| line.map(|line| evaluation::parse_gold_line(&line, tsv)))
+            .collect::<Result<_, _>>()?;
+
+        let stdout = io::stdout();
+        let mut writer = io::BufWriter::new(stdout.lock());
+        let header = "features\tword_precision\tword_recall\tword_f1\tboundary_f1";
+        if !write_output_line(&mut writer, header)? {
+            return Ok(());
+        }
+        for size in sizes {
+            let mut model = PrunableModel::load(&bytes)?;
+            let kept = model.prune(PruneStrategy::TopN(size))?;
+            let segmenter = match model {
+                PrunableModel::Segmentation(learner) => {
+                    Segmenter::try_with_learner(args.language, *learner)?
+                }
+                PrunableModel::TwoStage(learner) => {
+                    Segmenter::try_with_two_stage_learner(args.language, *learner)?
+                }
+            };
+            let metrics = evaluation::evaluate_segmentation(&segmenter, gold.iter().cloned());
+            let row = format!(
+                "{}\t{:.2}\t{:.2}\t{:.2}\t{:.2}",
+                kept,
+                metrics.word_precision,
+                metrics.word_recall,
+                metrics.word_f1,
+                metrics.boundary_f1
+            );
+            if !write_output_line(&mut writer, &row)? {
+                return Ok(());
+            }
+        }
+        return flush_output(&mut writer).map_err(Into::into);
+    }
+
+    let strategy = match (args.top_n, args.min_weight, args.per_template) {
+        (Some(n), _, _) => PruneStrategy::TopN(n),
+        (_, Some(threshold), _) => PruneStrategy::MinWeight(threshold),
+        (_, _, Some(n)) => PruneStrategy::PerTemplate(n),
+        _ => return Err("one of --top-n, --min-weight, --per-template is required".into()),
+    };
+    let output_file = args.output_file.ok_or("an output file is required")?;
+
+    let mut model = PrunableModel::load(&bytes)?;
+    // Pruning drops the input's packed tables; a binary output gets them
+    // recompiled for the same language.
+    let language = model.compiled_language();
+    let kept = model.prune(strategy)?;
+
+    let binary = ModelFormat::detect(&bytes) == ModelFormat::Binary;
+    let mut writer = io::BufWriter::new(File::create(output_file.as_path())?);
+    match (&model, binary) {
+        (PrunableModel::Segmentation(learner), true) => {
+            learner.save_binary_model_to_writer(&mut writer, language)?
+        }
+        (PrunableModel::Segmentation(learner), false) => {
+            learner.save_model_to_writer(&mut writer)?
+        }
+        (PrunableModel::TwoStage(learner), true) => {
+            learner.save_binary_model_to_writer(&mut writer, language)?
+        }
+        (PrunableModel::TwoStage(learner), false) => learner.save_model_to_writer(&mut writer)?,
+    }
+    writer.flush()?;
+
+    eprintln!("Pruned the model to {} features: {}", kept, output_file.display());
+    Ok(())
+}
+
+/// Select the sentences most worth annotating.
+/// This function segments every non-empty line of a raw corpus, ranks the
+/// lines by the uncertainty of their boundary decisions with a
+/// [`SentenceSelector`], and writes the top `--top` lines to standard
+/// output, most uncertain first, ready to be annotated.
+///
+/// # Arguments
+/// * `args` - The arguments for the select command [`SelectArgs`].
+///
+/// # Returns
+/// Returns a Result indicating success or failure.
+async fn select(args: SelectArgs) -> Result<(), Box<dyn Error>> {
+    let mut learner = AdaBoost::new(0.01, 100);
+    learner.load_model(args.model_uri.as_str()).await?;
+    let segmenter = Segmenter::try_with_learner(args.language, learner)?;
+
+    // Keep each line's 1-based number for --scores.
+    let mut numbers = Vec::new();
+    let mut sentences = Vec::new();
+    for (number, line) in
+        io::BufReader::new(File::open(args.corpus_file.as_path())?).lines().enumerate()
+    {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            numbers.push(number + 1);
+            sentences.push(line.to_string());
+        }
+    }
+
+    let mut selector = SentenceSelector::new()
+        .with_uncertainty(args.uncertainty)
+        .with_scale(args.scale);
+    if let Some(order) = args.diversity {
+        selector = selector.with_diversity(order, args.max_overlap);
+    }
+    let selected = selector.select(&segmenter, &sentences, args.top);
+
+    let stdout = io::stdout();
+    let mut writer = io::BufWriter::new(stdout.lock());
+    for choice in &selected {
+        let sentence = &sentences[choice.index];
+        let line = if args.scores {
+            format!("{}\t{:.4}\t{}", numbers[choice.index], choice.score, sentence)
+        } else {
+            sentence.clone()
+        };
+        if !write_output_line(&mut writer, &line)? {
+            return Ok(());
+        }
+    }
+    flush_output(&mut writer)?;
+
+    eprintln!("Selected {} of {} sentences", selected.len(), sentences.len());
+    Ok(())
+}
+
+/// Distill a model into a tag-free student.
+/// This function loads the teacher (a segmentation or two-stage model),
+/// labels every line of a raw corpus with its boundary decisions, leaving
+/// out those decided by less than `--min-margin`, and trains a tag-free
+/// boundary perceptron on the labels with [`BoundaryPerceptronTrainer`].
+/// The student segments on the pointwise fast path and declares the
+/// teacher's normalization.
+///
+/// # Arguments
+/// * `args` - The arguments for the distill command [`DistillArgs`].
+///
+/// # Returns
+/// Returns a Result indicating success or failure.
+async fn distill(args: DistillArgs) -> Result<(), Box<dyn Error>> {
+    let running = Arc::new(AtomicBool::new(true));
+    let r = running.clone();
+    ctrlc::set_handler(move || {
+        if r.load(Ordering::SeqCst) {
+            r.store(false, Ordering::SeqCst);
+        } else {
+            std::process::exit(0);
+        }
+    })?;
+
+    let bytes = litsea::model_io::read_model_bytes(args.teacher_uri.as_str()).await?;
+    let teacher = match ModelKind::detect_bytes(&bytes) {
+        ModelKind::AdaBoost => {
+            let mut learner = AdaBoost::new(0.01, 100);
+            learner.load_model_from_reader(bytes.as_slice())?;
+            Segmenter::try_with_learner(args.language, learner)?
+        }
+        ModelKind::TwoStage => {
+            let mut learner = TwoStageLearner::new();
+            learner.load_model_from_reader(bytes.as_slice())?;
+            Segmenter::try_with_two_stage_learner(args.language, learner)?
+        }
+        ModelKind::AveragedPerceptron => {
+            return Err("distill needs a segmentation or two-stage teacher model".into());
+        }
+    };
+
+    let threads = usize::from(args.threads);
+    let extractor = Extractor::new(args.language)
+        .with_normalization(teacher.normalization())
+        .with_threads(threads);
+    let mut trainer = BoundaryPerceptronTrainer::from_teacher(
+        args.num_epochs,
+        &extractor,
+        &teacher,
+        &args.corpus_file,
+        args.min_margin,
+        args.weight_by_margin,
+        true,
+    )?;
+    let mut metadata = ModelMetadata::new()
+        .with_language(args.language)
+        .with_corpus_checksum(metadata::checksum(File::open(&args.corpus_file)?)?);
+    if args.min_margin > 0.0 {
+        metadata = metadata.with_param("min_margin", args.min_margin);
+    }
+    if let Some(scale) = args.weight_by_margin {
+        metadata = metadata.with_param("weight_by_margin", scale);
+    }
+    trainer = trainer
+        .with_cutoff(FeatureCutoff::new(args.min_count))
+        .with_metadata(metadata)
+        .with_threads(threads);
+    if let Some(dev) = &args.dev {
+        trainer.load_dev_corpus(&extractor, dev, CorpusFormat::Space, true)?;
+    }
+    if let Some(patience) = args.patience {
+        trainer = trainer.with_patience(patience);
+    }
+    if let Some(seed) = args.seed {
+        trainer = trainer.with_seed(seed);
+    }
+
+    trainer.set_progress(progress_line());
+    let metrics = trainer.train(&running, &args.model_file);
+    end_progress_line();
+    let metrics = metrics?;
+
+    eprintln!("Result Metrics (Distilled Student):");
+    eprintln!("  Accuracy: {:.2}% ( {} )", metrics.accuracy, metrics.num_instances);
+    eprintln!("  Macro Precision: {:.2}%", metrics.macro_precision);
+    eprintln!("  Macro Recall: {:.2}%", metrics.macro_recall);
+    eprintln!("  Features Removed: {}", metrics.features_removed);
+    print_dev_curve("", &metrics.dev_curve);
+    Ok(())
+}
+
 /// Parses the command-line arguments and dispatches to the selected
 /// subcommand.
 ///
@@ -691,6 +1979,11 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         Commands::Train(args) => train(args).await,
         Commands::Segment(args) => segment(args).await,
         Commands::Evaluate(args) => evaluate(args).await,
+        Commands::Convert(args) => convert(args).await,
+        Commands::Inspect(args) => inspect(args).await,
+        Commands::Prune(args) => prune(args).await,
+        Commands::Select(args) => select(args).await,
+        Commands::Distill(args) => distill(args).await,
     }
 }
 
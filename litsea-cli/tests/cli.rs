@@ -1,7 +1,7 @@
 //! Integration tests for the litsea CLI binary.
 
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Output, Stdio};
 
 fn model_path(name: &str) -> PathBuf {
@@ -121,6 +121,122 @@ fn test_segment_threads_rejects_zero() {
     assert!(stderr.contains("--threads"), "unexpected stderr: {stderr}");
 }
 
+/// Pins `--user-dict`: a dictionary word comes out as one token, both
+/// single-threaded and with `--threads`, and the rest of the line is
+/// segmented by the model as before.
+#[test]
+fn test_segment_user_dict() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dict = dir.path().join("user.dict");
+    std::fs::write(&dict, "テストです\n").expect("write dictionary");
+    let model_owned = model_path("RWCP.model");
+    let model = model_owned.to_str().unwrap();
+    let dict = dict.to_str().unwrap();
+
+    for threads in ["1", "2"] {
+        let output = run_litsea(
+            &["segment", "--threads", threads, "--user-dict", dict, "-l", "japanese", model],
+            Some("これはテストです。\n"),
+        );
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "これ は テストです 。\n");
+    }
+
+    let output = run_litsea(
+        &["segment", "--user-dict", "/nonexistent/user.dict", "-l", "japanese", model],
+        Some("これはテストです。\n"),
+    );
+    assert!(!output.status.success());
+}
+
+/// Pins segment's `--patterns` and `--pattern-file`: matches come out as
+/// single tokens on both the sequential and parallel paths, and an
+/// invalid pattern is rejected before any input is read.
+#[test]
+fn test_segment_patterns() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let pattern_file = dir.path().join("patterns.txt");
+    std::fs::write(&pattern_file, "# ticket ids\n[A-Z]+-[0-9]+\n").expect("write patterns");
+    let model_owned = model_path("japanese.model");
+    let model = model_owned.to_str().unwrap();
+    let pattern_file = pattern_file.to_str().unwrap();
+
+    for threads in ["1", "2"] {
+        let output = run_litsea(
+            &[
+                "segment",
+                "--threads",
+                threads,
+                "--patterns",
+                "url,number",
+                "--pattern-file",
+                pattern_file,
+                model,
+            ],
+            Some("詳細はhttps://example.com/docsを参照、1,234円。\nJIRA-123を修正\n"),
+        );
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "詳細 は https://example.com/docs を 参照 、 1,234 円 。\nJIRA-123 を 修正\n"
+        );
+    }
+
+    let output = run_litsea(&["segment", "--patterns", "phone", model], Some("x\n"));
+    assert!(!output.status.success());
+    std::fs::write(dir.path().join("bad.txt"), "(\n").expect("write patterns");
+    let bad = dir.path().join("bad.txt");
+    let output =
+        run_litsea(&["segment", "--pattern-file", bad.to_str().unwrap(), model], Some("x\n"));
+    assert!(!output.status.success());
+}
+
+/// Pins segment's `--threshold`: an offset above every boundary score
+/// keeps each line whole, and a negative offset parses as a value rather
+/// than a flag.
+#[test]
+fn test_segment_threshold() {
+    let model_owned = model_path("RWCP.model");
+    let model = model_owned.to_str().unwrap();
+
+    let output = run_litsea(
+        &["segment", "--threshold", "1e9", "-l", "japanese", model],
+        Some("これはテストです。\n"),
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "これはテストです。\n");
+
+    let output = run_litsea(
+        &["segment", "--threshold", "-1e9", "-l", "japanese", model],
+        Some("これはテスト\n"),
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "こ れ は テ ス ト\n");
+}
+
+/// `--split-sentences` writes one output line per sentence of each input
+/// line, identically on the sequential and parallel paths.
+#[test]
+fn test_segment_split_sentences() {
+    let model_owned = model_path("RWCP.model");
+    let model = model_owned.to_str().unwrap();
+    let input = "これはテストです。これもテストです。\n";
+
+    let output = run_litsea(&["segment", "-l", "japanese", model], Some(input));
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 1);
+
+    let expected = "これ は テスト です 。\nこれ も テスト です 。\n";
+    for threads in ["1", "2"] {
+        let output = run_litsea(
+            &["segment", "--split-sentences", "--threads", threads, "-l", "japanese", model],
+            Some(input),
+        );
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected, "threads={threads}");
+    }
+}
+
 /// Pins segment's `--pos` routing: the two-stage model must be loaded and
 /// word/POS pairs printed (same expectation as the golden suite).
 #[test]
@@ -363,6 +479,66 @@ fn test_extract_pos_tsv_format() {
     );
 }
 
+/// Pins `extract --format partial`: only the annotated positions become
+/// rows, a malformed line fails the extraction, and `train --corpus`
+/// accepts the format too.
+#[test]
+fn test_extract_partial_format() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("corpus.txt");
+    std::fs::write(&corpus, "こ-れ|は|テ ス ト\nテ-ス-ト|で-す|。\n").expect("write corpus");
+    let features = dir.path().join("features.txt");
+
+    let output = run_litsea(
+        &[
+            "extract",
+            "--format",
+            "partial",
+            corpus.to_str().unwrap(),
+            features.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let content = std::fs::read_to_string(&features).expect("read features");
+    // 3 annotated positions after the first character, then 5.
+    assert_eq!(content.lines().count(), 8);
+
+    let model = dir.path().join("out.model");
+    let output = run_litsea(
+        &[
+            "train",
+            "--corpus",
+            corpus.to_str().unwrap(),
+            "--format",
+            "partial",
+            "-l",
+            "ja",
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    std::fs::write(&corpus, "これ|は\n").expect("write corpus");
+    let output = run_litsea(
+        &[
+            "extract",
+            "--format",
+            "partial",
+            corpus.to_str().unwrap(),
+            features.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("partial annotation"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
 /// Pins the evaluate subcommand: known model + tiny gold corpus must print
 /// the metrics block with plausible percentages.
 #[test]
@@ -390,6 +566,39 @@ fn test_evaluate_segmentation_output() {
     assert!(stderr.contains("Word F1: 100.00%"), "unexpected output: {stderr}");
 }
 
+/// Pins evaluate's `--sweep-threshold`: one tab-separated row per offset
+/// after a header, with the model's own operating point (offset 0)
+/// scoring exactly like the plain evaluation.
+#[test]
+fn test_evaluate_sweep_threshold() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let gold = dir.path().join("gold.txt");
+    std::fs::write(&gold, "これ は テスト です 。\n").expect("write gold");
+
+    let output = run_litsea(
+        &[
+            "evaluate",
+            "-l",
+            "japanese",
+            "--sweep-threshold=-1e9,0,1e9",
+            model_path("japanese.model").to_str().unwrap(),
+            gold.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<Vec<&str>> = stdout.lines().map(|line| line.split('\t').collect()).collect();
+    assert_eq!(rows.len(), 4, "unexpected output: {stdout}");
+    assert_eq!(rows[0][0], "threshold");
+    assert_eq!(rows[2], ["0", "100.00", "100.00", "100.00", "100.00"]);
+    // Every position is a boundary at -1e9: full recall, lower precision.
+    assert_eq!(rows[1][2], "100.00");
+    assert_ne!(rows[1][1], "100.00");
+    // No position is a boundary at 1e9.
+    assert_eq!(rows[3][2], "0.00");
+}
+
 /// Pins evaluate's `--format tsv` routing: a space token in the gold TSV is
 /// excluded from scoring but preserved in the reconstructed text.
 #[test]
@@ -514,6 +723,603 @@ fn test_train_smoke() {
     assert!(perceptron_model.exists() && std::fs::metadata(&perceptron_model).unwrap().len() > 0);
 }
 
+/// `--normalize` flows from extraction through the trained model's
+/// declaration to segmentation, whose output keeps the original text.
+#[test]
+fn test_dictionary_pipeline() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("corpus.txt");
+    std::fs::write(
+        &corpus,
+        "東京 都 から 神奈川 県 へ 引っ越し た\nこれ は テスト です 。\n".repeat(10),
+    )
+    .expect("write corpus");
+    let dictionary = dir.path().join("words.txt");
+    std::fs::write(&dictionary, "神奈川\n引っ越し\n").expect("write dictionary");
+    let features = dir.path().join("features.txt");
+    let output = run_litsea(
+        &[
+            "extract",
+            "--dictionary",
+            dictionary.to_str().unwrap(),
+            corpus.to_str().unwrap(),
+            features.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let extracted = std::fs::read_to_string(&features).expect("read features");
+    assert!(extracted.contains("\tDL:3") && extracted.contains("\tDI:4"));
+
+    let model = dir.path().join("out.model");
+    let output = run_litsea(
+        &[
+            "train",
+            "--boundary-perceptron",
+            "--num-epochs",
+            "3",
+            "--dictionary",
+            dictionary.to_str().unwrap(),
+            features.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let trained = std::fs::read_to_string(&model).expect("read model");
+    assert!(trained.contains("#dictionary\t引っ越し\n#dictionary\t神奈川\n"));
+
+    // The model carries its word list: segment needs no extra file.
+    let output =
+        run_litsea(&["segment", model.to_str().unwrap()], Some("東京都から神奈川県へ引っ越した\n"));
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "東京 都 から 神奈川 県 へ 引っ越し た\n"
+    );
+
+    let output = run_litsea(
+        &["train", "--perceptron", "--dictionary", dictionary.to_str().unwrap(), "f", "m"],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_normalize_pipeline() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("corpus.txt");
+    std::fs::write(&corpus, "ﾃｽﾄ を 書く 。\nＡＢＣ の ﾃﾞｰﾀ 。\n").expect("write corpus");
+    let features = dir.path().join("features.txt");
+    let output = run_litsea(
+        &[
+            "extract",
+            "--normalize",
+            "nfkc",
+            corpus.to_str().unwrap(),
+            features.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let extracted = std::fs::read_to_string(&features).expect("read features");
+    assert!(extracted.contains("テ") && extracted.contains("AB"));
+    assert!(!extracted.contains('ﾃ') && !extracted.contains('Ａ'));
+
+    let model = dir.path().join("out.model");
+    let output = run_litsea(
+        &[
+            "train",
+            "-i",
+            "20",
+            "--normalize",
+            "nfkc",
+            features.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let trained = std::fs::read_to_string(&model).expect("read model");
+    assert!(trained.starts_with("#normalization\tnfkc\n"));
+
+    let output = run_litsea(&["segment", model.to_str().unwrap()], Some("ﾃｽﾄを書く。\n"));
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let segmented = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(segmented.replace(' ', ""), "ﾃｽﾄを書く。\n");
+
+    // The override applies to a model that declares nothing.
+    let output = run_litsea(
+        &["segment", "--normalize", "nfkc", model_path("RWCP.model").to_str().unwrap()],
+        Some("これはﾃｽﾄです。\n"),
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "これ は ﾃｽﾄ です 。\n");
+}
+
+/// `train --boundary-perceptron` trains straight from ordinary extract
+/// output to an AdaBoost-format model that `segment` loads.
+#[test]
+fn test_train_boundary_perceptron() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("corpus.txt");
+    std::fs::write(&corpus, "これ は テスト です 。\nテスト を 書く 。\n").expect("write corpus");
+    let features = dir.path().join("features.txt");
+    let output =
+        run_litsea(&["extract", corpus.to_str().unwrap(), features.to_str().unwrap()], None);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let model = dir.path().join("out.model");
+    let output = run_litsea(
+        &[
+            "train",
+            "--boundary-perceptron",
+            "--num-epochs",
+            "5",
+            "-l",
+            "japanese",
+            features.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Result Metrics (Boundary Perceptron)"),
+        "unexpected output: {stderr}"
+    );
+    let trained = std::fs::read_to_string(&model).expect("read model");
+    assert!(trained.contains("#param.epochs\t5\n"), "unexpected model: {trained}");
+
+    let output = run_litsea(&["segment", model.to_str().unwrap()], Some("これはテストです。\n"));
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "これ は テスト です 。\n");
+
+    // Incremental training is not supported: the perceptron is gone once
+    // collapsed.
+    let output = run_litsea(
+        &[
+            "train",
+            "--boundary-perceptron",
+            "-m",
+            model.to_str().unwrap(),
+            features.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_extract_threads_output_identical() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("corpus.txt");
+    let mut content = String::new();
+    for i in 0..200 {
+        content.push_str(if i % 2 == 0 {
+            "これ は テスト です 。\n"
+        } else {
+            "テスト を 書く 。\n"
+        });
+    }
+    std::fs::write(&corpus, content).expect("write corpus");
+
+    let mut outputs = Vec::new();
+    for threads in ["1", "4"] {
+        let features = dir.path().join(format!("features_{threads}.txt"));
+        let output = run_litsea(
+            &[
+                "extract",
+                "--threads",
+                threads,
+                corpus.to_str().unwrap(),
+                features.to_str().unwrap(),
+            ],
+            None,
+        );
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        outputs.push(std::fs::read(&features).expect("read features"));
+    }
+    assert!(!outputs[0].is_empty());
+    assert_eq!(outputs[0], outputs[1]);
+}
+
+#[test]
+fn test_train_from_corpus() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("corpus.txt");
+    std::fs::write(&corpus, "これ は テスト です 。\nテスト を 書く 。\n").expect("write corpus");
+    let features = dir.path().join("features.txt");
+    let output =
+        run_litsea(&["extract", corpus.to_str().unwrap(), features.to_str().unwrap()], None);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let expected = dir.path().join("expected.model");
+    let output = run_litsea(
+        &["train", "-l", "ja", features.to_str().unwrap(), expected.to_str().unwrap()],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let model = dir.path().join("out.model");
+    let output = run_litsea(
+        &[
+            "train",
+            "--corpus",
+            corpus.to_str().unwrap(),
+            "-l",
+            "ja",
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    // Same weights as the extract + train route; only the checksummed
+    // training input differs.
+    let weights = |path: &std::path::Path| -> Vec<String> {
+        std::fs::read_to_string(path)
+            .expect("read model")
+            .lines()
+            .filter(|line| !line.starts_with("#corpus"))
+            .map(str::to_string)
+            .collect()
+    };
+    assert_eq!(weights(&model), weights(&expected));
+
+    let output = run_litsea(&["segment", model.to_str().unwrap()], Some("これはテストです。\n"));
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "これ は テスト です 。\n");
+
+    // The corpus's language must be named.
+    let output =
+        run_litsea(&["train", "--corpus", corpus.to_str().unwrap(), model.to_str().unwrap()], None);
+    assert!(!output.status.success());
+}
+
+/// `train --language` records the language, the training parameters, and
+/// the features checksum, and `segment`/`evaluate` reject the model for any
+/// other language.
+#[test]
+fn test_train_records_metadata() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let features = dir.path().join("features.txt");
+    std::fs::write(&features, "1\tUW4:a\n-1\tUW4:b\n1\tUW4:a\n-1\tUW4:b\n")
+        .expect("write features");
+    let model = dir.path().join("out.model");
+    let output = run_litsea(
+        &[
+            "train",
+            "-l",
+            "korean",
+            "-i",
+            "5",
+            features.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let trained = std::fs::read_to_string(&model).expect("read model");
+    for header in ["#language\tkorean\n", "#corpus\tfnv1a64:", "#param.iterations\t5\n"] {
+        assert!(trained.contains(header), "missing {header:?} in:\n{trained}");
+    }
+
+    let output = run_litsea(&["segment", "-l", "korean", model.to_str().unwrap()], Some("ab\n"));
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let output = run_litsea(&["segment", "-l", "japanese", model.to_str().unwrap()], Some("ab\n"));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("trained for korean"), "unexpected stderr: {stderr}");
+
+    let gold = dir.path().join("gold.txt");
+    std::fs::write(&gold, "a b\n").expect("write gold");
+    let output = run_litsea(
+        &["evaluate", "-l", "japanese", model.to_str().unwrap(), gold.to_str().unwrap()],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+/// `train --min-count` drops rare features before training, reports how
+/// many in the metrics, and records the cutoff; `--min-count-template`
+/// overrides it per template.
+#[test]
+fn test_train_min_count() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let features = dir.path().join("features.txt");
+    std::fs::write(&features, "1\tUW4:a\tBW1:x\n-1\tUW4:b\n1\tUW4:a\n-1\tUW4:b\tUW4:c\n")
+        .expect("write features");
+    let model = dir.path().join("out.model");
+
+    let output = run_litsea(
+        &["train", "--min-count", "2", features.to_str().unwrap(), model.to_str().unwrap()],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Features Removed: 2\n"), "unexpected stderr: {stderr}");
+    let trained = std::fs::read_to_string(&model).expect("read model");
+    assert!(trained.contains("#param.min_count\t2\n"), "missing min_count in:\n{trained}");
+    assert!(!trained.contains("UW4:c"), "rare feature kept in:\n{trained}");
+
+    let output = run_litsea(
+        &[
+            "train",
+            "--boundary-perceptron",
+            "--min-count-template",
+            "UW4=2",
+            features.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Features Removed: 1\n"), "unexpected stderr: {stderr}");
+
+    let output = run_litsea(
+        &[
+            "train",
+            "--min-count-template",
+            "UW4",
+            features.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+/// `train --dev` scores every perceptron epoch on a held-out set and
+/// prints the curve; `--patience` stops once it stops improving. AdaBoost
+/// training rejects `--dev`.
+#[test]
+fn test_train_dev_early_stopping() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("corpus.txt");
+    std::fs::write(&corpus, "これ は テスト です 。\nテスト を 書く 。\n".repeat(5))
+        .expect("write corpus");
+    let dev = dir.path().join("dev.txt");
+    std::fs::write(&dev, "これ を 書く 。\n").expect("write dev");
+    let model = dir.path().join("out.model");
+
+    let output = run_litsea(
+        &[
+            "train",
+            "--boundary-perceptron",
+            "--corpus",
+            corpus.to_str().unwrap(),
+            "-l",
+            "ja",
+            "--num-epochs",
+            "20",
+            "--dev",
+            dev.to_str().unwrap(),
+            "--patience",
+            "2",
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Dev Accuracy (epoch 1): "), "unexpected stderr: {stderr}");
+    assert!(stderr.contains("Best Dev Epoch: "), "unexpected stderr: {stderr}");
+    // The training set is learned within a few epochs, so patience cuts the
+    // 20-epoch budget short.
+    assert!(!stderr.contains("(epoch 20)"), "unexpected stderr: {stderr}");
+
+    let output = run_litsea(
+        &[
+            "train",
+            "--corpus",
+            corpus.to_str().unwrap(),
+            "-l",
+            "ja",
+            "--dev",
+            dev.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+/// `train --seed` shuffles perceptron training reproducibly: the same
+/// seed writes a byte-identical model file and records the seed.
+#[test]
+fn test_train_seed_is_reproducible() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let features = dir.path().join("features.txt");
+    std::fs::write(&features, "B\tf1\tf2\nO\tf2\tf3\nB\tf1\tf4\nO\tf3\tf5\nB\tf4\tf5\n")
+        .expect("write features");
+
+    let train = |name: &str| {
+        let model = dir.path().join(name);
+        let output = run_litsea(
+            &[
+                "train",
+                "--perceptron",
+                "--seed",
+                "42",
+                features.to_str().unwrap(),
+                model.to_str().unwrap(),
+            ],
+            None,
+        );
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        std::fs::read(model).expect("read model")
+    };
+    let first = train("first.model");
+    assert_eq!(first, train("second.model"));
+    assert!(String::from_utf8_lossy(&first).contains("#param.seed\t42\n"));
+
+    let model = dir.path().join("adaboost.model");
+    let output = run_litsea(
+        &["train", "--seed", "42", features.to_str().unwrap(), model.to_str().unwrap()],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+/// `train --threads` trains perceptron epochs by parameter mixing: the
+/// model is reproducible for a thread count, which it records, and
+/// AdaBoost training rejects the option.
+#[test]
+fn test_train_threads_is_reproducible() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let features = dir.path().join("features.txt");
+    std::fs::write(&features, "B\tf1\tf2\nO\tf2\tf3\nB\tf1\tf4\nO\tf3\tf5\nB\tf4\tf5\n")
+        .expect("write features");
+
+    let train = |name: &str| {
+        let model = dir.path().join(name);
+        let output = run_litsea(
+            &[
+                "train",
+                "--perceptron",
+                "--threads",
+                "2",
+                features.to_str().unwrap(),
+                model.to_str().unwrap(),
+            ],
+            None,
+        );
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        std::fs::read(model).expect("read model")
+    };
+    let first = train("first.model");
+    assert_eq!(first, train("second.model"));
+    assert!(String::from_utf8_lossy(&first).contains("#param.threads\t2\n"));
+
+    let model = dir.path().join("adaboost.model");
+    let output = run_litsea(
+        &["train", "--threads", "2", features.to_str().unwrap(), model.to_str().unwrap()],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+/// `train --resume` continues from a `--checkpoint` file: resuming the
+/// epoch-3 checkpoint of a 4-epoch run writes the run's model byte for
+/// byte, and a checkpoint of other training data is rejected.
+#[test]
+fn test_train_resume_from_checkpoint() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let features = dir.path().join("features.txt");
+    std::fs::write(&features, "B\tf1\tf2\nO\tf2\tf3\nB\tf1\tf4\nO\tf3\tf5\nB\tf4\tf5\n")
+        .expect("write features");
+    let checkpoint = dir.path().join("train.ckpt");
+
+    let train = |features: &std::path::Path, name: &str, extra: &[&str]| {
+        let model = dir.path().join(name);
+        let mut args = vec!["train", "--perceptron", "--num-epochs", "4", "--seed", "7"];
+        args.extend_from_slice(extra);
+        args.extend([features.to_str().unwrap(), model.to_str().unwrap()]);
+        (run_litsea(&args, None), model)
+    };
+    let (output, model) = train(
+        &features,
+        "full.model",
+        &["--checkpoint", checkpoint.to_str().unwrap(), "--checkpoint-every", "3"],
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let full = std::fs::read(model).expect("read model");
+
+    let (output, model) =
+        train(&features, "resumed.model", &["--resume", checkpoint.to_str().unwrap()]);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Resuming from "), "unexpected stderr: {stderr}");
+    assert!(
+        stderr.contains("(3 epoch(s) of stage 1 completed)"),
+        "unexpected stderr: {stderr}"
+    );
+    assert_eq!(full, std::fs::read(model).expect("read model"));
+
+    let other = dir.path().join("other.txt");
+    std::fs::write(&other, "B\tf1\nO\tf2\n").expect("write features");
+    let (output, _) = train(&other, "other.model", &["--resume", checkpoint.to_str().unwrap()]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("different training data"), "unexpected stderr: {stderr}");
+
+    let model = dir.path().join("adaboost.model");
+    let output = run_litsea(
+        &[
+            "train",
+            "--resume",
+            checkpoint.to_str().unwrap(),
+            features.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+/// Pins `convert`: a text model becomes a binary one that segments
+/// identically (with or without packed tables), and converting back yields
+/// a text model that segments the same way again.
+#[test]
+fn test_convert_round_trip() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    for (language, name, pos, input) in [
+        ("japanese", "japanese.model", false, "これはテストです。\n"),
+        ("korean", "korean_pos.model", true, "안녕하세요. 반갑습니다.\n"),
+    ] {
+        let text = model_path(name);
+        let segment = |model: &std::path::Path| {
+            let mut args = vec!["segment", "-l", language];
+            if pos {
+                args.push("--pos");
+            }
+            args.push(model.to_str().unwrap());
+            let output = run_litsea(&args, Some(input));
+            assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+            output.stdout
+        };
+        let expected = segment(&text);
+
+        let binary = dir.path().join(format!("{name}.bin"));
+        let output = run_litsea(
+            &["convert", "-l", language, text.to_str().unwrap(), binary.to_str().unwrap()],
+            None,
+        );
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert!(std::fs::read(&binary).expect("read binary").starts_with(b"litsea-binary v1 "));
+        assert_eq!(segment(&binary), expected);
+
+        let plain = dir.path().join(format!("{name}.plain.bin"));
+        let output =
+            run_litsea(&["convert", text.to_str().unwrap(), plain.to_str().unwrap()], None);
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert_eq!(segment(&plain), expected);
+
+        let back = dir.path().join(format!("{name}.txt"));
+        let output =
+            run_litsea(&["convert", binary.to_str().unwrap(), back.to_str().unwrap()], None);
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        let again = dir.path().join(format!("{name}.again.bin"));
+        let output = run_litsea(
+            &["convert", "-l", language, back.to_str().unwrap(), again.to_str().unwrap()],
+            None,
+        );
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert_eq!(segment(&again), expected);
+
+        // --language only applies when writing a binary model.
+        let output = run_litsea(
+            &["convert", "-l", language, binary.to_str().unwrap(), back.to_str().unwrap()],
+            None,
+        );
+        assert!(!output.status.success());
+    }
+}
+
 /// A missing model path must exit non-zero with an `Error:` line on stderr.
 #[test]
 fn test_missing_model_error() {
@@ -588,3 +1394,233 @@ fn test_broken_pipe_exits_zero() {
         stderr
     );
 }
+
+/// `inspect` reports a segmentation model as text, and a two-stage model's
+/// lexicon and stage-2 templates as JSON.
+#[test]
+fn test_inspect_output() {
+    let output = run_litsea(&["inspect", model_path("japanese.model").to_str().unwrap()], None);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in ["Kind: AdaBoost\n", "Classifier (segmentation):\n", "  Pointwise: no\n"] {
+        assert!(stdout.contains(line), "missing {line:?} in:\n{stdout}");
+    }
+    assert!(stdout.contains("Packed Memory (japanese):"), "unexpected output:\n{stdout}");
+
+    let output = run_litsea(
+        &[
+            "inspect",
+            "--json",
+            "-l",
+            "korean",
+            model_path("korean_pos.model").to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("{\"kind\":\"two-stage\""), "unexpected output:\n{stdout}");
+    assert!(stdout.contains("\"name\":\"stage2\""), "unexpected output:\n{stdout}");
+    assert!(stdout.contains("\"dominance\":0.99"), "unexpected output:\n{stdout}");
+    assert!(
+        stdout.contains("\"packed\":{\"language\":\"korean\""),
+        "unexpected output:\n{stdout}"
+    );
+    assert!(stdout.ends_with("}\n"));
+}
+
+/// `prune --top-n` writes a smaller model that still segments, and
+/// `--sweep` prints one row of feature count versus F1 per size.
+#[test]
+fn test_prune_and_sweep() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let model = model_path("japanese.model");
+    let pruned = dir.path().join("pruned.model");
+    let output = run_litsea(
+        &["prune", "--top-n", "5000", model.to_str().unwrap(), pruned.to_str().unwrap()],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let text = std::fs::read_to_string(&pruned).expect("read pruned model");
+    assert_eq!(text.lines().filter(|line| line.contains('\t')).count(), 5000);
+    let output = run_litsea(&["segment", pruned.to_str().unwrap()], Some("これはテストです。\n"));
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "これ は テスト です 。\n");
+
+    // A binary input keeps its packed tables: pruning it writes as much as
+    // converting the pruned text model for the same language does (the text
+    // round trip may round the bias, so the bytes are not compared).
+    let convert = |input: &Path, output: &Path| {
+        let args = ["convert", "-l", "japanese", input.to_str().unwrap(), output.to_str().unwrap()];
+        let result = run_litsea(&args, None);
+        assert!(result.status.success(), "stderr: {}", String::from_utf8_lossy(&result.stderr));
+    };
+    let binary = dir.path().join("japanese.bin");
+    convert(&model, &binary);
+    let pruned_binary = dir.path().join("pruned.bin");
+    let output = run_litsea(
+        &[
+            "prune",
+            "--top-n",
+            "5000",
+            binary.to_str().unwrap(),
+            pruned_binary.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let expected = dir.path().join("expected.bin");
+    convert(&pruned, &expected);
+    let len = |path: &Path| std::fs::metadata(path).expect("stat binary").len();
+    assert_eq!(len(&pruned_binary), len(&expected));
+
+    let gold = dir.path().join("gold.txt");
+    std::fs::write(&gold, "これ は テスト です 。\n").expect("write gold");
+    let output = run_litsea(
+        &[
+            "prune",
+            "--sweep",
+            "10,5000",
+            "--gold",
+            gold.to_str().unwrap(),
+            model.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<&str> = stdout.lines().collect();
+    assert_eq!(rows[0], "features\tword_precision\tword_recall\tword_f1\tboundary_f1");
+    assert!(rows[1].starts_with("10\t"), "unexpected output:\n{stdout}");
+    assert_eq!(rows[2], "5000\t100.00\t100.00\t100.00\t100.00");
+
+    // A strategy (or --sweep) is required.
+    let output = run_litsea(&["prune", model.to_str().unwrap(), pruned.to_str().unwrap()], None);
+    assert!(!output.status.success());
+}
+
+/// Pins select's output: the top-K non-empty lines, most uncertain first,
+/// with `--scores` line numbers counting the skipped blank line, and
+/// `--diversity` passing over a duplicate.
+#[test]
+fn test_select_output() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("raw.txt");
+    std::fs::write(
+        &corpus,
+        "これはテストです。\n\nきゃりーぱみゅぱみゅが歌う。\n東京都に住んでいます。\n東京都に住んでいます。\n",
+    )
+    .expect("write corpus");
+    let model = model_path("japanese.model");
+    let select = |extra: &[&str]| {
+        let mut args = vec!["select"];
+        args.extend_from_slice(extra);
+        args.extend([model.to_str().unwrap(), corpus.to_str().unwrap()]);
+        let output = run_litsea(&args, None);
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let stdout = select(&["-k", "1"]);
+    assert_eq!(stdout, "きゃりーぱみゅぱみゅが歌う。\n");
+
+    let stdout = select(&["--scores", "--uncertainty", "entropy"]);
+    let numbers: Vec<&str> = stdout.lines().map(|line| line.split('\t').next().unwrap()).collect();
+    assert_eq!(numbers.len(), 4, "unexpected output:\n{stdout}");
+    assert_eq!(numbers[0], "3");
+
+    let stdout = select(&["--diversity", "2"]);
+    assert_eq!(stdout.lines().filter(|line| line.starts_with("東京都")).count(), 1);
+    assert_eq!(stdout.lines().count(), 3);
+
+    // --max-overlap only applies with --diversity.
+    let output = run_litsea(
+        &[
+            "select",
+            "--max-overlap",
+            "0.2",
+            model.to_str().unwrap(),
+            corpus.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_distill_output() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let corpus = dir.path().join("raw.txt");
+    std::fs::write(&corpus, "これはテストです。\n東京都に住んでいます。\n\n")
+        .expect("write corpus");
+    let student = dir.path().join("student.model");
+    let teacher = model_path("japanese.model");
+    let output = run_litsea(
+        &[
+            "distill",
+            "--num-epochs",
+            "5",
+            teacher.to_str().unwrap(),
+            corpus.to_str().unwrap(),
+            student.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Distilled Student"));
+
+    // The student reproduces the teacher's segmentation of its corpus.
+    let input = "これはテストです。\n東京都に住んでいます。\n";
+    let want = run_litsea(&["segment", teacher.to_str().unwrap()], Some(input));
+    let got = run_litsea(&["segment", student.to_str().unwrap()], Some(input));
+    assert!(got.status.success(), "stderr: {}", String::from_utf8_lossy(&got.stderr));
+    assert_eq!(got.stdout, want.stdout);
+
+    // Weighting by the teacher's margins trains a different student, and
+    // the scale must be positive.
+    let weighted = dir.path().join("weighted.model");
+    for (scale, ok) in [("1000", true), ("0", false)] {
+        let output = run_litsea(
+            &[
+                "distill",
+                "--num-epochs",
+                "5",
+                "--weight-by-margin",
+                scale,
+                teacher.to_str().unwrap(),
+                corpus.to_str().unwrap(),
+                weighted.to_str().unwrap(),
+            ],
+            None,
+        );
+        assert_eq!(
+            output.status.success(),
+            ok,
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let weights = |path: &Path| {
+        let model = std::fs::read_to_string(path).expect("read student");
+        model
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    assert_ne!(weights(&weighted), weights(&student));
+
+    // A margin no decision reaches leaves nothing to train on.
+    let output = run_litsea(
+        &[
+            "distill",
+            "--min-margin",
+            "1e9",
+            teacher.to_str().unwrap(),
+            corpus.to_str().unwrap(),
+            student.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert!(!output.status.success());
+}
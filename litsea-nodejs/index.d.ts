@@ -84,6 +84,8 @@ export declare class PerceptronTrainer {
    * # Arguments
    * * `num_epochs` - Number of passes over the training data.
    * * `features_path` - Path to the features file.
+   * * `seed` - Seed for shuffling the instances every epoch; omit to
+   *   keep the file order.
    *
    * # Returns
    * The new `PerceptronTrainer`.
@@ -92,18 +94,20 @@ export declare class PerceptronTrainer {
    * Throws an error with the `io` or `parse` code if the features file
    * cannot be read.
    */
-  constructor(numEpochs: number, featuresPath: string)
+  constructor(numEpochs: number, featuresPath: string, seed?: number | undefined | null)
   /**
    * Trains the model off the event loop and writes it to `modelPath`.
    *
    * # Arguments
    * * `model_path` - Path of the model file to write.
    * * `cancel` - Optional `CancelToken`.
+   * * `on_progress` - Optional callback receiving a `TrainingProgress`
+   *   after every epoch, on the event loop.
    *
    * # Returns
    * A promise resolving to the training metrics.
    */
-  train(modelPath: string, cancel?: CancelToken | undefined | null): Promise<MulticlassMetrics>
+  train(modelPath: string, cancel?: CancelToken | undefined | null, onProgress?: ((arg: JsTrainingProgress) => unknown) | undefined | null): Promise<MulticlassMetrics>
 }
 export type JsPerceptronTrainer = PerceptronTrainer
 
@@ -199,6 +203,17 @@ export declare class Segmenter {
    * The tokens, with `pos` set to `null`.
    */
   segmentTokens(text: string): Array<Token>
+  /**
+   * Splits a sentence into tokens carrying byte offsets and a boundary
+   * confidence `score`.
+   *
+   * # Arguments
+   * * `text` - The sentence to segment.
+   *
+   * # Returns
+   * The tokens, with `score` set and `pos` set to `null`.
+   */
+  segmentWithScores(text: string): Array<Token>
   /**
    * Splits a sentence into tokens and tags each with a UPOS tag.
    *
@@ -255,11 +270,13 @@ export declare class Trainer {
    * * `model_path` - Path of the model file to write.
    * * `cancel` - Optional `CancelToken`; cancelling stops training early
    *   and still writes the partially trained model.
+   * * `on_progress` - Optional callback receiving a `TrainingProgress`
+   *   after every boosting iteration, on the event loop.
    *
    * # Returns
    * A promise resolving to the training metrics.
    */
-  train(modelPath: string, cancel?: CancelToken | undefined | null): Promise<BinaryMetrics>
+  train(modelPath: string, cancel?: CancelToken | undefined | null, onProgress?: ((arg: JsTrainingProgress) => unknown) | undefined | null): Promise<BinaryMetrics>
 }
 export type JsTrainer = Trainer
 
@@ -280,6 +297,8 @@ export declare class TwoStageTrainer {
    *   `.lexicon` files.
    * * `dominance` - Lexicon dominance threshold in `(0.5, 1.0]`;
    *   defaults to 0.99.
+   * * `seed` - Seed for shuffling the instances of both stages every
+   *   epoch; omit to keep the file order.
    *
    * # Returns
    * The new `TwoStageTrainer`.
@@ -288,7 +307,7 @@ export declare class TwoStageTrainer {
    * Throws an error with the `invalid_argument` code if `dominance` is
    * out of range, or `io` / `parse` if the feature files cannot be read.
    */
-  constructor(numEpochs: number, featuresPrefix: string, dominance?: number | undefined | null)
+  constructor(numEpochs: number, featuresPrefix: string, dominance?: number | undefined | null, seed?: number | undefined | null)
   /** Whether this trainer can still be used. */
   get available(): boolean
   /**
@@ -297,6 +316,8 @@ export declare class TwoStageTrainer {
    * # Arguments
    * * `model_path` - Path of the model file to write.
    * * `cancel` - Optional `CancelToken`.
+   * * `on_progress` - Optional callback receiving a `TrainingProgress`
+   *   after every epoch of either stage, on the event loop.
    *
    * # Returns
    * A promise resolving to the metrics of both stages.
@@ -305,7 +326,7 @@ export declare class TwoStageTrainer {
    * Rejects with an `invalid_argument` error if the trainer has already
    * been used.
    */
-  train(modelPath: string, cancel?: CancelToken | undefined | null): Promise<TwoStageMetrics>
+  train(modelPath: string, cancel?: CancelToken | undefined | null, onProgress?: ((arg: JsTrainingProgress) => unknown) | undefined | null): Promise<TwoStageMetrics>
 }
 export type JsTwoStageTrainer = TwoStageTrainer
 
@@ -356,6 +377,35 @@ export interface JsMulticlassMetrics {
   goldPerClass: Record<string, number>
 }
 
+/**
+ * The progress of a training run after one boosting iteration or
+ * perceptron epoch, as passed to a `train(..., onProgress)` callback.
+ *
+ * All percentages are 0-100.
+ */
+export interface JsTrainingProgress {
+  /**
+   * The model being trained: `"boosting"`, `"perceptron"`, `"boundary"`,
+   * or `"tagging"`.
+   */
+  stage: string
+  /** The iteration or epoch just completed, counting from 1. */
+  iteration: number
+  /** The configured number of iterations or epochs. */
+  total: number
+  /** The error rate of the iteration, as a percentage. */
+  errorRate: number
+  /** The number of updates the iteration made. */
+  updates: number
+  /**
+   * The development-set accuracy, as a percentage, when a development
+   * set is loaded.
+   */
+  devAccuracy?: number
+  /** Seconds since training started. */
+  elapsed: number
+}
+
 /** Metrics from training a two-stage model: one set per stage. */
 export interface JsTwoStageMetrics {
   /** Boundary-classifier (stage 1) metrics. */
@@ -396,6 +446,11 @@ export interface Token {
   start: number
   /** Ending byte offset (exclusive) in the input string. */
   end: number
+  /**
+   * The token's boundary confidence from `segmentWithScores` (small
+   * values flag tokens worth reviewing), or `undefined` otherwise.
+   */
+  score?: number
 }
 
 /**
@@ -1,8 +1,8 @@
-//! Training metric objects.
+//! Training metric and progress objects.
 
 use std::collections::HashMap;
 
-use litsea::{BinaryMetrics, MulticlassMetrics, TwoStageMetrics};
+use litsea::{BinaryMetrics, MulticlassMetrics, TrainingProgress, TwoStageMetrics};
 
 /// Metrics from training a binary (segmentation) model.
 ///
@@ -140,3 +140,48 @@ mod tests {
         assert_eq!(converted.get("NOUN"), Some(&42u32));
     }
 }
+
+/// The progress of a training run after one boosting iteration or
+/// perceptron epoch, as passed to a `train(..., onProgress)` callback.
+///
+/// All percentages are 0-100.
+#[napi(object)]
+pub struct JsTrainingProgress {
+    /// The model being trained: `"boosting"`, `"perceptron"`, `"boundary"`,
+    /// or `"tagging"`.
+    pub stage: String,
+    /// The iteration or epoch just completed, counting from 1.
+    pub iteration: u32,
+    /// The configured number of iterations or epochs.
+    pub total: u32,
+    /// The error rate of the iteration, as a percentage.
+    pub error_rate: f64,
+    /// The number of updates the iteration made.
+    pub updates: u32,
+    /// The development-set accuracy, as a percentage, when a development
+    /// set is loaded.
+    pub dev_accuracy: Option<f64>,
+    /// Seconds since training started.
+    pub elapsed: f64,
+}
+
+impl From<&TrainingProgress> for JsTrainingProgress {
+    /// Converts `litsea`'s report into the JavaScript-facing object.
+    ///
+    /// # Arguments
+    /// * `progress` - The report to convert.
+    ///
+    /// # Returns
+    /// The corresponding [`JsTrainingProgress`].
+    fn from(progress: &TrainingProgress) -> Self {
+        Self {
+            stage: progress.stage.as_str().to_string(),
+            iteration: progress.iteration as u32,
+            total: progress.total as u32,
+            error_rate: progress.error_rate,
+            updates: progress.updates as u32,
+            dev_accuracy: progress.dev_accuracy,
+            elapsed: progress.elapsed.as_secs_f64(),
+        }
+    }
+}
@@ -196,6 +196,19 @@ impl JsSegmenter {
         self.inner.segment_tokens(&text).into_iter().map(Token::from).collect()
     }
 
+    /// Splits a sentence into tokens carrying byte offsets and a boundary
+    /// confidence `score`.
+    ///
+    /// # Arguments
+    /// * `text` - The sentence to segment.
+    ///
+    /// # Returns
+    /// The tokens, with `score` set and `pos` set to `null`.
+    #[napi]
+    pub fn segment_with_scores(&self, text: String) -> Vec<Token> {
+        self.inner.segment_with_scores(&text).into_iter().map(Token::from).collect()
+    }
+
     /// Splits a sentence into tokens and tags each with a UPOS tag.
     ///
     /// # Arguments
@@ -23,6 +23,9 @@ pub struct Token {
     pub start: u32,
     /// Ending byte offset (exclusive) in the input string.
     pub end: u32,
+    /// The token's boundary confidence from `segmentWithScores` (small
+    /// values flag tokens worth reviewing), or `undefined` otherwise.
+    pub score: Option<f64>,
 }
 
 impl From<TokenView> for Token {
@@ -34,6 +37,7 @@ impl From<TokenView> for Token {
     /// # Returns
     /// The corresponding [`Token`].
     fn from(view: TokenView) -> Self {
+        let score = view.confidence();
         Self {
             surface: view.surface,
             // The tag travels as its name: JavaScript has no enum type, and
@@ -41,6 +45,7 @@ impl From<TokenView> for Token {
             pos: view.pos.map(|pos| pos.to_string()),
             start: view.byte_start as u32,
             end: view.byte_end as u32,
+            score,
         }
     }
 }
@@ -64,5 +69,6 @@ mod tests {
     fn test_untagged_token_has_no_pos() {
         let token = Token::from(TokenView::new("テスト", 0, 9, None));
         assert_eq!(token.pos, None);
+        assert_eq!(token.score, None);
     }
 }
@@ -3,20 +3,24 @@
 //! Extraction and training are blocking CPU work, so they run through
 //! [`AsyncTask`] on libuv's threadpool rather than on the event loop. That
 //! is also what makes [`JsCancelToken`] useful: JavaScript keeps running
-//! while a job is in flight, so it can cancel one.
+//! while a job is in flight, so it can cancel one. For the same reason, the
+//! `onProgress` callbacks of the `train` methods are called through a
+//! [`ThreadsafeFunction`], queued onto the event loop.
 
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, PoisonError};
 
+use litsea::TrainingProgress;
 use litsea_binding_core::{
     CancelToken, CoreExtractor, CorePerceptronTrainer, CoreTrainer, CoreTwoStageTrainer,
     CorpusFormat, parse_feature_set, parse_language,
 };
-use napi::bindgen_prelude::AsyncTask;
-use napi::{Env, Task};
+use napi::bindgen_prelude::{AsyncTask, Unknown};
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, Status, Task};
 
 use crate::error::{KindError, error_with_code, map_err, task_call};
-use crate::metrics::{JsBinaryMetrics, JsMulticlassMetrics, JsTwoStageMetrics};
+use crate::metrics::{JsBinaryMetrics, JsMulticlassMetrics, JsTrainingProgress, JsTwoStageMetrics};
 
 /// A flag that asks a running training job to stop.
 ///
@@ -73,6 +77,29 @@ impl JsCancelToken {
     }
 }
 
+/// A `train` method's `onProgress` callback, callable from the worker
+/// thread.
+type ProgressCallback =
+    ThreadsafeFunction<JsTrainingProgress, Unknown<'static>, JsTrainingProgress, Status, false>;
+
+/// Returns a progress forwarder that queues every report onto the event
+/// loop for `callback`.
+///
+/// The worker never waits for the callback, so training always continues;
+/// cancel a `CancelToken` from the callback to stop it.
+///
+/// # Arguments
+/// * `callback` - The JavaScript callback.
+///
+/// # Returns
+/// The forwarder to hand to `train_with_progress`.
+fn forward_to(callback: &ProgressCallback) -> impl FnMut(&TrainingProgress) -> bool + '_ {
+    move |progress| {
+        callback.call(JsTrainingProgress::from(progress), ThreadsafeFunctionCallMode::NonBlocking);
+        true
+    }
+}
+
 /// Extracts boundary-classification features on a worker thread.
 pub struct ExtractTask {
     /// The extractor, built on the main thread.
@@ -297,6 +324,8 @@ pub struct TrainTask {
     model_path: PathBuf,
     /// The cancellation flag training observes.
     cancel: CancelToken,
+    /// The callback receiving progress reports, if any.
+    on_progress: Option<ProgressCallback>,
     /// The failing error kind, recorded for `reject`.
     kind: Option<String>,
 }
@@ -314,7 +343,14 @@ impl Task for TrainTask {
     /// Returns the mapped core error if the model cannot be written.
     fn compute(&mut self) -> napi::Result<Self::Output> {
         let mut trainer = self.trainer.lock().unwrap_or_else(PoisonError::into_inner);
-        let trained = trainer.train(&self.cancel, &self.model_path);
+        let trained = match &self.on_progress {
+            Some(callback) => trainer.train_with_progress(
+                &self.cancel,
+                &self.model_path,
+                &mut forward_to(callback),
+            ),
+            None => trainer.train(&self.cancel, &self.model_path),
+        };
         task_call(&mut self.kind, trained)
     }
 
@@ -387,6 +423,8 @@ impl JsTrainer {
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`; cancelling stops training early
     ///   and still writes the partially trained model.
+    /// * `on_progress` - Optional callback receiving a `TrainingProgress`
+    ///   after every boosting iteration, on the event loop.
     ///
     /// # Returns
     /// A promise resolving to the training metrics.
@@ -395,11 +433,13 @@ impl JsTrainer {
         &self,
         model_path: String,
         cancel: Option<&JsCancelToken>,
+        on_progress: Option<ProgressCallback>,
     ) -> AsyncTask<TrainTask> {
         AsyncTask::new(TrainTask {
             trainer: Arc::clone(&self.inner),
             model_path: PathBuf::from(model_path),
             cancel: JsCancelToken::resolve(cancel),
+            on_progress,
             kind: None,
         })
     }
@@ -413,6 +453,8 @@ pub struct PerceptronTrainTask {
     model_path: PathBuf,
     /// The cancellation flag training observes.
     cancel: CancelToken,
+    /// The callback receiving progress reports, if any.
+    on_progress: Option<ProgressCallback>,
     /// The failing error kind, recorded for `reject`.
     kind: Option<String>,
 }
@@ -430,7 +472,14 @@ impl Task for PerceptronTrainTask {
     /// Returns the mapped core error if the model cannot be written.
     fn compute(&mut self) -> napi::Result<Self::Output> {
         let mut trainer = self.trainer.lock().unwrap_or_else(PoisonError::into_inner);
-        let trained = trainer.train(&self.cancel, &self.model_path);
+        let trained = match &self.on_progress {
+            Some(callback) => trainer.train_with_progress(
+                &self.cancel,
+                &self.model_path,
+                &mut forward_to(callback),
+            ),
+            None => trainer.train(&self.cancel, &self.model_path),
+        };
         task_call(&mut self.kind, trained)
     }
 
@@ -473,6 +522,8 @@ impl JsPerceptronTrainer {
     /// # Arguments
     /// * `num_epochs` - Number of passes over the training data.
     /// * `features_path` - Path to the features file.
+    /// * `seed` - Seed for shuffling the instances every epoch; omit to
+    ///   keep the file order.
     ///
     /// # Returns
     /// The new `PerceptronTrainer`.
@@ -481,9 +532,16 @@ impl JsPerceptronTrainer {
     /// Throws an error with the `io` or `parse` code if the features file
     /// cannot be read.
     #[napi(constructor)]
-    pub fn new(num_epochs: u32, features_path: String) -> Result<Self, KindError> {
-        let inner =
+    pub fn new(
+        num_epochs: u32,
+        features_path: String,
+        seed: Option<i64>,
+    ) -> Result<Self, KindError> {
+        let mut inner =
             map_err(CorePerceptronTrainer::new(num_epochs as usize, Path::new(&features_path)))?;
+        if let Some(seed) = seed {
+            inner = inner.with_seed(seed as u64);
+        }
         Ok(Self {
             inner: Arc::new(Mutex::new(inner)),
         })
@@ -494,6 +552,8 @@ impl JsPerceptronTrainer {
     /// # Arguments
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`.
+    /// * `on_progress` - Optional callback receiving a `TrainingProgress`
+    ///   after every epoch, on the event loop.
     ///
     /// # Returns
     /// A promise resolving to the training metrics.
@@ -502,11 +562,13 @@ impl JsPerceptronTrainer {
         &self,
         model_path: String,
         cancel: Option<&JsCancelToken>,
+        on_progress: Option<ProgressCallback>,
     ) -> AsyncTask<PerceptronTrainTask> {
         AsyncTask::new(PerceptronTrainTask {
             trainer: Arc::clone(&self.inner),
             model_path: PathBuf::from(model_path),
             cancel: JsCancelToken::resolve(cancel),
+            on_progress,
             kind: None,
         })
     }
@@ -520,6 +582,8 @@ pub struct TwoStageTrainTask {
     model_path: PathBuf,
     /// The cancellation flag training observes.
     cancel: CancelToken,
+    /// The callback receiving progress reports, if any.
+    on_progress: Option<ProgressCallback>,
     /// The failing error kind, recorded for `reject`.
     kind: Option<String>,
 }
@@ -538,7 +602,14 @@ impl Task for TwoStageTrainTask {
     /// used, or the mapped core error if the model cannot be written.
     fn compute(&mut self) -> napi::Result<Self::Output> {
         let mut trainer = self.trainer.lock().unwrap_or_else(PoisonError::into_inner);
-        let trained = trainer.train(&self.cancel, &self.model_path);
+        let trained = match &self.on_progress {
+            Some(callback) => trainer.train_with_progress(
+                &self.cancel,
+                &self.model_path,
+                &mut forward_to(callback),
+            ),
+            None => trainer.train(&self.cancel, &self.model_path),
+        };
         task_call(&mut self.kind, trained)
     }
 
@@ -588,6 +659,8 @@ impl JsTwoStageTrainer {
     ///   `.lexicon` files.
     /// * `dominance` - Lexicon dominance threshold in `(0.5, 1.0]`;
     ///   defaults to 0.99.
+    /// * `seed` - Seed for shuffling the instances of both stages every
+    ///   epoch; omit to keep the file order.
     ///
     /// # Returns
     /// The new `TwoStageTrainer`.
@@ -600,12 +673,16 @@ impl JsTwoStageTrainer {
         num_epochs: u32,
         features_prefix: String,
         dominance: Option<f64>,
+        seed: Option<i64>,
     ) -> Result<Self, KindError> {
-        let inner = map_err(CoreTwoStageTrainer::new(
+        let mut inner = map_err(CoreTwoStageTrainer::new(
             num_epochs as usize,
             dominance.unwrap_or(0.99),
             Path::new(&features_prefix),
         ))?;
+        if let Some(seed) = seed {
+            inner = inner.with_seed(seed as u64);
+        }
         Ok(Self {
             inner: Arc::new(Mutex::new(inner)),
         })
@@ -622,6 +699,8 @@ impl JsTwoStageTrainer {
     /// # Arguments
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`.
+    /// * `on_progress` - Optional callback receiving a `TrainingProgress`
+    ///   after every epoch of either stage, on the event loop.
     ///
     /// # Returns
     /// A promise resolving to the metrics of both stages.
@@ -634,11 +713,13 @@ impl JsTwoStageTrainer {
         &self,
         model_path: String,
         cancel: Option<&JsCancelToken>,
+        on_progress: Option<ProgressCallback>,
     ) -> AsyncTask<TwoStageTrainTask> {
         AsyncTask::new(TwoStageTrainTask {
             trainer: Arc::clone(&self.inner),
             model_path: PathBuf::from(model_path),
             cancel: JsCancelToken::resolve(cancel),
+            on_progress,
             kind: None,
         })
     }
@@ -46,6 +46,20 @@ for (const [language, model, sentence] of SEGMENTATION_CASES) {
     assert.equal(expectedStart, raw.length)
     assert.equal(tokens.map((t) => t.surface).join(''), sentence)
   })
+
+  test(`segmentWithScores keeps the segmentTokens offsets (${language})`, () => {
+    const seg = Segmenter.open(language, modelPath(model))
+    const scored = seg.segmentWithScores(sentence)
+    const plain = seg.segmentTokens(sentence)
+    assert.deepEqual(
+      scored.map((t) => [t.start, t.end]),
+      plain.map((t) => [t.start, t.end]),
+    )
+    for (const token of scored) {
+      assert.ok(token.score >= 0, 'confidence is non-negative')
+    }
+    assert.equal(plain[0].score, undefined)
+  })
 }
 
 for (const [language, model, sentence] of POS_CASES) {
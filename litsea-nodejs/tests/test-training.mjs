@@ -1,7 +1,7 @@
 /** Feature extraction, training, cancellation, and event-loop behaviour. */
 
 import assert from 'node:assert/strict'
-import { mkdtempSync, statSync, writeFileSync } from 'node:fs'
+import { mkdtempSync, readFileSync, statSync, writeFileSync } from 'node:fs'
 import { tmpdir } from 'node:os'
 import { join } from 'node:path'
 import { test } from 'node:test'
@@ -134,6 +134,63 @@ test('the perceptron trainer trains from stage-2 features', async () => {
   assert.ok(Object.keys(metrics.goldPerClass).length > 0)
 })
 
+test('the same seed writes a byte-identical perceptron model', async () => {
+  const dir = tempDir()
+  const corpus = writeCorpus(join(dir, 'corpus_pos.txt'), POS_SENTENCES)
+  const prefix = join(dir, 'features')
+  await new Extractor('japanese').extractTwoStage(corpus, prefix)
+
+  const models = [join(dir, 'first.model'), join(dir, 'second.model')]
+  for (const model of models) {
+    await new PerceptronTrainer(3, `${prefix}.stage2`, 7).train(model)
+  }
+
+  assert.deepEqual(readFileSync(models[0]), readFileSync(models[1]))
+})
+
+test('onProgress receives every epoch of both stages', async () => {
+  const dir = tempDir()
+  const corpus = writeCorpus(join(dir, 'corpus_pos.txt'), POS_SENTENCES)
+  const prefix = join(dir, 'features')
+  await new Extractor('japanese').extractTwoStage(corpus, prefix)
+
+  const reports = []
+  await new TwoStageTrainer(2, prefix).train(join(dir, 'two_stage.model'), null, (progress) => {
+    reports.push(progress)
+  })
+  // Reports are queued onto the event loop, so the last ones may land just
+  // after the promise resolves.
+  for (let i = 0; i < 100 && reports.length < 4; i += 1) {
+    await new Promise((resolve) => setTimeout(resolve, 10))
+  }
+
+  assert.deepEqual(
+    reports.map((p) => [p.stage, p.iteration, p.total]),
+    [
+      ['boundary', 1, 2],
+      ['boundary', 2, 2],
+      ['tagging', 1, 2],
+      ['tagging', 2, 2],
+    ],
+  )
+  assert.ok(reports.every((p) => p.errorRate >= 0 && p.errorRate <= 100 && p.elapsed >= 0))
+})
+
+test('cancelling from onProgress stops training', async () => {
+  const dir = tempDir()
+  const corpus = writeCorpus(join(dir, 'corpus.txt'), SENTENCES)
+  const features = join(dir, 'features.txt')
+  const model = join(dir, 'stopped.model')
+  await new Extractor('japanese').extract(corpus, features)
+
+  const cancel = new CancelToken()
+  const metrics = await new Trainer(0.0, 100000, features).train(model, cancel, () => cancel.cancel())
+
+  assert.equal(cancel.cancelled, true)
+  assert.ok(metrics.numInstances > 0)
+  assert.ok(statSync(model).size > 0)
+})
+
 test('cancelling before training still writes a model', async () => {
   const dir = tempDir()
   const corpus = writeCorpus(join(dir, 'corpus.txt'), SENTENCES)
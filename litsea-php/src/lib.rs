@@ -68,6 +68,7 @@ pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
         .class::<metrics::PhpBinaryMetrics>()
         .class::<metrics::PhpMulticlassMetrics>()
         .class::<metrics::PhpTwoStageMetrics>()
+        .class::<metrics::PhpTrainingProgress>()
         // Behaviour.
         .class::<segmenter::Segmenter>()
         .class::<trainer::CancelToken>()
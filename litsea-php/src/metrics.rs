@@ -1,7 +1,7 @@
-//! Training metric classes.
+//! Training metric and progress classes.
 
 use ext_php_rs::prelude::*;
-use litsea::{BinaryMetrics, MulticlassMetrics, TwoStageMetrics};
+use litsea::{BinaryMetrics, MulticlassMetrics, TrainingProgress, TwoStageMetrics};
 
 /// Metrics from training a binary (segmentation) model.
 ///
@@ -140,3 +140,57 @@ impl From<TwoStageMetrics> for PhpTwoStageMetrics {
         }
     }
 }
+
+/// The progress of a training run after one boosting iteration or
+/// perceptron epoch, as passed to a `train(..., $onProgress)` callback.
+///
+/// All percentages are 0-100.
+#[php_class]
+#[php(name = "Litsea\\TrainingProgress")]
+#[derive(Default, Clone)]
+pub struct PhpTrainingProgress {
+    /// The model being trained: `"boosting"`, `"perceptron"`, `"boundary"`,
+    /// or `"tagging"`.
+    #[php(prop)]
+    pub stage: String,
+    /// The iteration or epoch just completed, counting from 1.
+    #[php(prop)]
+    pub iteration: u64,
+    /// The configured number of iterations or epochs.
+    #[php(prop)]
+    pub total: u64,
+    /// The error rate of the iteration, as a percentage.
+    #[php(prop)]
+    pub error_rate: f64,
+    /// The number of updates the iteration made.
+    #[php(prop)]
+    pub updates: u64,
+    /// The development-set accuracy, as a percentage, when a development
+    /// set is loaded.
+    #[php(prop)]
+    pub dev_accuracy: Option<f64>,
+    /// Seconds since training started.
+    #[php(prop)]
+    pub elapsed: f64,
+}
+
+impl From<&TrainingProgress> for PhpTrainingProgress {
+    /// Converts `litsea`'s report into the PHP-facing class.
+    ///
+    /// # Arguments
+    /// * `progress` - The report to convert.
+    ///
+    /// # Returns
+    /// The corresponding [`PhpTrainingProgress`].
+    fn from(progress: &TrainingProgress) -> Self {
+        Self {
+            stage: progress.stage.as_str().to_string(),
+            iteration: progress.iteration as u64,
+            total: progress.total as u64,
+            error_rate: progress.error_rate,
+            updates: progress.updates as u64,
+            dev_accuracy: progress.dev_accuracy,
+            elapsed: progress.elapsed.as_secs_f64(),
+        }
+    }
+}
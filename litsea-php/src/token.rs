@@ -25,6 +25,10 @@ pub struct Token {
     /// Ending byte offset (exclusive) in the input string.
     #[php(prop)]
     pub end: u64,
+    /// The boundary confidence from `segmentWithScores` (small values flag
+    /// tokens worth reviewing), or `null` otherwise.
+    #[php(prop)]
+    pub score: Option<f64>,
 }
 
 #[php_impl]
@@ -50,6 +54,7 @@ impl From<TokenView> for Token {
     /// # Returns
     /// The corresponding [`Token`].
     fn from(view: TokenView) -> Self {
+        let score = view.confidence();
         Self {
             surface: view.surface,
             // The tag travels as its name: PHP enums cannot be registered
@@ -58,6 +63,7 @@ impl From<TokenView> for Token {
             pos: view.pos.map(|pos| pos.to_string()),
             start: view.byte_start as u64,
             end: view.byte_end as u64,
+            score,
         }
     }
 }
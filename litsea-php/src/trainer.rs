@@ -5,25 +5,35 @@
 //! The Python binding releases the GIL and the Node.js binding moves training
 //! onto libuv's threadpool, so in both a token can stop a run that is already
 //! going. **PHP has neither.** A request is single-threaded, and `pcntl`
-//! signal handlers cannot interrupt a blocking native call, so no PHP code
-//! runs while `train()` executes.
+//! signal handlers cannot interrupt a blocking native call, so no other PHP
+//! code runs while `train()` executes.
 //!
 //! [`CancelToken`] is still useful - cancelling it before `train()` makes the
 //! trainer stop at its first check point and write the partially trained
-//! model - but in-flight cancellation is not available here. That is a
-//! property of the host, not a gap in this binding.
+//! model. The one place PHP code does run during training is the
+//! `$onProgress` callback of the `train` methods: the binding hands it
+//! every report on the request's thread while training runs on a worker,
+//! so cancelling the token (or throwing) from the callback stops a run in
+//! flight.
 
 use std::cell::RefCell;
 use std::path::Path;
 
+use ext_php_rs::convert::IntoZval;
+use ext_php_rs::error::Error;
+use ext_php_rs::exception::PhpException;
 use ext_php_rs::prelude::*;
+use ext_php_rs::types::{ZendCallable, Zval};
+use litsea::TrainingProgress;
 use litsea_binding_core::{
-    CancelToken as CoreCancelToken, CoreExtractor, CorePerceptronTrainer, CoreTrainer,
+    CancelToken as CoreCancelToken, CoreExtractor, CorePerceptronTrainer, CoreResult, CoreTrainer,
     CoreTwoStageTrainer, CorpusFormat, parse_feature_set, parse_language,
 };
 
 use crate::error::map_err;
-use crate::metrics::{PhpBinaryMetrics, PhpMulticlassMetrics, PhpTwoStageMetrics};
+use crate::metrics::{
+    PhpBinaryMetrics, PhpMulticlassMetrics, PhpTrainingProgress, PhpTwoStageMetrics,
+};
 
 /// A flag that asks a training job to stop.
 ///
@@ -176,6 +186,56 @@ impl Extractor {
     }
 }
 
+/// A `train` method's progress forwarder, called with every report.
+type OnProgress<'a> = &'a mut dyn FnMut(&TrainingProgress) -> bool;
+
+/// Runs a training job, calling the PHP `on_progress` callable with a
+/// `Litsea\TrainingProgress` for every report.
+///
+/// If the callable throws, training is cancelled (the partially trained
+/// model is still written) and the exception is rethrown once training has
+/// returned.
+///
+/// # Arguments
+/// * `on_progress` - The callable, if any; `train` gets no forwarder
+///   without one.
+/// * `train` - Runs the training, forwarding reports to the given
+///   forwarder.
+///
+/// # Returns
+/// Whatever `train` returns.
+///
+/// # Errors
+/// Rethrows the callable's exception, or throws the training error mapped
+/// to the `Litsea\LitseaException` hierarchy.
+fn train_reporting<T>(
+    on_progress: Option<ZendCallable<'_>>,
+    train: impl FnOnce(Option<OnProgress<'_>>) -> CoreResult<T>,
+) -> PhpResult<T> {
+    let Some(callback) = on_progress else {
+        return map_err(train(None));
+    };
+    let mut raised = None;
+    let trained = train(Some(&mut |progress| match callback
+        .try_call(vec![&PhpTrainingProgress::from(progress)])
+    {
+        Ok(_) => true,
+        Err(error) => {
+            raised = Some(error);
+            false
+        }
+    }));
+    match raised {
+        Some(Error::Exception(exception)) => {
+            let mut object = Zval::new();
+            exception.set_zval(&mut object, false)?;
+            Err(PhpException::default(String::new()).with_object(object))
+        }
+        Some(error) => Err(error.into()),
+        None => map_err(trained),
+    }
+}
+
 /// Trains a segmentation model.
 #[php_class]
 #[php(name = "Litsea\\Trainer")]
@@ -236,21 +296,31 @@ impl Trainer {
     /// # Arguments
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`, which must already be cancelled
-    ///   to have any effect.
+    ///   or be cancelled from `on_progress` to have any effect.
+    /// * `on_progress` - Optional callable receiving a
+    ///   `Litsea\TrainingProgress` after every boosting iteration; if it
+    ///   throws, training stops as if cancelled and the exception propagates.
     ///
     /// # Returns
     /// The training metrics.
     ///
     /// # Errors
-    /// Throws `Litsea\IoException` if the model cannot be written.
-    #[php(defaults(cancel = None))]
+    /// Throws `Litsea\IoException` if the model cannot be written, or
+    /// rethrows the exception `on_progress` threw.
+    #[php(defaults(cancel = None, on_progress = None))]
     pub fn train(
         &self,
         model_path: String,
         cancel: Option<&CancelToken>,
+        on_progress: Option<ZendCallable>,
     ) -> PhpResult<PhpBinaryMetrics> {
         let token = CancelToken::resolve(cancel);
-        let metrics = map_err(self.inner.borrow_mut().train(&token, Path::new(&model_path)))?;
+        let model_path = Path::new(&model_path);
+        let mut inner = self.inner.borrow_mut();
+        let metrics = train_reporting(on_progress, |on_progress| match on_progress {
+            Some(on_progress) => inner.train_with_progress(&token, model_path, on_progress),
+            None => inner.train(&token, model_path),
+        })?;
         Ok(PhpBinaryMetrics::from(metrics))
     }
 }
@@ -270,6 +340,8 @@ impl PerceptronTrainer {
     /// # Arguments
     /// * `num_epochs` - Number of passes over the training data.
     /// * `features_path` - Path to the features file.
+    /// * `seed` - Seed for shuffling the instances every epoch; `null`
+    ///   keeps the file order.
     ///
     /// # Returns
     /// The new `PerceptronTrainer`.
@@ -277,11 +349,19 @@ impl PerceptronTrainer {
     /// # Errors
     /// Throws `Litsea\IoException` or `Litsea\ParseException` if the features
     /// file cannot be read.
-    pub fn __construct(num_epochs: i64, features_path: String) -> PhpResult<Self> {
-        let inner = map_err(CorePerceptronTrainer::new(
+    #[php(defaults(seed = None))]
+    pub fn __construct(
+        num_epochs: i64,
+        features_path: String,
+        seed: Option<i64>,
+    ) -> PhpResult<Self> {
+        let mut inner = map_err(CorePerceptronTrainer::new(
             num_epochs.max(0) as usize,
             Path::new(&features_path),
         ))?;
+        if let Some(seed) = seed {
+            inner = inner.with_seed(seed as u64);
+        }
         Ok(Self {
             inner: RefCell::new(inner),
         })
@@ -292,20 +372,30 @@ impl PerceptronTrainer {
     /// # Arguments
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`.
+    /// * `on_progress` - Optional callable receiving a
+    ///   `Litsea\TrainingProgress` after every epoch; if it throws, training
+    ///   stops as if cancelled and the exception propagates.
     ///
     /// # Returns
     /// The training metrics.
     ///
     /// # Errors
-    /// Throws `Litsea\IoException` if the model cannot be written.
-    #[php(defaults(cancel = None))]
+    /// Throws `Litsea\IoException` if the model cannot be written, or
+    /// rethrows the exception `on_progress` threw.
+    #[php(defaults(cancel = None, on_progress = None))]
     pub fn train(
         &self,
         model_path: String,
         cancel: Option<&CancelToken>,
+        on_progress: Option<ZendCallable>,
     ) -> PhpResult<PhpMulticlassMetrics> {
         let token = CancelToken::resolve(cancel);
-        let metrics = map_err(self.inner.borrow_mut().train(&token, Path::new(&model_path)))?;
+        let model_path = Path::new(&model_path);
+        let mut inner = self.inner.borrow_mut();
+        let metrics = train_reporting(on_progress, |on_progress| match on_progress {
+            Some(on_progress) => inner.train_with_progress(&token, model_path, on_progress),
+            None => inner.train(&token, model_path),
+        })?;
         Ok(PhpMulticlassMetrics::from(metrics))
     }
 }
@@ -331,6 +421,8 @@ impl TwoStageTrainer {
     /// * `features_prefix` - Prefix of the `.stage1` / `.stage2` /
     ///   `.lexicon` files.
     /// * `dominance` - Lexicon dominance threshold in `(0.5, 1.0]`.
+    /// * `seed` - Seed for shuffling the instances of both stages every
+    ///   epoch; `null` keeps the file order.
     ///
     /// # Returns
     /// The new `TwoStageTrainer`.
@@ -339,17 +431,21 @@ impl TwoStageTrainer {
     /// Throws `Litsea\InvalidArgumentException` if `dominance` is out of
     /// range, or `Litsea\IoException` / `Litsea\ParseException` if the
     /// feature files cannot be read.
-    #[php(defaults(dominance = 0.99))]
+    #[php(defaults(dominance = 0.99, seed = None))]
     pub fn __construct(
         num_epochs: i64,
         features_prefix: String,
         dominance: f64,
+        seed: Option<i64>,
     ) -> PhpResult<Self> {
-        let inner = map_err(CoreTwoStageTrainer::new(
+        let mut inner = map_err(CoreTwoStageTrainer::new(
             num_epochs.max(0) as usize,
             dominance,
             Path::new(&features_prefix),
         ))?;
+        if let Some(seed) = seed {
+            inner = inner.with_seed(seed as u64);
+        }
         Ok(Self {
             inner: RefCell::new(inner),
         })
@@ -368,21 +464,31 @@ impl TwoStageTrainer {
     /// # Arguments
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`.
+    /// * `on_progress` - Optional callable receiving a
+    ///   `Litsea\TrainingProgress` after every epoch of either stage; if it
+    ///   throws, training stops as if cancelled and the exception propagates.
     ///
     /// # Returns
     /// The metrics of both stages.
     ///
     /// # Errors
     /// Throws `Litsea\InvalidArgumentException` if the trainer has already
-    /// been used, or `Litsea\IoException` if the model cannot be written.
-    #[php(defaults(cancel = None))]
+    /// been used, `Litsea\IoException` if the model cannot be written, or
+    /// rethrows the exception `on_progress` threw.
+    #[php(defaults(cancel = None, on_progress = None))]
     pub fn train(
         &self,
         model_path: String,
         cancel: Option<&CancelToken>,
+        on_progress: Option<ZendCallable>,
     ) -> PhpResult<PhpTwoStageMetrics> {
         let token = CancelToken::resolve(cancel);
-        let metrics = map_err(self.inner.borrow_mut().train(&token, Path::new(&model_path)))?;
+        let model_path = Path::new(&model_path);
+        let mut inner = self.inner.borrow_mut();
+        let metrics = train_reporting(on_progress, |on_progress| match on_progress {
+            Some(on_progress) => inner.train_with_progress(&token, model_path, on_progress),
+            None => inner.train(&token, model_path),
+        })?;
         Ok(PhpTwoStageMetrics::from(metrics))
     }
 }
@@ -88,6 +88,21 @@ final class SegmenterTest extends LitseaTestCase
         $this->assertSame($sentence, $joined);
     }
 
+    #[DataProvider('segmentationCases')]
+    public function testSegmentWithScoresKeepsTheTokenOffsets(string $language, string $model, string $sentence): void
+    {
+        $seg = Segmenter::open($language, self::modelPath($model));
+        $scored = $seg->segmentWithScores($sentence);
+        $plain = $seg->segmentTokens($sentence);
+
+        $offsets = static fn (Token $token) => [$token->start, $token->end];
+        $this->assertSame(array_map($offsets, $plain), array_map($offsets, $scored));
+        foreach ($scored as $token) {
+            $this->assertGreaterThanOrEqual(0.0, $token->score);
+        }
+        $this->assertNull($plain[0]->score);
+    }
+
     public function testWhitespaceIsItsOwnToken(): void
     {
         $seg = Segmenter::open('korean', self::modelPath('korean.model'));
@@ -11,6 +11,7 @@ use Litsea\IoException;
 use Litsea\PerceptronTrainer;
 use Litsea\Segmenter;
 use Litsea\Trainer;
+use Litsea\TrainingProgress;
 use Litsea\TwoStageTrainer;
 
 /**
@@ -141,10 +142,28 @@ final class TrainingTest extends LitseaTestCase
         $this->assertFileExists($dir . '/perceptron.model');
     }
 
+    public function testPerceptronSeedIsReproducible(): void
+    {
+        $dir = self::tempDir();
+        $corpus = self::writeCorpus($dir . '/corpus_pos.txt', self::POS_SENTENCES);
+        $prefix = $dir . '/features';
+        (new Extractor('japanese'))->extractTwoStage($corpus, $prefix);
+
+        foreach (['first', 'second'] as $name) {
+            (new PerceptronTrainer(3, $prefix . '.stage2', 7))->train($dir . "/$name.model");
+        }
+
+        $this->assertSame(
+            file_get_contents($dir . '/first.model'),
+            file_get_contents($dir . '/second.model'),
+        );
+    }
+
     public function testCancelBeforeTrainingStillWritesAModel(): void
     {
-        // PHP cannot cancel a run in flight -- a request is single-threaded --
-        // so the token has to be cancelled before `train()`. Cancelling still
+        // Outside the progress callback, PHP cannot cancel a run in flight --
+        // a request is single-threaded -- so the token has to be cancelled
+        // before `train()`. Cancelling still
         // means "stop early and keep the partial model", not "fail".
         $dir = self::tempDir();
         $corpus = self::writeCorpus($dir . '/corpus.txt', self::SENTENCES);
@@ -162,6 +181,71 @@ final class TrainingTest extends LitseaTestCase
         $this->assertFileExists($model);
     }
 
+    public function testOnProgressReceivesEveryEpoch(): void
+    {
+        $dir = self::tempDir();
+        $corpus = self::writeCorpus($dir . '/corpus_pos.txt', self::POS_SENTENCES);
+        $prefix = $dir . '/features';
+        (new Extractor('japanese'))->extractTwoStage($corpus, $prefix);
+
+        $reports = [];
+        (new TwoStageTrainer(2, $prefix))->train(
+            $dir . '/two_stage.model',
+            null,
+            function (TrainingProgress $progress) use (&$reports): void {
+                $reports[] = [$progress->stage, $progress->iteration, $progress->total];
+            },
+        );
+
+        $this->assertSame(
+            [['boundary', 1, 2], ['boundary', 2, 2], ['tagging', 1, 2], ['tagging', 2, 2]],
+            $reports,
+        );
+    }
+
+    public function testCancellingFromOnProgressStopsTraining(): void
+    {
+        $dir = self::tempDir();
+        $corpus = self::writeCorpus($dir . '/corpus.txt', self::SENTENCES);
+        $features = $dir . '/features.txt';
+        $model = $dir . '/stopped.model';
+        (new Extractor('japanese'))->extract($corpus, $features);
+
+        $cancel = new CancelToken();
+        $calls = 0;
+        $metrics = (new Trainer(0.0, 100000, $features))->train(
+            $model,
+            $cancel,
+            function () use ($cancel, &$calls): void {
+                $calls++;
+                $cancel->cancel();
+            },
+        );
+
+        $this->assertTrue($cancel->isCancelled());
+        $this->assertSame(1, $calls);
+        $this->assertGreaterThan(0, $metrics->numInstances);
+        $this->assertFileExists($model);
+    }
+
+    public function testOnProgressExceptionPropagates(): void
+    {
+        $dir = self::tempDir();
+        $corpus = self::writeCorpus($dir . '/corpus.txt', self::SENTENCES);
+        $features = $dir . '/features.txt';
+        (new Extractor('japanese'))->extract($corpus, $features);
+
+        $this->expectException(\RuntimeException::class);
+        $this->expectExceptionMessage('stop');
+        (new Trainer(0.0, 100000, $features))->train(
+            $dir . '/stopped.model',
+            null,
+            function (): void {
+                throw new \RuntimeException('stop');
+            },
+        );
+    }
+
     public function testCancelTokenReset(): void
     {
         $token = new CancelToken();
@@ -29,6 +29,7 @@ from ._litsea import (
     Segmenter,
     Token,
     Trainer,
+    TrainingProgress,
     TwoStageMetrics,
     TwoStageTrainer,
     UnsupportedError,
@@ -53,6 +54,7 @@ __all__ = [
     "Segmenter",
     "Token",
     "Trainer",
+    "TrainingProgress",
     "TwoStageMetrics",
     "TwoStageTrainer",
     "UnsupportedError",
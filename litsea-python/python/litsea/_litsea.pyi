@@ -1,8 +1,8 @@
 """Type stubs for the compiled `litsea._litsea` extension module."""
 
 import os
-from collections.abc import Mapping, Sequence
-from typing import ClassVar, TypeAlias
+from collections.abc import Callable, Mapping, Sequence
+from typing import ClassVar, Literal, TypeAlias
 
 __version__: str
 
@@ -92,6 +92,8 @@ class Token:
     def start(self) -> int: ...
     @property
     def end(self) -> int: ...
+    @property
+    def score(self) -> float | None: ...
 
 LanguageArg: TypeAlias = Language | str
 
@@ -123,12 +125,21 @@ class Segmenter:
     def segment_tokens(self, text: str) -> list[Token]:
         """Split a sentence into tokens carrying byte offsets."""
 
+    def segment_with_scores(self, text: str) -> list[Token]:
+        """Split a sentence into tokens carrying byte offsets and a boundary confidence score."""
+
     def segment_with_pos(self, text: str) -> list[Token]:
         """Split a sentence into tokens and tag each with a UPOS tag."""
 
     def segment_with_pos_batch(self, texts: Sequence[str]) -> list[list[Token]]:
         """Split and tag several sentences, releasing the GIL."""
 
+    def learn(self, sentence: str, words: Sequence[str], *, margin: float = 1.0) -> int:
+        """Learn from a corrected segmentation; returns the number of weight updates."""
+
+    def save_model(self, path: str | os.PathLike[str], *, binary: bool = False) -> None:
+        """Save the segmentation model, with the ``learn`` updates."""
+
 class CancelToken:
     """A flag that asks a running training job to stop."""
 
@@ -184,6 +195,24 @@ class TwoStageMetrics:
     @property
     def stage2(self) -> MulticlassMetrics: ...
 
+class TrainingProgress:
+    """The progress of a training run after one iteration or epoch."""
+
+    @property
+    def stage(self) -> Literal["boosting", "perceptron", "boundary", "tagging"]: ...
+    @property
+    def iteration(self) -> int: ...
+    @property
+    def total(self) -> int: ...
+    @property
+    def error_rate(self) -> float: ...
+    @property
+    def updates(self) -> int: ...
+    @property
+    def dev_accuracy(self) -> float | None: ...
+    @property
+    def elapsed(self) -> float: ...
+
 class Extractor:
     """Extracts training features from a corpus."""
 
@@ -220,18 +249,26 @@ class Trainer:
         model_path: str | os.PathLike[str],
         *,
         cancel: CancelToken | None = None,
+        on_progress: Callable[[TrainingProgress], object] | None = None,
     ) -> BinaryMetrics: ...
 
 class PerceptronTrainer:
     """Trains a label-agnostic Averaged Perceptron model."""
 
-    def __init__(self, num_epochs: int, features_path: str | os.PathLike[str]) -> None: ...
+    def __init__(
+        self,
+        num_epochs: int,
+        features_path: str | os.PathLike[str],
+        *,
+        seed: int | None = None,
+    ) -> None: ...
     def load_model(self, model_uri: str) -> None: ...
     def train(
         self,
         model_path: str | os.PathLike[str],
         *,
         cancel: CancelToken | None = None,
+        on_progress: Callable[[TrainingProgress], object] | None = None,
     ) -> MulticlassMetrics: ...
 
 class TwoStageTrainer:
@@ -246,6 +283,7 @@ class TwoStageTrainer:
         features_prefix: str | os.PathLike[str],
         *,
         dominance: float = 0.99,
+        seed: int | None = None,
     ) -> None: ...
     @property
     def available(self) -> bool: ...
@@ -254,6 +292,7 @@ class TwoStageTrainer:
         model_path: str | os.PathLike[str],
         *,
         cancel: CancelToken | None = None,
+        on_progress: Callable[[TrainingProgress], object] | None = None,
     ) -> TwoStageMetrics: ...
 
 class LitseaError(Exception):
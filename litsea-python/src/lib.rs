@@ -58,6 +58,7 @@ fn _litsea(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<metrics::PyBinaryMetrics>()?;
     m.add_class::<metrics::PyMulticlassMetrics>()?;
     m.add_class::<metrics::PyTwoStageMetrics>()?;
+    m.add_class::<metrics::PyTrainingProgress>()?;
 
     error::register(m)?;
 
@@ -1,8 +1,8 @@
-//! Training metric classes.
+//! Training metric and progress classes.
 
 use std::collections::HashMap;
 
-use litsea::{BinaryMetrics, MulticlassMetrics, TwoStageMetrics};
+use litsea::{BinaryMetrics, MulticlassMetrics, TrainingProgress, TwoStageMetrics};
 use pyo3::prelude::*;
 
 /// Metrics from training a binary (segmentation) model.
@@ -190,3 +190,76 @@ impl From<TwoStageMetrics> for PyTwoStageMetrics {
         }
     }
 }
+
+/// The progress of a training run after one boosting iteration or
+/// perceptron epoch, as passed to a `train(..., on_progress=...)` callback.
+///
+/// All percentages are 0-100.
+#[pyclass(
+    name = "TrainingProgress",
+    frozen,
+    skip_from_py_object,
+    module = "litsea"
+)]
+#[derive(Debug, Clone)]
+pub struct PyTrainingProgress {
+    /// The model being trained: `"boosting"`, `"perceptron"`, `"boundary"`,
+    /// or `"tagging"`.
+    #[pyo3(get)]
+    stage: &'static str,
+    /// The iteration or epoch just completed, counting from 1.
+    #[pyo3(get)]
+    iteration: usize,
+    /// The configured number of iterations or epochs.
+    #[pyo3(get)]
+    total: usize,
+    /// The error rate of the iteration, as a percentage.
+    #[pyo3(get)]
+    error_rate: f64,
+    /// The number of updates the iteration made.
+    #[pyo3(get)]
+    updates: usize,
+    /// The development-set accuracy, as a percentage, when a development
+    /// set is loaded.
+    #[pyo3(get)]
+    dev_accuracy: Option<f64>,
+    /// Seconds since training started.
+    #[pyo3(get)]
+    elapsed: f64,
+}
+
+#[pymethods]
+impl PyTrainingProgress {
+    /// Returns a readable representation.
+    ///
+    /// # Returns
+    /// For example
+    /// `TrainingProgress(stage=boosting, iteration=3/100, error_rate=12.50%)`.
+    fn __repr__(&self) -> String {
+        format!(
+            "TrainingProgress(stage={}, iteration={}/{}, error_rate={:.2}%, updates={})",
+            self.stage, self.iteration, self.total, self.error_rate, self.updates
+        )
+    }
+}
+
+impl From<&TrainingProgress> for PyTrainingProgress {
+    /// Converts `litsea`'s report into the Python-facing class.
+    ///
+    /// # Arguments
+    /// * `progress` - The report to convert.
+    ///
+    /// # Returns
+    /// The corresponding [`PyTrainingProgress`].
+    fn from(progress: &TrainingProgress) -> Self {
+        Self {
+            stage: progress.stage.as_str(),
+            iteration: progress.iteration,
+            total: progress.total,
+            error_rate: progress.error_rate,
+            updates: progress.updates,
+            dev_accuracy: progress.dev_accuracy,
+            elapsed: progress.elapsed.as_secs_f64(),
+        }
+    }
+}
@@ -17,10 +17,12 @@ use crate::upos::PyToken;
 /// `segment_with_pos` works; a segmentation-only model produces one where it
 /// raises `PosUnavailableError`.
 ///
-/// Instances are immutable and safe to share between threads. Reusing one
-/// across calls is the intended usage: an internal scratch buffer reaches a
-/// steady state where segmentation allocates only the output strings.
-#[pyclass(name = "Segmenter", frozen, module = "litsea")]
+/// Instances are safe to share between threads. Reusing one across calls is
+/// the intended usage: an internal scratch buffer reaches a steady state
+/// where segmentation allocates only the output strings. `learn` updates
+/// the model in place and needs the instance to itself: it raises
+/// `RuntimeError` while another call on it is running.
+#[pyclass(name = "Segmenter", module = "litsea")]
 pub struct PySegmenter {
     /// The wrapped core segmenter.
     inner: CoreSegmenter,
@@ -141,6 +143,18 @@ impl PySegmenter {
         self.inner.segment_tokens(text).into_iter().map(PyToken::from).collect()
     }
 
+    /// Splits a sentence into tokens carrying byte offsets and a boundary
+    /// confidence `score`.
+    ///
+    /// # Arguments
+    /// * `text` - The sentence to segment.
+    ///
+    /// # Returns
+    /// The tokens, with `score` set and `pos` set to `None`.
+    fn segment_with_scores(&self, text: &str) -> Vec<PyToken> {
+        self.inner.segment_with_scores(text).into_iter().map(PyToken::from).collect()
+    }
+
     /// Splits a sentence into tokens and tags each with a UPOS tag.
     ///
     /// # Arguments
@@ -182,6 +196,46 @@ impl PySegmenter {
             .collect())
     }
 
+    /// Learns from a corrected segmentation, so later calls segment the
+    /// sentence as corrected.
+    ///
+    /// Each position the model gets wrong, or right by less than `margin`,
+    /// gets a perceptron-style update of the boundary weights. `margin` is
+    /// in the units of the model's weights: the default suits the bundled
+    /// models, while a boosted model such as `RWCP.model` wants about 0.1.
+    /// Persist the result with `save_model`.
+    ///
+    /// # Arguments
+    /// * `sentence` - The sentence, as passed to `segment`.
+    /// * `words` - Its corrected segmentation, spelling out `sentence`.
+    /// * `margin` - The score every position must reach.
+    ///
+    /// # Returns
+    /// The number of weight updates applied; 0 if the model already
+    /// segmented the sentence as corrected.
+    ///
+    /// # Errors
+    /// Raises `InvalidArgumentError` if the words do not spell out the
+    /// sentence or `margin` is not positive.
+    #[pyo3(signature = (sentence, words, *, margin=1.0))]
+    fn learn(&mut self, sentence: &str, words: Vec<String>, margin: f64) -> PyResult<usize> {
+        map_err(self.inner.learn(sentence, &words, margin))
+    }
+
+    /// Saves the segmentation model, with the `learn` updates.
+    ///
+    /// # Arguments
+    /// * `path` - The file to write.
+    /// * `binary` - Write the binary model format instead of text.
+    ///
+    /// # Errors
+    /// Raises `UnsupportedError` for a two-stage POS model, or `IoError` if
+    /// the file cannot be written.
+    #[pyo3(signature = (path, *, binary=false))]
+    fn save_model(&self, py: Python<'_>, path: PathBuf, binary: bool) -> PyResult<()> {
+        py.detach(|| map_err(self.inner.save_model(&path, binary)))
+    }
+
     /// Returns a readable representation.
     ///
     /// # Returns
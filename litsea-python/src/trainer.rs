@@ -2,15 +2,16 @@
 
 use std::path::PathBuf;
 
+use litsea::TrainingProgress;
 use litsea_binding_core::{
-    CancelToken, CoreExtractor, CorePerceptronTrainer, CoreTrainer, CoreTwoStageTrainer,
-    CorpusFormat, parse_feature_set,
+    CancelToken, CoreExtractor, CorePerceptronTrainer, CoreResult, CoreTrainer,
+    CoreTwoStageTrainer, CorpusFormat, parse_feature_set,
 };
 use pyo3::prelude::*;
 
 use crate::error::map_err;
 use crate::language::LanguageArg;
-use crate::metrics::{PyBinaryMetrics, PyMulticlassMetrics, PyTwoStageMetrics};
+use crate::metrics::{PyBinaryMetrics, PyMulticlassMetrics, PyTrainingProgress, PyTwoStageMetrics};
 
 /// A flag that asks a running training job to stop.
 ///
@@ -80,6 +81,55 @@ impl PyCancelToken {
     }
 }
 
+/// A `train` method's progress forwarder, called with every report.
+type OnProgress<'a> = &'a mut dyn FnMut(&TrainingProgress) -> bool;
+
+/// Runs a training job with the GIL released, calling the Python
+/// `on_progress` callable with a `TrainingProgress` for every report.
+///
+/// The callable runs on the calling thread with the GIL re-acquired. If it
+/// raises, training is cancelled (the partially trained model is still
+/// written) and the exception is re-raised once training has returned.
+///
+/// # Arguments
+/// * `py` - The GIL token.
+/// * `on_progress` - The callable, if any; `train` gets no forwarder
+///   without one.
+/// * `train` - Runs the training, forwarding reports to the given
+///   forwarder.
+///
+/// # Returns
+/// Whatever `train` returns.
+///
+/// # Errors
+/// Raises the callable's exception, or the training error mapped to the
+/// `LitseaError` hierarchy.
+fn train_reporting<T, F>(py: Python<'_>, on_progress: Option<Py<PyAny>>, train: F) -> PyResult<T>
+where
+    T: Send,
+    F: FnOnce(Option<OnProgress<'_>>) -> CoreResult<T> + Send,
+{
+    let Some(callback) = on_progress else {
+        return py.detach(|| map_err(train(None)));
+    };
+    let mut raised = None;
+    let trained = py.detach(|| {
+        train(Some(&mut |progress| {
+            Python::attach(|py| match callback.call1(py, (PyTrainingProgress::from(progress),)) {
+                Ok(_) => true,
+                Err(error) => {
+                    raised = Some(error);
+                    false
+                }
+            })
+        }))
+    });
+    match raised {
+        Some(error) => Err(error),
+        None => map_err(trained),
+    }
+}
+
 /// Extracts training features from a corpus.
 #[pyclass(name = "Extractor", frozen, module = "litsea")]
 pub struct PyExtractor {
@@ -231,21 +281,29 @@ impl PyTrainer {
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`; cancelling stops training early
     ///   and still writes the partially trained model.
+    /// * `on_progress` - Optional callable receiving a `TrainingProgress`
+    ///   after every boosting iteration; if it raises, training stops as if
+    ///   cancelled and the exception propagates.
     ///
     /// # Returns
     /// The training metrics.
     ///
     /// # Errors
-    /// Raises `IoError` if the model cannot be written.
-    #[pyo3(signature = (model_path, *, cancel=None))]
+    /// Raises `IoError` if the model cannot be written, or the exception
+    /// `on_progress` raised.
+    #[pyo3(signature = (model_path, *, cancel=None, on_progress=None))]
     fn train(
         &mut self,
         py: Python<'_>,
         model_path: PathBuf,
         cancel: Option<&PyCancelToken>,
+        on_progress: Option<Py<PyAny>>,
     ) -> PyResult<PyBinaryMetrics> {
         let token = PyCancelToken::resolve(cancel);
-        let metrics = py.detach(|| map_err(self.inner.train(&token, &model_path)))?;
+        let metrics = train_reporting(py, on_progress, |on_progress| match on_progress {
+            Some(on_progress) => self.inner.train_with_progress(&token, &model_path, on_progress),
+            None => self.inner.train(&token, &model_path),
+        })?;
         Ok(PyBinaryMetrics::from(metrics))
     }
 }
@@ -264,6 +322,8 @@ impl PyPerceptronTrainer {
     /// # Arguments
     /// * `num_epochs` - Number of passes over the training data.
     /// * `features_path` - Path to the features file.
+    /// * `seed` - Seed for shuffling the instances every epoch; `None`
+    ///   keeps the file order.
     ///
     /// # Returns
     /// The new `PerceptronTrainer`.
@@ -272,9 +332,18 @@ impl PyPerceptronTrainer {
     /// Raises `IoError` or `ParseError` if the features file cannot be read
     /// or parsed.
     #[new]
-    fn new(py: Python<'_>, num_epochs: usize, features_path: PathBuf) -> PyResult<Self> {
-        let inner =
+    #[pyo3(signature = (num_epochs, features_path, *, seed=None))]
+    fn new(
+        py: Python<'_>,
+        num_epochs: usize,
+        features_path: PathBuf,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let mut inner =
             py.detach(|| map_err(CorePerceptronTrainer::new(num_epochs, &features_path)))?;
+        if let Some(seed) = seed {
+            inner = inner.with_seed(seed);
+        }
         Ok(Self { inner })
     }
 
@@ -298,21 +367,29 @@ impl PyPerceptronTrainer {
     /// # Arguments
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`.
+    /// * `on_progress` - Optional callable receiving a `TrainingProgress`
+    ///   after every epoch; if it raises, training stops as if cancelled and
+    ///   the exception propagates.
     ///
     /// # Returns
     /// The training metrics.
     ///
     /// # Errors
-    /// Raises `IoError` if the model cannot be written.
-    #[pyo3(signature = (model_path, *, cancel=None))]
+    /// Raises `IoError` if the model cannot be written, or the exception
+    /// `on_progress` raised.
+    #[pyo3(signature = (model_path, *, cancel=None, on_progress=None))]
     fn train(
         &mut self,
         py: Python<'_>,
         model_path: PathBuf,
         cancel: Option<&PyCancelToken>,
+        on_progress: Option<Py<PyAny>>,
     ) -> PyResult<PyMulticlassMetrics> {
         let token = PyCancelToken::resolve(cancel);
-        let metrics = py.detach(|| map_err(self.inner.train(&token, &model_path)))?;
+        let metrics = train_reporting(py, on_progress, |on_progress| match on_progress {
+            Some(on_progress) => self.inner.train_with_progress(&token, &model_path, on_progress),
+            None => self.inner.train(&token, &model_path),
+        })?;
         Ok(PyMulticlassMetrics::from(metrics))
     }
 }
@@ -337,6 +414,8 @@ impl PyTwoStageTrainer {
     /// * `dominance` - Lexicon dominance threshold, in `(0.5, 1.0]`.
     /// * `features_prefix` - Prefix of the `.stage1` / `.stage2` /
     ///   `.lexicon` files written by `Extractor.extract_two_stage`.
+    /// * `seed` - Seed for shuffling the instances of both stages every
+    ///   epoch; `None` keeps the file order.
     ///
     /// # Returns
     /// The new `TwoStageTrainer`.
@@ -345,16 +424,20 @@ impl PyTwoStageTrainer {
     /// Raises `InvalidArgumentError` if `dominance` is out of range, or
     /// `IoError` / `ParseError` if the feature files cannot be read.
     #[new]
-    #[pyo3(signature = (num_epochs, features_prefix, *, dominance=0.99))]
+    #[pyo3(signature = (num_epochs, features_prefix, *, dominance=0.99, seed=None))]
     fn new(
         py: Python<'_>,
         num_epochs: usize,
         features_prefix: PathBuf,
         dominance: f64,
+        seed: Option<u64>,
     ) -> PyResult<Self> {
-        let inner = py.detach(|| {
+        let mut inner = py.detach(|| {
             map_err(CoreTwoStageTrainer::new(num_epochs, dominance, &features_prefix))
         })?;
+        if let Some(seed) = seed {
+            inner = inner.with_seed(seed);
+        }
         Ok(Self { inner })
     }
 
@@ -369,22 +452,30 @@ impl PyTwoStageTrainer {
     /// # Arguments
     /// * `model_path` - Path of the model file to write.
     /// * `cancel` - Optional `CancelToken`.
+    /// * `on_progress` - Optional callable receiving a `TrainingProgress`
+    ///   after every epoch of either stage; if it raises, training stops as
+    ///   if cancelled and the exception propagates.
     ///
     /// # Returns
     /// The metrics of both stages.
     ///
     /// # Errors
     /// Raises `InvalidArgumentError` if the trainer has already been used,
-    /// or `IoError` if the model cannot be written.
-    #[pyo3(signature = (model_path, *, cancel=None))]
+    /// `IoError` if the model cannot be written, or the exception
+    /// `on_progress` raised.
+    #[pyo3(signature = (model_path, *, cancel=None, on_progress=None))]
     fn train(
         &mut self,
         py: Python<'_>,
         model_path: PathBuf,
         cancel: Option<&PyCancelToken>,
+        on_progress: Option<Py<PyAny>>,
     ) -> PyResult<PyTwoStageMetrics> {
         let token = PyCancelToken::resolve(cancel);
-        let metrics = py.detach(|| map_err(self.inner.train(&token, &model_path)))?;
+        let metrics = train_reporting(py, on_progress, |on_progress| match on_progress {
+            Some(on_progress) => self.inner.train_with_progress(&token, &model_path, on_progress),
+            None => self.inner.train(&token, &model_path),
+        })?;
         Ok(PyTwoStageMetrics::from(metrics))
     }
 }
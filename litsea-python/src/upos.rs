@@ -146,7 +146,7 @@ impl From<Upos> for PyUpos {
 /// `text.encode()[token.start:token.end].decode()` is `token.surface`.
 /// They are exact for both segmentation and POS output.
 #[pyclass(name = "Token", frozen, skip_from_py_object, module = "litsea")]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PyToken {
     /// The token's surface form.
     #[pyo3(get)]
@@ -160,6 +160,10 @@ pub struct PyToken {
     /// Ending byte offset (exclusive) in the input string.
     #[pyo3(get)]
     end: usize,
+    /// The boundary confidence from `segment_with_scores` (small values
+    /// flag tokens worth reviewing), or `None` otherwise.
+    #[pyo3(get)]
+    score: Option<f64>,
 }
 
 #[pymethods]
@@ -181,7 +185,7 @@ impl PyToken {
         }
     }
 
-    /// Compares two tokens by all four fields.
+    /// Compares two tokens by all five fields.
     ///
     /// # Arguments
     /// * `other` - The token to compare against.
@@ -202,11 +206,13 @@ impl From<TokenView> for PyToken {
     /// # Returns
     /// The corresponding [`PyToken`].
     fn from(view: TokenView) -> Self {
+        let score = view.confidence();
         Self {
             surface: view.surface,
             pos: view.pos.map(PyUpos::from),
             start: view.byte_start,
             end: view.byte_end,
+            score,
         }
     }
 }
@@ -8,7 +8,15 @@ from pathlib import Path
 
 import pytest
 from conftest import run_cli
-from litsea import Language, PosUnavailableError, Segmenter, Token, Upos
+from litsea import (
+    InvalidArgumentError,
+    Language,
+    PosUnavailableError,
+    Segmenter,
+    Token,
+    UnsupportedError,
+    Upos,
+)
 
 # One sentence per language, with the segmentation model that handles it.
 SEGMENTATION_CASES = [
@@ -77,6 +85,18 @@ def test_offsets_reconstruct_the_input(models_dir: Path, language: str, model: s
     assert "".join(token.surface for token in tokens) == sentence
 
 
+@pytest.mark.parametrize(("language", "model", "sentence"), SEGMENTATION_CASES)
+def test_scores_keep_the_token_offsets(models_dir: Path, language: str, model: str, sentence: str) -> None:
+    """segment_with_scores returns segment_tokens' tokens with a score attached."""
+    seg = Segmenter.open(language, models_dir / model)
+    scored = seg.segment_with_scores(sentence)
+    plain = seg.segment_tokens(sentence)
+
+    assert [(t.start, t.end) for t in scored] == [(t.start, t.end) for t in plain]
+    assert all(token.score is not None and token.score >= 0 for token in scored)
+    assert all(token.score is None for token in plain)
+
+
 def test_whitespace_is_its_own_token(models_dir: Path) -> None:
     """Space-delimited languages keep the space as a token."""
     seg = Segmenter.open(Language.KOREAN, models_dir / "korean.model")
@@ -168,6 +188,31 @@ def test_shared_between_threads(models_dir: Path) -> None:
     assert all(result == expected for result in results)
 
 
+def test_learn_and_save_model(models_dir: Path, tmp_path: Path) -> None:
+    """A learned correction applies at once and survives a save and reload."""
+    sentence = "きゃりーぱみゅぱみゅが歌う。"
+    words = ["きゃりーぱみゅぱみゅ", "が", "歌う", "。"]
+    seg = Segmenter.open(Language.JAPANESE, models_dir / "japanese.model")
+    assert seg.segment(sentence) != words
+
+    assert seg.learn(sentence, words) > 0
+    assert seg.segment(sentence) == words
+    assert seg.learn(sentence, words) == 0
+
+    for binary in (False, True):
+        path = tmp_path / ("learned.bin" if binary else "learned.model")
+        seg.save_model(path, binary=binary)
+        assert Segmenter.open(Language.JAPANESE, path).segment(sentence) == words
+
+    with pytest.raises(InvalidArgumentError):
+        seg.learn(sentence, ["きゃりー"])
+    with pytest.raises(InvalidArgumentError):
+        seg.learn(sentence, words, margin=0.0)
+    pos = Segmenter.open(Language.KOREAN, models_dir / "korean_pos.model")
+    with pytest.raises(UnsupportedError):
+        pos.save_model(tmp_path / "pos.model")
+
+
 def test_language_enum() -> None:
     """`Language` exposes its members, names, and parsing."""
     assert Language.parse("ko") == Language.KOREAN
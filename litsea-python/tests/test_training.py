@@ -16,6 +16,7 @@ from litsea import (
     PerceptronTrainer,
     Segmenter,
     Trainer,
+    TrainingProgress,
     TwoStageTrainer,
 )
 
@@ -125,6 +126,58 @@ def test_perceptron_trainer(tmp_path: Path) -> None:
     assert model.exists()
 
 
+def test_perceptron_seed_is_reproducible(tmp_path: Path) -> None:
+    """The same seed and inputs write a byte-identical model."""
+    corpus = write_corpus(tmp_path / "corpus.txt", POS_SENTENCES)
+    prefix = tmp_path / "features"
+    Extractor(Language.JAPANESE).extract_two_stage(corpus, prefix)
+
+    models = [tmp_path / "first.model", tmp_path / "second.model"]
+    for model in models:
+        PerceptronTrainer(3, tmp_path / "features.stage2", seed=7).train(model)
+
+    assert models[0].read_bytes() == models[1].read_bytes()
+
+
+def test_on_progress_reports_every_epoch(tmp_path: Path) -> None:
+    """Two-stage training reports each epoch of both stages, in order."""
+    corpus = write_corpus(tmp_path / "corpus_pos.txt", POS_SENTENCES)
+    prefix = tmp_path / "features"
+    Extractor(Language.JAPANESE).extract_two_stage(corpus, prefix)
+
+    reports: list[TrainingProgress] = []
+    TwoStageTrainer(2, prefix).train(tmp_path / "two_stage.model", on_progress=reports.append)
+
+    assert [(p.stage, p.iteration, p.total) for p in reports] == [
+        ("boundary", 1, 2),
+        ("boundary", 2, 2),
+        ("tagging", 1, 2),
+        ("tagging", 2, 2),
+    ]
+    assert all(0.0 <= p.error_rate <= 100.0 and p.elapsed >= 0.0 for p in reports)
+    assert all(p.dev_accuracy is None for p in reports)
+
+
+def test_on_progress_exception_stops_training(tmp_path: Path) -> None:
+    """An exception from the callback cancels training and propagates."""
+    corpus = write_corpus(tmp_path / "corpus.txt", SENTENCES)
+    features = tmp_path / "features.txt"
+    model = tmp_path / "stopped.model"
+    Extractor(Language.JAPANESE).extract(corpus, features)
+
+    calls: list[int] = []
+
+    def stop(progress: TrainingProgress) -> None:
+        calls.append(progress.iteration)
+        raise RuntimeError("stop")
+
+    with pytest.raises(RuntimeError, match="stop"):
+        Trainer(0.0, 100_000, features).train(model, on_progress=stop)
+
+    assert calls == [1]
+    assert model.exists(), "the partially trained model is still written"
+
+
 def test_cancel_before_training_still_writes_a_model(tmp_path: Path) -> None:
     """Cancelling is cooperative: it is not an error."""
     corpus = write_corpus(tmp_path / "corpus.txt", SENTENCES)
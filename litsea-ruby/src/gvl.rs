@@ -4,13 +4,16 @@
 //! Lock blocks every other Ruby thread in the process - including the one
 //! that would cancel it. Releasing the lock is what makes
 //! `Litsea::CancelToken` usable while training runs, exactly as
-//! `Python::detach` does for the Python binding.
+//! `Python::detach` does for the Python binding. [`with_gvl`] takes the lock
+//! back for the moments the released work has to call into Ruby, such as
+//! the block that receives training progress.
 //!
-//! Neither magnus nor `rb-sys` exposes `rb_thread_call_without_gvl`: magnus
-//! lists it among the C functions it does not wrap, and it is declared in
-//! `ruby/thread.h`, which is outside the bindings `rb-sys` generates. The
-//! declaration below is therefore written by hand. The symbol resolves
-//! because the extension links against libruby regardless.
+//! Neither magnus nor `rb-sys` exposes `rb_thread_call_without_gvl` or
+//! `rb_thread_call_with_gvl`: magnus lists them among the C functions it
+//! does not wrap, and they are declared in `ruby/thread.h`, which is outside
+//! the bindings `rb-sys` generates. The declarations below are therefore
+//! written by hand. The symbols resolve because the extension links against
+//! libruby regardless.
 
 use std::ffi::c_void;
 use std::panic::{AssertUnwindSafe, catch_unwind};
@@ -29,6 +32,13 @@ unsafe extern "C" {
         ubf: *const c_void,
         data2: *mut c_void,
     ) -> *mut c_void;
+
+    /// Runs `func` with the GVL re-acquired, from a thread that released it
+    /// through `rb_thread_call_without_gvl`.
+    fn rb_thread_call_with_gvl(
+        func: unsafe extern "C" fn(*mut c_void) -> *mut c_void,
+        data1: *mut c_void,
+    ) -> *mut c_void;
 }
 
 /// State handed to the trampoline and filled in with the closure's result.
@@ -39,18 +49,19 @@ struct Payload<F, R> {
     result: Option<R>,
 }
 
-/// The `extern "C"` entry point Ruby calls with the GVL released.
+/// The `extern "C"` entry point Ruby calls with the GVL released or
+/// re-acquired.
 ///
 /// # Safety
 /// `data` must be a valid `*mut Payload<F, R>` that outlives the call, which
-/// [`without_gvl`] guarantees by keeping the payload on its own stack frame
-/// for the duration of `rb_thread_call_without_gvl`.
+/// [`without_gvl`] and [`with_gvl`] guarantee by keeping the payload on
+/// their own stack frame for the duration of the C call.
 unsafe extern "C" fn trampoline<F, R>(data: *mut c_void) -> *mut c_void
 where
     F: FnOnce() -> R,
 {
-    // SAFETY: `without_gvl` passes a pointer to a live `Payload<F, R>` and
-    // does not touch it until this function returns.
+    // SAFETY: `without_gvl` and `with_gvl` pass a pointer to a live
+    // `Payload<F, R>` and do not touch it until this function returns.
     let payload = unsafe { &mut *(data as *mut Payload<F, R>) };
 
     if let Some(func) = payload.func.take() {
@@ -67,9 +78,9 @@ where
 /// Runs `func` with the GVL released, so other Ruby threads keep running.
 ///
 /// The closure runs on the calling thread, not a new one. It **must not**
-/// touch the Ruby API - doing so without the GVL is undefined behaviour.
-/// Every caller in this crate passes pure Rust work (segmentation or
-/// training) that only reaches `litsea`.
+/// touch the Ruby API - doing so without the GVL is undefined behaviour -
+/// except inside [`with_gvl`]. Every caller in this crate passes pure Rust
+/// work (segmentation or training) that only reaches `litsea`.
 ///
 /// # Arguments
 /// * `func` - The work to run without the GVL.
@@ -110,3 +121,42 @@ where
         None => panic!("a Litsea operation panicked while the GVL was released"),
     }
 }
+
+/// Runs `func` with the GVL re-acquired, so it may call into Ruby.
+///
+/// Must only be called from inside a [`without_gvl`] closure, on the thread
+/// that released the lock. `func` must not let a Ruby exception unwind
+/// through it; magnus's calls (`Proc::call` and friends) already return
+/// exceptions as an [`Error`](magnus::error::Error).
+///
+/// # Arguments
+/// * `func` - The work to run with the GVL.
+///
+/// # Returns
+/// Whatever `func` returns.
+///
+/// # Panics
+/// Re-panics on the Rust side if `func` panicked, after the panic has been
+/// contained inside the C call.
+pub fn with_gvl<F, R>(func: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let mut payload = Payload {
+        func: Some(func),
+        result: None,
+    };
+
+    // SAFETY: `trampoline::<F, R>` matches the signature Ruby expects, and
+    // `&mut payload` stays valid for the whole call because
+    // `rb_thread_call_with_gvl` returns before this frame is dropped. The
+    // caller contract above guarantees the GVL was released by this thread.
+    unsafe {
+        rb_thread_call_with_gvl(trampoline::<F, R>, std::ptr::addr_of_mut!(payload) as *mut c_void);
+    }
+
+    match payload.result.take() {
+        Some(result) => result,
+        None => panic!("a Litsea callback panicked while the GVL was re-acquired"),
+    }
+}
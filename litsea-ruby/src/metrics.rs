@@ -1,6 +1,6 @@
-//! Training metric classes.
+//! Training metric and progress classes.
 
-use litsea::{BinaryMetrics, MulticlassMetrics, TwoStageMetrics};
+use litsea::{BinaryMetrics, MulticlassMetrics, TrainingProgress, TwoStageMetrics};
 use magnus::{Module, RModule, Ruby, error::Error};
 
 /// Metrics from training a binary (segmentation) model.
@@ -191,6 +191,82 @@ impl From<TwoStageMetrics> for RbTwoStageMetrics {
     }
 }
 
+/// The progress of a training run after one boosting iteration or
+/// perceptron epoch, as yielded to the block given to `train`.
+///
+/// All percentages are 0-100.
+#[magnus::wrap(class = "Litsea::TrainingProgress", free_immediately, size)]
+pub struct RbTrainingProgress {
+    /// The wrapped report.
+    inner: TrainingProgress,
+}
+
+impl RbTrainingProgress {
+    /// The model being trained: `"boosting"`, `"perceptron"`, `"boundary"`,
+    /// or `"tagging"`.
+    fn stage(&self) -> &'static str {
+        self.inner.stage.as_str()
+    }
+
+    /// The iteration or epoch just completed, counting from 1.
+    fn iteration(&self) -> usize {
+        self.inner.iteration
+    }
+
+    /// The configured number of iterations or epochs.
+    fn total(&self) -> usize {
+        self.inner.total
+    }
+
+    /// The error rate of the iteration, as a percentage.
+    fn error_rate(&self) -> f64 {
+        self.inner.error_rate
+    }
+
+    /// The number of updates the iteration made.
+    fn updates(&self) -> usize {
+        self.inner.updates
+    }
+
+    /// The development-set accuracy, as a percentage, or `nil` without a
+    /// development set.
+    fn dev_accuracy(&self) -> Option<f64> {
+        self.inner.dev_accuracy
+    }
+
+    /// Seconds since training started.
+    fn elapsed(&self) -> f64 {
+        self.inner.elapsed.as_secs_f64()
+    }
+
+    /// Returns a readable representation.
+    ///
+    /// # Returns
+    /// For example
+    /// `#<Litsea::TrainingProgress stage=boosting iteration=3/100 error_rate=12.50%>`.
+    fn inspect(&self) -> String {
+        format!(
+            "#<Litsea::TrainingProgress stage={} iteration={}/{} error_rate={:.2}%>",
+            self.inner.stage, self.inner.iteration, self.inner.total, self.inner.error_rate
+        )
+    }
+}
+
+impl From<&TrainingProgress> for RbTrainingProgress {
+    /// Wraps a copy of `litsea`'s report for Ruby.
+    ///
+    /// # Arguments
+    /// * `progress` - The report to wrap.
+    ///
+    /// # Returns
+    /// The corresponding [`RbTrainingProgress`].
+    fn from(progress: &TrainingProgress) -> Self {
+        Self {
+            inner: progress.clone(),
+        }
+    }
+}
+
 /// Defines the metric classes.
 ///
 /// # Arguments
@@ -246,5 +322,16 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     two_stage.define_method("inspect", magnus::method!(RbTwoStageMetrics::inspect, 0))?;
     two_stage.define_method("to_s", magnus::method!(RbTwoStageMetrics::inspect, 0))?;
 
+    let progress = module.define_class("TrainingProgress", ruby.class_object())?;
+    progress.define_method("stage", magnus::method!(RbTrainingProgress::stage, 0))?;
+    progress.define_method("iteration", magnus::method!(RbTrainingProgress::iteration, 0))?;
+    progress.define_method("total", magnus::method!(RbTrainingProgress::total, 0))?;
+    progress.define_method("error_rate", magnus::method!(RbTrainingProgress::error_rate, 0))?;
+    progress.define_method("updates", magnus::method!(RbTrainingProgress::updates, 0))?;
+    progress.define_method("dev_accuracy", magnus::method!(RbTrainingProgress::dev_accuracy, 0))?;
+    progress.define_method("elapsed", magnus::method!(RbTrainingProgress::elapsed, 0))?;
+    progress.define_method("inspect", magnus::method!(RbTrainingProgress::inspect, 0))?;
+    progress.define_method("to_s", magnus::method!(RbTrainingProgress::inspect, 0))?;
+
     Ok(())
 }
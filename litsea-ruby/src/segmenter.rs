@@ -147,6 +147,22 @@ impl Segmenter {
         tokens_to_array(ruby, rb_self.inner.segment_tokens(&text))
     }
 
+    /// Splits a sentence into tokens carrying byte offsets and a boundary
+    /// confidence `score`.
+    ///
+    /// # Arguments
+    /// * `ruby` - The Ruby handle for the current thread.
+    /// * `text` - The sentence to segment.
+    ///
+    /// # Returns
+    /// The tokens, with `score` set and `pos` set to `nil`.
+    ///
+    /// # Errors
+    /// Returns a Ruby exception if the result array cannot be built.
+    fn segment_with_scores(ruby: &Ruby, rb_self: &Self, text: String) -> Result<RArray, Error> {
+        tokens_to_array(ruby, rb_self.inner.segment_with_scores(&text))
+    }
+
     /// Splits a sentence into tokens and tags each with a UPOS tag.
     ///
     /// # Arguments
@@ -232,6 +248,8 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     class.define_method("segment", magnus::method!(Segmenter::segment, 1))?;
     class.define_method("segment_batch", magnus::method!(Segmenter::segment_batch, 1))?;
     class.define_method("segment_tokens", magnus::method!(Segmenter::segment_tokens, 1))?;
+    class
+        .define_method("segment_with_scores", magnus::method!(Segmenter::segment_with_scores, 1))?;
     class.define_method("segment_with_pos", magnus::method!(Segmenter::segment_with_pos, 1))?;
     class.define_method(
         "segment_with_pos_batch",
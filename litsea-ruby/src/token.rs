@@ -19,6 +19,9 @@ pub struct Token {
     start: usize,
     /// Ending byte offset (exclusive) in the input string.
     end: usize,
+    /// The boundary confidence from `segment_with_scores`, or `nil`
+    /// otherwise.
+    score: Option<f64>,
 }
 
 impl Token {
@@ -54,6 +57,15 @@ impl Token {
         self.end
     }
 
+    /// Returns the boundary confidence.
+    ///
+    /// # Returns
+    /// The confidence (small values flag tokens worth reviewing), or `nil`
+    /// unless the token came from `segment_with_scores`.
+    fn score(&self) -> Option<f64> {
+        self.score
+    }
+
     /// Returns a readable representation.
     ///
     /// # Returns
@@ -77,6 +89,7 @@ impl From<TokenView> for Token {
     /// # Returns
     /// The corresponding [`Token`].
     fn from(view: TokenView) -> Self {
+        let score = view.confidence();
         Self {
             surface: view.surface,
             // The tag travels as its name, matching the other bindings'
@@ -84,6 +97,7 @@ impl From<TokenView> for Token {
             pos: view.pos.map(|pos| pos.to_string()),
             start: view.byte_start,
             end: view.byte_end,
+            score,
         }
     }
 }
@@ -105,6 +119,7 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     class.define_method("pos", magnus::method!(Token::pos, 0))?;
     class.define_method("start", magnus::method!(Token::start, 0))?;
     class.define_method("end", magnus::method!(Token::end, 0))?;
+    class.define_method("score", magnus::method!(Token::score, 0))?;
     class.define_method("inspect", magnus::method!(Token::inspect, 0))?;
     class.define_method("to_s", magnus::method!(Token::inspect, 0))?;
     Ok(())
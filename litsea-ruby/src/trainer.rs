@@ -2,23 +2,27 @@
 //!
 //! Training releases the GVL (see [`crate::gvl`]), so other Ruby threads keep
 //! running while it works - which is what makes [`CancelToken`] usable while
-//! a run is already going, as in the Python and Node.js bindings.
+//! a run is already going, as in the Python and Node.js bindings. A block
+//! given to `train` receives every progress report, with the GVL briefly
+//! re-acquired.
 
 use std::cell::RefCell;
 use std::path::PathBuf;
 
+use litsea::TrainingProgress;
 use litsea_binding_core::{
-    CancelToken as CoreCancelToken, CoreExtractor, CorePerceptronTrainer, CoreTrainer,
+    CancelToken as CoreCancelToken, CoreExtractor, CorePerceptronTrainer, CoreResult, CoreTrainer,
     CoreTwoStageTrainer, CorpusFormat, parse_feature_set,
 };
+use magnus::block::Proc;
 use magnus::{
     Module, Object, RModule, Ruby, Value, error::Error, function, method, scan_args::scan_args,
 };
 
 use crate::error::map_err;
-use crate::gvl::without_gvl;
+use crate::gvl::{with_gvl, without_gvl};
 use crate::language::language_from_value;
-use crate::metrics::{RbBinaryMetrics, RbMulticlassMetrics, RbTwoStageMetrics};
+use crate::metrics::{RbBinaryMetrics, RbMulticlassMetrics, RbTrainingProgress, RbTwoStageMetrics};
 
 /// A flag that asks a running training job to stop.
 ///
@@ -73,7 +77,7 @@ impl CancelToken {
     }
 }
 
-/// Reads the optional `cancel:` keyword argument.
+/// Reads the optional `cancel:` keyword argument and progress block.
 ///
 /// magnus cannot express an optional keyword argument in a `method!` arity,
 /// so the trainers take their arguments through `scan_args`.
@@ -82,12 +86,15 @@ impl CancelToken {
 /// * `args` - The raw Ruby arguments.
 ///
 /// # Returns
-/// The model path and the cancellation flag to observe.
+/// The model path, the cancellation flag to observe, and the block, if
+/// given.
 ///
 /// # Errors
 /// Returns a Ruby `ArgumentError` if the arguments do not match.
-fn scan_train_args(args: &[magnus::Value]) -> Result<(PathBuf, CoreCancelToken), Error> {
-    let args = scan_args::<(String,), (), (), (), _, ()>(args)?;
+fn scan_train_args(
+    args: &[magnus::Value],
+) -> Result<(PathBuf, CoreCancelToken, Option<Proc>), Error> {
+    let args = scan_args::<(String,), (), (), (), _, Option<Proc>>(args)?;
     let (model_path,) = args.required;
     let kwargs = magnus::scan_args::get_kwargs::<_, (), (Option<Option<&CancelToken>>,), ()>(
         args.keywords,
@@ -96,7 +103,53 @@ fn scan_train_args(args: &[magnus::Value]) -> Result<(PathBuf, CoreCancelToken),
     )?;
     let (cancel,) = kwargs.optional;
 
-    Ok((PathBuf::from(model_path), CancelToken::resolve(cancel.flatten())))
+    Ok((PathBuf::from(model_path), CancelToken::resolve(cancel.flatten()), args.block))
+}
+
+/// A `train` method's progress forwarder, called with every report.
+type OnProgress<'a> = &'a mut dyn FnMut(&TrainingProgress) -> bool;
+
+/// Runs a training job with the GVL released, yielding a
+/// `Litsea::TrainingProgress` to `block` for every report.
+///
+/// The block runs on the calling thread with the GVL re-acquired. If it
+/// raises, training is cancelled (the partially trained model is still
+/// written) and the exception is re-raised once training has returned.
+///
+/// # Arguments
+/// * `block` - The block, if any; `train` gets no forwarder without one.
+/// * `train` - Runs the training, forwarding reports to the given
+///   forwarder.
+///
+/// # Returns
+/// Whatever `train` returns.
+///
+/// # Errors
+/// Re-raises the block's exception, or raises the training error mapped to
+/// the `Litsea::Error` hierarchy.
+fn train_reporting<T>(
+    block: Option<Proc>,
+    train: impl FnOnce(Option<OnProgress<'_>>) -> CoreResult<T>,
+) -> Result<T, Error> {
+    let Some(block) = block else {
+        return map_err(without_gvl(|| train(None)));
+    };
+    let mut raised = None;
+    let trained = without_gvl(|| {
+        train(Some(&mut |progress| {
+            with_gvl(|| match block.call::<_, Value>((RbTrainingProgress::from(progress),)) {
+                Ok(_) => true,
+                Err(error) => {
+                    raised = Some(error);
+                    false
+                }
+            })
+        }))
+    });
+    match raised {
+        Some(error) => Err(error),
+        None => map_err(trained),
+    }
 }
 
 /// Extracts training features from a corpus.
@@ -249,17 +302,25 @@ impl Trainer {
     /// cancel it through the `cancel:` token.
     ///
     /// # Arguments
-    /// * `args` - `model_path` and an optional `cancel:` token.
+    /// * `args` - `model_path`, an optional `cancel:` token, and an
+    ///   optional block receiving a `Litsea::TrainingProgress` after every
+    ///   boosting iteration; if the block raises, training stops as if cancelled and
+    ///   the exception propagates.
     ///
     /// # Returns
     /// The training metrics.
     ///
     /// # Errors
-    /// Raises `Litsea::IoError` if the model cannot be written.
+    /// Raises `Litsea::IoError` if the model cannot be written, or re-raises
+    /// the block's exception.
     fn train(&self, args: &[magnus::Value]) -> Result<RbBinaryMetrics, Error> {
-        let (model_path, cancel) = scan_train_args(args)?;
-        let trained = without_gvl(|| self.inner.borrow_mut().train(&cancel, &model_path));
-        Ok(RbBinaryMetrics::from(map_err(trained)?))
+        let (model_path, cancel, block) = scan_train_args(args)?;
+        let mut inner = self.inner.borrow_mut();
+        let metrics = train_reporting(block, |on_progress| match on_progress {
+            Some(on_progress) => inner.train_with_progress(&cancel, &model_path, on_progress),
+            None => inner.train(&cancel, &model_path),
+        })?;
+        Ok(RbBinaryMetrics::from(metrics))
     }
 }
 
@@ -273,9 +334,11 @@ pub struct PerceptronTrainer {
 impl PerceptronTrainer {
     /// Loads a features file and prepares training.
     ///
+    /// Accepts a `seed:` keyword argument that shuffles the instances every
+    /// epoch.
+    ///
     /// # Arguments
-    /// * `num_epochs` - Number of passes over the training data.
-    /// * `features_path` - Path to the features file.
+    /// * `args` - `num_epochs`, `features_path`, and the keyword.
     ///
     /// # Returns
     /// The new trainer.
@@ -283,9 +346,21 @@ impl PerceptronTrainer {
     /// # Errors
     /// Raises `Litsea::IoError` or `Litsea::ParseError` if the features file
     /// cannot be read.
-    fn new(num_epochs: usize, features_path: String) -> Result<Self, Error> {
-        let inner =
+    fn new(args: &[magnus::Value]) -> Result<Self, Error> {
+        let args = scan_args::<(usize, String), (), (), (), _, ()>(args)?;
+        let (num_epochs, features_path) = args.required;
+        let kwargs = magnus::scan_args::get_kwargs::<_, (), (Option<u64>,), ()>(
+            args.keywords,
+            &[],
+            &["seed"],
+        )?;
+        let (seed,) = kwargs.optional;
+
+        let mut inner =
             map_err(CorePerceptronTrainer::new(num_epochs, std::path::Path::new(&features_path)))?;
+        if let Some(seed) = seed {
+            inner = inner.with_seed(seed);
+        }
         Ok(Self {
             inner: RefCell::new(inner),
         })
@@ -294,17 +369,25 @@ impl PerceptronTrainer {
     /// Trains the model and writes it to `model_path`.
     ///
     /// # Arguments
-    /// * `args` - `model_path` and an optional `cancel:` token.
+    /// * `args` - `model_path`, an optional `cancel:` token, and an
+    ///   optional block receiving a `Litsea::TrainingProgress` after every
+    ///   epoch; if the block raises, training stops as if cancelled and
+    ///   the exception propagates.
     ///
     /// # Returns
     /// The training metrics.
     ///
     /// # Errors
-    /// Raises `Litsea::IoError` if the model cannot be written.
+    /// Raises `Litsea::IoError` if the model cannot be written, or re-raises
+    /// the block's exception.
     fn train(&self, args: &[magnus::Value]) -> Result<RbMulticlassMetrics, Error> {
-        let (model_path, cancel) = scan_train_args(args)?;
-        let trained = without_gvl(|| self.inner.borrow_mut().train(&cancel, &model_path));
-        Ok(RbMulticlassMetrics::from(map_err(trained)?))
+        let (model_path, cancel, block) = scan_train_args(args)?;
+        let mut inner = self.inner.borrow_mut();
+        let metrics = train_reporting(block, |on_progress| match on_progress {
+            Some(on_progress) => inner.train_with_progress(&cancel, &model_path, on_progress),
+            None => inner.train(&cancel, &model_path),
+        })?;
+        Ok(RbMulticlassMetrics::from(metrics))
     }
 }
 
@@ -322,10 +405,10 @@ pub struct TwoStageTrainer {
 impl TwoStageTrainer {
     /// Loads a two-stage features prefix and prepares training.
     ///
-    /// Accepts a `dominance:` keyword argument.
+    /// Accepts `dominance:` and `seed:` keyword arguments.
     ///
     /// # Arguments
-    /// * `args` - `num_epochs`, `features_prefix`, and the keyword.
+    /// * `args` - `num_epochs`, `features_prefix`, and the keywords.
     ///
     /// # Returns
     /// The new trainer.
@@ -337,18 +420,21 @@ impl TwoStageTrainer {
     fn new(args: &[magnus::Value]) -> Result<Self, Error> {
         let args = scan_args::<(usize, String), (), (), (), _, ()>(args)?;
         let (num_epochs, features_prefix) = args.required;
-        let kwargs = magnus::scan_args::get_kwargs::<_, (), (Option<f64>,), ()>(
+        let kwargs = magnus::scan_args::get_kwargs::<_, (), (Option<f64>, Option<u64>), ()>(
             args.keywords,
             &[],
-            &["dominance"],
+            &["dominance", "seed"],
         )?;
-        let (dominance,) = kwargs.optional;
+        let (dominance, seed) = kwargs.optional;
 
-        let inner = map_err(CoreTwoStageTrainer::new(
+        let mut inner = map_err(CoreTwoStageTrainer::new(
             num_epochs,
             dominance.unwrap_or(0.99),
             std::path::Path::new(&features_prefix),
         ))?;
+        if let Some(seed) = seed {
+            inner = inner.with_seed(seed);
+        }
         Ok(Self {
             inner: RefCell::new(inner),
         })
@@ -365,18 +451,26 @@ impl TwoStageTrainer {
     /// Trains both stages and writes the model.
     ///
     /// # Arguments
-    /// * `args` - `model_path` and an optional `cancel:` token.
+    /// * `args` - `model_path`, an optional `cancel:` token, and an
+    ///   optional block receiving a `Litsea::TrainingProgress` after every
+    ///   epoch of either stage; if the block raises, training stops as if cancelled and
+    ///   the exception propagates.
     ///
     /// # Returns
     /// The metrics of both stages.
     ///
     /// # Errors
     /// Raises `Litsea::InvalidArgumentError` if the trainer has already been
-    /// used, or `Litsea::IoError` if the model cannot be written.
+    /// used, `Litsea::IoError` if the model cannot be written, or re-raises
+    /// the block's exception.
     fn train(&self, args: &[magnus::Value]) -> Result<RbTwoStageMetrics, Error> {
-        let (model_path, cancel) = scan_train_args(args)?;
-        let trained = without_gvl(|| self.inner.borrow_mut().train(&cancel, &model_path));
-        Ok(RbTwoStageMetrics::from(map_err(trained)?))
+        let (model_path, cancel, block) = scan_train_args(args)?;
+        let mut inner = self.inner.borrow_mut();
+        let metrics = train_reporting(block, |on_progress| match on_progress {
+            Some(on_progress) => inner.train_with_progress(&cancel, &model_path, on_progress),
+            None => inner.train(&cancel, &model_path),
+        })?;
+        Ok(RbTwoStageMetrics::from(metrics))
     }
 }
 
@@ -409,7 +503,7 @@ pub fn define(ruby: &Ruby, module: &RModule) -> Result<(), Error> {
     trainer.define_method("train", method!(Trainer::train, -1))?;
 
     let perceptron = module.define_class("PerceptronTrainer", ruby.class_object())?;
-    perceptron.define_singleton_method("new", function!(PerceptronTrainer::new, 2))?;
+    perceptron.define_singleton_method("new", function!(PerceptronTrainer::new, -1))?;
     perceptron.define_method("train", method!(PerceptronTrainer::train, -1))?;
 
     let two_stage = module.define_class("TwoStageTrainer", ruby.class_object())?;
@@ -55,6 +55,18 @@ class TestSegmenter < LitseaTest
     end
   end
 
+  def test_segment_with_scores_keeps_the_token_offsets
+    SEGMENTATION_CASES.each do |language, model, sentence|
+      seg = Litsea::Segmenter.open(language, model_path(model))
+      scored = seg.segment_with_scores(sentence)
+      plain = seg.segment_tokens(sentence)
+
+      assert_equal plain.map { |t| [t.start, t.end] }, scored.map { |t| [t.start, t.end] }, language
+      scored.each { |token| assert_operator token.score, :>=, 0, language }
+      assert_nil plain.first.score
+    end
+  end
+
   def test_whitespace_is_its_own_token
     seg = Litsea::Segmenter.open(:korean, model_path('korean.model'))
     assert_equal ['안녕하세요', ' ', '반갑습니다'], seg.segment('안녕하세요 반갑습니다')
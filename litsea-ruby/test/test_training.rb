@@ -90,6 +90,21 @@ class TestTraining < LitseaTest
     end
   end
 
+  def test_perceptron_seed_is_reproducible
+    Dir.mktmpdir do |dir|
+      corpus = write_corpus(File.join(dir, 'corpus_pos.txt'), POS_SENTENCES)
+      prefix = File.join(dir, 'features')
+      Litsea::Extractor.new(:japanese).extract_two_stage(corpus, prefix)
+
+      models = %w[first second].map { |name| File.join(dir, "#{name}.model") }
+      models.each do |model|
+        Litsea::PerceptronTrainer.new(3, "#{prefix}.stage2", seed: 7).train(model)
+      end
+
+      assert_equal File.binread(models[0]), File.binread(models[1])
+    end
+  end
+
   def test_cancelling_before_training_still_writes_a_model
     Dir.mktmpdir do |dir|
       corpus = write_corpus(File.join(dir, 'corpus.txt'), SENTENCES)
@@ -109,6 +124,43 @@ class TestTraining < LitseaTest
     end
   end
 
+  def test_block_receives_every_epoch_of_both_stages
+    Dir.mktmpdir do |dir|
+      corpus = write_corpus(File.join(dir, 'corpus_pos.txt'), POS_SENTENCES)
+      prefix = File.join(dir, 'features')
+      Litsea::Extractor.new(:japanese).extract_two_stage(corpus, prefix)
+
+      reports = []
+      Litsea::TwoStageTrainer.new(2, prefix).train(File.join(dir, 'two_stage.model')) do |progress|
+        reports << [progress.stage, progress.iteration, progress.total]
+      end
+
+      assert_equal [['boundary', 1, 2], ['boundary', 2, 2], ['tagging', 1, 2], ['tagging', 2, 2]], reports
+    end
+  end
+
+  def test_an_exception_from_the_block_stops_training
+    Dir.mktmpdir do |dir|
+      corpus = write_corpus(File.join(dir, 'corpus.txt'), SENTENCES)
+      features = File.join(dir, 'features.txt')
+      model = File.join(dir, 'stopped.model')
+      Litsea::Extractor.new(:japanese).extract(corpus, features)
+
+      calls = 0
+      error = assert_raises(RuntimeError) do
+        Litsea::Trainer.new(0.0, 100_000, features).train(model) do |_progress|
+          calls += 1
+          raise 'stop'
+        end
+      end
+
+      assert_equal 'stop', error.message
+      assert_equal 1, calls
+      # Like a cancellation, stopping still writes the partial model.
+      assert_path_exists model
+    end
+  end
+
   def test_cancel_token_reset
     token = Litsea::CancelToken.new
     refute token.cancelled?
@@ -97,6 +97,19 @@ impl Segmenter {
         self.inner.segment_tokens(text).into_iter().map(Token::from).collect()
     }
 
+    /// Splits a sentence into tokens carrying byte offsets and a boundary
+    /// confidence `score`.
+    ///
+    /// # Arguments
+    /// * `text` - The sentence to segment.
+    ///
+    /// # Returns
+    /// An array of `Token`s with `score` set and `pos` unset.
+    #[wasm_bindgen(js_name = segmentWithScores)]
+    pub fn segment_with_scores(&self, text: &str) -> Vec<Token> {
+        self.inner.segment_with_scores(text).into_iter().map(Token::from).collect()
+    }
+
     /// Splits a sentence into tokens and tags each with a UPOS tag.
     ///
     /// # Arguments
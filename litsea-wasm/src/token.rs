@@ -23,6 +23,9 @@ pub struct Token {
     start: u32,
     /// Ending byte offset (exclusive) in the input.
     end: u32,
+    /// The boundary confidence, or `undefined` unless the token came from
+    /// `segmentWithScores`.
+    score: Option<f64>,
 }
 
 #[wasm_bindgen]
@@ -51,6 +54,13 @@ impl Token {
         self.end
     }
 
+    /// The boundary confidence from `segmentWithScores` (small values flag
+    /// tokens worth reviewing), or `undefined`.
+    #[wasm_bindgen(getter)]
+    pub fn score(&self) -> Option<f64> {
+        self.score
+    }
+
     /// Returns a readable representation.
     ///
     /// # Returns
@@ -73,11 +83,13 @@ impl From<TokenView> for Token {
     /// # Returns
     /// The corresponding [`Token`].
     fn from(view: TokenView) -> Self {
+        let score = view.confidence();
         Self {
             surface: view.surface,
             pos: view.pos.map(|pos| pos.to_string()),
             start: view.byte_start as u32,
             end: view.byte_end as u32,
+            score,
         }
     }
 }
@@ -163,6 +163,20 @@ fn byte_offsets_tile_the_input() {
     }
 }
 
+#[wasm_bindgen_test]
+fn scores_keep_the_token_offsets() {
+    let segmenter = Segmenter::from_bytes("korean", KOREAN).unwrap();
+    let sentence = "안녕하세요 반갑습니다";
+    let scored = segmenter.segment_with_scores(sentence);
+    let plain = segmenter.segment_tokens(sentence);
+    assert_eq!(scored.len(), plain.len());
+    for (scored, plain) in scored.iter().zip(&plain) {
+        assert_eq!((scored.start(), scored.end()), (plain.start(), plain.end()));
+        assert!(scored.score().is_some_and(|score| score >= 0.0));
+        assert!(plain.score().is_none());
+    }
+}
+
 #[wasm_bindgen_test]
 fn whitespace_is_its_own_token() {
     let segmenter = Segmenter::from_bytes("korean", KOREAN).unwrap();
@@ -32,11 +32,28 @@ use std::io::BufReader;
 // public predict(), where FxHash is still faster than the default SipHash.
 use rustc_hash::FxHashMap;
 
+use crate::binary_model::{self, Decoder, Encoder};
+use crate::cutoff::FeatureCutoff;
 use crate::error::{LitseaError, Result};
+use crate::language::Language;
+use crate::metadata::ModelMetadata;
 use crate::metrics::BinaryMetrics;
+use crate::normalize::Normalization;
+use crate::packed_model::PackedModel;
+use crate::progress::Progress;
+use crate::two_stage::ModelKind;
+use crate::user_dict::UserDictionary;
 
 type Label = i8;
 
+/// Key of the optional `#normalization\t<form>` header line that declares
+/// the [`Normalization`] a model was trained with.
+const NORMALIZATION_HEADER: &str = "normalization";
+
+/// Key of the `#dictionary\t<word>` header lines, one per word of the
+/// model's dictionary (see [`AdaBoost::set_dictionary`]).
+const DICTIONARY_HEADER: &str = "dictionary";
+
 /// Accumulator for the feature-vocabulary pass over a features file.
 ///
 /// The pass is shared by the path-based and in-memory entry points, which
@@ -77,6 +94,20 @@ impl FeaturePass {
     }
 }
 
+/// How [`AdaBoost::prune`] selects the features to keep. Rankings are by
+/// absolute weight, ties broken by feature name, so pruning is
+/// deterministic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PruneStrategy {
+    /// Keep the `n` features with the largest absolute weight.
+    TopN(usize),
+    /// Keep the features whose absolute weight is at least the threshold.
+    MinWeight(f64),
+    /// Keep the `n` features with the largest absolute weight of each
+    /// template (feature prefix before the `:`, e.g. `UW4`).
+    PerTemplate(usize),
+}
+
 /// AdaBoost binary classifier used for word-boundary prediction.
 ///
 /// Weak hypotheses are presence stumps over string features. The learner
@@ -120,6 +151,19 @@ pub struct AdaBoost {
     /// Cached value of `-sum(model) / 2.0`, kept in sync by every
     /// weight-mutating path so `bias()` is O(1) on the inference hot path.
     cached_bias: f64,
+    /// The text normalization the model expects its input to have had.
+    normalization: Normalization,
+    /// Provenance recorded in the model file (language, templates,
+    /// training parameters, corpus checksum).
+    metadata: ModelMetadata,
+    /// The word list the dictionary-match features are computed from;
+    /// empty for a model trained without one.
+    dictionary: UserDictionary,
+    /// Packed scoring tables read from a binary model file along with the
+    /// weights, and the language they were compiled for. Handed over to
+    /// the first segmenter built for that language; dropped by every
+    /// weight-mutating path (see `recompute_bias`).
+    compiled: Option<(Language, PackedModel)>,
 }
 
 impl Default for AdaBoost {
@@ -162,6 +206,10 @@ impl AdaBoost {
             instances: vec![],
             num_instances: 0,
             cached_bias: 0.0,
+            normalization: Normalization::None,
+            metadata: ModelMetadata::default(),
+            dictionary: UserDictionary::new(),
+            compiled: None,
         }
     }
 
@@ -177,12 +225,83 @@ impl AdaBoost {
         self.num_iterations
     }
 
+    /// Returns the text normalization this model declares: the form its
+    /// training text had, and therefore the form
+    /// [`Segmenter::with_learner`](crate::segmenter::Segmenter::with_learner)
+    /// applies to input by default.
+    #[must_use]
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Sets the text normalization this model declares. It is written as a
+    /// `#normalization` header line by [`save_model`](Self::save_model)
+    /// (nothing is written for [`Normalization::None`], so such models keep
+    /// the original format byte for byte).
+    ///
+    /// # Arguments
+    /// * `normalization`: The form the training text was normalized to.
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+    }
+
+    /// Returns the provenance this model records (see
+    /// [`ModelMetadata`]).
+    #[must_use]
+    pub fn metadata(&self) -> &ModelMetadata {
+        &self.metadata
+    }
+
+    /// Sets the provenance this model records. It is written as
+    /// `#key\tvalue` header lines by [`save_model`](Self::save_model)
+    /// after the `#normalization` header (nothing is written for empty
+    /// metadata).
+    ///
+    /// # Arguments
+    /// * `metadata`: The model's language, templates, training parameters
+    ///   and corpus checksum.
+    pub fn set_metadata(&mut self, metadata: ModelMetadata) {
+        self.metadata = metadata;
+    }
+
+    /// Returns the word list this model's dictionary-match features are
+    /// computed from, or `None` for a model without one.
+    #[must_use]
+    pub fn dictionary(&self) -> Option<&UserDictionary> {
+        (!self.dictionary.is_empty()).then_some(&self.dictionary)
+    }
+
+    /// Sets the word list the dictionary-match features are computed from:
+    /// the one the training features were extracted with (see
+    /// [`Extractor::with_dictionary`](crate::extractor::Extractor::with_dictionary)).
+    /// It is stored in the model as one `#dictionary\t<word>` header line
+    /// per word, so segmenters loading the model match it against their
+    /// input without a separate file. An empty dictionary removes it.
+    ///
+    /// # Arguments
+    /// * `dictionary`: The word list.
+    pub fn set_dictionary(&mut self, dictionary: UserDictionary) {
+        self.dictionary = dictionary;
+        // Compiled tables were built without consulting the dictionary, but
+        // a segmenter adopting them must still match the new one.
+        self.compiled = None;
+    }
+
+    /// Returns whether no feature belongs to a tag-dependent template
+    /// (`UP*`/`BP*`/`UQ*`/`BQ*`/`TQ*`), i.e. whether the model is
+    /// pointwise. Used to record the template set of a trained model.
+    pub(crate) fn is_tag_free(&self) -> bool {
+        !self.features.iter().any(|f| crate::packed_model::is_tag_dependent_feature(f))
+    }
+
     /// Recomputes the cached bias from the current model weights. Must be
     /// called by every path that changes weight values (summing in model
     /// order keeps the float result identical to the previous on-demand
-    /// computation).
+    /// computation). Precompiled tables no longer match the weights, so
+    /// they are dropped here.
     fn recompute_bias(&mut self) {
         self.cached_bias = -self.model.iter().sum::<f64>() / 2.0;
+        self.compiled = None;
     }
 
     /// Initializes the features from a file.
@@ -423,6 +542,21 @@ impl AdaBoost {
     /// Training is a no-op when no instances have been added, and stops early
     /// if the instance weight sum degenerates (non-positive or non-finite).
     pub fn train(&mut self, running: &AtomicBool) {
+        self.train_observed(running, None);
+    }
+
+    /// Trains the model, reporting every completed boosting iteration.
+    ///
+    /// The body of [`train`](Self::train).
+    ///
+    /// # Arguments
+    /// * `running` - A flag for interrupting the training.
+    /// * `progress` - Receives a report after every iteration, if any.
+    pub(crate) fn train_observed(
+        &mut self,
+        running: &AtomicBool,
+        mut progress: Option<&mut Progress<'_>>,
+    ) {
         // Without instances (or features) there is nothing to learn; the
         // error-rate computation below would divide by zero.
         if self.num_instances == 0 || self.features.is_empty() {
@@ -434,7 +568,7 @@ impl AdaBoost {
         // training, so one allocation suffices.
         let mut errors = vec![0.0f64; num_features];
 
-        for _t in 0..self.num_iterations {
+        for t in 0..self.num_iterations {
             if !running.load(Ordering::SeqCst) {
                 break;
             }
@@ -493,12 +627,14 @@ impl AdaBoost {
             self.model[h_best] += alpha;
 
             // Update model
+            let mut misclassified = 0;
             for i in 0..self.num_instances {
                 let label = self.labels[i];
                 let (start, end) = self.instances[i];
                 let hs = &self.instances_buf[start..end];
                 let prediction = if hs.binary_search(&h_best).is_ok() { 1 } else { -1 };
                 if label * prediction < 0 {
+                    misclassified += 1;
                     self.instance_weights[i] *= alpha_exp;
                 } else {
                     self.instance_weights[i] /= alpha_exp;
@@ -512,6 +648,9 @@ impl AdaBoost {
                     *d /= sum_w;
                 }
             }
+            if let Some(progress) = progress.as_deref_mut() {
+                progress.report(t + 1, best_error_rate * 100.0, misclassified, None);
+            }
         }
 
         // The training loop mutates model weights directly; refresh the
@@ -536,7 +675,11 @@ impl AdaBoost {
     ///
     /// This method writes the model to a file in a tab-separated format,
     /// where each line contains a feature and its corresponding weight,
-    /// in the learner's (deterministic) feature order.
+    /// in the learner's (deterministic) feature order, preceded by a
+    /// `#normalization\t<form>` header line when the model declares a
+    /// normalization (see [`set_normalization`](Self::set_normalization))
+    /// and by one `#key\tvalue` line per recorded metadata entry (see
+    /// [`set_metadata`](Self::set_metadata)).
     /// The last line contains the bias term, which is calculated as the negative sum of the model weights divided by 2.
     /// The bias bucket (the empty-string feature `""`) is identified by name
     /// and folded into the bias line instead of being written as a feature.
@@ -560,12 +703,16 @@ impl AdaBoost {
     ///
     /// # Errors
     /// Returns [`LitseaError::InvalidInput`] if the model has no real
-    /// (non-bias) feature, or an I/O error if writing fails.
+    /// (non-bias) feature or a metadata value cannot be written as a
+    /// header line, or an I/O error if writing fails.
     pub fn save_model_to_writer<W: Write>(&self, writer: &mut W) -> Result<()> {
         // A model without any real (non-bias) feature has nothing to save.
         if !self.features.iter().any(|f| !f.is_empty()) {
             return Err(LitseaError::InvalidInput("Cannot save an empty model".to_string()));
         }
+        for (key, value) in self.headers()? {
+            writeln!(writer, "#{}\t{}", key, value)?;
+        }
         let mut bias = match self.feature_index.get("") {
             Some(&idx) => -self.model[idx],
             None => 0.0,
@@ -627,27 +774,43 @@ impl AdaBoost {
     /// for incremental training. Otherwise the model is loaded as-is, with
     /// features sorted by name and the bias bucket `""` kept at index 0.
     ///
+    /// The reader may also hold a binary model written by
+    /// [`save_binary_model`](Self::save_binary_model), recognized by its
+    /// magic line. It is loaded the same way without any text parsing, and
+    /// the packed tables it carries are kept for the segmenter.
+    ///
     /// # Arguments
     /// * `reader`: A buffered reader containing the model data.
     ///
     /// # Errors
     /// Returns `LitseaError::InvalidData` if the content cannot be parsed or
-    /// violates the model format: the file must consist of unique
-    /// tab-separated weight lines plus exactly one bias line (a single
-    /// number), and every value must be finite. An empty file, a file
+    /// violates the model format: the file must consist of optional
+    /// `#key\tvalue` header lines (`#normalization`, naming a
+    /// [`Normalization`], and the [`ModelMetadata`] keys, each at most
+    /// once) before unique tab-separated weight
+    /// lines plus exactly one bias line (a single number), and every value
+    /// must be finite. An empty file, a file
     /// without a bias line (e.g. a truncated download), or a file with more
-    /// than one bias line is rejected. The learner is not modified on error.
+    /// than one bias line is rejected. A binary model is rejected when it is
+    /// truncated, holds another model kind, or is otherwise malformed. The
+    /// learner is not modified on error.
     ///
     /// `save_model` always writes the bias line last; weight lines after the
     /// bias line are nevertheless accepted for compatibility with legacy
     /// models (e.g. `RWCP.model`), with the bias-bucket weight computed from
     /// the weights preceding the bias line exactly as the historical loader
     /// did.
-    pub fn load_model_from_reader<R: BufRead>(&mut self, reader: R) -> Result<()> {
+    pub fn load_model_from_reader<R: BufRead>(&mut self, mut reader: R) -> Result<()> {
+        if let Some(bytes) = binary_model::read_if_binary(&mut reader)? {
+            return self.load_binary_model(&bytes);
+        }
         let mut m: FxHashMap<String, f64> = FxHashMap::default();
         let mut weight_sum = 0.0;
         let mut bias_seen = false;
         let mut any_line = false;
+        let mut normalization = Normalization::None;
+        let mut metadata = ModelMetadata::default();
+        let mut dictionary = UserDictionary::new();
 
         for (line_num, line) in reader.lines().enumerate() {
             let line = line?;
@@ -658,6 +821,22 @@ impl AdaBoost {
                 )));
             }
             any_line = true;
+            // Header lines declare model properties; feature names always
+            // start with a template prefix, never with '#'.
+            if let Some(header) = line.strip_prefix('#') {
+                if bias_seen || !m.is_empty() {
+                    return Err(LitseaError::InvalidData(format!(
+                        "Header line after the model weights at line {}",
+                        line_num + 1
+                    )));
+                }
+                let (key, value) = header.split_once('\t').unwrap_or((header, ""));
+                apply_header(&mut normalization, &mut metadata, &mut dictionary, key, value)
+                    .map_err(|e| {
+                        LitseaError::InvalidData(format!("{} at line {}", e, line_num + 1))
+                    })?;
+                continue;
+            }
             // Model lines are tab-separated ("feature\tweight", written by
             // save_model); feature names may embed any non-tab character.
             let mut parts = line.split('\t');
@@ -728,25 +907,89 @@ impl AdaBoost {
             ));
         }
 
-        // A learner is "fresh" when it holds no instances and no real
-        // features (only the bias bucket registered by `new()`); in that case
-        // the loaded model replaces everything. Otherwise the weights are
-        // merged into the existing feature index.
-        if self.num_instances == 0 && self.features.len() <= 1 {
-            // Fresh load: replace everything, features sorted by name. The
-            // bias bucket "" must exist even if the file has no bias line so
-            // that it stays at index 0 (it sorts first in the BTreeMap).
-            let mut sorted: BTreeMap<_, _> = m.into_iter().collect();
-            sorted.entry(String::new()).or_insert(0.0);
-            self.features = sorted.keys().cloned().collect();
-            self.model = sorted.values().cloned().collect();
+        // The bias bucket "" must exist even if the file has no bias line so
+        // that it stays at index 0 (it sorts first in the BTreeMap).
+        let mut sorted: BTreeMap<_, _> = m.into_iter().collect();
+        sorted.entry(String::new()).or_insert(0.0);
+        self.install_weights(
+            sorted.into_iter().collect(),
+            normalization,
+            metadata,
+            dictionary,
+            None,
+        );
+        Ok(())
+    }
+
+    /// Creates a model from per-feature weights and a bias, as derived by
+    /// converting another linear classifier (the two-stage trainer's
+    /// stage-1 collapse). Loading the equivalent model text would re-derive
+    /// the bias from a float sum whose last bits depend on the order the
+    /// weights are listed in; here the bias is taken as given, so exact
+    /// ties keep their sign.
+    ///
+    /// # Arguments
+    /// * `weights` - `(feature, weight)` pairs with unique, non-empty
+    ///   feature names; zero weights are dropped.
+    /// * `bias` - The bias term of the model.
+    ///
+    /// # Returns
+    /// A model holding the weights, ready to save or segment with.
+    pub(crate) fn from_weights<I>(weights: I, bias: f64) -> Self
+    where
+        I: IntoIterator<Item = (String, f64)>,
+    {
+        let mut sorted: BTreeMap<String, f64> =
+            weights.into_iter().filter(|&(_, w)| w != 0.0).collect();
+        let bucket = -bias * 2.0 - sorted.values().sum::<f64>();
+        sorted.insert(String::new(), bucket);
+        let mut model = AdaBoost::default();
+        model.install_weights(
+            sorted.into_iter().collect(),
+            Normalization::None,
+            ModelMetadata::default(),
+            UserDictionary::new(),
+            None,
+        );
+        model.cached_bias = bias;
+        model
+    }
+
+    /// Installs loaded weights, shared by the text and binary loaders.
+    ///
+    /// A learner is "fresh" when it holds no instances and no real features
+    /// (only the bias bucket registered by `new()`); in that case the loaded
+    /// model replaces everything and keeps `compiled`. Otherwise the weights
+    /// are merged into the existing feature index and `compiled` is
+    /// discarded, since it does not cover the merged features.
+    ///
+    /// # Arguments
+    /// * `weights` - `(feature, weight)` pairs sorted by name, starting
+    ///   with the bias bucket `""`.
+    /// * `normalization` - The normalization the model declares.
+    /// * `metadata` - The provenance the model records.
+    /// * `dictionary` - The word list the model's dictionary features are
+    ///   computed from.
+    /// * `compiled` - Packed tables compiled from exactly these weights.
+    fn install_weights(
+        &mut self,
+        weights: Vec<(String, f64)>,
+        normalization: Normalization,
+        metadata: ModelMetadata,
+        dictionary: UserDictionary,
+        compiled: Option<(Language, PackedModel)>,
+    ) {
+        let fresh = self.num_instances == 0 && self.features.len() <= 1;
+        if fresh {
+            // Fresh load: replace everything, features sorted by name.
             self.feature_index =
-                self.features.iter().enumerate().map(|(i, f)| (f.clone(), i)).collect();
+                weights.iter().enumerate().map(|(i, (f, _))| (f.clone(), i)).collect();
+            (self.features, self.model) = weights.into_iter().unzip();
         } else {
             // Incremental load: merge weights by feature name so that indices
             // referenced by already-built instances stay valid; append
             // features that are not part of the training data.
-            for (feature, weight) in m {
+            for (feature, weight) in weights {
                 if let Some(&idx) = self.feature_index.get(&feature) {
                     self.model[idx] = weight;
                 } else {
@@ -757,10 +1000,243 @@ impl AdaBoost {
                 }
             }
         }
+        self.normalization = normalization;
+        self.metadata = metadata;
+        self.dictionary = dictionary;
         self.recompute_bias();
+        if fresh {
+            self.compiled = compiled;
+        }
+    }
+
+    /// Saves the model to a file in the binary model format (see
+    /// [`save_binary_model_to_writer`](Self::save_binary_model_to_writer)).
+    ///
+    /// # Arguments
+    /// * `filename` - The path of the file to write the model to.
+    /// * `language` - The language to compile packed scoring tables for,
+    ///   or `None` to store the weights only.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the model has no real
+    /// (non-bias) feature, or an I/O error if the file cannot be created or
+    /// written to.
+    pub fn save_binary_model(&self, filename: &Path, language: Option<Language>) -> Result<()> {
+        let mut file = std::io::BufWriter::new(File::create(filename)?);
+        self.save_binary_model_to_writer(&mut file, language)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Writes the model to an arbitrary writer in the binary model format
+    /// (`litsea-binary v1 adaboost`).
+    ///
+    /// The file holds the same header values and weights as the text
+    /// format, with each weight stored bit for bit, so
+    /// [`load_model_from_reader`](Self::load_model_from_reader) reads back
+    /// exactly this learner. With a `language`, the packed scoring tables
+    /// compiled for it are stored as well, and a
+    /// [`Segmenter`](crate::segmenter::Segmenter) built for that language
+    /// from the loaded learner uses them instead of compiling its own.
+    /// The writer is not flushed.
+    ///
+    /// # Arguments
+    /// * `writer` - The writer receiving the model bytes.
+    /// * `language` - The language to compile packed scoring tables for,
+    ///   or `None` to store the weights only.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the model has no real
+    /// (non-bias) feature, or an I/O error if writing fails.
+    pub fn save_binary_model_to_writer<W: Write>(
+        &self,
+        writer: &mut W,
+        language: Option<Language>,
+    ) -> Result<()> {
+        let mut enc = Encoder::new(writer, ModelKind::AdaBoost)?;
+        self.write_binary(&mut enc, language)
+    }
+
+    /// Returns the header `(key, value)` pairs shared by the text and
+    /// binary writers: `normalization` (unless [`Normalization::None`]),
+    /// the metadata entries, then one `dictionary` entry per word in
+    /// sorted order.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if a metadata value or a
+    /// dictionary word cannot be written as a header line.
+    fn headers(&self) -> Result<Vec<(String, String)>> {
+        self.metadata.validate()?;
+        let mut headers = Vec::new();
+        if self.normalization != Normalization::None {
+            headers.push((NORMALIZATION_HEADER.to_string(), self.normalization.to_string()));
+        }
+        headers.extend(self.metadata.headers());
+        for word in self.dictionary.words() {
+            if word.contains(['\t', '\n', '\r']) {
+                return Err(LitseaError::InvalidInput(format!(
+                    "dictionary word cannot be written as a header line: '{}'",
+                    word.escape_debug()
+                )));
+            }
+            headers.push((DICTIONARY_HEADER.to_string(), word));
+        }
+        Ok(headers)
+    }
+
+    /// Writes the model's binary payload: the header values as
+    /// `(key, value)` string pairs, the bias bucket and every non-zero
+    /// weight sorted by feature name, and the optional language tag
+    /// followed by its packed tables. Also embedded by the two-stage
+    /// binary format.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the model has no real
+    /// (non-bias) feature, or an I/O error if writing fails.
+    pub(crate) fn write_binary<W: Write>(
+        &self,
+        enc: &mut Encoder<'_, W>,
+        language: Option<Language>,
+    ) -> Result<()> {
+        if !self.features.iter().any(|f| !f.is_empty()) {
+            return Err(LitseaError::InvalidInput("Cannot save an empty model".to_string()));
+        }
+        let headers = self.headers()?;
+        enc.len(headers.len())?;
+        for (key, value) in &headers {
+            enc.str(key)?;
+            enc.str(value)?;
+        }
+        let mut weights: Vec<(&str, f64)> = self
+            .features
+            .iter()
+            .zip(self.model.iter())
+            .filter(|(f, w)| f.is_empty() || **w != 0.0)
+            .map(|(f, &w)| (f.as_str(), w))
+            .collect();
+        weights.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        enc.len(weights.len())?;
+        for (feature, weight) in weights {
+            enc.str(feature)?;
+            enc.f64(weight)?;
+        }
+        enc.language(language)?;
+        if let Some(language) = language {
+            match &self.compiled {
+                Some((compiled_for, packed)) if *compiled_for == language => {
+                    packed.write_binary(enc)?;
+                }
+                _ => PackedModel::build(language, self).write_binary(enc)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a payload written by [`write_binary`](Self::write_binary) into
+    /// a fresh learner (default hyperparameters), keeping its packed
+    /// tables for [`take_compiled`](Self::take_compiled).
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the payload is truncated, a
+    /// header is unknown or invalid, the features are not sorted and
+    /// unique with the bias bucket first, a weight is not finite, or the
+    /// packed tables are malformed.
+    pub(crate) fn read_binary(dec: &mut Decoder<'_>) -> Result<Self> {
+        let mut normalization = Normalization::None;
+        let mut metadata = ModelMetadata::default();
+        let mut dictionary = UserDictionary::new();
+        for _ in 0..dec.len(8)? {
+            let key = dec.str()?;
+            let value = dec.str()?;
+            apply_header(&mut normalization, &mut metadata, &mut dictionary, key, value)
+                .map_err(LitseaError::InvalidData)?;
+        }
+        let len = dec.len(4 + 8)?;
+        let mut weights: Vec<(String, f64)> = Vec::with_capacity(len);
+        for _ in 0..len {
+            let feature = dec.str()?;
+            let weight = dec.f64()?;
+            if !weight.is_finite() {
+                return Err(LitseaError::InvalidData(format!(
+                    "Non-finite weight for feature '{}'",
+                    feature
+                )));
+            }
+            let in_order = match weights.last() {
+                Some((prev, _)) => prev.as_str() < feature,
+                None => feature.is_empty(),
+            };
+            if !in_order {
+                return Err(LitseaError::InvalidData(format!(
+                    "binary model features are not sorted with the bias bucket first at '{}'",
+                    feature
+                )));
+            }
+            weights.push((feature.to_string(), weight));
+        }
+        if weights.is_empty() {
+            return Err(LitseaError::InvalidData("binary model has no bias bucket".to_string()));
+        }
+        let compiled = match dec.language()? {
+            Some(language) => Some((language, PackedModel::read_binary(language, dec)?)),
+            None => None,
+        };
+        let mut learner = AdaBoost::default();
+        learner.install_weights(weights, normalization, metadata, dictionary, compiled);
+        let weights = PackedModel::dictionary_weights(&learner);
+        if let Some((_, packed)) = learner.compiled.as_mut() {
+            packed.dictionary = weights;
+        }
+        Ok(learner)
+    }
+
+    /// Loads a binary model file into this learner: replaces a fresh
+    /// learner's state (keeping its hyperparameters) or merges the weights
+    /// like the text loader.
+    fn load_binary_model(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut dec = Decoder::new(bytes, ModelKind::AdaBoost)?;
+        let mut loaded = Self::read_binary(&mut dec)?;
+        dec.finish()?;
+        let weights = loaded.features.drain(..).zip(loaded.model.drain(..)).collect();
+        let metadata = std::mem::take(&mut loaded.metadata);
+        let dictionary = std::mem::take(&mut loaded.dictionary);
+        self.install_weights(
+            weights,
+            loaded.normalization,
+            metadata,
+            dictionary,
+            loaded.compiled.take(),
+        );
         Ok(())
     }
 
+    /// Returns the language of the packed tables read from a binary model,
+    /// or `None` if the model carried none (a text model, or a binary one
+    /// written without a language) or its weights have changed since.
+    ///
+    /// Pass it back to
+    /// [`save_binary_model_to_writer`](Self::save_binary_model_to_writer)
+    /// to keep the tables in a rewritten model.
+    #[must_use]
+    pub fn compiled_language(&self) -> Option<Language> {
+        self.compiled.as_ref().map(|(language, _)| *language)
+    }
+
+    /// Takes the packed tables read from a binary model, if they were
+    /// compiled for `language` and the weights have not changed since.
+    ///
+    /// # Arguments
+    /// * `language` - The language the caller is about to compile for.
+    ///
+    /// # Returns
+    /// The precompiled tables, or `None` if the caller must build them.
+    pub(crate) fn take_compiled(&mut self, language: Language) -> Option<PackedModel> {
+        match self.compiled.take() {
+            Some((compiled_for, packed)) if compiled_for == language => Some(packed),
+            _ => None,
+        }
+    }
+
     /// Adds a new instance to the model.
     /// The instance is represented by a set of attributes and a label.
     ///
@@ -793,6 +1269,122 @@ impl AdaBoost {
         // weight sum) is unchanged; no recompute needed on this hot-ish path.
     }
 
+    /// Puts the features that [`add_instance`](Self::add_instance) collected
+    /// in arrival order into the sorted order the features-file route
+    /// builds (see [`initialize_features`](Self::initialize_features)).
+    ///
+    /// Boosting breaks ties between equally good features by index, so a
+    /// learner fed instance by instance trains exactly the model its
+    /// extracted features file would only once its features are in the same
+    /// order.
+    ///
+    /// # Returns
+    /// A result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if no real (non-bias) feature
+    /// was added.
+    pub(crate) fn sort_features(&mut self) -> Result<()> {
+        if self.features.len() <= 1 {
+            return Err(LitseaError::InvalidData(
+                "No features found in the training data (only bias term present)".to_string(),
+            ));
+        }
+
+        // The bias bucket "" sorts first, so it keeps index 0.
+        let mut order: Vec<usize> = (0..self.features.len()).collect();
+        order.sort_unstable_by(|&a, &b| self.features[a].cmp(&self.features[b]));
+        let mut remap = vec![0; order.len()];
+        for (new, &old) in order.iter().enumerate() {
+            remap[old] = new;
+        }
+
+        let mut features = std::mem::take(&mut self.features);
+        self.features = order.iter().map(|&old| std::mem::take(&mut features[old])).collect();
+        self.model = order.iter().map(|&old| self.model[old]).collect();
+        for idx in self.feature_index.values_mut() {
+            *idx = remap[*idx];
+        }
+        for h in &mut self.instances_buf {
+            *h = remap[*h];
+        }
+        for &(start, end) in &self.instances {
+            self.instances_buf[start..end].sort_unstable();
+        }
+
+        self.recompute_bias();
+        Ok(())
+    }
+
+    /// Drops the features that occur in fewer training instances than the
+    /// cutoff requires, from the instances and the feature vocabulary.
+    ///
+    /// Counts are taken over the instances loaded so far, so call this
+    /// after the instances are in and before [`train`](Self::train).
+    /// The bias bucket, and features that carry a weight (from a loaded
+    /// model), are kept whatever their count. Instance boosting weights are
+    /// left as they are: a dropped feature's weight is zero, so the scores
+    /// they were seeded from do not change.
+    ///
+    /// # Arguments
+    /// * `cutoff`: The minimum counts.
+    ///
+    /// # Returns
+    /// The number of features dropped.
+    pub fn apply_cutoff(&mut self, cutoff: &FeatureCutoff) -> usize {
+        if cutoff.is_noop() {
+            return 0;
+        }
+
+        let mut counts = vec![0usize; self.features.len()];
+        for &h in &self.instances_buf {
+            counts[h] += 1;
+        }
+        let mut remap = vec![usize::MAX; self.features.len()];
+        let mut kept = 0;
+        for (idx, feature) in self.features.iter().enumerate() {
+            if idx == 0 || self.model[idx] != 0.0 || cutoff.keeps(feature, counts[idx]) {
+                remap[idx] = kept;
+                kept += 1;
+            }
+        }
+        let removed = self.features.len() - kept;
+        if removed == 0 {
+            return 0;
+        }
+
+        // Compact the vocabulary; kept features keep their relative order.
+        let features = std::mem::take(&mut self.features);
+        let model = std::mem::take(&mut self.model);
+        for ((feature, weight), &new) in features.into_iter().zip(model).zip(&remap) {
+            if new != usize::MAX {
+                self.features.push(feature);
+                self.model.push(weight);
+            }
+        }
+        self.feature_index =
+            self.features.iter().enumerate().map(|(i, f)| (f.clone(), i)).collect();
+
+        // Compact the instances in place; order within each stays sorted
+        // because the remap is monotonic.
+        let mut write = 0;
+        for range in &mut self.instances {
+            let start = write;
+            for read in range.0..range.1 {
+                let new = remap[self.instances_buf[read]];
+                if new != usize::MAX {
+                    self.instances_buf[write] = new;
+                    write += 1;
+                }
+            }
+            *range = (start, write);
+        }
+        self.instances_buf.truncate(write);
+
+        self.recompute_bias();
+        removed
+    }
+
     /// Predicts the label for a given set of attributes.
     ///
     /// # Arguments
@@ -811,18 +1403,72 @@ impl AdaBoost {
         if score >= 0.0 { 1 } else { -1 }
     }
 
+    /// Applies one online perceptron update for a labeled instance, in the
+    /// passive-aggressive form: if the instance scores less than `margin`
+    /// on the side of `label`, the weights of its attributes and the bias
+    /// all move along `label` by the smallest equal step that brings it to
+    /// `margin`; otherwise nothing changes.
+    ///
+    /// The step is sized by the score itself, so an update is as large as
+    /// the mistake whatever the scale of the model's weights (a few tenths
+    /// for a boosted model, tens for a collapsed perceptron). Attributes
+    /// the model does not know yet are added.
+    ///
+    /// # Arguments
+    /// * `attributes` - The attributes of the instance.
+    /// * `label` - The gold label, `1` or `-1`.
+    /// * `margin` - The score the instance must reach on the side of
+    ///   `label`; positive, so that `-1` instances end strictly below zero.
+    ///
+    /// # Returns
+    /// Whether the weights changed.
+    pub fn update(&mut self, attributes: &HashSet<String>, label: i8, margin: f64) -> bool {
+        let direction = if label > 0 { 1.0 } else { -1.0 };
+        let mut score = self.bias();
+        for attr in attributes {
+            if let Some(&idx) = self.feature_index.get(attr) {
+                score += self.model[idx];
+            }
+        }
+        let loss = margin - direction * score;
+        if loss <= 0.0 {
+            return false;
+        }
+
+        // The bias counts as one more attribute of the instance.
+        let step = direction * loss / (attributes.len() + 1) as f64;
+        for attr in attributes {
+            let idx = match self.feature_index.get(attr) {
+                Some(&idx) => idx,
+                None => {
+                    let idx = self.features.len();
+                    self.features.push(attr.clone());
+                    self.model.push(0.0);
+                    self.feature_index.insert(attr.clone(), idx);
+                    idx
+                }
+            };
+            self.model[idx] += step;
+        }
+        // bias = -sum(model) / 2, so the bucket takes back the attributes'
+        // steps and twice the bias step.
+        self.model[0] -= (attributes.len() + 2) as f64 * step;
+        self.recompute_bias();
+        true
+    }
+
     /// Returns the model weight of a single attribute (0.0 if unknown).
     ///
-    /// Test-only: the production scoring path looks weights up by packed
-    /// integer key (see `crate::packed_model`); this string-keyed lookup
-    /// remains as the reference implementation for differential tests.
+    /// The production scoring path looks weights up by packed integer key
+    /// (see `crate::packed_model`); this string-keyed lookup reads the
+    /// handful of dictionary-match weights when the tables are compiled,
+    /// and remains the reference implementation for differential tests.
     ///
     /// # Arguments
     /// * `attr` - The attribute (feature string) to look up.
     ///
     /// # Returns
     /// The weight of the attribute, or 0.0 if the model does not contain it.
-    #[cfg(test)]
     pub(crate) fn weight(&self, attr: &str) -> f64 {
         self.feature_index.get(attr).map_or(0.0, |&idx| self.model[idx])
     }
@@ -855,6 +1501,92 @@ impl AdaBoost {
         self.cached_bias
     }
 
+    /// Prunes the model to a subset of its features, keeping the bias
+    /// unchanged (the bias bucket absorbs the removed weights). Smaller
+    /// models compile smaller packed tables and segment faster; quality
+    /// typically degrades gracefully down to a language-specific cliff, so
+    /// compare a few sizes on held-out data.
+    ///
+    /// # Arguments
+    /// * `strategy` - Which features to keep.
+    ///
+    /// # Returns
+    /// The number of features kept.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the learner holds training
+    /// instances (their feature indices would be invalidated) or the
+    /// [`PruneStrategy::MinWeight`] threshold is negative or not finite.
+    pub fn prune(&mut self, strategy: PruneStrategy) -> Result<usize> {
+        if self.num_instances > 0 {
+            return Err(LitseaError::InvalidInput(
+                "cannot prune a learner holding training instances".to_string(),
+            ));
+        }
+        if let PruneStrategy::MinWeight(threshold) = strategy {
+            if !(threshold.is_finite() && threshold >= 0.0) {
+                return Err(LitseaError::InvalidInput(format!(
+                    "minimum weight must be finite and non-negative, got {}",
+                    threshold
+                )));
+            }
+        }
+
+        // Index 0 is the bias bucket, which is never a pruning candidate.
+        let mut ranked: Vec<usize> = (1..self.features.len()).collect();
+        ranked.sort_by(|&a, &b| {
+            self.model[b]
+                .abs()
+                .total_cmp(&self.model[a].abs())
+                .then_with(|| self.features[a].cmp(&self.features[b]))
+        });
+        let mut keep = vec![false; self.features.len()];
+        match strategy {
+            PruneStrategy::TopN(n) => {
+                for &i in ranked.iter().take(n) {
+                    keep[i] = true;
+                }
+            }
+            PruneStrategy::MinWeight(threshold) => {
+                for &i in &ranked {
+                    keep[i] = self.model[i].abs() >= threshold;
+                }
+            }
+            PruneStrategy::PerTemplate(n) => {
+                let mut kept: FxHashMap<&str, usize> = FxHashMap::default();
+                for &i in &ranked {
+                    let feature = self.features[i].as_str();
+                    let template = feature.split_once(':').map_or(feature, |(prefix, _)| prefix);
+                    let count = kept.entry(template).or_default();
+                    if *count < n {
+                        *count += 1;
+                        keep[i] = true;
+                    }
+                }
+            }
+        }
+
+        let bias = self.bias();
+        let mut features = vec![String::new()];
+        let mut model = vec![0.0];
+        let old = std::mem::take(&mut self.features)
+            .into_iter()
+            .zip(std::mem::take(&mut self.model));
+        for ((feature, weight), keep) in old.zip(keep).skip(1) {
+            if keep {
+                features.push(feature);
+                model.push(weight);
+            }
+        }
+        // bias = -sum(model) / 2, so this bucket weight preserves it.
+        model[0] = -2.0 * bias - model[1..].iter().sum::<f64>();
+        self.feature_index = features.iter().enumerate().map(|(i, f)| (f.clone(), i)).collect();
+        self.features = features;
+        self.model = model;
+        self.recompute_bias();
+        Ok(self.features.len() - 1)
+    }
+
     /// Calculates and returns the performance metrics of the model on the training data.
     #[must_use]
     pub fn metrics(&self) -> BinaryMetrics {
@@ -900,8 +1632,36 @@ impl AdaBoost {
             false_positives,
             false_negatives,
             true_negatives,
+            features_removed: 0,
+        }
+    }
+}
+
+/// Applies one model header, shared by the text and binary loaders:
+/// `normalization`, a `dictionary` word, or a [`ModelMetadata`] key.
+///
+/// # Errors
+/// Returns a message naming the header if the key is unknown or the value
+/// is invalid.
+fn apply_header(
+    normalization: &mut Normalization,
+    metadata: &mut ModelMetadata,
+    dictionary: &mut UserDictionary,
+    key: &str,
+    value: &str,
+) -> std::result::Result<(), String> {
+    if key == NORMALIZATION_HEADER {
+        *normalization = value.parse().map_err(|e| format!("Invalid header '#{}': {}", key, e))?;
+        return Ok(());
+    }
+    if key == DICTIONARY_HEADER {
+        if value.is_empty() {
+            return Err(format!("Invalid header '#{}': empty word", key));
         }
+        dictionary.insert(value);
+        return Ok(());
     }
+    metadata.apply_header(key, value)
 }
 
 #[cfg(test)]
@@ -1270,6 +2030,276 @@ mod tests {
         assert!(matches!(result, Err(LitseaError::InvalidData(_))));
     }
 
+    #[test]
+    fn test_normalization_header_round_trip() -> Result<()> {
+        let mut learner = AdaBoost::new(0.01, 10);
+        learner.load_model_from_reader("feat1\t0.5\n0.1\n".as_bytes())?;
+        assert_eq!(learner.normalization(), Normalization::None);
+        let mut plain = Vec::new();
+        learner.save_model_to_writer(&mut plain)?;
+        assert!(!plain.starts_with(b"#"));
+
+        learner.set_normalization(Normalization::Nfkc);
+        let mut declared = Vec::new();
+        learner.save_model_to_writer(&mut declared)?;
+        assert!(declared.starts_with(b"#normalization\tnfkc\n"));
+        assert_eq!(&declared[b"#normalization\tnfkc\n".len()..], plain.as_slice());
+
+        let mut loaded = AdaBoost::new(0.01, 10);
+        loaded.load_model_from_reader(declared.as_slice())?;
+        assert_eq!(loaded.normalization(), Normalization::Nfkc);
+        assert_eq!(loaded.bias(), learner.bias());
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_moves_a_mistake_to_the_margin() -> Result<()> {
+        let mut learner = AdaBoost::new(0.01, 10);
+        learner.load_model_from_reader("UW1:a\t3.0\nUW2:b\t-1.0\n0.5\n".as_bytes())?;
+        let instance: HashSet<String> = ["UW1:a", "UW3:c"].iter().map(|s| s.to_string()).collect();
+        let score =
+            |learner: &AdaBoost| learner.bias() + learner.weight("UW1:a") + learner.weight("UW3:c");
+        assert_eq!(learner.predict(&instance), 1);
+
+        // Already on the right side by more than the margin: no change.
+        assert!(!learner.update(&instance, 1, 1.0));
+        assert_eq!(score(&learner), 3.5);
+
+        // A mistake moves the score to exactly -margin, spread evenly over
+        // the two attributes (one of them new) and the bias.
+        assert!(learner.update(&instance, -1, 1.0));
+        assert!((score(&learner) + 1.0).abs() < 1e-12);
+        assert_eq!(learner.predict(&instance), -1);
+        assert!((learner.weight("UW1:a") - (3.0 - 1.5)).abs() < 1e-12);
+        assert!((learner.weight("UW3:c") + 1.5).abs() < 1e-12);
+        assert!((learner.bias() - (0.5 - 1.5)).abs() < 1e-12);
+        assert_eq!(learner.weight("UW2:b"), -1.0);
+
+        // The updated model saves and reloads with the same scores.
+        let mut text = Vec::new();
+        learner.save_model_to_writer(&mut text)?;
+        let mut reloaded = AdaBoost::new(0.01, 10);
+        reloaded.load_model_from_reader(text.as_slice())?;
+        assert!((score(&reloaded) + 1.0).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prune_strategies_keep_the_bias() -> Result<()> {
+        let model = "UW1:a\t3.0\nUW1:b\t-0.5\nUW2:c\t-2.0\nUW2:d\t1.0\nBW1:ef\t0.25\n0.75\n";
+        let load = || -> Result<AdaBoost> {
+            let mut learner = AdaBoost::new(0.01, 10);
+            learner.load_model_from_reader(model.as_bytes())?;
+            Ok(learner)
+        };
+        let kept = |learner: &AdaBoost| -> Vec<String> {
+            learner.feature_weights().map(|(f, _)| f.to_string()).collect()
+        };
+
+        let mut learner = load()?;
+        let bias = learner.bias();
+        assert_eq!(learner.prune(PruneStrategy::TopN(2))?, 2);
+        assert_eq!(kept(&learner), ["UW1:a", "UW2:c"]);
+        assert!((learner.bias() - bias).abs() < 1e-12);
+        assert_eq!(learner.weight("UW1:a"), 3.0);
+        assert_eq!(learner.weight("UW1:b"), 0.0);
+
+        let mut learner = load()?;
+        assert_eq!(learner.prune(PruneStrategy::MinWeight(0.5))?, 4);
+        assert_eq!(kept(&learner), ["UW1:a", "UW1:b", "UW2:c", "UW2:d"]);
+
+        let mut learner = load()?;
+        assert_eq!(learner.prune(PruneStrategy::PerTemplate(1))?, 3);
+        assert_eq!(kept(&learner), ["BW1:ef", "UW1:a", "UW2:c"]);
+        assert!((learner.bias() - bias).abs() < 1e-12);
+
+        // A pruned model saves and reloads with the same bias.
+        let mut text = Vec::new();
+        learner.save_model_to_writer(&mut text)?;
+        let mut reloaded = AdaBoost::new(0.01, 10);
+        reloaded.load_model_from_reader(text.as_slice())?;
+        assert!((reloaded.bias() - bias).abs() < 1e-12);
+
+        let result = load()?.prune(PruneStrategy::MinWeight(f64::NAN));
+        assert!(matches!(result, Err(LitseaError::InvalidInput(_))));
+        let mut training = AdaBoost::new(0.01, 10);
+        training.initialize_features_from_str("1\tUW1:a\n")?;
+        training.initialize_instances_from_str("1\tUW1:a\n")?;
+        let result = training.prune(PruneStrategy::TopN(1));
+        assert!(matches!(result, Err(LitseaError::InvalidInput(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_metadata_header_round_trip() -> Result<()> {
+        let mut learner = AdaBoost::new(0.01, 10);
+        learner.load_model_from_reader("feat1\t0.5\n0.1\n".as_bytes())?;
+        learner.set_normalization(Normalization::Nfkc);
+        let metadata = ModelMetadata::new()
+            .with_language(Language::Korean)
+            .with_templates(crate::metadata::TEMPLATES_TAG_FREE)
+            .with_param("iterations", 10);
+        learner.set_metadata(metadata.clone());
+
+        let mut text = Vec::new();
+        learner.save_model_to_writer(&mut text)?;
+        assert!(text.starts_with(
+            b"#normalization\tnfkc\n#language\tkorean\n#templates\ttag-free\n\
+              #param.iterations\t10\nfeat1\t0.5\n"
+        ));
+        let mut binary = Vec::new();
+        learner.save_binary_model_to_writer(&mut binary, None)?;
+
+        for bytes in [text, binary] {
+            let mut loaded = AdaBoost::new(0.01, 10);
+            loaded.load_model_from_reader(bytes.as_slice())?;
+            assert_eq!(loaded.metadata(), &metadata);
+            assert_eq!(loaded.normalization(), Normalization::Nfkc);
+        }
+
+        learner.set_metadata(ModelMetadata::new().with_templates("a\nb"));
+        let result = learner.save_model_to_writer(&mut Vec::new());
+        assert!(matches!(result, Err(LitseaError::InvalidInput(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary_header_round_trip() -> Result<()> {
+        let mut learner = AdaBoost::new(0.01, 10);
+        learner.load_model_from_reader("DR:2\t0.5\nUW4:あ\t0.1\n0.1\n".as_bytes())?;
+        assert!(learner.dictionary().is_none());
+        let mut dictionary = UserDictionary::new();
+        dictionary.insert("東京都");
+        dictionary.insert("東京");
+        learner.set_dictionary(dictionary);
+
+        let mut text = Vec::new();
+        learner.save_model_to_writer(&mut text)?;
+        assert!(text.starts_with("#dictionary\t東京\n#dictionary\t東京都\nDR:2\t".as_bytes()));
+        let mut binary = Vec::new();
+        learner.save_binary_model_to_writer(&mut binary, Some(Language::Japanese))?;
+
+        for bytes in [text, binary] {
+            let mut loaded = AdaBoost::new(0.01, 10);
+            loaded.load_model_from_reader(bytes.as_slice())?;
+            let words = loaded.dictionary().map(UserDictionary::words);
+            assert_eq!(words, Some(vec!["東京".to_string(), "東京都".to_string()]));
+            assert_eq!(loaded.weight("DR:2"), 0.5);
+        }
+
+        let mut dictionary = UserDictionary::new();
+        dictionary.insert("a\tb");
+        learner.set_dictionary(dictionary);
+        let result = learner.save_model_to_writer(&mut Vec::new());
+        assert!(matches!(result, Err(LitseaError::InvalidInput(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_model_rejects_bad_headers() {
+        for model in [
+            "#normalization\tnfd\nfeat1\t0.5\n0.1\n",
+            "#lang\tjapanese\nfeat1\t0.5\n0.1\n",
+            "#language\tklingon\nfeat1\t0.5\n0.1\n",
+            "#language\tjapanese\n#language\tjapanese\nfeat1\t0.5\n0.1\n",
+            "feat1\t0.5\n#normalization\tnfkc\n0.1\n",
+            "feat1\t0.5\n#language\tjapanese\n0.1\n",
+            "#dictionary\t\nfeat1\t0.5\n0.1\n",
+        ] {
+            let mut learner = AdaBoost::new(0.01, 10);
+            let result = learner.load_model_from_reader(model.as_bytes());
+            assert!(matches!(result, Err(LitseaError::InvalidData(_))), "{model:?}");
+        }
+    }
+
+    #[test]
+    fn test_binary_model_round_trip() -> Result<()> {
+        let mut learner = AdaBoost::new(0.01, 10);
+        learner
+            .load_model_from_reader("UW4:あ\t0.5\nBW2:あい\t-0.3\nUC4:H\t0.1\n0.7\n".as_bytes())?;
+        learner.set_normalization(Normalization::Nfkc);
+        let mut text = Vec::new();
+        learner.save_model_to_writer(&mut text)?;
+
+        for language in [None, Some(Language::Japanese)] {
+            let mut binary = Vec::new();
+            learner.save_binary_model_to_writer(&mut binary, language)?;
+            assert!(binary.starts_with(b"litsea-binary v1 adaboost\n"));
+
+            let mut loaded = AdaBoost::new(0.01, 10);
+            loaded.load_model_from_reader(binary.as_slice())?;
+            assert_eq!(loaded.features, learner.features);
+            let bits = |l: &AdaBoost| l.model.iter().map(|w| w.to_bits()).collect::<Vec<_>>();
+            assert_eq!(bits(&loaded), bits(&learner));
+            assert_eq!(loaded.bias().to_bits(), learner.bias().to_bits());
+            assert_eq!(loaded.normalization(), Normalization::Nfkc);
+            assert_eq!(loaded.compiled.is_some(), language.is_some());
+
+            // Converting back to text reproduces the text output exactly.
+            let mut back = Vec::new();
+            loaded.save_model_to_writer(&mut back)?;
+            assert_eq!(back, text);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_model_compiled_tables_follow_the_weights() -> Result<()> {
+        let mut learner = AdaBoost::new(0.01, 10);
+        learner.load_model_from_reader("UW4:あ\t0.5\n0.1\n".as_bytes())?;
+        let mut binary = Vec::new();
+        learner.save_binary_model_to_writer(&mut binary, Some(Language::Japanese))?;
+        let load = || -> Result<AdaBoost> {
+            let mut loaded = AdaBoost::default();
+            loaded.load_model_from_reader(binary.as_slice())?;
+            Ok(loaded)
+        };
+
+        assert!(load()?.take_compiled(Language::Japanese).is_some());
+        assert!(load()?.take_compiled(Language::Korean).is_none());
+
+        // Any weight change drops the tables.
+        let mut trained = load()?;
+        trained.add_instance(attrs_of("UW4:あ"), -1);
+        assert!(trained.compiled.is_some());
+        trained.train(&AtomicBool::new(true));
+        assert!(trained.take_compiled(Language::Japanese).is_none());
+
+        // So does merging into a learner that holds training data.
+        let mut merged = AdaBoost::default();
+        merged.add_instance(attrs_of("UW4:い"), 1);
+        merged.load_model_from_reader(binary.as_slice())?;
+        assert!(merged.take_compiled(Language::Japanese).is_none());
+        assert!((merged.weight("UW4:あ") - 0.5).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_model_rejects_malformed_input() -> Result<()> {
+        let mut learner = AdaBoost::new(0.01, 10);
+        learner.load_model_from_reader("UW4:あ\t0.5\n0.1\n".as_bytes())?;
+        let mut binary = Vec::new();
+        learner.save_binary_model_to_writer(&mut binary, Some(Language::English))?;
+
+        for end in 0..binary.len() {
+            let mut loaded = AdaBoost::default();
+            let result = loaded.load_model_from_reader(&binary[..end]);
+            assert!(result.is_err(), "accepted a model cut at {end}");
+            assert_eq!(loaded.features.len(), 1);
+        }
+        let mut trailing = binary.clone();
+        trailing.push(0);
+        assert!(AdaBoost::default().load_model_from_reader(trailing.as_slice()).is_err());
+
+        let mut perceptron = Vec::new();
+        let mut other = crate::perceptron::AveragedPerceptron::new();
+        other.load_model_from_reader("1\nB\nf\tB\t1\n".as_bytes())?;
+        other.save_binary_model_to_writer(&mut perceptron)?;
+        let result = AdaBoost::default().load_model_from_reader(perceptron.as_slice());
+        assert!(matches!(result, Err(LitseaError::InvalidData(_))));
+        Ok(())
+    }
+
     #[test]
     fn test_load_model_from_reader_invalid_bias() {
         let mut learner = AdaBoost::new(0.01, 10);
@@ -0,0 +1,415 @@
+//! Binary model container.
+//!
+//! A text model is parsed line by line (every weight goes through
+//! `str::parse::<f64>`) and then compiled into the packed scoring tables,
+//! which dominates the cold start of a short-lived process. The binary
+//! form stores the same learner state as length-prefixed little-endian
+//! records, so a loader reads the file once and copies numbers out without
+//! parsing text. It can additionally carry the packed tables compiled for
+//! one language, which a [`Segmenter`](crate::segmenter::Segmenter) built
+//! for that language adopts instead of compiling its own.
+//!
+//! # Layout
+//!
+//! ```text
+//! litsea-binary v1 <kind>\n     ASCII magic line; <kind> is adaboost,
+//!                               perceptron or two-stage
+//! <payload>                     the kind's records (see each learner)
+//! ```
+//!
+//! Records are built from five primitives: `u8`; `u32` and `u64` in
+//! little-endian order; `f64` as its little-endian IEEE 754 bits (exact, so
+//! a text -> binary -> text conversion loses nothing); and strings as a
+//! `u32` byte length followed by UTF-8. Collections are a `u64` count
+//! followed by their elements, written in a sorted order so the output is
+//! deterministic.
+//!
+//! The magic line keeps [`ModelKind::detect`] working on the first line of
+//! either form, and every `load_model_from_reader` accepts both forms, so
+//! callers that dispatch on the model kind need no change to load a
+//! binary file.
+
+use std::io::{self, Write};
+
+use crate::error::{LitseaError, Result};
+use crate::language::Language;
+use crate::two_stage::ModelKind;
+
+/// Prefix shared by all (current and future) binary magic lines.
+pub(crate) const MAGIC_PREFIX: &str = "litsea-binary ";
+/// Format version written after [`MAGIC_PREFIX`].
+const VERSION: &str = "v1";
+
+/// The on-disk form of a model file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelFormat {
+    /// The line-oriented text format written by `save_model`.
+    Text,
+    /// The binary container written by `save_binary_model`.
+    Binary,
+}
+
+impl ModelFormat {
+    /// Detects the form of a model file from its first bytes.
+    ///
+    /// # Arguments
+    /// * `content` - The model file content (or at least its first line).
+    ///
+    /// # Returns
+    /// [`ModelFormat::Binary`] if the content starts with a
+    /// `litsea-binary` magic line, [`ModelFormat::Text`] otherwise.
+    #[must_use]
+    pub fn detect(content: &[u8]) -> Self {
+        if content.starts_with(MAGIC_PREFIX.as_bytes()) {
+            ModelFormat::Binary
+        } else {
+            ModelFormat::Text
+        }
+    }
+}
+
+/// Returns the magic-line name of a model kind.
+pub(crate) fn kind_name(kind: ModelKind) -> &'static str {
+    match kind {
+        ModelKind::AdaBoost => "adaboost",
+        ModelKind::AveragedPerceptron => "perceptron",
+        ModelKind::TwoStage => "two-stage",
+    }
+}
+
+/// Maps the kind name of a binary magic line back to its [`ModelKind`].
+///
+/// # Arguments
+/// * `line` - The first line of a model file, starting with
+///   [`MAGIC_PREFIX`].
+///
+/// # Returns
+/// The kind named by the line, or `None` if the name is unknown.
+pub(crate) fn kind_from_magic(line: &str) -> Option<ModelKind> {
+    let rest = line.strip_prefix(MAGIC_PREFIX)?;
+    let (_, name) = rest.split_once(' ')?;
+    [ModelKind::AdaBoost, ModelKind::AveragedPerceptron, ModelKind::TwoStage]
+        .into_iter()
+        .find(|&kind| kind_name(kind) == name.trim_end())
+}
+
+/// Record writer over an arbitrary [`Write`].
+pub(crate) struct Encoder<'a, W: Write> {
+    writer: &'a mut W,
+}
+
+impl<'a, W: Write> Encoder<'a, W> {
+    /// Writes the magic line for `kind` and returns an encoder for its
+    /// payload.
+    pub(crate) fn new(writer: &'a mut W, kind: ModelKind) -> Result<Self> {
+        writeln!(writer, "{}{} {}", MAGIC_PREFIX, VERSION, kind_name(kind))?;
+        Ok(Encoder { writer })
+    }
+
+    /// Returns an encoder that writes no magic line, for containers with
+    /// their own (training checkpoints).
+    pub(crate) fn headerless(writer: &'a mut W) -> Self {
+        Encoder { writer }
+    }
+
+    pub(crate) fn u8(&mut self, value: u8) -> io::Result<()> {
+        self.writer.write_all(&[value])
+    }
+
+    pub(crate) fn bool(&mut self, value: bool) -> io::Result<()> {
+        self.u8(u8::from(value))
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) -> io::Result<()> {
+        self.writer.write_all(&value.to_le_bytes())
+    }
+
+    pub(crate) fn u64(&mut self, value: u64) -> io::Result<()> {
+        self.writer.write_all(&value.to_le_bytes())
+    }
+
+    /// Writes a collection length.
+    pub(crate) fn len(&mut self, len: usize) -> io::Result<()> {
+        self.u64(len as u64)
+    }
+
+    pub(crate) fn f64(&mut self, value: f64) -> io::Result<()> {
+        self.writer.write_all(&value.to_le_bytes())
+    }
+
+    /// Writes a length-prefixed run of `f64` values.
+    pub(crate) fn f64s(&mut self, values: &[f64]) -> io::Result<()> {
+        self.len(values.len())?;
+        values.iter().try_for_each(|&v| self.f64(v))
+    }
+
+    pub(crate) fn str(&mut self, value: &str) -> Result<()> {
+        let len = u32::try_from(value.len()).map_err(|_| {
+            LitseaError::InvalidInput("string too long for the binary model format".to_string())
+        })?;
+        self.u32(len)?;
+        self.writer.write_all(value.as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes an optional language tag: `0`, or `1` followed by the
+    /// language name.
+    pub(crate) fn language(&mut self, language: Option<Language>) -> Result<()> {
+        match language {
+            Some(language) => {
+                self.bool(true)?;
+                self.str(&language.to_string())
+            }
+            None => Ok(self.bool(false)?),
+        }
+    }
+}
+
+/// Record reader over an in-memory model file. Every read checks the
+/// remaining length, so a truncated or corrupt file yields
+/// [`LitseaError::InvalidData`] rather than a panic or a huge allocation.
+pub(crate) struct Decoder<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// Checks the magic line of `bytes` and returns a decoder positioned
+    /// at the payload.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the magic line is missing,
+    /// names an unsupported version, or holds a kind other than `expected`.
+    pub(crate) fn new(bytes: &'a [u8], expected: ModelKind) -> Result<Self> {
+        let end = bytes.iter().position(|&b| b == b'\n').ok_or_else(truncated)?;
+        let line = std::str::from_utf8(&bytes[..end])
+            .map_err(|_| LitseaError::InvalidData("invalid binary model magic line".to_string()))?;
+        let version = line
+            .strip_prefix(MAGIC_PREFIX)
+            .and_then(|rest| rest.split(' ').next())
+            .ok_or_else(|| {
+                LitseaError::InvalidData(format!(
+                    "missing '{}' magic line",
+                    MAGIC_PREFIX.trim_end()
+                ))
+            })?;
+        if version != VERSION {
+            return Err(LitseaError::InvalidData(format!(
+                "unsupported binary model version: '{}'",
+                line
+            )));
+        }
+        let kind = kind_from_magic(line).ok_or_else(|| {
+            LitseaError::InvalidData(format!("unknown binary model kind: '{}'", line))
+        })?;
+        if kind != expected {
+            return Err(LitseaError::InvalidData(format!(
+                "binary model holds a {} model, expected {}",
+                kind_name(kind),
+                kind_name(expected)
+            )));
+        }
+        Ok(Decoder {
+            buf: &bytes[end + 1..],
+        })
+    }
+
+    /// Returns a decoder over `bytes` without checking a magic line, for
+    /// containers with their own (training checkpoints).
+    pub(crate) fn headerless(bytes: &'a [u8]) -> Self {
+        Decoder { buf: bytes }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.buf.len() < n {
+            return Err(truncated());
+        }
+        let (head, rest) = self.buf.split_at(n);
+        self.buf = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn bool(&mut self) -> Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(LitseaError::InvalidData(format!("invalid flag byte in binary model: {}", b))),
+        }
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a collection length whose elements take at least
+    /// `min_element_size` bytes each, rejecting a length the remaining
+    /// bytes cannot hold (so a corrupt count never drives an allocation).
+    pub(crate) fn len(&mut self, min_element_size: usize) -> Result<usize> {
+        let len = usize::try_from(self.u64()?).map_err(|_| truncated())?;
+        if len.saturating_mul(min_element_size.max(1)) > self.buf.len() {
+            return Err(truncated());
+        }
+        Ok(len)
+    }
+
+    pub(crate) fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a length-prefixed run of `f64` values.
+    pub(crate) fn f64s(&mut self) -> Result<Vec<f64>> {
+        let len = self.len(8)?;
+        let bytes = self.take(len * 8)?;
+        Ok(bytes
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes(b.try_into().expect("chunk of 8 bytes")))
+            .collect())
+    }
+
+    /// Reads exactly `n` `f64` values (a fixed-width row).
+    pub(crate) fn f64_array<const N: usize>(&mut self) -> Result<[f64; N]> {
+        let mut out = [0.0; N];
+        for v in &mut out {
+            *v = self.f64()?;
+        }
+        Ok(out)
+    }
+
+    pub(crate) fn str(&mut self) -> Result<&'a str> {
+        let len = self.u32()? as usize;
+        std::str::from_utf8(self.take(len)?)
+            .map_err(|_| LitseaError::InvalidData("invalid UTF-8 in binary model".to_string()))
+    }
+
+    /// Reads an optional language tag written by [`Encoder::language`].
+    pub(crate) fn language(&mut self) -> Result<Option<Language>> {
+        if !self.bool()? {
+            return Ok(None);
+        }
+        let name = self.str()?;
+        name.parse().map(Some).map_err(|e| LitseaError::InvalidData(format!("{}", e)))
+    }
+
+    /// Checks that the whole payload was consumed.
+    pub(crate) fn finish(self) -> Result<()> {
+        if self.buf.is_empty() {
+            Ok(())
+        } else {
+            Err(LitseaError::InvalidData(format!(
+                "{} trailing bytes after the binary model payload",
+                self.buf.len()
+            )))
+        }
+    }
+}
+
+/// The error for a read past the end of the payload.
+fn truncated() -> LitseaError {
+    LitseaError::InvalidData("binary model is truncated".to_string())
+}
+
+/// Reads the rest of a reader that holds a binary model, or returns `None`
+/// (consuming nothing) if it starts with a text model. Shared by every
+/// learner's `load_model_from_reader`.
+///
+/// # Arguments
+/// * `reader` - The buffered reader about to be loaded from.
+///
+/// # Errors
+/// Propagates I/O errors from the reader.
+pub(crate) fn read_if_binary<R: io::BufRead>(reader: &mut R) -> Result<Option<Vec<u8>>> {
+    if ModelFormat::detect(reader.fill_buf()?) == ModelFormat::Text {
+        return Ok(None);
+    }
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(kind: ModelKind, f: impl FnOnce(&mut Encoder<'_, Vec<u8>>)) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut enc = Encoder::new(&mut out, kind).unwrap();
+        f(&mut enc);
+        out
+    }
+
+    #[test]
+    fn test_primitives_roundtrip() {
+        let bytes = encoded(ModelKind::AdaBoost, |enc| {
+            enc.u8(7).unwrap();
+            enc.bool(true).unwrap();
+            enc.u32(0xdead_beef).unwrap();
+            enc.f64(-0.1).unwrap();
+            enc.f64s(&[1.5, f64::MIN_POSITIVE]).unwrap();
+            enc.str("テスト").unwrap();
+            enc.language(Some(Language::Korean)).unwrap();
+            enc.language(None).unwrap();
+        });
+        assert!(bytes.starts_with(b"litsea-binary v1 adaboost\n"));
+        assert_eq!(ModelFormat::detect(&bytes), ModelFormat::Binary);
+
+        let mut dec = Decoder::new(&bytes, ModelKind::AdaBoost).unwrap();
+        assert_eq!(dec.u8().unwrap(), 7);
+        assert!(dec.bool().unwrap());
+        assert_eq!(dec.u32().unwrap(), 0xdead_beef);
+        assert_eq!(dec.f64().unwrap().to_bits(), (-0.1f64).to_bits());
+        assert_eq!(dec.f64s().unwrap(), vec![1.5, f64::MIN_POSITIVE]);
+        assert_eq!(dec.str().unwrap(), "テスト");
+        assert_eq!(dec.language().unwrap(), Some(Language::Korean));
+        assert_eq!(dec.language().unwrap(), None);
+        dec.finish().unwrap();
+    }
+
+    #[test]
+    fn test_detects_kind_and_rejects_mismatches() {
+        for kind in [ModelKind::AdaBoost, ModelKind::AveragedPerceptron, ModelKind::TwoStage] {
+            let bytes = encoded(kind, |_| {});
+            let line = std::str::from_utf8(&bytes).unwrap();
+            assert_eq!(kind_from_magic(line), Some(kind));
+            assert_eq!(ModelKind::detect(line), kind);
+        }
+        assert_eq!(ModelFormat::detect(b"UW4:\xe3\x81\x82\t1.0\n"), ModelFormat::Text);
+
+        let bytes = encoded(ModelKind::TwoStage, |_| {});
+        let err = Decoder::new(&bytes, ModelKind::AdaBoost).err().unwrap();
+        assert!(err.to_string().contains("holds a two-stage model"), "{err}");
+        let err = Decoder::new(b"litsea-binary v2 adaboost\n", ModelKind::AdaBoost).err().unwrap();
+        assert!(err.to_string().contains("unsupported binary model version"), "{err}");
+        let err = Decoder::new(b"litsea-binary v1 crf\n", ModelKind::AdaBoost).err().unwrap();
+        assert!(err.to_string().contains("unknown binary model kind"), "{err}");
+    }
+
+    #[test]
+    fn test_truncated_and_trailing_bytes_are_rejected() {
+        let bytes = encoded(ModelKind::AdaBoost, |enc| enc.f64s(&[1.0, 2.0]).unwrap());
+        for end in 26..bytes.len() {
+            let mut dec = Decoder::new(&bytes[..end], ModelKind::AdaBoost).unwrap();
+            assert!(dec.f64s().is_err(), "accepted a payload cut at {end}");
+        }
+        // A huge count must fail before allocating.
+        let bytes = encoded(ModelKind::AdaBoost, |enc| enc.u64(u64::MAX).unwrap());
+        let mut dec = Decoder::new(&bytes, ModelKind::AdaBoost).unwrap();
+        assert!(dec.len(1).is_err());
+
+        let bytes = encoded(ModelKind::AdaBoost, |enc| enc.u8(0).unwrap());
+        let dec = Decoder::new(&bytes, ModelKind::AdaBoost).unwrap();
+        assert!(dec.finish().is_err());
+    }
+}
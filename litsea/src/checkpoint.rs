@@ -0,0 +1,230 @@
+//! Training checkpoints.
+//!
+//! Defines [`Checkpoint`], the resumable state of perceptron training: for
+//! every stage trained so far, each feature's live weights together with
+//! its averaging state (the `acc`/`ts` accumulators), the step counter,
+//! and the progress of the run (epoch, position within the epoch, shuffle
+//! order and generator, development curve). The perceptron trainers write
+//! one every few epochs and when interrupted (`with_checkpoint`), and
+//! continue from one (`resume`) exactly where the run stopped: the resumed
+//! run produces the same model, byte for byte, as one never interrupted.
+//!
+//! # Layout
+//!
+//! ```text
+//! litsea-checkpoint v1\n     ASCII magic line
+//! <u64 stage count>          1, or 2 for a two-stage run past stage 1
+//! <stage state>...           one per stage, in training order
+//! ```
+//!
+//! The stage states use the records of [`crate::binary_model`]. A
+//! checkpoint is tied to the training data it was written for; resuming
+//! with other inputs or options is rejected.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::binary_model::{Decoder, Encoder};
+use crate::error::{LitseaError, Result};
+use crate::perceptron::LearnerState;
+
+/// Magic line of a checkpoint file, without its newline.
+const MAGIC: &str = "litsea-checkpoint v1";
+
+/// The resumable state of an interrupted perceptron training run.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    /// The state of each stage trained so far; all but the last are
+    /// finished.
+    stages: Vec<LearnerState>,
+}
+
+impl Checkpoint {
+    /// Creates a checkpoint from the states of the stages trained so far.
+    pub(crate) fn new(stages: Vec<LearnerState>) -> Self {
+        Checkpoint { stages }
+    }
+
+    /// Returns the stage states, in training order.
+    pub(crate) fn into_stages(self) -> Vec<LearnerState> {
+        self.stages
+    }
+
+    /// Returns the number of stages the checkpoint holds.
+    ///
+    /// # Returns
+    /// `1` for a single-model run, or the number of the stage in progress
+    /// for a two-stage run.
+    #[must_use]
+    pub fn num_stages(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns the number of epochs the stage in progress has completed.
+    ///
+    /// # Returns
+    /// The completed epochs of the last stage.
+    #[must_use]
+    pub fn epochs_completed(&self) -> usize {
+        self.stages.last().map_or(0, LearnerState::epochs_completed)
+    }
+
+    /// Saves the checkpoint to a file.
+    ///
+    /// The checkpoint is written next to `path` first and then renamed over
+    /// it, so an interruption while saving leaves the previous checkpoint
+    /// intact.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the checkpoint file.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the file cannot be written or renamed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let mut file = io::BufWriter::new(File::create(&tmp)?);
+        self.write_to_writer(&mut file)?;
+        file.into_inner().map_err(io::IntoInnerError::into_error)?.sync_all()?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Writes the checkpoint to a writer (see the module docs for the
+    /// layout). The writer is not flushed.
+    ///
+    /// # Arguments
+    /// * `writer` - The writer receiving the checkpoint bytes.
+    ///
+    /// # Errors
+    /// Returns an I/O error if writing fails.
+    pub fn write_to_writer<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writeln!(writer, "{}", MAGIC)?;
+        let mut enc = Encoder::headerless(writer);
+        enc.len(self.stages.len())?;
+        self.stages.iter().try_for_each(|stage| stage.write(&mut enc))
+    }
+
+    /// Loads a checkpoint from a file.
+    ///
+    /// # Arguments
+    /// * `path` - The path of a file written by [`save`](Self::save).
+    ///
+    /// # Returns
+    /// The checkpoint.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the file cannot be read, or
+    /// [`LitseaError::InvalidData`] if it is not a valid checkpoint.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Reads a checkpoint from a reader.
+    ///
+    /// The in-memory counterpart of [`load`](Self::load).
+    ///
+    /// # Arguments
+    /// * `reader` - A reader over the checkpoint bytes.
+    ///
+    /// # Returns
+    /// The checkpoint.
+    ///
+    /// # Errors
+    /// Returns an I/O error if reading fails, or
+    /// [`LitseaError::InvalidData`] if the bytes are not a valid
+    /// checkpoint.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let payload = bytes
+            .strip_prefix(MAGIC.as_bytes())
+            .and_then(|rest| rest.strip_prefix(b"\n"))
+            .ok_or_else(|| LitseaError::InvalidData(format!("missing '{}' magic line", MAGIC)))?;
+        let mut dec = Decoder::headerless(payload);
+        let count = dec.len(8)?;
+        if count == 0 {
+            return Err(LitseaError::InvalidData("checkpoint holds no stages".to_string()));
+        }
+        let stages = (0..count).map(|_| LearnerState::read(&mut dec)).collect::<Result<_>>()?;
+        dec.finish()?;
+        Ok(Checkpoint { stages })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::atomic::AtomicBool;
+
+    use super::*;
+    use crate::perceptron::AveragedPerceptron;
+
+    fn learner() -> AveragedPerceptron {
+        let mut learner = AveragedPerceptron::new();
+        for (feats, label) in [(["a", "b"], "X"), (["b", "c"], "Y"), (["a", "c"], "X")] {
+            let feats: HashSet<String> = feats.iter().map(|f| f.to_string()).collect();
+            learner.add_instance(feats, label.to_string());
+        }
+        learner.set_seed(Some(3));
+        learner
+    }
+
+    fn checkpoint_after(epochs: usize) -> Checkpoint {
+        let mut states = Vec::new();
+        let mut sink = |state| {
+            states.push(state);
+            Ok(())
+        };
+        learner()
+            .train_checkpointed(
+                epochs,
+                None,
+                &AtomicBool::new(true),
+                Some((epochs, &mut sink)),
+                None,
+            )
+            .unwrap();
+        Checkpoint::new(states)
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let checkpoint = checkpoint_after(2);
+        let mut bytes = Vec::new();
+        checkpoint.write_to_writer(&mut bytes)?;
+
+        let loaded = Checkpoint::from_reader(bytes.as_slice())?;
+        assert_eq!(loaded.num_stages(), 1);
+        assert_eq!(loaded.epochs_completed(), 2);
+        let mut again = Vec::new();
+        loaded.write_to_writer(&mut again)?;
+        assert_eq!(bytes, again);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        let err = Checkpoint::from_reader(&b"2\nX\nY\n"[..]).unwrap_err();
+        assert!(err.to_string().contains("magic line"), "{}", err);
+
+        let mut bytes = Vec::new();
+        checkpoint_after(1).write_to_writer(&mut bytes).unwrap();
+        bytes.truncate(bytes.len() - 3);
+        assert!(matches!(
+            Checkpoint::from_reader(bytes.as_slice()),
+            Err(LitseaError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn test_resume_rejects_other_data() {
+        let state = checkpoint_after(1).into_stages().remove(0);
+        let mut other = learner();
+        other.add_instance(HashSet::from(["d".to_string()]), "Y".to_string());
+        let err = other.resume(state).unwrap_err();
+        assert!(err.to_string().contains("different training data"), "{}", err);
+    }
+}
@@ -0,0 +1,193 @@
+//! Feature frequency cutoff for training.
+//!
+//! Defines [`FeatureCutoff`], the minimum number of training instances a
+//! feature must occur in to be learned. Features seen in fewer (typically
+//! once) mostly memorize the training corpus: they bloat the model without
+//! helping it generalize. The trainers count every feature over their
+//! loaded instances and drop the rare ones before training; the number
+//! dropped is reported in the training metrics.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Minimum occurrence counts below which training features are dropped.
+///
+/// A feature's count is the number of training instances containing it. A
+/// global minimum applies to every feature; per-template minimums override
+/// it for the features of one template (the feature prefix before the `:`,
+/// e.g. `UW4`). A minimum of `0` or `1` keeps everything, which is the
+/// default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureCutoff {
+    /// Minimum count for features without a template override.
+    min_count: usize,
+    /// Template prefix -> minimum count for its features.
+    templates: BTreeMap<String, usize>,
+}
+
+impl FeatureCutoff {
+    /// Creates a cutoff with a global minimum count.
+    ///
+    /// # Arguments
+    /// * `min_count` - The number of instances a feature must occur in to
+    ///   be kept.
+    ///
+    /// # Returns
+    /// The new cutoff, without template overrides.
+    #[must_use]
+    pub fn new(min_count: usize) -> Self {
+        FeatureCutoff {
+            min_count,
+            templates: BTreeMap::new(),
+        }
+    }
+
+    /// Overrides the minimum count for the features of one template.
+    ///
+    /// # Arguments
+    /// * `template` - The template prefix, e.g. `UW4` (without the `:`).
+    /// * `min_count` - The minimum count for its features.
+    ///
+    /// # Returns
+    /// The cutoff with the override set.
+    #[must_use]
+    pub fn with_template(mut self, template: &str, min_count: usize) -> Self {
+        self.templates.insert(template.to_string(), min_count);
+        self
+    }
+
+    /// Returns the global minimum count.
+    #[must_use]
+    pub fn min_count(&self) -> usize {
+        self.min_count
+    }
+
+    /// Returns the minimum count a feature must reach.
+    ///
+    /// # Arguments
+    /// * `feature` - The feature name.
+    ///
+    /// # Returns
+    /// Its template's override, or the global minimum.
+    #[must_use]
+    pub fn min_count_for(&self, feature: &str) -> usize {
+        let template = feature.split_once(':').map_or(feature, |(prefix, _)| prefix);
+        self.templates.get(template).copied().unwrap_or(self.min_count)
+    }
+
+    /// Returns whether a feature seen in `count` instances is kept.
+    ///
+    /// # Arguments
+    /// * `feature` - The feature name.
+    /// * `count` - The number of training instances containing it.
+    #[must_use]
+    pub fn keeps(&self, feature: &str, count: usize) -> bool {
+        count >= self.min_count_for(feature)
+    }
+
+    /// Returns whether the cutoff drops nothing, whatever the counts.
+    #[must_use]
+    pub fn is_noop(&self) -> bool {
+        self.min_count <= 1 && self.templates.values().all(|&m| m <= 1)
+    }
+}
+
+/// Formats the cutoff as it is recorded in a model's `[params]`: the global
+/// minimum followed by the overrides, e.g. `2,UW4=3`.
+impl fmt::Display for FeatureCutoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.min_count)?;
+        for (template, min_count) in &self.templates {
+            write!(f, ",{}={}", template, min_count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a [`TemplateCutoff`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid template cutoff: '{input}'. Expected TEMPLATE=N, e.g. UW4=3")]
+pub struct ParseTemplateCutoffError {
+    input: String,
+}
+
+impl ParseTemplateCutoffError {
+    /// Returns the string that failed to parse.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+/// A per-template minimum count, written `TEMPLATE=N` (e.g. `UW4=3`), as
+/// the CLI's `--min-count-template` takes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateCutoff {
+    /// The template prefix.
+    pub template: String,
+    /// The minimum count for its features.
+    pub min_count: usize,
+}
+
+impl FromStr for TemplateCutoff {
+    type Err = ParseTemplateCutoffError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseTemplateCutoffError {
+            input: s.to_string(),
+        };
+        let (template, min_count) = s.split_once('=').ok_or_else(invalid)?;
+        let template = template.trim();
+        if template.is_empty() || template.contains(':') {
+            return Err(invalid());
+        }
+        let min_count = min_count.trim().parse().map_err(|_| invalid())?;
+        Ok(TemplateCutoff {
+            template: template.to_string(),
+            min_count,
+        })
+    }
+}
+
+impl fmt::Display for TemplateCutoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.template, self.min_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_count_for_uses_the_template_override() {
+        let cutoff = FeatureCutoff::new(2).with_template("UW4", 5).with_template("BQ1", 0);
+        assert_eq!(cutoff.min_count(), 2);
+        assert_eq!(cutoff.min_count_for("UW4:あ"), 5);
+        assert_eq!(cutoff.min_count_for("UW3:あ"), 2);
+        assert_eq!(cutoff.min_count_for("BQ1:OBあ"), 0);
+        assert!(cutoff.keeps("UW3:あ", 2));
+        assert!(!cutoff.keeps("UW4:あ", 4));
+        assert!(!cutoff.is_noop());
+        assert!(FeatureCutoff::default().is_noop());
+        assert!(FeatureCutoff::new(1).with_template("UW4", 0).is_noop());
+        assert_eq!(cutoff.to_string(), "2,BQ1=0,UW4=5");
+    }
+
+    #[test]
+    fn test_template_cutoff_from_str() {
+        let parsed: TemplateCutoff = "UW4=3".parse().expect("valid");
+        assert_eq!(
+            parsed,
+            TemplateCutoff {
+                template: "UW4".to_string(),
+                min_count: 3
+            }
+        );
+        assert_eq!(parsed.to_string(), "UW4=3");
+        for bad in ["UW4", "=3", "UW4=x", "UW4:=3", "UW4=-1"] {
+            assert!(bad.parse::<TemplateCutoff>().is_err(), "{bad}");
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Error types for the litsea library.
 
+use crate::language::Language;
+
 /// Errors returned by litsea operations.
 ///
 /// Marked `#[non_exhaustive]`: new variants are added as the library grows
@@ -36,6 +38,17 @@ pub enum LitseaError {
     #[error("POS learner is not set; build the segmenter with with_two_stage_learner()")]
     PosLearnerNotSet,
 
+    /// The model records a training language (see
+    /// [`ModelMetadata`](crate::metadata::ModelMetadata)) other than the
+    /// one the segmenter was built for.
+    #[error("model was trained for {model}, but the segmenter is configured for {requested}")]
+    LanguageMismatch {
+        /// The language recorded in the model.
+        model: Language,
+        /// The language the caller asked for.
+        requested: Language,
+    },
+
     /// Downloading a remote model failed.
     #[cfg(feature = "remote_model")]
     #[error("failed to download model: {0}")]
@@ -56,6 +69,15 @@ mod tests {
 
         let err = LitseaError::Unsupported("no file system");
         assert_eq!(err.to_string(), "unsupported: no file system");
+
+        let err = LitseaError::LanguageMismatch {
+            model: Language::Korean,
+            requested: Language::Japanese,
+        };
+        assert_eq!(
+            err.to_string(),
+            "model was trained for korean, but the segmenter is configured for japanese"
+        );
     }
 
     #[test]
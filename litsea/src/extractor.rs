@@ -7,7 +7,16 @@
 //! [`Extractor::extract_two_stage`] extracts the same kind of rows for the
 //! two-stage architecture instead, splitting them across the three files
 //! read by [`TwoStageTrainer`](crate::trainer::TwoStageTrainer).
+//! [`Extractor::extract_instances`] streams the boundary rows to a callback
+//! instead, so a trainer can learn from a corpus without the intermediate
+//! file; [`Extractor::extract_distilled_instances`] does the same for a raw
+//! corpus labeled by a teacher [`Segmenter`].
+//!
+//! Every method can spread the corpus lines over worker threads
+//! ([`Extractor::with_threads`]); the output stays in corpus order, byte for
+//! byte what one thread writes.
 
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt;
 use std::io::Write;
@@ -26,12 +35,15 @@ use rustc_hash::FxHashMap;
 use crate::error::Result;
 use crate::evaluation::parse_gold_pos_line;
 use crate::language::Language;
-use crate::segmenter::Segmenter;
+use crate::normalize::Normalization;
+use crate::partial;
+use crate::segmenter::{SegmentBuffer, Segmenter};
 use crate::two_stage::{TwoStageFeatureSet, sort_lexicon_entry, write_lexicon};
 // Only the path-based two-stage extractor derives the three file names.
 #[cfg(not(target_arch = "wasm32"))]
 use crate::two_stage::two_stage_paths;
 use crate::upos::{SegmentLabel, Upos};
+use crate::user_dict::UserDictionary;
 use crate::word_features::write_word_features;
 
 /// Extractor struct for processing text data and extracting features.
@@ -45,6 +57,28 @@ use crate::word_features::write_word_features;
 #[derive(Debug)]
 pub struct Extractor {
     segmenter: Segmenter,
+    /// Number of worker threads extraction is spread over.
+    threads: usize,
+}
+
+/// Corpus lines read per parallel chunk: large enough to amortize the
+/// per-chunk thread spawns, small enough that the buffered feature rows of a
+/// chunk (a few hundred bytes per character) stay bounded.
+const PARALLEL_CHUNK_LINES: usize = 1024;
+
+/// Layout of a segmented (boundary-training) corpus line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CorpusFormat {
+    /// Space-separated words, as read by [`Extractor::extract`].
+    #[default]
+    Space,
+    /// Tab-separated tokens, a token possibly a literal space, as read by
+    /// [`Extractor::extract_tsv`].
+    Tsv,
+    /// Partially annotated characters (KyTea-style `|`/`-`/space
+    /// delimiters), as read by [`Extractor::extract_partial`]: only the
+    /// annotated positions become instances.
+    Partial,
 }
 
 impl Default for Extractor {
@@ -92,6 +126,19 @@ impl LexiconCounts {
             })
             .collect()
     }
+
+    /// Adds another accumulator's counts to these, leaving it empty.
+    ///
+    /// # Arguments
+    /// * `other` - The counts to move over.
+    fn merge(&mut self, other: &mut LexiconCounts) {
+        for (surface, tags) in other.counts.drain() {
+            let entry = self.counts.entry(surface).or_default();
+            for (tag, count) in tags {
+                *entry.entry(tag).or_insert(0) += count;
+            }
+        }
+    }
 }
 
 /// Scratch buffers reused across corpus lines by the two-stage extractor.
@@ -103,6 +150,53 @@ struct TwoStageLineState {
     stage2_feats: Vec<String>,
 }
 
+/// What the two-stage extractor accumulates over a run of corpus lines
+/// before writing it out.
+#[derive(Default)]
+struct TwoStageOutput {
+    /// Stage-1 rows, newline-terminated.
+    stage1: Vec<u8>,
+    /// Stage-2 rows, newline-terminated.
+    stage2: Vec<u8>,
+    /// Lexicon counts of the lines.
+    lexicon: LexiconCounts,
+    /// Reusable scratch buffers.
+    state: TwoStageLineState,
+}
+
+impl TwoStageOutput {
+    /// Writes the accumulated rows out and moves the lexicon counts into
+    /// `lexicon`, leaving `self` empty.
+    ///
+    /// # Arguments
+    /// * `stage1_out` - Where to write the stage-1 rows.
+    /// * `stage2_out` - Where to write the stage-2 rows.
+    /// * `lexicon` - The lexicon counts of the whole corpus.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an error if a writer fails.
+    fn drain_into<W1, W2>(
+        &mut self,
+        stage1_out: &mut W1,
+        stage2_out: &mut W2,
+        lexicon: &mut LexiconCounts,
+    ) -> Result<()>
+    where
+        W1: Write,
+        W2: Write,
+    {
+        stage1_out.write_all(&self.stage1)?;
+        self.stage1.clear();
+        stage2_out.write_all(&self.stage2)?;
+        self.stage2.clear();
+        lexicon.merge(&mut self.lexicon);
+        Ok(())
+    }
+}
+
 impl Extractor {
     /// Creates a new instance of [`Extractor`].
     ///
@@ -114,9 +208,94 @@ impl Extractor {
     pub fn new(language: Language) -> Self {
         Extractor {
             segmenter: Segmenter::new(language),
+            threads: 1,
         }
     }
 
+    /// Sets the number of worker threads extraction is spread over.
+    ///
+    /// The corpus is read in chunks of lines, each chunk split into
+    /// `threads` contiguous slices extracted in parallel, and the results
+    /// are written in corpus order: the output (including the two-stage
+    /// lexicon) is byte-identical to a single-threaded run. `0` is treated
+    /// as `1`. On `wasm32`, which has no threads, extraction always runs on
+    /// the calling thread.
+    ///
+    /// # Arguments
+    /// * `threads` - Number of worker threads (the default is 1).
+    ///
+    /// # Returns
+    /// The extractor with the thread count set.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Returns the number of worker threads extraction is spread over.
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Sets the Unicode normalization applied to every corpus word before
+    /// its features are extracted.
+    ///
+    /// Use the same form the model will be segmented with: record it in
+    /// the trained model (e.g. with
+    /// [`Trainer::with_normalization`](crate::trainer::Trainer::with_normalization))
+    /// and the [`Segmenter`] loading it applies it to input by default.
+    /// Each word is normalized on its own, so the corpus boundaries are
+    /// kept; the two-stage lexicon stores the normalized surfaces.
+    ///
+    /// # Arguments
+    /// * `normalization` - The normalization form to apply.
+    ///
+    /// # Returns
+    /// The extractor with the normalization set.
+    #[must_use]
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.segmenter = self.segmenter.with_normalization(normalization);
+        self
+    }
+
+    /// Returns the normalization applied to corpus words.
+    #[must_use]
+    pub fn normalization(&self) -> Normalization {
+        self.segmenter.normalization()
+    }
+
+    /// Sets the word list whose matches are extracted as dictionary
+    /// features alongside the boundary templates.
+    ///
+    /// At every character position the extractor adds one feature per
+    /// kind of match of a dictionary word: a word ending just before the
+    /// position (`DL`), starting at it (`DR`), or spanning it (`DI`), keyed
+    /// by the word's length in characters (capped at 4), as in KyTea. Train
+    /// the model with the same dictionary (e.g.
+    /// [`Trainer::with_dictionary`](crate::trainer::Trainer::with_dictionary)):
+    /// it is stored inside the model, so segmenting needs no separate file.
+    /// Matching runs on the normalized text, so the words should be in the
+    /// [normalization](Self::with_normalization) form.
+    ///
+    /// # Arguments
+    /// * `dictionary` - The word list to match.
+    ///
+    /// # Returns
+    /// The extractor with the dictionary set.
+    #[must_use]
+    pub fn with_dictionary(mut self, dictionary: UserDictionary) -> Self {
+        self.segmenter.learner_mut().set_dictionary(dictionary);
+        self
+    }
+
+    /// Returns the word list matched for dictionary features, or `None`
+    /// when none is set (or it is empty).
+    #[must_use]
+    pub fn dictionary(&self) -> Option<&UserDictionary> {
+        self.segmenter.learner().dictionary()
+    }
+
     /// Extracts features from a corpus file and writes them to a specified output file.
     ///
     /// Corpus format: one sentence per line, each line consisting of
@@ -139,10 +318,11 @@ impl Extractor {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn extract(&self, corpus_path: &Path, features_path: &Path) -> Result<()> {
         let segmenter = &self.segmenter;
-        Self::write_features(corpus_path, features_path, |line, rows| {
+        self.write_features(corpus_path, features_path, |line, rows| {
             segmenter.add_corpus_with_writer(line, |attrs, label| {
                 rows.push(Self::format_row(attrs, label));
             });
+            Ok(())
         })
     }
 
@@ -163,10 +343,11 @@ impl Extractor {
     /// Returns an error if the writer fails.
     pub fn extract_to_writer<W: Write>(&self, corpus: &str, writer: &mut W) -> Result<()> {
         let segmenter = &self.segmenter;
-        Self::write_features_to_writer(corpus, writer, |line, rows| {
+        self.write_features_to_writer(corpus, writer, |line, rows| {
             segmenter.add_corpus_with_writer(line, |attrs, label| {
                 rows.push(Self::format_row(attrs, label));
             });
+            Ok(())
         })
     }
 
@@ -194,10 +375,11 @@ impl Extractor {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn extract_tsv(&self, corpus_path: &Path, features_path: &Path) -> Result<()> {
         let segmenter = &self.segmenter;
-        Self::write_features(corpus_path, features_path, |line, rows| {
+        self.write_features(corpus_path, features_path, |line, rows| {
             segmenter.add_corpus_tsv_with_writer(line, |attrs, label| {
                 rows.push(Self::format_row(attrs, label));
             });
+            Ok(())
         })
     }
 
@@ -218,10 +400,11 @@ impl Extractor {
     /// Returns an error if the writer fails.
     pub fn extract_tsv_to_writer<W: Write>(&self, corpus: &str, writer: &mut W) -> Result<()> {
         let segmenter = &self.segmenter;
-        Self::write_features_to_writer(corpus, writer, |line, rows| {
+        self.write_features_to_writer(corpus, writer, |line, rows| {
             segmenter.add_corpus_tsv_with_writer(line, |attrs, label| {
                 rows.push(Self::format_row(attrs, label));
             });
+            Ok(())
         })
     }
 
@@ -251,11 +434,12 @@ impl Extractor {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn extract_tag_free(&self, corpus_path: &Path, features_path: &Path) -> Result<()> {
         let segmenter = &self.segmenter;
-        Self::write_features(corpus_path, features_path, |line, rows| {
+        self.write_features(corpus_path, features_path, |line, rows| {
             segmenter.add_corpus_with_writer(line, |mut attrs, label| {
                 attrs.retain(|a| !crate::packed_model::is_tag_dependent_feature(a));
                 rows.push(Self::format_row(attrs, label));
             });
+            Ok(())
         })
     }
 
@@ -276,11 +460,12 @@ impl Extractor {
     /// Returns an error if the writer fails.
     pub fn extract_tag_free_to_writer<W: Write>(&self, corpus: &str, writer: &mut W) -> Result<()> {
         let segmenter = &self.segmenter;
-        Self::write_features_to_writer(corpus, writer, |line, rows| {
+        self.write_features_to_writer(corpus, writer, |line, rows| {
             segmenter.add_corpus_with_writer(line, |mut attrs, label| {
                 attrs.retain(|a| !crate::packed_model::is_tag_dependent_feature(a));
                 rows.push(Self::format_row(attrs, label));
             });
+            Ok(())
         })
     }
 
@@ -302,11 +487,12 @@ impl Extractor {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn extract_tsv_tag_free(&self, corpus_path: &Path, features_path: &Path) -> Result<()> {
         let segmenter = &self.segmenter;
-        Self::write_features(corpus_path, features_path, |line, rows| {
+        self.write_features(corpus_path, features_path, |line, rows| {
             segmenter.add_corpus_tsv_with_writer(line, |mut attrs, label| {
                 attrs.retain(|a| !crate::packed_model::is_tag_dependent_feature(a));
                 rows.push(Self::format_row(attrs, label));
             });
+            Ok(())
         })
     }
 
@@ -331,14 +517,417 @@ impl Extractor {
         writer: &mut W,
     ) -> Result<()> {
         let segmenter = &self.segmenter;
-        Self::write_features_to_writer(corpus, writer, |line, rows| {
+        self.write_features_to_writer(corpus, writer, |line, rows| {
             segmenter.add_corpus_tsv_with_writer(line, |mut attrs, label| {
                 attrs.retain(|a| !crate::packed_model::is_tag_dependent_feature(a));
                 rows.push(Self::format_row(attrs, label));
             });
+            Ok(())
+        })
+    }
+
+    /// Extracts features from a partially annotated corpus file (KyTea-style)
+    /// and writes them to a specified output file.
+    ///
+    /// Corpus format: one sentence per line, its characters separated by
+    /// one delimiter each: `|` (a word starts at the next character), `-`
+    /// (the next character continues the word), or a space (unannotated);
+    /// a backslash makes the next character part of the text (`\|`, `\-`,
+    /// `\ `, `\\`). For example, `コ-ー-ス|が|設 定` leaves only the position
+    /// inside `設定` unannotated. Rows are written only for the annotated
+    /// positions (except the first character, as in
+    /// [`extract`](Self::extract)), while every row's features are computed
+    /// from the whole sentence; see
+    /// [`Segmenter::add_corpus_partial_with_writer`]. A fully annotated line
+    /// (no spaces) writes the same rows as its segmented form does with
+    /// [`extract`](Self::extract).
+    ///
+    /// # Arguments
+    /// * `corpus_path` - The path to the partially annotated corpus file.
+    /// * `features_path` - The path to the output features file.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the corpus file cannot be read or the
+    /// features file cannot be created or written, or
+    /// [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a line is malformed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn extract_partial(&self, corpus_path: &Path, features_path: &Path) -> Result<()> {
+        let segmenter = &self.segmenter;
+        self.write_features(corpus_path, features_path, |line, rows| {
+            segmenter.add_corpus_partial_with_writer(line, |attrs, label| {
+                rows.push(Self::format_row(attrs, label));
+            })
+        })
+    }
+
+    /// Extracts boundary-classification features from an in-memory
+    /// partially annotated corpus to a writer.
+    ///
+    /// The in-memory counterpart of [`extract_partial`](Self::extract_partial), for
+    /// callers with no filesystem (WebAssembly) or with the corpus already
+    /// in memory. The output is byte-identical.
+    ///
+    /// # Arguments
+    /// * `corpus` - The corpus contents, one sentence per line.
+    /// * `writer` - Where to write the `label\tfeature...` rows.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an error if the writer fails, or
+    /// [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a line is malformed.
+    pub fn extract_partial_to_writer<W: Write>(&self, corpus: &str, writer: &mut W) -> Result<()> {
+        let segmenter = &self.segmenter;
+        self.write_features_to_writer(corpus, writer, |line, rows| {
+            segmenter.add_corpus_partial_with_writer(line, |attrs, label| {
+                rows.push(Self::format_row(attrs, label));
+            })
+        })
+    }
+
+    /// Extracts features like [`extract_partial`](Self::extract_partial),
+    /// but drops the 16 tag-dependent templates — the tag-free variant of
+    /// [`extract_tag_free`](Self::extract_tag_free) for partially annotated
+    /// corpora.
+    ///
+    /// # Arguments
+    /// * `corpus_path` - The path to the partially annotated corpus file.
+    /// * `features_path` - The path to the output features file.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the corpus file cannot be read or the
+    /// features file cannot be created or written, or
+    /// [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a line is malformed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn extract_partial_tag_free(&self, corpus_path: &Path, features_path: &Path) -> Result<()> {
+        let segmenter = &self.segmenter;
+        self.write_features(corpus_path, features_path, |line, rows| {
+            segmenter.add_corpus_partial_with_writer(line, |mut attrs, label| {
+                attrs.retain(|a| !crate::packed_model::is_tag_dependent_feature(a));
+                rows.push(Self::format_row(attrs, label));
+            })
+        })
+    }
+
+    /// Extracts tag-free boundary-classification features from an in-memory
+    /// partially annotated corpus to a writer.
+    ///
+    /// The in-memory counterpart of [`extract_partial_tag_free`](Self::extract_partial_tag_free), for
+    /// callers with no filesystem (WebAssembly) or with the corpus already
+    /// in memory. The output is byte-identical.
+    ///
+    /// # Arguments
+    /// * `corpus` - The corpus contents, one sentence per line.
+    /// * `writer` - Where to write the `label\tfeature...` rows.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an error if the writer fails, or
+    /// [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a line is malformed.
+    pub fn extract_partial_tag_free_to_writer<W: Write>(
+        &self,
+        corpus: &str,
+        writer: &mut W,
+    ) -> Result<()> {
+        let segmenter = &self.segmenter;
+        self.write_features_to_writer(corpus, writer, |line, rows| {
+            segmenter.add_corpus_partial_with_writer(line, |mut attrs, label| {
+                attrs.retain(|a| !crate::packed_model::is_tag_dependent_feature(a));
+                rows.push(Self::format_row(attrs, label));
+            })
         })
     }
 
+    /// Streams the boundary-classification instances of a corpus file to a
+    /// callback, without writing a features file.
+    ///
+    /// Each call receives what the matching `extract*` method would write
+    /// as one row ([`extract`](Self::extract),
+    /// [`extract_tsv`](Self::extract_tsv),
+    /// [`extract_partial`](Self::extract_partial), or their `_tag_free`
+    /// variants, by `format` and `tag_free`), in the same order: the attribute set and the label
+    /// (1 = word start, -1 = continuation). The trainers'
+    /// `from_corpus` constructors feed it straight into a learner.
+    ///
+    /// # Arguments
+    /// * `corpus_path` - The path to the segmented corpus file.
+    /// * `format` - The layout of the corpus lines.
+    /// * `tag_free` - Whether to drop the 16 tag-dependent templates.
+    /// * `sink` - Receives each instance.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the corpus file cannot be opened or read, or
+    /// [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a partially annotated line is malformed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn extract_instances<F>(
+        &self,
+        corpus_path: &Path,
+        format: CorpusFormat,
+        tag_free: bool,
+        mut sink: F,
+    ) -> Result<()>
+    where
+        F: FnMut(HashSet<String>, i8),
+    {
+        // Streamed like `write_features`: the point is to never hold the
+        // corpus's features at rest.
+        let corpus = io::BufReader::new(File::open(corpus_path)?);
+        self.for_each_line(
+            corpus.lines().map(|line| line.map_err(Into::into)),
+            |line, instances| self.line_instances(line, format, tag_free, instances),
+            |instances| Self::drain_instances(instances, &mut sink),
+        )
+    }
+
+    /// Streams the boundary-classification instances of an in-memory corpus
+    /// to a callback.
+    ///
+    /// The in-memory counterpart of
+    /// [`extract_instances`](Self::extract_instances).
+    ///
+    /// # Arguments
+    /// * `corpus` - The corpus contents, one sentence per line.
+    /// * `format` - The layout of the corpus lines.
+    /// * `tag_free` - Whether to drop the 16 tag-dependent templates.
+    /// * `sink` - Receives each instance.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a partially annotated line is malformed; the instances of the
+    /// lines before it have been passed on.
+    pub fn extract_instances_from_str<F>(
+        &self,
+        corpus: &str,
+        format: CorpusFormat,
+        tag_free: bool,
+        mut sink: F,
+    ) -> Result<()>
+    where
+        F: FnMut(HashSet<String>, i8),
+    {
+        self.for_each_line(
+            corpus.lines().map(Ok),
+            |line, instances| self.line_instances(line, format, tag_free, instances),
+            |instances| Self::drain_instances(instances, &mut sink),
+        )
+    }
+
+    /// Streams the boundary-classification instances of a raw corpus
+    /// labeled by a teacher segmenter to a callback, for distilling the
+    /// teacher into a student model.
+    ///
+    /// Every line is pseudo-labeled with
+    /// [`Segmenter::pseudo_label`] and extracted as a partially annotated
+    /// line, so the positions the teacher decided by less than
+    /// `min_margin` yield no instance. The labels are written in the
+    /// teacher's normalized text; build the extractor with the teacher's
+    /// [`normalization`](Segmenter::normalization) so the student declares
+    /// the same one.
+    ///
+    /// # Arguments
+    /// * `teacher` - The segmenter whose decisions become the labels.
+    /// * `corpus_path` - The path to the raw corpus file, one sentence per
+    ///   line.
+    /// * `min_margin` - The smallest absolute teacher margin trained on;
+    ///   `0.0` keeps every position.
+    /// * `tag_free` - Whether to drop the 16 tag-dependent templates.
+    /// * `sink` - Receives each instance with the teacher's absolute margin
+    ///   at its position.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the corpus file cannot be opened or read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn extract_distilled_instances<F>(
+        &self,
+        teacher: &Segmenter,
+        corpus_path: &Path,
+        min_margin: f64,
+        tag_free: bool,
+        mut sink: F,
+    ) -> Result<()>
+    where
+        F: FnMut(HashSet<String>, i8, f64),
+    {
+        let corpus = io::BufReader::new(File::open(corpus_path)?);
+        self.for_each_line(
+            corpus.lines().map(|line| line.map_err(Into::into)),
+            |line, instances| {
+                self.distilled_line_instances(teacher, line, min_margin, tag_free, instances)
+            },
+            |instances| Self::drain_distilled_instances(instances, &mut sink),
+        )
+    }
+
+    /// Streams the boundary-classification instances of an in-memory raw
+    /// corpus labeled by a teacher segmenter to a callback.
+    ///
+    /// The in-memory counterpart of
+    /// [`extract_distilled_instances`](Self::extract_distilled_instances).
+    ///
+    /// # Arguments
+    /// * `teacher` - The segmenter whose decisions become the labels.
+    /// * `corpus` - The raw corpus contents, one sentence per line.
+    /// * `min_margin` - The smallest absolute teacher margin trained on;
+    ///   `0.0` keeps every position.
+    /// * `tag_free` - Whether to drop the 16 tag-dependent templates.
+    /// * `sink` - Receives each instance with the teacher's absolute margin
+    ///   at its position.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Propagates extraction errors, which do not occur in practice: a
+    /// pseudo-labeled line is always well formed.
+    pub fn extract_distilled_instances_from_str<F>(
+        &self,
+        teacher: &Segmenter,
+        corpus: &str,
+        min_margin: f64,
+        tag_free: bool,
+        mut sink: F,
+    ) -> Result<()>
+    where
+        F: FnMut(HashSet<String>, i8, f64),
+    {
+        self.for_each_line(
+            corpus.lines().map(Ok),
+            |line, instances| {
+                self.distilled_line_instances(teacher, line, min_margin, tag_free, instances)
+            },
+            |instances| Self::drain_distilled_instances(instances, &mut sink),
+        )
+    }
+
+    /// Collects the instances of one raw corpus line pseudo-labeled by
+    /// `teacher`, each with the teacher's absolute margin at its position.
+    fn distilled_line_instances(
+        &self,
+        teacher: &Segmenter,
+        line: &str,
+        min_margin: f64,
+        tag_free: bool,
+        instances: &mut Vec<(HashSet<String>, i8, f64)>,
+    ) -> Result<()> {
+        let (mut labeled, mut margins) = (String::new(), Vec::new());
+        teacher.pseudo_label_into(
+            line,
+            min_margin,
+            &mut SegmentBuffer::new(),
+            &mut labeled,
+            &mut margins,
+        );
+        let mut rows = Vec::with_capacity(margins.len());
+        self.line_instances(&labeled, CorpusFormat::Partial, tag_free, &mut rows)?;
+        // A partial line yields one instance per decided delimiter, in order.
+        debug_assert_eq!(rows.len(), margins.len());
+        instances
+            .extend(rows.into_iter().zip(margins).map(|((attrs, label), m)| (attrs, label, m)));
+        Ok(())
+    }
+
+    /// Collects the instances of one corpus line.
+    ///
+    /// # Arguments
+    /// * `line` - The trimmed sentence; blank lines are skipped.
+    /// * `format` - The layout of the line.
+    /// * `tag_free` - Whether to drop the 16 tag-dependent templates.
+    /// * `instances` - Where to append the instances.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a partially annotated line is malformed.
+    fn line_instances(
+        &self,
+        line: &str,
+        format: CorpusFormat,
+        tag_free: bool,
+        instances: &mut Vec<(HashSet<String>, i8)>,
+    ) -> Result<()> {
+        if line.is_empty() {
+            return Ok(());
+        }
+        let emit = |mut attrs: HashSet<String>, label: i8| {
+            if tag_free {
+                attrs.retain(|a| !crate::packed_model::is_tag_dependent_feature(a));
+            }
+            instances.push((attrs, label));
+        };
+        match format {
+            CorpusFormat::Space => self.segmenter.add_corpus_with_writer(line, emit),
+            CorpusFormat::Tsv => self.segmenter.add_corpus_tsv_with_writer(line, emit),
+            CorpusFormat::Partial => self.segmenter.add_corpus_partial_with_writer(line, emit)?,
+        }
+        Ok(())
+    }
+
+    /// Passes collected instances to a callback, in order, leaving the
+    /// buffer empty.
+    ///
+    /// # Arguments
+    /// * `instances` - The instances to pass on.
+    /// * `sink` - Receives each instance.
+    ///
+    /// # Returns
+    /// Always `Ok`; the signature fits [`for_each_line`](Self::for_each_line).
+    fn drain_instances<F>(instances: &mut Vec<(HashSet<String>, i8)>, sink: &mut F) -> Result<()>
+    where
+        F: FnMut(HashSet<String>, i8),
+    {
+        for (attrs, label) in instances.drain(..) {
+            sink(attrs, label);
+        }
+        Ok(())
+    }
+
+    /// Passes collected distilled instances and their margins to a
+    /// callback, in order, leaving the buffer empty.
+    ///
+    /// # Arguments
+    /// * `instances` - The instances to pass on.
+    /// * `sink` - Receives each instance and its margin.
+    ///
+    /// # Returns
+    /// Always `Ok`; the signature fits [`for_each_line`](Self::for_each_line).
+    fn drain_distilled_instances<F>(
+        instances: &mut Vec<(HashSet<String>, i8, f64)>,
+        sink: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(HashSet<String>, i8, f64),
+    {
+        for (attrs, label, margin) in instances.drain(..) {
+            sink(attrs, label, margin);
+        }
+        Ok(())
+    }
+
     /// Extracts two-stage training features (issue #147) from a POS-tagged
     /// corpus in a single pass: stage-1 boundary features (2-class labels
     /// `B`/`O`, using the same character-level feature templates as
@@ -376,7 +965,7 @@ impl Extractor {
         output_prefix: &Path,
         feature_set: TwoStageFeatureSet,
     ) -> Result<()> {
-        self.extract_two_stage_impl(corpus_path, output_prefix, feature_set, false)
+        self.extract_two_stage_impl(corpus_path, output_prefix, feature_set, CorpusFormat::Space)
     }
 
     /// Extracts two-stage features from an in-memory corpus to three writers.
@@ -417,7 +1006,7 @@ impl Extractor {
             stage2_out,
             lexicon_out,
             feature_set,
-            false,
+            CorpusFormat::Space,
         )
     }
 
@@ -465,7 +1054,7 @@ impl Extractor {
         output_prefix: &Path,
         feature_set: TwoStageFeatureSet,
     ) -> Result<()> {
-        self.extract_two_stage_impl(corpus_path, output_prefix, feature_set, true)
+        self.extract_two_stage_impl(corpus_path, output_prefix, feature_set, CorpusFormat::Tsv)
     }
 
     /// Extracts two-stage features from a space-preserving TSV corpus from an in-memory corpus to three writers.
@@ -506,17 +1095,102 @@ impl Extractor {
             stage2_out,
             lexicon_out,
             feature_set,
-            true,
+            CorpusFormat::Tsv,
+        )
+    }
+
+    /// Partially annotated variant of
+    /// [`extract_two_stage`](Self::extract_two_stage): the corpus is in the
+    /// format of [`extract_partial`](Self::extract_partial), and a fully
+    /// annotated word may carry a `/TAG` suffix after its last character,
+    /// as in `こ-れ/PRON|は/ADP|テ ス ト` (a `/` in the text is escaped,
+    /// `\/`).
+    ///
+    /// Stage 1 gets a row for every annotated position, first character
+    /// included; stage 2 and the lexicon get the tagged words only, with
+    /// context features computed from the whole sentence. A word without a
+    /// tag trains stage 1 alone. As in
+    /// [`extract_two_stage_tsv`](Self::extract_two_stage_tsv), a
+    /// whitespace word gets a lexicon entry but no stage-2 row.
+    ///
+    /// # Arguments
+    /// * `corpus_path` - The path to the partially annotated corpus file.
+    /// * `output_prefix` - Base path for the three output files; see
+    ///   [`extract_two_stage`](Self::extract_two_stage).
+    /// * `feature_set` - Which stage-2 word templates to write; see
+    ///   [`TwoStageFeatureSet`].
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the corpus file cannot be opened or read, or
+    /// if any output file cannot be created or written, or
+    /// [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a line is malformed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn extract_two_stage_partial(
+        &self,
+        corpus_path: &Path,
+        output_prefix: &Path,
+        feature_set: TwoStageFeatureSet,
+    ) -> Result<()> {
+        self.extract_two_stage_impl(corpus_path, output_prefix, feature_set, CorpusFormat::Partial)
+    }
+
+    /// Extracts two-stage features from an in-memory partially annotated
+    /// corpus to three writers.
+    ///
+    /// The in-memory counterpart of [`extract_two_stage_partial`](Self::extract_two_stage_partial): instead of
+    /// `{prefix}.stage1` / `.stage2` / `.lexicon`, the three outputs go to
+    /// the writers given here, byte for byte the same. Feed them to
+    /// [`TwoStageTrainer::from_features`](crate::trainer::TwoStageTrainer::from_features).
+    ///
+    /// # Arguments
+    /// * `corpus` - The corpus contents, one sentence per line.
+    /// * `stage1_out` - Where to write the stage-1 (`B`/`O`) rows.
+    /// * `stage2_out` - Where to write the stage-2 (UPOS) rows.
+    /// * `lexicon_out` - Where to write the lexicon.
+    /// * `feature_set` - Which stage-2 word templates to write.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an error if any writer fails, or
+    /// [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if a line is malformed.
+    pub fn extract_two_stage_partial_to_writers<W1, W2, W3>(
+        &self,
+        corpus: &str,
+        stage1_out: &mut W1,
+        stage2_out: &mut W2,
+        lexicon_out: &mut W3,
+        feature_set: TwoStageFeatureSet,
+    ) -> Result<()>
+    where
+        W1: Write,
+        W2: Write,
+        W3: Write,
+    {
+        self.extract_two_stage_to_writers_impl(
+            corpus,
+            stage1_out,
+            stage2_out,
+            lexicon_out,
+            feature_set,
+            CorpusFormat::Partial,
         )
     }
 
     /// Shared implementation behind
-    /// [`extract_two_stage`](Self::extract_two_stage) and
-    /// [`extract_two_stage_tsv`](Self::extract_two_stage_tsv).
+    /// [`extract_two_stage`](Self::extract_two_stage),
+    /// [`extract_two_stage_tsv`](Self::extract_two_stage_tsv), and
+    /// [`extract_two_stage_partial`](Self::extract_two_stage_partial).
     ///
     /// Each corpus line is parsed **twice** — once by the segmenter for
     /// stage-1 character features, once here for stage-2 word features and
-    /// the lexicon. Both parses are driven by this single `tsv` flag on
+    /// the lexicon. Both parses are driven by this single `format` on
     /// purpose: if they disagreed on the separator, the stage-1 training
     /// text and the stage-2 character offsets would silently desync, with
     /// nothing to catch it.
@@ -526,7 +1200,7 @@ impl Extractor {
         corpus_path: &Path,
         output_prefix: &Path,
         feature_set: TwoStageFeatureSet,
-        tsv: bool,
+        format: CorpusFormat,
     ) -> Result<()> {
         let (stage1_path, stage2_path, lexicon_path) = two_stage_paths(output_prefix);
 
@@ -538,19 +1212,11 @@ impl Extractor {
         let mut stage2_out = io::BufWriter::new(File::create(stage2_path)?);
         let mut lexicon = LexiconCounts::default();
 
-        let mut state = TwoStageLineState::default();
-        for line in corpus.lines() {
-            let line = line?;
-            self.extract_two_stage_line(
-                line.trim(),
-                &mut stage1_out,
-                &mut stage2_out,
-                &mut lexicon,
-                &mut state,
-                feature_set,
-                tsv,
-            )?;
-        }
+        self.for_each_line(
+            corpus.lines().map(|line| line.map_err(Into::into)),
+            |line, output| self.extract_two_stage_line(line, output, feature_set, format),
+            |output| output.drain_into(&mut stage1_out, &mut stage2_out, &mut lexicon),
+        )?;
         stage1_out.flush()?;
         stage2_out.flush()?;
 
@@ -571,13 +1237,14 @@ impl Extractor {
     /// * `stage2_out` - Where to write the stage-2 (UPOS) rows.
     /// * `lexicon_out` - Where to write the lexicon.
     /// * `feature_set` - Which stage-2 word templates to write.
-    /// * `tsv` - Whether the corpus is in the space-preserving TSV format.
+    /// * `format` - The layout of the corpus lines.
     ///
     /// # Returns
     /// Returns a Result indicating success or failure.
     ///
     /// # Errors
-    /// Returns an error if any writer fails.
+    /// Returns an error if any writer fails or a partially annotated line
+    /// is malformed.
     fn extract_two_stage_to_writers_impl<W1, W2, W3>(
         &self,
         corpus: &str,
@@ -585,7 +1252,7 @@ impl Extractor {
         stage2_out: &mut W2,
         lexicon_out: &mut W3,
         feature_set: TwoStageFeatureSet,
-        tsv: bool,
+        format: CorpusFormat,
     ) -> Result<()>
     where
         W1: Write,
@@ -593,19 +1260,12 @@ impl Extractor {
         W3: Write,
     {
         let mut lexicon = LexiconCounts::default();
-        let mut state = TwoStageLineState::default();
-
-        for line in corpus.lines() {
-            self.extract_two_stage_line(
-                line.trim(),
-                stage1_out,
-                stage2_out,
-                &mut lexicon,
-                &mut state,
-                feature_set,
-                tsv,
-            )?;
-        }
+
+        self.for_each_line(
+            corpus.lines().map(Ok),
+            |line, output| self.extract_two_stage_line(line, output, feature_set, format),
+            |output| output.drain_into(stage1_out, stage2_out, &mut lexicon),
+        )?;
 
         write_lexicon(&lexicon.finish(), lexicon_out)
     }
@@ -614,43 +1274,46 @@ impl Extractor {
     ///
     /// # Arguments
     /// * `line` - The trimmed sentence; blank lines are skipped.
-    /// * `stage1_out` - Where to write the stage-1 rows.
-    /// * `stage2_out` - Where to write the stage-2 rows.
-    /// * `lexicon` - The lexicon counts accumulated so far.
-    /// * `state` - Reusable scratch buffers.
+    /// * `output` - Where to append the rows and lexicon counts.
     /// * `feature_set` - Which stage-2 word templates to write.
-    /// * `tsv` - Whether the corpus is in the space-preserving TSV format.
+    /// * `format` - The layout of the corpus lines.
     ///
     /// # Returns
     /// Returns a Result indicating success or failure.
     ///
     /// # Errors
-    /// Returns an error if a writer fails.
-    #[allow(clippy::too_many_arguments)]
-    fn extract_two_stage_line<W1, W2>(
+    /// Returns an error if writing a row fails or a partially annotated
+    /// line is malformed.
+    fn extract_two_stage_line(
         &self,
         line: &str,
-        stage1_out: &mut W1,
-        stage2_out: &mut W2,
-        lexicon: &mut LexiconCounts,
-        state: &mut TwoStageLineState,
+        output: &mut TwoStageOutput,
         feature_set: TwoStageFeatureSet,
-        tsv: bool,
-    ) -> Result<()>
-    where
-        W1: Write,
-        W2: Write,
-    {
+        format: CorpusFormat,
+    ) -> Result<()> {
         if line.is_empty() {
             return Ok(());
         }
+        let tsv = match format {
+            CorpusFormat::Space => false,
+            CorpusFormat::Tsv => true,
+            CorpusFormat::Partial => {
+                return self.extract_two_stage_partial_line(line, output, feature_set);
+            }
+        };
 
         let segmenter = &self.segmenter;
         let language = segmenter.language();
-        let TwoStageLineState {
-            stage1_rows,
-            stage2_feats,
-        } = state;
+        let TwoStageOutput {
+            stage1: stage1_out,
+            stage2: stage2_out,
+            lexicon,
+            state:
+                TwoStageLineState {
+                    stage1_rows,
+                    stage2_feats,
+                },
+        } = output;
 
         // Stage 1: character-level attribute generation over the POS-tagged
         // corpus, with the label collapsed to the boundary class.
@@ -673,7 +1336,13 @@ impl Extractor {
         // Stage 2 + lexicon: one row per word, keyed by its UPOS tag.
         // Parsed with the same separator stage 1 just used, so `sent` and
         // the offsets below match the stage-1 training text exactly.
-        let tokens = parse_gold_pos_line(line, tsv);
+        let mut tokens = parse_gold_pos_line(line, tsv);
+        for (surface, _) in &mut tokens {
+            // The same per-word normalization stage 1 applied.
+            if let Cow::Owned(normalized) = segmenter.normalization().normalize(surface) {
+                *surface = normalized;
+            }
+        }
         let sent: Vec<char> = tokens.iter().flat_map(|(w, _)| w.chars()).collect();
         let type_ids: Vec<u8> = sent.iter().map(|&c| language.char_type_id(c)).collect();
         let mut start = 0usize;
@@ -707,17 +1376,84 @@ impl Extractor {
         Ok(())
     }
 
+    /// Processes one partially annotated line of the two-stage pipeline.
+    ///
+    /// # Arguments
+    /// * `line` - The trimmed, non-empty sentence.
+    /// * `output` - Where to append the rows and lexicon counts.
+    /// * `feature_set` - Which stage-2 word templates to write.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an error if writing a row fails, or
+    /// [`LitseaError::InvalidData`](crate::error::LitseaError::InvalidData)
+    /// if the line is malformed.
+    fn extract_two_stage_partial_line(
+        &self,
+        line: &str,
+        output: &mut TwoStageOutput,
+        feature_set: TwoStageFeatureSet,
+    ) -> Result<()> {
+        let segmenter = &self.segmenter;
+        let language = segmenter.language();
+        let TwoStageOutput {
+            stage1: stage1_out,
+            stage2: stage2_out,
+            lexicon,
+            state:
+                TwoStageLineState {
+                    stage1_rows,
+                    stage2_feats,
+                },
+        } = output;
+
+        let sentence = partial::parse_partial_line(line, true, segmenter.normalization())?;
+        segmenter.process_partial(&sentence, true, |attrs, start| {
+            stage1_rows.push(Self::format_row(attrs, if start { "B" } else { "O" }));
+        });
+        for row in stage1_rows.drain(..) {
+            writeln!(stage1_out, "{}", row)?;
+        }
+
+        // Only the tagged words train stage 2, with the whole sentence as
+        // their context.
+        let sent: Vec<char> = sentence.text.chars().collect();
+        let type_ids: Vec<u8> = sent.iter().map(|&c| language.char_type_id(c)).collect();
+        for &(start, end, tag) in &sentence.words {
+            let surface: String = sent[start..end].iter().collect();
+            if !surface.chars().all(char::is_whitespace) {
+                stage2_feats.clear();
+                write_word_features(
+                    language,
+                    &sent,
+                    &type_ids,
+                    start,
+                    end,
+                    |tid| feature_set.includes(tid),
+                    &mut |f| stage2_feats.push(f),
+                );
+                writeln!(stage2_out, "{}\t{}", tag, stage2_feats.join("\t"))?;
+            }
+            lexicon.observe(&surface, tag);
+        }
+
+        Ok(())
+    }
+
     /// Shared extraction pipeline: reads the corpus line by line, lets
     /// `process_line` convert each non-empty line into formatted feature rows,
     /// and writes the rows to the features file.
     #[cfg(not(target_arch = "wasm32"))]
     fn write_features<P>(
+        &self,
         corpus_path: &Path,
         features_path: &Path,
-        mut process_line: P,
+        process_line: P,
     ) -> Result<()>
     where
-        P: FnMut(&str, &mut Vec<String>),
+        P: Fn(&str, &mut Vec<String>) -> Result<()> + Sync,
     {
         // Read sentences from the corpus file, one line per sentence. The
         // corpus is streamed rather than slurped: a real one (a Wikipedia
@@ -729,11 +1465,11 @@ impl Extractor {
         let features_file = File::create(features_path)?;
         let mut features = io::BufWriter::new(features_file);
 
-        let mut rows: Vec<String> = Vec::new();
-        for line in corpus.lines() {
-            let line = line?;
-            Self::write_feature_line(line.trim(), &mut features, &mut rows, &mut process_line)?;
-        }
+        self.for_each_line(
+            corpus.lines().map(|line| line.map_err(Into::into)),
+            |line, rows| Self::feature_rows(line, rows, &process_line),
+            |rows| Self::write_rows(rows, &mut features),
+        )?;
 
         // Flushed explicitly so a write error surfaces here rather than
         // being swallowed by `BufWriter`'s drop.
@@ -757,56 +1493,137 @@ impl Extractor {
     /// # Errors
     /// Returns an error if the writer fails.
     fn write_features_to_writer<W, P>(
+        &self,
         corpus: &str,
         writer: &mut W,
-        mut process_line: P,
+        process_line: P,
     ) -> Result<()>
     where
         W: Write,
-        P: FnMut(&str, &mut Vec<String>),
+        P: Fn(&str, &mut Vec<String>) -> Result<()> + Sync,
     {
-        let mut rows: Vec<String> = Vec::new();
-        for line in corpus.lines() {
-            Self::write_feature_line(line.trim(), writer, &mut rows, &mut process_line)?;
-        }
-
-        Ok(())
+        self.for_each_line(
+            corpus.lines().map(Ok),
+            |line, rows| Self::feature_rows(line, rows, &process_line),
+            |rows| Self::write_rows(rows, writer),
+        )
     }
 
-    /// Turns one corpus line into feature rows and writes them.
+    /// Turns one corpus line into feature rows.
     ///
     /// # Arguments
     /// * `line` - The trimmed sentence; blank lines are skipped.
-    /// * `writer` - Where to write the feature rows.
-    /// * `rows` - Scratch buffer, drained on every call.
+    /// * `rows` - Where to append the feature rows.
     /// * `process_line` - Turns the sentence into feature rows.
     ///
     /// # Returns
     /// Returns a Result indicating success or failure.
     ///
     /// # Errors
-    /// Returns an error if the writer fails.
-    fn write_feature_line<W, P>(
-        line: &str,
-        writer: &mut W,
-        rows: &mut Vec<String>,
-        process_line: &mut P,
-    ) -> Result<()>
+    /// Returns the error of `process_line`.
+    fn feature_rows<P>(line: &str, rows: &mut Vec<String>, process_line: &P) -> Result<()>
     where
-        W: Write,
-        P: FnMut(&str, &mut Vec<String>),
+        P: Fn(&str, &mut Vec<String>) -> Result<()>,
     {
         if line.is_empty() {
             return Ok(());
         }
-        process_line(line, rows);
+        process_line(line, rows)
+    }
+
+    /// Writes buffered feature rows, leaving the buffer empty.
+    ///
+    /// # Arguments
+    /// * `rows` - The rows to write, in order.
+    /// * `writer` - Where to write them.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns an error if the writer fails.
+    fn write_rows<W: Write>(rows: &mut Vec<String>, writer: &mut W) -> Result<()> {
         for row in rows.drain(..) {
             writeln!(writer, "{}", row)?;
         }
-
         Ok(())
     }
 
+    /// Shared driver of every extraction method: folds each trimmed corpus
+    /// line into an accumulator with `work` and hands the accumulator to
+    /// `consume`, which writes it out and leaves it empty.
+    ///
+    /// With one thread, every line is consumed as soon as it is folded.
+    /// With more, lines are read in chunks of [`PARALLEL_CHUNK_LINES`], each
+    /// chunk is split into `threads` contiguous slices folded in parallel
+    /// (one accumulator per slice), and the accumulators are consumed in
+    /// slice order - so whatever `consume` writes is byte-identical either
+    /// way, provided `work` only appends.
+    ///
+    /// # Arguments
+    /// * `lines` - The corpus lines.
+    /// * `work` - Folds one trimmed line into an accumulator.
+    /// * `consume` - Writes out an accumulator and empties it.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Propagates the first error of `lines`, `work`, or `consume`.
+    fn for_each_line<L, I, T, F, C>(&self, mut lines: I, work: F, mut consume: C) -> Result<()>
+    where
+        L: AsRef<str> + Sync,
+        I: Iterator<Item = Result<L>>,
+        T: Default + Send,
+        F: Fn(&str, &mut T) -> Result<()> + Sync,
+        C: FnMut(&mut T) -> Result<()>,
+    {
+        // wasm32 has no threads to spawn.
+        let threads = if cfg!(target_arch = "wasm32") { 1 } else { self.threads };
+        if threads <= 1 {
+            let mut acc = T::default();
+            for line in lines {
+                work(line?.as_ref().trim(), &mut acc)?;
+                consume(&mut acc)?;
+            }
+            return Ok(());
+        }
+
+        let mut chunk: Vec<L> = Vec::with_capacity(PARALLEL_CHUNK_LINES);
+        loop {
+            chunk.clear();
+            for line in lines.by_ref().take(PARALLEL_CHUNK_LINES) {
+                chunk.push(line?);
+            }
+            if chunk.is_empty() {
+                return Ok(());
+            }
+            let slice_len = chunk.len().div_ceil(threads);
+            let outputs: Result<Vec<T>> = std::thread::scope(|scope| {
+                let handles: Vec<_> = chunk
+                    .chunks(slice_len)
+                    .map(|slice| {
+                        let work = &work;
+                        scope.spawn(move || {
+                            let mut acc = T::default();
+                            for line in slice {
+                                work(line.as_ref().trim(), &mut acc)?;
+                            }
+                            Ok(acc)
+                        })
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("extraction worker panicked"))
+                    .collect()
+            });
+            for mut acc in outputs? {
+                consume(&mut acc)?;
+            }
+        }
+    }
+
     /// Formats one feature row: the label followed by the sorted attributes,
     /// tab-separated.
     fn format_row(attributes: HashSet<String>, label: impl fmt::Display) -> String {
@@ -1176,4 +1993,300 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_extract_partial() -> Result<()> {
+        use crate::error::LitseaError;
+
+        // A fully annotated line extracts exactly like its segmented form.
+        let extractor = Extractor::default();
+        let mut got = Vec::new();
+        extractor.extract_partial_to_writer("こ-れ|は|テ-ス-ト|で-す|。", &mut got)?;
+        let mut want = Vec::new();
+        extractor.extract_to_writer("これ は テスト です 。", &mut want)?;
+        assert_eq!(got, want);
+        let mut got = Vec::new();
+        extractor.extract_partial_tag_free_to_writer("こ-れ|は|テ-ス-ト", &mut got)?;
+        let mut want = Vec::new();
+        extractor.extract_tag_free_to_writer("これ は テスト", &mut want)?;
+        assert_eq!(got, want);
+
+        // Unannotated positions get no row; the rows before them are the
+        // ones the full annotation writes, as their history is known.
+        let mut partial = Vec::new();
+        extractor.extract_partial_to_writer("こ-れ|は|テ ス ト", &mut partial)?;
+        let partial = String::from_utf8(partial).expect("features are UTF-8");
+        let mut full = Vec::new();
+        extractor.extract_to_writer("これ は テスト", &mut full)?;
+        let full = String::from_utf8(full).expect("features are UTF-8");
+        let rows: Vec<&str> = partial.lines().collect();
+        assert_eq!(rows, full.lines().take(3).collect::<Vec<_>>());
+
+        // A row right after a gap has the tag-free row's attributes: its
+        // history is unknown, so no tag-dependent template fires.
+        let mut partial = Vec::new();
+        extractor.extract_partial_to_writer("こ れ|は|テ-ス-ト", &mut partial)?;
+        let partial = String::from_utf8(partial).expect("features are UTF-8");
+        let mut tag_free = Vec::new();
+        extractor.extract_tag_free_to_writer("これ は テスト", &mut tag_free)?;
+        let tag_free = String::from_utf8(tag_free).expect("features are UTF-8");
+        let mut full = Vec::new();
+        extractor.extract_to_writer("これ は テスト", &mut full)?;
+        let full = String::from_utf8(full).expect("features are UTF-8");
+        let (partial, tag_free, full): (Vec<&str>, Vec<&str>, Vec<&str>) =
+            (partial.lines().collect(), tag_free.lines().collect(), full.lines().collect());
+        // Rows for は, テ, ス cover the gap; ト's window (は, テ, ス) does not.
+        assert_eq!(partial.len(), 4);
+        assert_eq!(partial[..3], tag_free[1..4]);
+        assert_eq!(partial[3], full[4]);
+
+        let mut sink = Vec::new();
+        assert!(matches!(
+            extractor.extract_partial_to_writer("これ|は", &mut sink),
+            Err(LitseaError::InvalidData(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_distilled() -> Result<()> {
+        use crate::adaboost::AdaBoost;
+
+        let model_file =
+            std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models/japanese.model");
+        let mut learner = AdaBoost::new(0.01, 100);
+        learner.load_model_from_path(&model_file)?;
+        let teacher = Segmenter::with_learner(Language::Japanese, learner);
+        let extractor = Extractor::new(Language::Japanese);
+
+        // Every position of a fully trusted teacher becomes an instance,
+        // labeled the way the teacher segments the line.
+        let (mut labels, mut margins) = (Vec::new(), Vec::new());
+        extractor.extract_distilled_instances_from_str(
+            &teacher,
+            "これはテストです。\n\n",
+            0.0,
+            true,
+            |_, label, margin| {
+                labels.push(label);
+                margins.push(margin);
+            },
+        )?;
+        assert_eq!(labels, [-1, 1, 1, -1, -1, 1, -1, 1]);
+        // Each instance carries the teacher's absolute margin at its gap.
+        let mut buf = SegmentBuffer::new();
+        let expected: Vec<f64> = teacher
+            .boundary_margins_into("これはテストです。", &mut buf)
+            .iter()
+            .map(|m| m.abs())
+            .collect();
+        assert_eq!(margins, expected);
+
+        // A cutoff keeps the gaps at or above it, with their margins.
+        let mut sorted = margins.clone();
+        sorted.sort_by(f64::total_cmp);
+        let cutoff = sorted[sorted.len() / 2];
+        let mut kept = Vec::new();
+        extractor.extract_distilled_instances_from_str(
+            &teacher,
+            "これはテストです。",
+            cutoff,
+            true,
+            |_, _, margin| kept.push(margin),
+        )?;
+        let expected: Vec<f64> = margins.iter().copied().filter(|&m| m >= cutoff).collect();
+        assert_eq!(kept, expected);
+
+        // An unreachable margin leaves nothing to learn from.
+        let mut count = 0;
+        extractor.extract_distilled_instances_from_str(
+            &teacher,
+            "これはテストです。",
+            f64::INFINITY,
+            true,
+            |_, _, _| count += 1,
+        )?;
+        assert_eq!(count, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_two_stage_partial() -> Result<()> {
+        let extractor = Extractor::default();
+
+        // Fully annotated and tagged: the same outputs as the POS corpus.
+        let (mut s1, mut s2, mut lex) = (Vec::new(), Vec::new(), Vec::new());
+        extractor.extract_two_stage_partial_to_writers(
+            "こ-れ/PRON|は/PART|テ-ス-ト/NOUN|。/PUNCT",
+            &mut s1,
+            &mut s2,
+            &mut lex,
+            TwoStageFeatureSet::Full,
+        )?;
+        let (mut w1, mut w2, mut wlex) = (Vec::new(), Vec::new(), Vec::new());
+        extractor.extract_two_stage_to_writers(
+            "これ/PRON は/PART テスト/NOUN 。/PUNCT",
+            &mut w1,
+            &mut w2,
+            &mut wlex,
+            TwoStageFeatureSet::Full,
+        )?;
+        assert_eq!((s1, s2, lex), (w1, w2, wlex));
+
+        // Partially annotated: stage 1 covers the annotated positions, and
+        // only the tagged word reaches stage 2 and the lexicon.
+        let (mut s1, mut s2, mut lex) = (Vec::new(), Vec::new(), Vec::new());
+        extractor.extract_two_stage_partial_to_writers(
+            "こ-れ/PRON|は|テ ス ト",
+            &mut s1,
+            &mut s2,
+            &mut lex,
+            TwoStageFeatureSet::Full,
+        )?;
+        let stage1 = String::from_utf8(s1).expect("features are UTF-8");
+        let labels: Vec<&str> = stage1.lines().map(|l| l.split('\t').next().unwrap()).collect();
+        assert_eq!(labels, vec!["B", "O", "B", "B"]);
+        let stage2 = String::from_utf8(s2).expect("features are UTF-8");
+        assert_eq!(stage2.lines().count(), 1);
+        assert!(stage2.starts_with("PRON\t"));
+        assert_eq!(String::from_utf8(lex).expect("lexicon is UTF-8"), "これ\tPRON:1\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalization_extracts_like_the_normalized_corpus() -> Result<()> {
+        // An NFKC extractor over a half-width/full-width corpus must write
+        // exactly what a plain extractor writes for the normalized corpus.
+        let raw = "ﾃﾞｰﾀ を ＡＢＣ に 送る 。\n";
+        let normalized = "データ を ABC に 送る 。\n";
+        let nfkc = Extractor::default().with_normalization(Normalization::Nfkc);
+        assert_eq!(nfkc.normalization(), Normalization::Nfkc);
+
+        let mut got = Vec::new();
+        nfkc.extract_to_writer(raw, &mut got)?;
+        let mut want = Vec::new();
+        Extractor::default().extract_to_writer(normalized, &mut want)?;
+        assert_eq!(got, want);
+
+        let (mut s1, mut s2, mut lex) = (Vec::new(), Vec::new(), Vec::new());
+        nfkc.extract_two_stage_to_writers(
+            "ﾃﾞｰﾀ/NOUN を/ADP 送る/VERB",
+            &mut s1,
+            &mut s2,
+            &mut lex,
+            TwoStageFeatureSet::Full,
+        )?;
+        let (mut w1, mut w2, mut wlex) = (Vec::new(), Vec::new(), Vec::new());
+        Extractor::default().extract_two_stage_to_writers(
+            "データ/NOUN を/ADP 送る/VERB",
+            &mut w1,
+            &mut w2,
+            &mut wlex,
+            TwoStageFeatureSet::Full,
+        )?;
+        assert_eq!((s1, s2, lex), (w1, w2, wlex));
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_dictionary_features() -> Result<()> {
+        let mut dictionary = UserDictionary::new();
+        dictionary.insert("東京");
+        dictionary.insert("東京都");
+        let extractor = Extractor::default().with_dictionary(dictionary);
+        assert_eq!(extractor.dictionary().map(UserDictionary::len), Some(2));
+
+        let mut out = Vec::new();
+        extractor.extract_to_writer("東京 都 に 行く\n", &mut out)?;
+        let rows: Vec<Vec<&str>> = std::str::from_utf8(&out)
+            .unwrap()
+            .lines()
+            .map(|row| row.split('\t').filter(|f| f.starts_with('D')).collect())
+            .collect();
+        // Rows start at 京: inside both words; 都 follows 東京 and is inside
+        // 東京都; に follows 東京都; 行 and く touch no word.
+        assert_eq!(
+            rows,
+            [vec!["DI:2", "DI:3"], vec!["DI:3", "DL:2"], vec!["DL:3"], vec![], vec![]]
+        );
+
+        // Without a dictionary no such feature is written.
+        let mut plain = Vec::new();
+        Extractor::default().extract_to_writer("東京 都 に 行く\n", &mut plain)?;
+        assert!(!std::str::from_utf8(&plain).unwrap().contains("\tD"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_threaded_extraction_matches_sequential() -> Result<()> {
+        // More lines than one parallel chunk, with blank lines mixed in, so
+        // chunk and slice boundaries both fall mid-corpus.
+        let words = ["これ", "は", "テスト", "です", "。", "日本語", "を", "書く"];
+        let tags = ["PRON", "ADP", "NOUN", "AUX", "PUNCT", "NOUN", "ADP", "VERB"];
+        let (mut corpus, mut pos_corpus) = (String::new(), String::new());
+        for i in 0..PARALLEL_CHUNK_LINES + 300 {
+            if i % 97 == 0 {
+                corpus.push('\n');
+                pos_corpus.push('\n');
+            }
+            let picks: Vec<usize> =
+                (0..4).map(|j| (i * 7 + j * (i % 5 + 1)) % words.len()).collect();
+            let line: Vec<&str> = picks.iter().map(|&k| words[k]).collect();
+            corpus.push_str(&line.join(" "));
+            corpus.push('\n');
+            let line: Vec<String> =
+                picks.iter().map(|&k| format!("{}/{}", words[k], tags[k])).collect();
+            pos_corpus.push_str(&line.join(" "));
+            pos_corpus.push('\n');
+        }
+
+        let sequential = Extractor::default();
+        let threaded = Extractor::default().with_threads(4);
+        assert_eq!(threaded.threads(), 4);
+        assert_eq!(Extractor::default().with_threads(0).threads(), 1);
+
+        let (mut want, mut got) = (Vec::new(), Vec::new());
+        sequential.extract_tag_free_to_writer(&corpus, &mut want)?;
+        threaded.extract_tag_free_to_writer(&corpus, &mut got)?;
+        assert!(!want.is_empty());
+        assert_eq!(got, want);
+
+        let mut want = Vec::new();
+        sequential.extract_instances_from_str(&corpus, CorpusFormat::Space, false, |a, l| {
+            want.push((a, l))
+        })?;
+        let mut got = Vec::new();
+        threaded.extract_instances_from_str(&corpus, CorpusFormat::Space, false, |a, l| {
+            got.push((a, l))
+        })?;
+        assert_eq!(got, want);
+
+        let (mut s1, mut s2, mut lex) = (Vec::new(), Vec::new(), Vec::new());
+        sequential.extract_two_stage_to_writers(
+            &pos_corpus,
+            &mut s1,
+            &mut s2,
+            &mut lex,
+            TwoStageFeatureSet::Fast,
+        )?;
+        let (mut t1, mut t2, mut tlex) = (Vec::new(), Vec::new(), Vec::new());
+        threaded.extract_two_stage_to_writers(
+            &pos_corpus,
+            &mut t1,
+            &mut t2,
+            &mut tlex,
+            TwoStageFeatureSet::Fast,
+        )?;
+        assert_eq!((t1, t2, tlex), (s1, s2, lex));
+
+        // The path-based methods share the driver.
+        let mut corpus_file = NamedTempFile::new()?;
+        corpus_file.write_all(corpus.as_bytes())?;
+        let want_file = NamedTempFile::new()?;
+        sequential.extract(corpus_file.path(), want_file.path())?;
+        let got_file = NamedTempFile::new()?;
+        threaded.extract(corpus_file.path(), got_file.path())?;
+        assert_eq!(std::fs::read(got_file.path())?, std::fs::read(want_file.path())?);
+        Ok(())
+    }
 }
@@ -0,0 +1,91 @@
+//! Extended grapheme cluster boundaries for the segmenter.
+//!
+//! The model scores a boundary before every `char`, but a user-perceived
+//! character may span several: a base plus combining marks (`e` + U+0301),
+//! an emoji ZWJ sequence (👩‍💻), a regional-indicator flag pair (🇯🇵), an
+//! emoji with a variation selector or skin-tone modifier, or a conjoining
+//! Hangul jamo sequence. [`for_each_continuation`] reports every `char`
+//! that continues a cluster, so the segmenter can forbid a boundary there.
+//!
+//! Running the full UAX #29 rules over every sentence would cost more than
+//! the scoring itself on plain CJK text, so a sentence made only of
+//! characters that never join a neighbour's cluster (see [`is_isolated`])
+//! skips them entirely.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Calls `f` with the byte offset in `text` of every `char` that does not
+/// start an extended grapheme cluster, in increasing order.
+pub(crate) fn for_each_continuation(text: &str, mut f: impl FnMut(usize)) {
+    if text.chars().all(is_isolated) {
+        return;
+    }
+    for (start, cluster) in text.grapheme_indices(true) {
+        for (offset, _) in cluster.char_indices().skip(1) {
+            f(start + offset);
+        }
+    }
+}
+
+/// Returns true when `c` always forms a cluster of its own: it is neither
+/// a mark, joiner, or modifier that extends its predecessor, nor a
+/// prepended character, regional indicator, conjoining jamo, or CR that
+/// binds to its successor. The ranges cover ASCII and Latin-1 letters, kana
+/// without the combining voiced sound marks, CJK ideographs and
+/// punctuation, precomposed Hangul syllables (a following trailing jamo is
+/// caught as non-isolated itself), and the full-width forms.
+#[inline]
+fn is_isolated(c: char) -> bool {
+    matches!(
+        c,
+        '\0'..='\x0c'
+            | '\x0e'..='\u{2ff}'
+            | '\u{3000}'..='\u{3029}'
+            | '\u{3030}'..='\u{3098}'
+            | '\u{309b}'..='\u{30ff}'
+            | '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{ac00}'..='\u{d7a3}'
+            | '\u{ff01}'..='\u{ff9d}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn continuations(text: &str) -> Vec<usize> {
+        let mut offsets = Vec::new();
+        for_each_continuation(text, |offset| offsets.push(offset));
+        offsets
+    }
+
+    #[test]
+    fn test_isolated_ranges_never_join_a_cluster() {
+        // Every isolated character pair must be two clusters, so the fast
+        // path agrees with the full rules.
+        let isolated: Vec<char> = ('\0'..='\u{ffff}').filter(|&c| is_isolated(c)).collect();
+        let probes = ['a', '\n', 'あ', 'ア', '漢', '가', '각', '。', 'Ａ', 'ｱ'];
+        for &c in &isolated {
+            for &probe in &probes {
+                for text in [format!("{c}{probe}"), format!("{probe}{c}")] {
+                    assert_eq!(text.graphemes(true).count(), 2, "{text:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reports_every_char_inside_a_cluster() {
+        assert!(continuations("これはテストです。").is_empty());
+        assert_eq!(continuations("e\u{301}"), vec![1]);
+        // CR LF is a single cluster.
+        assert_eq!(continuations("a\r\nb"), vec![2]);
+        // Woman + ZWJ + laptop.
+        assert_eq!(continuations("\u{1f469}\u{200d}\u{1f4bb}"), vec![4, 7]);
+        // Two flags: the pairs stay together, the flags apart.
+        assert_eq!(continuations("🇯🇵🇰🇷"), vec![4, 12]);
+        // Conjoining jamo 가 + trailing ㄱ.
+        assert_eq!(continuations("\u{1100}\u{1161}\u{11a8}"), vec![3, 6]);
+    }
+}
@@ -0,0 +1,511 @@
+//! Model introspection.
+//!
+//! [`AdaBoost::inspect`], [`AveragedPerceptron::inspect`] and
+//! [`TwoStageLearner::inspect`] summarize a loaded model as a
+//! [`ModelInspection`]: one [`ClassifierInspection`] per classifier (the
+//! feature count per template prefix, whether the model is pointwise, the
+//! weight distribution, and the class list), the two-stage lexicon, the
+//! recorded [`ModelMetadata`], and the estimated memory of the packed
+//! tables a segmenter compiles from the model. `litsea inspect` prints the
+//! report as text or, via [`ModelInspection::to_json`], as JSON.
+//!
+//! Character-level classifiers (segmentation models, two-stage stage 1,
+//! and the generic perceptron) are broken down by the segmentation
+//! templates; the two-stage stage-2 tagger by the word-level templates.
+
+use std::fmt::Write as _;
+
+use crate::adaboost::AdaBoost;
+use crate::binary_model;
+use crate::language::Language;
+use crate::metadata::ModelMetadata;
+use crate::packed_model::{DICTIONARY_PREFIXES, PackedModel, TEMPLATES, is_tag_dependent_feature};
+use crate::packed_two_stage::PackedTwoStageModel;
+use crate::perceptron::AveragedPerceptron;
+use crate::two_stage::{ModelKind, TwoStageLearner, is_dominance_fixed};
+use crate::word_features::WORD_TEMPLATE_PREFIXES;
+
+/// A summary of a loaded model.
+#[derive(Debug, Clone)]
+pub struct ModelInspection {
+    /// The kind of model.
+    pub kind: ModelKind,
+    /// The provenance recorded in the model.
+    pub metadata: ModelMetadata,
+    /// The model's classifiers: one for a segmentation or perceptron
+    /// model, `stage1` and `stage2` for a two-stage model.
+    pub classifiers: Vec<ClassifierInspection>,
+    /// The candidate-tag lexicon of a two-stage model.
+    pub lexicon: Option<LexiconInspection>,
+    /// The estimated memory of the packed tables a segmenter compiles from
+    /// the model (`None` for a generic perceptron, which no segmenter uses).
+    pub packed: Option<PackedFootprint>,
+}
+
+/// A summary of one classifier of a model.
+#[derive(Debug, Clone)]
+pub struct ClassifierInspection {
+    /// Role of the classifier: `segmentation`, `perceptron`, `stage1` or
+    /// `stage2`.
+    pub name: &'static str,
+    /// Number of features.
+    pub num_features: usize,
+    /// Feature count per template prefix, in template order, omitting
+    /// templates without features.
+    pub templates: Vec<(&'static str, usize)>,
+    /// Number of features whose prefix is not a known template.
+    pub other_features: usize,
+    /// Whether no tag-dependent (`UP*`/`BP*`/`UQ*`/`BQ*`/`TQ*`) feature has
+    /// a non-zero weight, i.e. whether segmentation can skip the
+    /// sequential pass. `None` for the word-level stage-2 tagger.
+    pub pointwise: Option<bool>,
+    /// Class names; empty for a binary AdaBoost-format classifier.
+    pub classes: Vec<String>,
+    /// Distribution of the feature weights (one per class for a
+    /// perceptron).
+    pub weights: WeightStats,
+}
+
+/// Distribution statistics of a set of weights.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WeightStats {
+    /// Number of weights.
+    pub count: usize,
+    /// Number of non-zero weights.
+    pub nonzero: usize,
+    /// Smallest weight (`0.0` when there are none).
+    pub min: f64,
+    /// Largest weight (`0.0` when there are none).
+    pub max: f64,
+    /// Mean weight.
+    pub mean: f64,
+    /// Mean absolute weight.
+    pub mean_abs: f64,
+    /// Population standard deviation.
+    pub std_dev: f64,
+}
+
+/// A summary of a two-stage model's candidate-tag lexicon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexiconInspection {
+    /// Number of surfaces in the lexicon.
+    pub entries: usize,
+    /// The classifier-skip threshold.
+    pub dominance: f64,
+    /// Number of surfaces tagged without the classifier (a single observed
+    /// tag, or a dominant one).
+    pub fixed: usize,
+}
+
+impl LexiconInspection {
+    /// Returns the fraction of lexicon surfaces tagged without the
+    /// classifier (`0.0` for an empty lexicon).
+    #[must_use]
+    pub fn fixed_ratio(&self) -> f64 {
+        if self.entries == 0 { 0.0 } else { self.fixed as f64 / self.entries as f64 }
+    }
+}
+
+/// The estimated memory of a model's packed tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedFootprint {
+    /// The language the tables were compiled for (the footprint depends on
+    /// its character types).
+    pub language: Language,
+    /// Estimated heap size in bytes.
+    pub bytes: usize,
+}
+
+impl ModelInspection {
+    /// Renders the inspection as a JSON object.
+    ///
+    /// # Returns
+    /// The JSON text (a single line, without a trailing newline).
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(out, "{{\"kind\":{}", json_string(binary_model::kind_name(self.kind)));
+
+        out.push_str(",\"metadata\":{");
+        let mut fields = Vec::new();
+        if let Some(language) = self.metadata.language() {
+            fields.push(format!("\"language\":{}", json_string(&language.to_string())));
+        }
+        if let Some(templates) = self.metadata.templates() {
+            fields.push(format!("\"templates\":{}", json_string(templates)));
+        }
+        if let Some(checksum) = self.metadata.corpus_checksum() {
+            fields.push(format!("\"corpus\":{}", json_string(checksum)));
+        }
+        let params: Vec<String> = self
+            .metadata
+            .params()
+            .map(|(name, value)| format!("{}:{}", json_string(name), json_string(value)))
+            .collect();
+        fields.push(format!("\"params\":{{{}}}", params.join(",")));
+        out.push_str(&fields.join(","));
+        out.push('}');
+
+        out.push_str(",\"classifiers\":[");
+        for (i, classifier) in self.classifiers.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            classifier.write_json(&mut out);
+        }
+        out.push(']');
+
+        match &self.lexicon {
+            Some(lexicon) => {
+                let _ = write!(
+                    out,
+                    ",\"lexicon\":{{\"entries\":{},\"dominance\":{},\"fixed\":{},\"fixed_ratio\":{}}}",
+                    lexicon.entries,
+                    lexicon.dominance,
+                    lexicon.fixed,
+                    lexicon.fixed_ratio()
+                );
+            }
+            None => out.push_str(",\"lexicon\":null"),
+        }
+        match &self.packed {
+            Some(packed) => {
+                let _ = write!(
+                    out,
+                    ",\"packed\":{{\"language\":{},\"bytes\":{}}}",
+                    json_string(&packed.language.to_string()),
+                    packed.bytes
+                );
+            }
+            None => out.push_str(",\"packed\":null"),
+        }
+        out.push('}');
+        out
+    }
+}
+
+impl ClassifierInspection {
+    /// Summarizes a classifier from its `(feature, weights)` pairs.
+    ///
+    /// # Arguments
+    /// * `name` - The role of the classifier.
+    /// * `prefixes` - The template prefixes to count features by.
+    /// * `char_level` - Whether the features are segmentation templates,
+    ///   for which pointwise-ness is reported.
+    /// * `classes` - The class names.
+    /// * `features` - Each feature with its weights.
+    fn new<'a>(
+        name: &'static str,
+        prefixes: &[&'static str],
+        char_level: bool,
+        classes: Vec<String>,
+        features: impl Iterator<Item = (&'a str, &'a [f64])>,
+    ) -> Self {
+        let mut counts = vec![0usize; prefixes.len()];
+        let mut num_features = 0;
+        let mut other_features = 0;
+        let mut tag_dependent = false;
+        let mut weights = Vec::new();
+        for (feature, feature_weights) in features {
+            num_features += 1;
+            let prefix = feature.split_once(':').map_or(feature, |(prefix, _)| prefix);
+            match prefixes.iter().position(|p| *p == prefix) {
+                Some(i) => counts[i] += 1,
+                None => other_features += 1,
+            }
+            if char_level
+                && feature_weights.iter().any(|&w| w != 0.0)
+                && is_tag_dependent_feature(feature)
+            {
+                tag_dependent = true;
+            }
+            weights.extend_from_slice(feature_weights);
+        }
+
+        ClassifierInspection {
+            name,
+            num_features,
+            templates: prefixes
+                .iter()
+                .zip(counts)
+                .filter(|(_, count)| *count > 0)
+                .map(|(prefix, count)| (*prefix, count))
+                .collect(),
+            other_features,
+            pointwise: char_level.then_some(!tag_dependent),
+            classes,
+            weights: WeightStats::from_weights(&weights),
+        }
+    }
+
+    /// Summarizes an AdaBoost-format classifier.
+    fn from_adaboost(name: &'static str, learner: &AdaBoost) -> Self {
+        let features: Vec<(&str, [f64; 1])> =
+            learner.feature_weights().map(|(feature, weight)| (feature, [weight])).collect();
+        Self::new(
+            name,
+            &segmentation_prefixes(),
+            true,
+            Vec::new(),
+            features.iter().map(|(feature, weight)| (*feature, weight.as_slice())),
+        )
+    }
+
+    /// Appends the classifier as a JSON object to `out`.
+    fn write_json(&self, out: &mut String) {
+        let _ = write!(
+            out,
+            "{{\"name\":{},\"features\":{},\"templates\":{{",
+            json_string(self.name),
+            self.num_features
+        );
+        for (i, (prefix, count)) in self.templates.iter().enumerate() {
+            let _ =
+                write!(out, "{}{}:{}", if i > 0 { "," } else { "" }, json_string(prefix), count);
+        }
+        let pointwise = match self.pointwise {
+            Some(pointwise) => pointwise.to_string(),
+            None => "null".to_string(),
+        };
+        let classes: Vec<String> = self.classes.iter().map(|c| json_string(c)).collect();
+        let w = &self.weights;
+        let _ = write!(
+            out,
+            "}},\"other_features\":{},\"pointwise\":{},\"classes\":[{}],\"weights\":{{\
+             \"count\":{},\"nonzero\":{},\"min\":{},\"max\":{},\"mean\":{},\"mean_abs\":{},\
+             \"std_dev\":{}}}}}",
+            self.other_features,
+            pointwise,
+            classes.join(","),
+            w.count,
+            w.nonzero,
+            w.min,
+            w.max,
+            w.mean,
+            w.mean_abs,
+            w.std_dev
+        );
+    }
+}
+
+impl WeightStats {
+    /// Computes the statistics of `weights`.
+    fn from_weights(weights: &[f64]) -> Self {
+        if weights.is_empty() {
+            return WeightStats::default();
+        }
+        let n = weights.len() as f64;
+        let mean = weights.iter().sum::<f64>() / n;
+        let variance = weights.iter().map(|w| (w - mean) * (w - mean)).sum::<f64>() / n;
+        WeightStats {
+            count: weights.len(),
+            nonzero: weights.iter().filter(|&&w| w != 0.0).count(),
+            min: weights.iter().copied().fold(f64::INFINITY, f64::min),
+            max: weights.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean,
+            mean_abs: weights.iter().map(|w| w.abs()).sum::<f64>() / n,
+            std_dev: variance.sqrt(),
+        }
+    }
+}
+
+impl AdaBoost {
+    /// Summarizes the model (see [`crate::inspect`]).
+    ///
+    /// # Arguments
+    /// * `language` - The language to estimate the packed tables for.
+    ///
+    /// # Returns
+    /// The model's [`ModelInspection`].
+    #[must_use]
+    pub fn inspect(&self, language: Language) -> ModelInspection {
+        ModelInspection {
+            kind: ModelKind::AdaBoost,
+            metadata: self.metadata().clone(),
+            classifiers: vec![ClassifierInspection::from_adaboost("segmentation", self)],
+            lexicon: None,
+            packed: Some(PackedFootprint {
+                language,
+                bytes: PackedModel::build(language, self).heap_bytes(),
+            }),
+        }
+    }
+}
+
+impl AveragedPerceptron {
+    /// Summarizes the model (see [`crate::inspect`]). A generic perceptron
+    /// is not compiled by any segmenter, so no packed footprint is
+    /// reported.
+    ///
+    /// # Returns
+    /// The model's [`ModelInspection`].
+    #[must_use]
+    pub fn inspect(&self) -> ModelInspection {
+        ModelInspection {
+            kind: ModelKind::AveragedPerceptron,
+            metadata: self.metadata().clone(),
+            classifiers: vec![ClassifierInspection::new(
+                "perceptron",
+                &segmentation_prefixes(),
+                true,
+                self.class_names().to_vec(),
+                self.feature_class_weights(),
+            )],
+            lexicon: None,
+            packed: None,
+        }
+    }
+}
+
+impl TwoStageLearner {
+    /// Summarizes the model (see [`crate::inspect`]).
+    ///
+    /// # Arguments
+    /// * `language` - The language to estimate the packed tables for.
+    ///
+    /// # Returns
+    /// The model's [`ModelInspection`].
+    #[must_use]
+    pub fn inspect(&self, language: Language) -> ModelInspection {
+        let stage2 = self.stage2();
+        let lexicon = self.lexicon();
+        let stage1_bytes = PackedModel::build(language, self.stage1()).heap_bytes();
+        let stage2_bytes =
+            PackedTwoStageModel::build(language, stage2, lexicon, self.dominance()).heap_bytes();
+        ModelInspection {
+            kind: ModelKind::TwoStage,
+            metadata: self.metadata().clone(),
+            classifiers: vec![
+                ClassifierInspection::from_adaboost("stage1", self.stage1()),
+                ClassifierInspection::new(
+                    "stage2",
+                    &WORD_TEMPLATE_PREFIXES,
+                    false,
+                    stage2.class_names().to_vec(),
+                    stage2.feature_class_weights(),
+                ),
+            ],
+            lexicon: Some(LexiconInspection {
+                entries: lexicon.len(),
+                dominance: self.dominance(),
+                fixed: lexicon
+                    .values()
+                    .filter(|entry| is_dominance_fixed(entry, self.dominance()))
+                    .count(),
+            }),
+            packed: Some(PackedFootprint {
+                language,
+                bytes: stage1_bytes + stage2_bytes,
+            }),
+        }
+    }
+}
+
+/// Returns the segmentation template prefixes, in template order,
+/// followed by the dictionary-match feature prefixes.
+fn segmentation_prefixes() -> Vec<&'static str> {
+    TEMPLATES
+        .iter()
+        .map(|template| template.prefix)
+        .chain(DICTIONARY_PREFIXES)
+        .collect()
+}
+
+/// Quotes and escapes `text` as a JSON string.
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inspect_adaboost() {
+        let mut learner = AdaBoost::new(0.01, 10);
+        learner
+            .load_model_from_reader(
+                "#language\tjapanese\nUW4:あ\t0.5\nUW4:い\t-0.25\nBC1:HH\t0.0\nXX:y\t1.0\n0.1\n"
+                    .as_bytes(),
+            )
+            .unwrap();
+        let report = learner.inspect(Language::Japanese);
+        assert_eq!(report.kind, ModelKind::AdaBoost);
+        assert_eq!(report.metadata.language(), Some(Language::Japanese));
+        let classifier = &report.classifiers[0];
+        assert_eq!(classifier.num_features, 4);
+        assert_eq!(classifier.templates, vec![("UW4", 2), ("BC1", 1)]);
+        assert_eq!(classifier.other_features, 1);
+        assert_eq!(classifier.pointwise, Some(true));
+        assert_eq!(classifier.weights.count, 4);
+        assert_eq!(classifier.weights.nonzero, 3);
+        assert_eq!(classifier.weights.min, -0.25);
+        assert_eq!(classifier.weights.max, 1.0);
+        assert!(report.lexicon.is_none());
+        assert!(report.packed.is_some_and(|packed| packed.bytes > 0));
+
+        learner.load_model_from_reader("UP1:B\t0.5\n0.1\n".as_bytes()).unwrap();
+        assert_eq!(learner.inspect(Language::Japanese).classifiers[0].pointwise, Some(false));
+
+        // Dictionary-match features count under their own prefixes.
+        learner.load_model_from_reader("DR:2\t0.5\n0.1\n".as_bytes()).unwrap();
+        let classifier = &learner.inspect(Language::Japanese).classifiers[0];
+        assert_eq!(classifier.templates.last(), Some(&("DR", 1)));
+        assert_eq!(classifier.other_features, 1);
+    }
+
+    #[test]
+    fn test_inspect_two_stage_lexicon() {
+        let model = "litsea-two-stage v1\n[params]\ndominance\t0.9\n[stage1]\nUW4:a\t0.5\n0.1\n\
+                     [lexicon]\nrun\tVERB:9,NOUN:1\nbank\tNOUN:5,VERB:5\nthe\tDET:3\n\
+                     [stage2]\n2\nNOUN\nVERB\nWS:run\tNOUN\t0.5\nWS:run\tVERB\t-0.5\n";
+        let mut learner = TwoStageLearner::new();
+        learner.load_model_from_reader(model.as_bytes()).unwrap();
+        let report = learner.inspect(Language::English);
+        assert_eq!(report.kind, ModelKind::TwoStage);
+        let lexicon = report.lexicon.unwrap();
+        assert_eq!((lexicon.entries, lexicon.fixed), (3, 2));
+        assert!((lexicon.fixed_ratio() - 2.0 / 3.0).abs() < 1e-12);
+        let stage2 = &report.classifiers[1];
+        assert_eq!(stage2.name, "stage2");
+        assert_eq!(stage2.templates, vec![("WS", 1)]);
+        assert_eq!(stage2.pointwise, None);
+        assert_eq!(stage2.classes, vec!["NOUN", "VERB"]);
+        assert_eq!(stage2.weights.count, 2);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut learner = AveragedPerceptron::new();
+        learner
+            .load_model_from_reader("2\nB\nO\nUW4:\"\tB\t1\nUW4:\"\tO\t-1\n".as_bytes())
+            .unwrap();
+        learner.set_metadata(ModelMetadata::new().with_param("epochs", 3));
+        let json = learner.inspect().to_json();
+        assert!(
+            json.starts_with(
+                "{\"kind\":\"perceptron\",\"metadata\":{\"params\":{\"epochs\":\"3\"}}"
+            )
+        );
+        assert!(json.contains("\"templates\":{\"UW4\":1}"), "{json}");
+        assert!(json.contains("\"classes\":[\"B\",\"O\"]"), "{json}");
+        assert!(json.contains("\"lexicon\":null,\"packed\":null}"), "{json}");
+        assert_eq!(json_string("a\"b\\\t\u{1}"), "\"a\\\"b\\\\\\t\\u0001\"");
+    }
+}
@@ -17,32 +17,57 @@
 //! - English
 
 pub mod adaboost;
+pub mod binary_model;
+pub mod checkpoint;
+pub mod cutoff;
 pub mod error;
 pub mod evaluation;
 pub mod extractor;
+mod grapheme;
+pub mod inspect;
 pub mod language;
+pub mod metadata;
 pub mod metrics;
 pub mod model_io;
+pub mod normalize;
 mod packed_model;
 mod packed_two_stage;
+mod partial;
+pub mod pattern;
 pub mod perceptron;
+pub mod progress;
 pub mod segmenter;
+pub mod select;
+pub mod sentence;
 pub mod trainer;
 pub mod two_stage;
 pub mod upos;
+pub mod user_dict;
 mod word_features;
 
-pub use adaboost::AdaBoost;
+pub use adaboost::{AdaBoost, PruneStrategy};
+pub use binary_model::ModelFormat;
+pub use checkpoint::Checkpoint;
+pub use cutoff::{FeatureCutoff, ParseTemplateCutoffError, TemplateCutoff};
 pub use error::{LitseaError, Result};
 pub use evaluation::{PosMetrics, SegmentationMetrics};
-pub use extractor::Extractor;
+pub use extractor::{CorpusFormat, Extractor};
+pub use inspect::ModelInspection;
 pub use language::{Language, ParseLanguageError};
+pub use metadata::ModelMetadata;
 pub use metrics::{BinaryMetrics, MulticlassMetrics};
+pub use normalize::{Normalization, ParseNormalizationError};
+pub use pattern::{BuiltinPattern, ParseBuiltinPatternError, PatternSet};
 pub use perceptron::AveragedPerceptron;
-pub use segmenter::{SegmentBuffer, Segmenter};
-pub use trainer::{PerceptronTrainer, Trainer, TwoStageMetrics, TwoStageTrainer};
+pub use progress::{ProgressObserver, TrainingProgress, TrainingStage};
+pub use segmenter::{SegmentBuffer, Segmenter, TokenScore};
+pub use select::{ParseUncertaintyError, SelectedSentence, SentenceSelector, Uncertainty};
+pub use trainer::{
+    BoundaryPerceptronTrainer, PerceptronTrainer, Trainer, TwoStageMetrics, TwoStageTrainer,
+};
 pub use two_stage::{ModelKind, ParseTwoStageFeatureSetError, TwoStageFeatureSet, TwoStageLearner};
 pub use upos::{ParseSegmentLabelError, ParseUposError, SegmentLabel, Upos};
+pub use user_dict::UserDictionary;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -0,0 +1,420 @@
+//! Model metadata: the provenance a model file records about itself.
+//!
+//! A model file is otherwise just weights, so nothing stops a caller from
+//! loading `korean.model` into a Japanese segmenter, which silently
+//! produces garbage. [`ModelMetadata`] records the language a model was
+//! trained for, its template set, the training parameters and a checksum
+//! of the training data. Every model format carries it optionally and
+//! backward-compatibly:
+//!
+//! - AdaBoost and Averaged Perceptron text models: `#key\tvalue` header
+//!   lines before the weights (the AdaBoost format already used this shape
+//!   for `#normalization`).
+//! - Two-stage models: extra `key\tvalue` lines in the `[params]` section.
+//! - Binary models: the same `(key, value)` pairs in the payload.
+//!
+//! | Key | Value |
+//! |-----|-------|
+//! | `language` | a [`Language`] name (`japanese`, `chinese`, `korean`, `english`) |
+//! | `templates` | the feature template set, e.g. `full` or `tag-free` for a boundary model, or a [`TwoStageFeatureSet`](crate::two_stage::TwoStageFeatureSet) name for a two-stage model |
+//! | `corpus` | a checksum of the training data (see [`checksum`]) |
+//! | `param.<name>` | a training parameter, e.g. `param.epochs` |
+//!
+//! Nothing is written for an empty metadata set, so models without it
+//! (including every bundled model) keep their original layout byte for
+//! byte. [`Segmenter::try_with_learner`](crate::segmenter::Segmenter::try_with_learner)
+//! uses the recorded language to reject a mismatched segmenter.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::str::FromStr;
+
+use crate::error::{LitseaError, Result};
+use crate::language::Language;
+
+/// Header key of the language the model was trained for.
+const LANGUAGE_KEY: &str = "language";
+/// Header key of the feature template set.
+const TEMPLATES_KEY: &str = "templates";
+/// Header key of the training-data checksum.
+const CORPUS_KEY: &str = "corpus";
+/// Prefix of the training-parameter header keys.
+const PARAM_PREFIX: &str = "param.";
+
+/// Template set name of a boundary model trained with the tag-dependent
+/// templates.
+pub const TEMPLATES_FULL: &str = "full";
+/// Template set name of a pointwise boundary model (no tag-dependent
+/// templates; see
+/// [`Extractor::extract_tag_free`](crate::extractor::Extractor::extract_tag_free)).
+pub const TEMPLATES_TAG_FREE: &str = "tag-free";
+
+/// Provenance recorded in a model file.
+///
+/// Every field is optional; an empty value (the default) writes nothing.
+/// Built with the `with_*` methods:
+///
+/// ```
+/// use litsea::language::Language;
+/// use litsea::metadata::ModelMetadata;
+///
+/// let metadata = ModelMetadata::new()
+///     .with_language(Language::Japanese)
+///     .with_param("epochs", 50);
+/// assert_eq!(metadata.language(), Some(Language::Japanese));
+/// assert_eq!(metadata.param("epochs"), Some("50"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelMetadata {
+    /// The language the model was trained for.
+    language: Option<Language>,
+    /// The feature template set the model was trained with.
+    templates: Option<String>,
+    /// Checksum of the training data.
+    corpus_checksum: Option<String>,
+    /// Training parameters by name, sorted for deterministic output.
+    params: BTreeMap<String, String>,
+}
+
+impl ModelMetadata {
+    /// Creates an empty metadata set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language the model was trained for.
+    ///
+    /// # Arguments
+    /// * `language` - The training language.
+    #[must_use]
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// Sets the feature template set the model was trained with
+    /// ([`TEMPLATES_FULL`], [`TEMPLATES_TAG_FREE`], or a two-stage feature
+    /// set name).
+    ///
+    /// # Arguments
+    /// * `templates` - The template set name.
+    #[must_use]
+    pub fn with_templates(mut self, templates: impl Into<String>) -> Self {
+        self.templates = Some(templates.into());
+        self
+    }
+
+    /// Sets the checksum of the training data (see [`checksum`]).
+    ///
+    /// # Arguments
+    /// * `checksum` - The checksum string.
+    #[must_use]
+    pub fn with_corpus_checksum(mut self, checksum: impl Into<String>) -> Self {
+        self.corpus_checksum = Some(checksum.into());
+        self
+    }
+
+    /// Records a training parameter, replacing an earlier value of the
+    /// same name.
+    ///
+    /// # Arguments
+    /// * `name` - The parameter name, e.g. `epochs`.
+    /// * `value` - The parameter value.
+    #[must_use]
+    pub fn with_param(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.params.insert(name.into(), value.to_string());
+        self
+    }
+
+    /// Returns the language the model was trained for, if recorded.
+    #[must_use]
+    pub fn language(&self) -> Option<Language> {
+        self.language
+    }
+
+    /// Returns the feature template set, if recorded.
+    #[must_use]
+    pub fn templates(&self) -> Option<&str> {
+        self.templates.as_deref()
+    }
+
+    /// Returns the training-data checksum, if recorded.
+    #[must_use]
+    pub fn corpus_checksum(&self) -> Option<&str> {
+        self.corpus_checksum.as_deref()
+    }
+
+    /// Returns a training parameter, if recorded.
+    ///
+    /// # Arguments
+    /// * `name` - The parameter name.
+    #[must_use]
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    /// Returns every recorded training parameter, sorted by name.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns whether nothing is recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks that a model with this metadata may be used for `language`.
+    ///
+    /// # Arguments
+    /// * `language` - The language the caller is about to segment.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::LanguageMismatch`] if the model records a
+    /// different training language. A model without a recorded language
+    /// is accepted.
+    pub fn check_language(&self, language: Language) -> Result<()> {
+        match self.language {
+            Some(model) if model != language => Err(LitseaError::LanguageMismatch {
+                model,
+                requested: language,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the metadata as `(key, value)` header pairs in their
+    /// canonical order: language, templates, corpus, then the parameters
+    /// by name.
+    pub(crate) fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if let Some(language) = self.language {
+            headers.push((LANGUAGE_KEY.to_string(), language.to_string()));
+        }
+        if let Some(templates) = &self.templates {
+            headers.push((TEMPLATES_KEY.to_string(), templates.clone()));
+        }
+        if let Some(checksum) = &self.corpus_checksum {
+            headers.push((CORPUS_KEY.to_string(), checksum.clone()));
+        }
+        for (name, value) in &self.params {
+            headers.push((format!("{}{}", PARAM_PREFIX, name), value.clone()));
+        }
+        headers
+    }
+
+    /// Validates that every header value can be written on one header
+    /// line.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if a template set, checksum,
+    /// parameter name or parameter value is empty or contains a tab or a
+    /// line break.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Some(name) = self.params.keys().find(|name| !is_header_text(name)) {
+            return Err(LitseaError::InvalidInput(format!(
+                "metadata parameter name cannot be written as a header line: '{}'",
+                name.escape_debug()
+            )));
+        }
+        for (key, value) in self.headers() {
+            if !is_header_text(&value) {
+                return Err(LitseaError::InvalidInput(format!(
+                    "metadata '{}' cannot be written as a header line: '{}'",
+                    key.escape_debug(),
+                    value.escape_debug()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies one header read from a model file whose only headers are
+    /// metadata (the Averaged Perceptron format).
+    ///
+    /// # Errors
+    /// Returns a message naming the header if the key is not a metadata
+    /// key or the value is invalid.
+    pub(crate) fn apply_header(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> std::result::Result<(), String> {
+        match self.set_header(key, value) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(format!("Unknown header '#{}'", key)),
+            Err(e) => Err(format!("Invalid header '#{}': {}", key, e)),
+        }
+    }
+
+    /// Applies one header read from a model file.
+    ///
+    /// # Arguments
+    /// * `key` - The header key.
+    /// * `value` - The header value.
+    ///
+    /// # Returns
+    /// `Ok(true)` if the key is a metadata key, `Ok(false)` if it is not
+    /// (the caller decides whether it knows the key).
+    ///
+    /// # Errors
+    /// Returns a message if the key is a metadata key but the value is
+    /// invalid or the key was already set.
+    pub(crate) fn set_header(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> std::result::Result<bool, String> {
+        let duplicate = || format!("duplicate '{}'", key);
+        if !is_header_text(value) {
+            if key == LANGUAGE_KEY
+                || key == TEMPLATES_KEY
+                || key == CORPUS_KEY
+                || key.starts_with(PARAM_PREFIX)
+            {
+                return Err(format!("empty value for '{}'", key));
+            }
+            return Ok(false);
+        }
+        match key {
+            LANGUAGE_KEY => {
+                let language = Language::from_str(value).map_err(|e| e.to_string())?;
+                if self.language.replace(language).is_some() {
+                    return Err(duplicate());
+                }
+            }
+            TEMPLATES_KEY => {
+                if self.templates.replace(value.to_string()).is_some() {
+                    return Err(duplicate());
+                }
+            }
+            CORPUS_KEY => {
+                if self.corpus_checksum.replace(value.to_string()).is_some() {
+                    return Err(duplicate());
+                }
+            }
+            _ => match key.strip_prefix(PARAM_PREFIX) {
+                Some(name) if !name.is_empty() => {
+                    if self.params.insert(name.to_string(), value.to_string()).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+                Some(_) => return Err("empty parameter name".to_string()),
+                None => return Ok(false),
+            },
+        }
+        Ok(true)
+    }
+}
+
+/// Returns whether `text` fits on one header line as a key or value.
+fn is_header_text(text: &str) -> bool {
+    !text.is_empty() && !text.contains(['\t', '\n', '\r'])
+}
+
+/// Computes the checksum recorded as a model's `corpus` metadata: the
+/// 64-bit FNV-1a hash of the bytes, as `fnv1a64:<16 hex digits>`.
+///
+/// FNV-1a is not a cryptographic hash; it identifies which training data
+/// produced a model, which is all a release audit needs, without adding a
+/// dependency.
+///
+/// # Arguments
+/// * `reader` - The training data (a corpus or features file).
+///
+/// # Returns
+/// The checksum string.
+///
+/// # Errors
+/// Returns an I/O error if reading fails.
+pub fn checksum<R: Read>(mut reader: R) -> Result<String> {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        for &byte in &buf[..n] {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    Ok(format!("fnv1a64:{:016x}", hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headers_round_trip() {
+        let metadata = ModelMetadata::new()
+            .with_param("threshold", 0.01)
+            .with_language(Language::Korean)
+            .with_corpus_checksum("fnv1a64:0123456789abcdef")
+            .with_templates(TEMPLATES_TAG_FREE)
+            .with_param("iterations", 100);
+        let headers = metadata.headers();
+        let keys: Vec<&str> = headers.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            ["language", "templates", "corpus", "param.iterations", "param.threshold"]
+        );
+
+        let mut parsed = ModelMetadata::new();
+        for (key, value) in &headers {
+            assert_eq!(parsed.set_header(key, value), Ok(true));
+        }
+        assert_eq!(parsed, metadata);
+        assert!(ModelMetadata::new().is_empty());
+        assert!(ModelMetadata::new().headers().is_empty());
+    }
+
+    #[test]
+    fn test_set_header_rejects_bad_values() {
+        let mut metadata = ModelMetadata::new();
+        assert_eq!(metadata.set_header("normalization", "nfkc"), Ok(false));
+        assert!(metadata.set_header("language", "klingon").is_err());
+        assert!(metadata.set_header("language", "").is_err());
+        assert!(metadata.set_header("param.", "1").is_err());
+        assert_eq!(metadata.set_header("language", "ja"), Ok(true));
+        assert!(metadata.set_header("language", "ja").is_err());
+        assert!(!metadata.is_empty());
+
+        let invalid = ModelMetadata::new().with_templates("a\tb");
+        assert!(matches!(invalid.validate(), Err(LitseaError::InvalidInput(_))));
+        let invalid = ModelMetadata::new().with_param("", 1);
+        assert!(matches!(invalid.validate(), Err(LitseaError::InvalidInput(_))));
+        assert!(ModelMetadata::new().with_param("epochs", 5).validate().is_ok());
+    }
+
+    #[test]
+    fn test_check_language() {
+        let metadata = ModelMetadata::new().with_language(Language::Korean);
+        assert!(metadata.check_language(Language::Korean).is_ok());
+        let err = metadata.check_language(Language::Japanese).unwrap_err();
+        assert!(matches!(
+            err,
+            LitseaError::LanguageMismatch {
+                model: Language::Korean,
+                requested: Language::Japanese
+            }
+        ));
+        assert!(ModelMetadata::new().check_language(Language::Japanese).is_ok());
+    }
+
+    #[test]
+    fn test_checksum() -> Result<()> {
+        // Reference values of 64-bit FNV-1a.
+        assert_eq!(checksum(&b""[..])?, "fnv1a64:cbf29ce484222325");
+        assert_eq!(checksum(&b"a"[..])?, "fnv1a64:af63dc4c8601ec8c");
+        Ok(())
+    }
+}
@@ -21,6 +21,9 @@ pub struct BinaryMetrics {
     pub false_negatives: usize,
     /// True Negatives count
     pub true_negatives: usize,
+    /// Number of features dropped by the frequency cutoff before training
+    /// (see [`crate::cutoff::FeatureCutoff`])
+    pub features_removed: usize,
 }
 
 /// Evaluation metrics for multiclass classification
@@ -46,4 +49,11 @@ pub struct MulticlassMetrics {
     pub predicted_per_class: HashMap<String, usize>,
     /// Gold (true) label count per class
     pub gold_per_class: HashMap<String, usize>,
+    /// Number of features dropped by the frequency cutoff before training
+    /// (see [`crate::cutoff::FeatureCutoff`])
+    pub features_removed: usize,
+    /// Development-set accuracy in percentage (%) after each training
+    /// epoch (see [`crate::perceptron::AveragedPerceptron::train_with_dev`]);
+    /// empty when training had no development set
+    pub dev_curve: Vec<f64>,
 }
@@ -0,0 +1,240 @@
+//! Unicode normalization applied in front of segmentation and feature
+//! extraction.
+//!
+//! Real input mixes half-width katakana (`ﾃｽﾄ`), full-width ASCII (`ＡＢＣ`)
+//! and compatibility characters (`㍿`), while a model only knows the forms
+//! it was trained on. A [`Normalization`] set on the
+//! [`Segmenter`](crate::segmenter::Segmenter) and the
+//! [`Extractor`](crate::extractor::Extractor) makes training and inference
+//! see identical text; an AdaBoost model records the normalization it was
+//! trained with (see [`AdaBoost::normalization`](crate::adaboost::AdaBoost::normalization)),
+//! and a segmenter built from it applies the same one by default.
+//!
+//! Normalization runs chunk by chunk: the text is cut before every
+//! character that cannot interact with what precedes it (a starter that
+//! never composes backward), each chunk is normalized on its own, and
+//! every output character remembers the byte offset of the chunk it came
+//! from. That offset map is what lets the segmenter report token ranges in
+//! the caller's original string; a chunk whose output has several
+//! characters (`㍿` → `株式会社`) is never split.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::iter;
+use std::str::FromStr;
+
+use unicode_normalization::char::{
+    canonical_combining_class, decompose_canonical, decompose_compatible,
+};
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick, is_nfkc_quick};
+
+/// Error returned when parsing a [`Normalization`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unsupported normalization: '{input}'. Supported: none, nfc, nfkc")]
+pub struct ParseNormalizationError {
+    input: String,
+}
+
+impl ParseNormalizationError {
+    /// Returns the string that failed to parse.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+/// Unicode normalization form applied to text before it is segmented or
+/// turned into training features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Normalization {
+    /// No normalization: the text is used as-is.
+    #[default]
+    None,
+    /// Canonical composition (NFC): only canonically equivalent forms are
+    /// unified (e.g. decomposed `か` + `゙` becomes `が`).
+    Nfc,
+    /// Compatibility composition (NFKC): additionally folds half-width
+    /// katakana, full-width ASCII, and compatibility characters.
+    Nfkc,
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Normalization::None => write!(f, "none"),
+            Normalization::Nfc => write!(f, "nfc"),
+            Normalization::Nfkc => write!(f, "nfkc"),
+        }
+    }
+}
+
+impl FromStr for Normalization {
+    type Err = ParseNormalizationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Normalization::None),
+            "nfc" => Ok(Normalization::Nfc),
+            "nfkc" => Ok(Normalization::Nfkc),
+            _ => Err(ParseNormalizationError {
+                input: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl Normalization {
+    /// Normalizes `text`.
+    ///
+    /// # Arguments
+    /// * `text` - The text to normalize.
+    ///
+    /// # Returns
+    /// The normalized text; borrowed when normalization is
+    /// [`None`](Normalization::None) or `text` is already normalized.
+    ///
+    /// # Example
+    /// ```
+    /// use litsea::normalize::Normalization;
+    ///
+    /// assert_eq!(Normalization::Nfkc.normalize("ﾃｽﾄＡ１"), "テストA1");
+    /// assert_eq!(Normalization::None.normalize("ﾃｽﾄ"), "ﾃｽﾄ");
+    /// ```
+    #[must_use]
+    pub fn normalize(self, text: &str) -> Cow<'_, str> {
+        let normalized = match self {
+            Normalization::None => true,
+            Normalization::Nfc => is_nfc_quick(text.chars()) == IsNormalized::Yes,
+            Normalization::Nfkc => is_nfkc_quick(text.chars()) == IsNormalized::Yes,
+        };
+        if normalized {
+            return Cow::Borrowed(text);
+        }
+        let mut out = String::with_capacity(text.len());
+        self.for_each_char(text, |_, c| out.push(c));
+        Cow::Owned(out)
+    }
+
+    /// Calls `f` with every character of the normalized text, in order,
+    /// together with the byte offset in `text` of the chunk it came from.
+    ///
+    /// Consecutive characters sharing an offset come from one chunk (one
+    /// original character plus any marks that combine with it), so a
+    /// caller mapping positions back to `text` must not split between
+    /// them. Without normalization every character is its own chunk.
+    pub(crate) fn for_each_char(self, text: &str, mut f: impl FnMut(usize, char)) {
+        if self == Normalization::None {
+            text.char_indices().for_each(|(i, c)| f(i, c));
+            return;
+        }
+        let mut start = 0;
+        for (i, c) in text.char_indices() {
+            if i > start && self.starts_chunk(c) {
+                self.normalize_chunk(&text[start..i], |c| f(start, c));
+                start = i;
+            }
+        }
+        if start < text.len() {
+            self.normalize_chunk(&text[start..], |c| f(start, c));
+        }
+    }
+
+    /// Normalizes one chunk, passing each output character to `f`.
+    fn normalize_chunk(self, chunk: &str, f: impl FnMut(char)) {
+        match self {
+            Normalization::None => chunk.chars().for_each(f),
+            Normalization::Nfc => chunk.nfc().for_each(f),
+            Normalization::Nfkc => chunk.nfkc().for_each(f),
+        }
+    }
+
+    /// Returns true when normalization never carries across the position
+    /// before `c`: its decomposition starts with a starter that does not
+    /// compose with a preceding character.
+    fn starts_chunk(self, c: char) -> bool {
+        let mut first = None;
+        let mut record = |d: char| {
+            first.get_or_insert(d);
+        };
+        match self {
+            Normalization::None => return true,
+            Normalization::Nfc => decompose_canonical(c, &mut record),
+            Normalization::Nfkc => decompose_compatible(c, &mut record),
+        }
+        let Some(first) = first else {
+            return true;
+        };
+        let stable = match self {
+            Normalization::Nfc => is_nfc_quick(iter::once(first)),
+            _ => is_nfkc_quick(iter::once(first)),
+        };
+        canonical_combining_class(first) == 0 && stable == IsNormalized::Yes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunks(normalization: Normalization, text: &str) -> Vec<(usize, String)> {
+        let mut out: Vec<(usize, String)> = Vec::new();
+        normalization.for_each_char(text, |offset, c| match out.last_mut() {
+            Some((last, chunk)) if *last == offset => chunk.push(c),
+            _ => out.push((offset, c.to_string())),
+        });
+        out
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for normalization in [Normalization::None, Normalization::Nfc, Normalization::Nfkc] {
+            assert_eq!(normalization.to_string().parse::<Normalization>(), Ok(normalization));
+        }
+        assert_eq!("NFKC".parse::<Normalization>(), Ok(Normalization::Nfkc));
+        let err = "nfd".parse::<Normalization>().unwrap_err();
+        assert_eq!(err.input(), "nfd");
+    }
+
+    #[test]
+    fn test_nfkc_folds_width_and_compatibility_characters() {
+        let nfkc = Normalization::Nfkc;
+        assert_eq!(nfkc.normalize("ﾃﾞｰﾀＡＢＣ１２３"), "データABC123");
+        assert_eq!(nfkc.normalize("㍿"), "株式会社");
+        assert!(matches!(nfkc.normalize("テスト"), Cow::Borrowed(_)));
+        // NFC leaves compatibility characters alone but composes marks.
+        assert_eq!(Normalization::Nfc.normalize("ﾃｽﾄ"), "ﾃｽﾄ");
+        assert_eq!(Normalization::Nfc.normalize("か\u{3099}"), "が");
+    }
+
+    #[test]
+    fn test_chunked_output_matches_whole_text_normalization() {
+        let texts = [
+            "ｶﾞｷﾞｸﾞ ﾊﾟﾋﾟﾌﾟ",
+            "か\u{3099}き\u{309a}",
+            "Ａ\u{0301}e\u{0301}\u{0323}",
+            "\u{1100}\u{1161}\u{11a8}각",
+            "㍿①ﬁ",
+            "\u{0301}leading mark",
+        ];
+        for text in texts {
+            let whole: String = text.nfkc().collect();
+            assert_eq!(Normalization::Nfkc.normalize(text), whole, "{text:?}");
+            let whole: String = text.nfc().collect();
+            assert_eq!(Normalization::Nfc.normalize(text), whole, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_chunks_map_back_to_original_offsets() {
+        // The voiced sound mark joins the preceding kana's chunk; every
+        // other half-width character starts its own.
+        assert_eq!(
+            chunks(Normalization::Nfkc, "ｶﾞｽ㍿"),
+            vec![(0, "ガ".to_string()), (6, "ス".to_string()), (9, "株式会社".to_string())]
+        );
+        assert_eq!(
+            chunks(Normalization::None, "ｶﾞ"),
+            vec![(0, "ｶ".to_string()), (3, "ﾞ".to_string())]
+        );
+    }
+}
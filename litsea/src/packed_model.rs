@@ -23,11 +23,24 @@
 //! by the exact-equality differential tests (zero divergence across all
 //! bundled models and corpora). The language-gated `WC1`..`WC4` templates
 //! sit last so that [`templates_for`] can hand out a prefix slice.
+//!
+//! The optional dictionary-match family (`DL`/`DR`/`DI`, KyTea-style) is
+//! not slot-based and lives outside the table: it reads the matches of the
+//! word list a model stores (see
+//! [`AdaBoost::set_dictionary`](crate::adaboost::AdaBoost::set_dictionary))
+//! through [`dictionary_masks_into`], is written after the table's
+//! templates, and is scored in the static pass from
+//! [`PackedModel::dictionary`].
+
+use std::io::Write;
 
 use rustc_hash::FxHashMap;
 
 use crate::adaboost::AdaBoost;
+use crate::binary_model::{Decoder, Encoder};
+use crate::error::{LitseaError, Result};
 use crate::language::Language;
+use crate::user_dict::UserDictionary;
 
 /// Boundary-tag id for "U" (unknown / padding).
 pub(crate) const TAG_U: u8 = 0;
@@ -319,6 +332,65 @@ pub(crate) const TAG_TAIL_IDS: std::ops::Range<usize> = 27..38;
 /// emitted only for languages using all templates (see [`templates_for`]).
 pub(crate) const WC_IDS: std::ops::Range<usize> = 38..42;
 
+/// Prefixes of the dictionary-match features, by kind: a dictionary word
+/// ends right before the position (`DL`), starts at it (`DR`), or spans it
+/// (`DI`). The value is the word's length in characters, e.g. `DR:2`.
+pub(crate) const DICTIONARY_PREFIXES: [&str; 3] = ["DL", "DR", "DI"];
+/// Longest word length with a feature of its own; longer words share the
+/// feature of this length.
+pub(crate) const DICTIONARY_MAX_LEN: usize = 4;
+/// Number of dictionary-match features: one per kind and length.
+pub(crate) const DICTIONARY_FEATURES: usize = DICTIONARY_PREFIXES.len() * DICTIONARY_MAX_LEN;
+
+/// Fills `masks` with the dictionary-match features of every character of
+/// `codes` (the real characters of a sentence, no sentinels): bit
+/// `kind * DICTIONARY_MAX_LEN + len - 1` of `masks[r]` is set when a word
+/// of that kind and capped length fires at the position before character
+/// `r`. Every occurrence of every word counts, so overlapping words all
+/// contribute; a feature fired by several words is set once, as in the
+/// attribute set the string writer builds.
+///
+/// # Arguments
+/// * `dictionary` - The word list to match.
+/// * `codes` - The sentence's characters as code points.
+/// * `masks` - Output buffer, cleared and refilled with one mask per
+///   character.
+pub(crate) fn dictionary_masks_into(
+    dictionary: &UserDictionary,
+    codes: &[u32],
+    masks: &mut Vec<u32>,
+) {
+    masks.clear();
+    masks.resize(codes.len(), 0);
+    for start in 0..codes.len() {
+        dictionary.for_each_prefix(&codes[start..], |len| {
+            let shift = len.min(DICTIONARY_MAX_LEN) - 1;
+            let end = start + len;
+            masks[start] |= 1 << (DICTIONARY_MAX_LEN + shift);
+            if end < codes.len() {
+                masks[end] |= 1 << shift;
+            }
+            for mask in &mut masks[start + 1..end] {
+                *mask |= 1 << (2 * DICTIONARY_MAX_LEN + shift);
+            }
+        });
+    }
+}
+
+/// Renders dictionary-match feature `feature` (a bit index of
+/// [`dictionary_masks_into`]'s masks) into `buf`.
+pub(crate) fn push_dictionary_feature(buf: &mut String, feature: usize) {
+    buf.push_str(DICTIONARY_PREFIXES[feature / DICTIONARY_MAX_LEN]);
+    buf.push(':');
+    buf.push(char::from(b'1' + (feature % DICTIONARY_MAX_LEN) as u8));
+}
+
+/// Estimates the heap memory of a hash table: one `(K, V)` slot plus one
+/// control byte per bucket.
+pub(crate) fn table_bytes<K, V>(table: &FxHashMap<K, V>) -> usize {
+    table.capacity() * (size_of::<(K, V)>() + 1)
+}
+
 /// Returns true when `feature` (a rendered attribute string such as
 /// `"UP1:U"`, or a model-file line starting with one) belongs to one of the
 /// 16 tag-dependent templates (`UP*`/`BP*`/`UQ*`/`BQ*`/`TQ*`). Used by
@@ -483,6 +555,11 @@ pub(crate) struct PackedModel {
     /// two paths are exactly equivalent — the skipped loads contribute
     /// nothing — so the gate cannot change output.
     pub(crate) has_tag_features: bool,
+    /// Dictionary-match weights, indexed like the bits of
+    /// [`dictionary_masks_into`]'s masks; all zero for a model trained
+    /// without a dictionary. Not stored in binary files: it is read from
+    /// the learner's weights (see [`dictionary_weights`](Self::dictionary_weights)).
+    pub(crate) dictionary: [f64; DICTIONARY_FEATURES],
 }
 
 impl PackedModel {
@@ -513,6 +590,7 @@ impl PackedModel {
             .map(|t| if t.is_dense() { vec![0.0; t.dense_size(type_radix)] } else { Vec::new() })
             .collect();
         let mut keys = Vec::new();
+        // Dictionary features parse as no template and are read apart.
         for (feature, weight) in learner.feature_weights() {
             keys.clear();
             parse_feature_keys(language, feature, &mut keys);
@@ -541,6 +619,46 @@ impl PackedModel {
                 }
             }
         }
+        let mut packed = Self::from_tables(type_radix, uw, bw, wc, dense);
+        packed.dictionary = Self::dictionary_weights(learner);
+        packed
+    }
+
+    /// Returns the learner's dictionary-match weights, indexed like the
+    /// bits of [`dictionary_masks_into`]'s masks.
+    ///
+    /// # Arguments
+    /// * `learner` - The learner whose weights to read.
+    ///
+    /// # Returns
+    /// The weights; zero for every feature the learner does not have.
+    pub(crate) fn dictionary_weights(learner: &AdaBoost) -> [f64; DICTIONARY_FEATURES] {
+        let mut name = String::with_capacity(4);
+        std::array::from_fn(|feature| {
+            name.clear();
+            push_dictionary_feature(&mut name, feature);
+            learner.weight(&name)
+        })
+    }
+
+    /// Returns true when any dictionary-match weight is non-zero, i.e. when
+    /// scoring must match the model's dictionary against the input.
+    pub(crate) fn has_dictionary_features(&self) -> bool {
+        self.dictionary.iter().any(|&w| w != 0.0)
+    }
+
+    /// Assembles a model from its canonical tables, deriving the scatter
+    /// views and the pointwise flag. Shared by [`build`](Self::build) and
+    /// [`read_binary`](Self::read_binary), so a model read back from a
+    /// binary file is indistinguishable from a freshly compiled one once
+    /// its [`dictionary`](Self::dictionary) weights are filled in.
+    fn from_tables(
+        type_radix: usize,
+        uw: FxHashMap<u32, [f64; 6]>,
+        bw: FxHashMap<u64, [f64; 3]>,
+        wc: FxHashMap<u32, Box<[f64]>>,
+        dense: Vec<Vec<f64>>,
+    ) -> Self {
         // Derive the scatter twins of the type-only dense tables: family
         // slot k of entry v holds dense[first_id + k][v]. The dense tables
         // stay canonical; these views trade memory (a few KB) for one direct
@@ -570,8 +688,131 @@ impl PackedModel {
             tc,
             dense,
             has_tag_features,
+            dictionary: [0.0; DICTIONARY_FEATURES],
+        }
+    }
+
+    /// Estimates the heap memory held by the compiled tables (reported by
+    /// [`crate::inspect`]).
+    ///
+    /// # Returns
+    /// The estimated size in bytes.
+    pub(crate) fn heap_bytes(&self) -> usize {
+        table_bytes(&self.uw)
+            + table_bytes(&self.bw)
+            + table_bytes(&self.wc)
+            + self.wc.values().map(|row| size_of_val::<[f64]>(row)).sum::<usize>()
+            + size_of_val::<[[f64; 6]]>(&self.uc)
+            + size_of_val::<[[f64; 3]]>(&self.bc)
+            + size_of_val::<[[f64; 4]]>(&self.tc)
+            + self.dense.capacity() * size_of::<Vec<f64>>()
+            + self
+                .dense
+                .iter()
+                .map(|table| table.capacity() * size_of::<f64>())
+                .sum::<usize>()
+    }
+
+    /// Writes the canonical tables (`uw`, `bw`, `wc`, `dense`) as records
+    /// of the binary model format, map entries sorted by key. The derived
+    /// scatter views are rebuilt on read.
+    ///
+    /// # Arguments
+    /// * `enc` - The encoder receiving the records.
+    ///
+    /// # Errors
+    /// Propagates I/O errors from the underlying writer.
+    pub(crate) fn write_binary<W: Write>(&self, enc: &mut Encoder<'_, W>) -> Result<()> {
+        let mut uw: Vec<_> = self.uw.iter().collect();
+        uw.sort_unstable_by_key(|(code, _)| **code);
+        enc.len(uw.len())?;
+        for (&code, row) in uw {
+            enc.u32(code)?;
+            row.iter().try_for_each(|&w| enc.f64(w))?;
         }
+        let mut bw: Vec<_> = self.bw.iter().collect();
+        bw.sort_unstable_by_key(|(pair, _)| **pair);
+        enc.len(bw.len())?;
+        for (&pair, row) in bw {
+            enc.u64(pair)?;
+            row.iter().try_for_each(|&w| enc.f64(w))?;
+        }
+        let mut wc: Vec<_> = self.wc.iter().collect();
+        wc.sort_unstable_by_key(|(code, _)| **code);
+        enc.len(wc.len())?;
+        for (&code, row) in wc {
+            enc.u32(code)?;
+            enc.f64s(row)?;
+        }
+        enc.len(self.dense.len())?;
+        for table in &self.dense {
+            enc.f64s(table)?;
+        }
+        Ok(())
     }
+
+    /// Reads tables written by [`write_binary`](Self::write_binary) for
+    /// `language`, checking every table size against the language's type
+    /// codes so the scorer's direct indexing stays in bounds. The
+    /// [`dictionary`](Self::dictionary) weights are left zero for the
+    /// caller to fill from the learner.
+    ///
+    /// # Arguments
+    /// * `language` - The language the tables were compiled for.
+    /// * `dec` - The decoder positioned at the tables.
+    ///
+    /// # Returns
+    /// The compiled model.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the records are truncated
+    /// or a table has the wrong size.
+    pub(crate) fn read_binary(language: Language, dec: &mut Decoder<'_>) -> Result<Self> {
+        let type_radix = language.type_codes().len();
+        let n = dec.len(4 + 6 * 8)?;
+        let mut uw = FxHashMap::with_capacity_and_hasher(n, Default::default());
+        for _ in 0..n {
+            uw.insert(dec.u32()?, dec.f64_array()?);
+        }
+        let n = dec.len(8 + 3 * 8)?;
+        let mut bw = FxHashMap::with_capacity_and_hasher(n, Default::default());
+        for _ in 0..n {
+            bw.insert(dec.u64()?, dec.f64_array()?);
+        }
+        let n = dec.len(4 + 8)?;
+        let mut wc = FxHashMap::with_capacity_and_hasher(n, Default::default());
+        for _ in 0..n {
+            let code = dec.u32()?;
+            let row = dec.f64s()?;
+            if row.len() != 4 * type_radix {
+                return Err(table_size_error("WC", row.len()));
+            }
+            wc.insert(code, row.into_boxed_slice());
+        }
+        if dec.len(8)? != TEMPLATES.len() {
+            return Err(LitseaError::InvalidData(
+                "packed model does not have one table per template".to_string(),
+            ));
+        }
+        let mut dense = Vec::with_capacity(TEMPLATES.len());
+        for template in &TEMPLATES {
+            let table = dec.f64s()?;
+            let expected = if template.is_dense() { template.dense_size(type_radix) } else { 0 };
+            if table.len() != expected {
+                return Err(table_size_error(template.prefix, table.len()));
+            }
+            dense.push(table);
+        }
+        Ok(Self::from_tables(type_radix, uw, bw, wc, dense))
+    }
+}
+
+/// The error for a packed table whose size does not match the language.
+fn table_size_error(table: &str, len: usize) -> LitseaError {
+    LitseaError::InvalidData(format!(
+        "packed {} table has {} entries, which does not match the model language",
+        table, len
+    ))
 }
 
 #[cfg(test)]
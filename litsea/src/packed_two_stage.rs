@@ -22,10 +22,16 @@
 //! candidate-masked argmax; unknown words fall back to the full argmax
 //! over all classes.
 
+use std::io::Write;
+
 use rustc_hash::FxHashMap;
 
+use crate::binary_model::{Decoder, Encoder};
+use crate::error::{LitseaError, Result};
 use crate::language::Language;
+use crate::packed_model::table_bytes;
 use crate::perceptron::AveragedPerceptron;
+use crate::two_stage::is_dominance_fixed;
 use crate::upos::Upos;
 use crate::word_features::{
     BOS_CODE, CONTEXT_WINDOW, EOS_CODE, F_CL1, F_CR1, F_FT, F_LT, N_TYPE_FAMILIES,
@@ -160,8 +166,7 @@ impl PackedTwoStageModel {
             // The entry is sorted most-frequent-first with a deterministic
             // tie-break (TwoStageLearner invariant), so entry[0] is the
             // dominant candidate.
-            let total: u32 = entry.iter().map(|(_, count)| count).sum();
-            if entry.len() == 1 || f64::from(entry[0].1) / f64::from(total) >= dominance {
+            if is_dominance_fixed(entry, dominance) {
                 slot.0 = Some(entry[0].0);
             }
             let mut candidates: Vec<u16> = entry
@@ -212,6 +217,165 @@ impl PackedTwoStageModel {
         }
     }
 
+    /// Estimates the heap memory held by the compiled tables (reported by
+    /// [`crate::inspect`]).
+    ///
+    /// # Returns
+    /// The estimated size in bytes.
+    pub(crate) fn heap_bytes(&self) -> usize {
+        let words: usize = self
+            .words
+            .iter()
+            .map(|(surface, entry)| {
+                surface.capacity()
+                    + size_of_val::<[u16]>(&entry.candidates)
+                    + size_of_val::<[(u16, f64)]>(&entry.ws_row)
+            })
+            .sum();
+        let rows: usize = self.hash.values().map(|row| size_of_val::<[(u16, f64)]>(row)).sum();
+        size_of_val::<[Upos]>(&self.classes)
+            + table_bytes(&self.words)
+            + words
+            + table_bytes(&self.hash)
+            + rows
+            + size_of_val::<[f64]>(&self.dense_wl)
+            + size_of_val::<[f64]>(&self.dense_t)
+    }
+
+    /// Writes the tables as records of the binary model format, map
+    /// entries sorted by key. Classes and fixed tags are stored as their
+    /// index in [`Upos::ALL`].
+    ///
+    /// # Arguments
+    /// * `enc` - The encoder receiving the records.
+    ///
+    /// # Errors
+    /// Propagates I/O errors from the underlying writer.
+    pub(crate) fn write_binary<W: Write>(&self, enc: &mut Encoder<'_, W>) -> Result<()> {
+        enc.len(self.classes.len())?;
+        for &class in self.classes.iter() {
+            enc.u8(class.index())?;
+        }
+        let mut words: Vec<_> = self.words.iter().collect();
+        words.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        enc.len(words.len())?;
+        for (surface, entry) in words {
+            enc.str(surface)?;
+            match entry.fixed {
+                Some(tag) => {
+                    enc.bool(true)?;
+                    enc.u8(tag.index())?;
+                }
+                None => enc.bool(false)?,
+            }
+            enc.len(entry.candidates.len())?;
+            entry.candidates.iter().try_for_each(|&c| enc.u32(u32::from(c)))?;
+            write_row(enc, &entry.ws_row)?;
+        }
+        let mut hash: Vec<_> = self.hash.iter().collect();
+        hash.sort_unstable_by_key(|(key, _)| **key);
+        enc.len(hash.len())?;
+        for (&key, row) in hash {
+            enc.u64(key)?;
+            write_row(enc, row)?;
+        }
+        self.has.iter().try_for_each(|&b| enc.bool(b))?;
+        enc.f64s(&self.dense_wl)?;
+        enc.bool(self.wl_used)?;
+        enc.f64s(&self.dense_t)?;
+        self.t_used.iter().try_for_each(|&b| enc.bool(b))?;
+        Ok(())
+    }
+
+    /// Reads tables written by [`write_binary`](Self::write_binary) for
+    /// `language`, checking every class index and dense-table size so the
+    /// tagging loop's indexing stays in bounds.
+    ///
+    /// # Arguments
+    /// * `language` - The language the tables were compiled for.
+    /// * `dec` - The decoder positioned at the tables.
+    ///
+    /// # Returns
+    /// The compiled model.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the records are truncated,
+    /// a tag or class index is out of range, or a dense table has the
+    /// wrong size.
+    pub(crate) fn read_binary(language: Language, dec: &mut Decoder<'_>) -> Result<Self> {
+        let n = dec.len(1)?;
+        let classes: Box<[Upos]> = (0..n).map(|_| read_upos(dec)).collect::<Result<_>>()?;
+        let type_stride = language.type_codes().len() + 2;
+        let class_index = |dec: &mut Decoder<'_>| -> Result<u16> {
+            let c = dec.u32()?;
+            if c as usize >= n {
+                return Err(LitseaError::InvalidData(format!(
+                    "packed POS class index {} out of range",
+                    c
+                )));
+            }
+            Ok(c as u16)
+        };
+        let read_row = |dec: &mut Decoder<'_>| -> Result<Box<[(u16, f64)]>> {
+            let len = dec.len(12)?;
+            (0..len).map(|_| Ok((class_index(dec)?, dec.f64()?))).collect()
+        };
+
+        let len = dec.len(4 + 1 + 8 + 8)?;
+        let mut words = FxHashMap::with_capacity_and_hasher(len, Default::default());
+        for _ in 0..len {
+            let surface = dec.str()?.to_string();
+            let fixed = if dec.bool()? { Some(read_upos(dec)?) } else { None };
+            let count = dec.len(4)?;
+            let candidates = (0..count).map(|_| class_index(dec)).collect::<Result<_>>()?;
+            let ws_row = read_row(dec)?;
+            words.insert(
+                surface,
+                WordEntry {
+                    fixed,
+                    candidates,
+                    ws_row,
+                },
+            );
+        }
+        let len = dec.len(8 + 8)?;
+        let mut hash = FxHashMap::with_capacity_and_hasher(len, Default::default());
+        for _ in 0..len {
+            let key = dec.u64()?;
+            hash.insert(key, read_row(dec)?);
+        }
+        let mut has = [false; N_WORD_TEMPLATES];
+        for b in &mut has {
+            *b = dec.bool()?;
+        }
+        let dense_wl = dec.f64s()?.into_boxed_slice();
+        let wl_used = dec.bool()?;
+        let dense_t = dec.f64s()?.into_boxed_slice();
+        let mut t_used = [false; N_TYPE_FAMILIES];
+        for b in &mut t_used {
+            *b = dec.bool()?;
+        }
+        if dense_wl.len() != (WL_CAP + 1) * n || dense_t.len() != N_TYPE_FAMILIES * type_stride * n
+        {
+            return Err(LitseaError::InvalidData(
+                "packed POS dense tables do not match the model language".to_string(),
+            ));
+        }
+
+        Ok(PackedTwoStageModel {
+            classes,
+            n_classes: n,
+            type_stride,
+            words,
+            hash,
+            has,
+            dense_wl,
+            wl_used,
+            dense_t,
+            t_used,
+        })
+    }
+
     /// Adds a sparse hash row to the score vector.
     #[inline]
     fn add_hash(&self, scores: &mut [f64], key: u64) {
@@ -378,6 +542,24 @@ impl PackedTwoStageModel {
     }
 }
 
+/// Reads one tag stored by its [`Upos::ALL`] index.
+pub(crate) fn read_upos(dec: &mut Decoder<'_>) -> Result<Upos> {
+    let index = dec.u8()?;
+    Upos::from_index(index).ok_or_else(|| {
+        LitseaError::InvalidData(format!("invalid UPOS index {} in binary model", index))
+    })
+}
+
+/// Writes a sparse `(class, weight)` row.
+fn write_row<W: Write>(enc: &mut Encoder<'_, W>, row: &[(u16, f64)]) -> Result<()> {
+    enc.len(row.len())?;
+    for &(c, w) in row {
+        enc.u32(u32::from(c))?;
+        enc.f64(w)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
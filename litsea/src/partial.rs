@@ -0,0 +1,286 @@
+//! Partially annotated corpora (KyTea-style), for training on the annotated
+//! positions of a sentence only.
+//!
+//! A partially annotated line spells the sentence out character by
+//! character, with one delimiter between every two characters:
+//!
+//! | Delimiter | Meaning |
+//! |-----------|---------|
+//! | `\|` | a word starts at the next character |
+//! | `-` | the next character continues the word |
+//! | ` ` (space) | unannotated |
+//!
+//! so `コ-ー-ス|が|設 定` annotates every position but the one inside `設定`.
+//! A backslash makes the next character part of the text (`\|`, `\-`,
+//! `\ `, `\\`, and in tagged lines `\/`). In a tagged line (the two-stage
+//! pipeline), `/TAG` after a word's last character tags the word; the word
+//! must be annotated in full: its start known (the line start or a `|`),
+//! every position inside it `-`, and the tag followed by `|` or the end of
+//! the line, as in `こ-れ/PRON|は/ADP|テ ス ト`.
+
+use crate::error::{LitseaError, Result};
+use crate::normalize::Normalization;
+use crate::upos::Upos;
+
+/// Delimiter: a word starts at the next character.
+const BOUNDARY: char = '|';
+/// Delimiter: the next character continues the word.
+const NO_BOUNDARY: char = '-';
+/// Delimiter: the position is unannotated.
+const UNKNOWN: char = ' ';
+/// Makes the next character part of the text.
+const ESCAPE: char = '\\';
+/// Starts a word's tag in a tagged line.
+const TAG: char = '/';
+
+/// A parsed partially annotated sentence.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PartialSentence {
+    /// The sentence text, normalized.
+    pub(crate) text: String,
+    /// Per character of `text`: whether a word starts there, or `None` if
+    /// the position is unannotated. The first character always starts one.
+    pub(crate) starts: Vec<Option<bool>>,
+    /// The tagged words, as `start..end` character ranges of `text` with
+    /// their tag.
+    pub(crate) words: Vec<(usize, usize, Upos)>,
+}
+
+/// Parses one partially annotated line.
+///
+/// The text is normalized with `normalization` the way the segmenter
+/// normalizes its input: a character that expands keeps its annotation on
+/// the first character it expands to, and the rest continue its word.
+///
+/// # Arguments
+/// * `line` - The trimmed line.
+/// * `tagged` - Whether `/TAG` suffixes are read (the two-stage pipeline);
+///   otherwise `/` is ordinary text.
+/// * `normalization` - The normalization applied to the text.
+///
+/// # Returns
+/// The parsed sentence; empty for an empty line.
+///
+/// # Errors
+/// Returns [`LitseaError::InvalidData`] if the line starts or ends with a
+/// delimiter, has two characters or two delimiters in a row, ends with an
+/// escape, or tags a word that is not annotated in full.
+pub(crate) fn parse_partial_line(
+    line: &str,
+    tagged: bool,
+    normalization: Normalization,
+) -> Result<PartialSentence> {
+    let invalid = |message: &str| {
+        LitseaError::InvalidData(format!("{} in partial annotation '{}'", message, line))
+    };
+
+    // The raw text, with each character's byte offset and annotation, and
+    // the tagged words as byte ranges of it.
+    let mut raw = String::new();
+    let mut raw_starts: Vec<(usize, Option<bool>)> = Vec::new();
+    let mut raw_words: Vec<(usize, usize, Upos)> = Vec::new();
+    // The annotation of the next character, while one is expected.
+    let mut next: Option<Option<bool>> = Some(Some(true));
+    // Where the current word starts, while every position since is known.
+    let mut word_start = Some(0);
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some(start) = next {
+            let ch = match c {
+                ESCAPE => chars.next().ok_or_else(|| invalid("trailing escape"))?,
+                BOUNDARY | NO_BOUNDARY | UNKNOWN if raw.is_empty() => {
+                    return Err(invalid("delimiter before the first character"));
+                }
+                BOUNDARY | NO_BOUNDARY | UNKNOWN => return Err(invalid("two delimiters in a row")),
+                TAG if tagged => return Err(invalid("tag without a word")),
+                c => c,
+            };
+            raw_starts.push((raw.len(), start));
+            raw.push(ch);
+            next = None;
+            continue;
+        }
+        match c {
+            BOUNDARY => {
+                next = Some(Some(true));
+                word_start = Some(raw.len());
+            }
+            NO_BOUNDARY => next = Some(Some(false)),
+            UNKNOWN => {
+                next = Some(None);
+                word_start = None;
+            }
+            TAG if tagged => {
+                let mut tag = String::new();
+                while let Some(&c) = chars.peek() {
+                    if matches!(c, BOUNDARY | NO_BOUNDARY | UNKNOWN) {
+                        break;
+                    }
+                    tag.push(c);
+                    chars.next();
+                }
+                if chars.peek().is_some_and(|&c| c != BOUNDARY) {
+                    return Err(invalid("a tag must end a word"));
+                }
+                let start =
+                    word_start.ok_or_else(|| invalid("tagged word is not fully annotated"))?;
+                raw_words.push((start, raw.len(), tag.parse().unwrap_or(Upos::X)));
+                // Only a `|` or the end of the line can follow.
+                word_start = None;
+            }
+            _ => return Err(invalid("missing delimiter between two characters")),
+        }
+    }
+    if next.is_some() && !raw.is_empty() {
+        return Err(invalid("delimiter after the last character"));
+    }
+
+    // Every normalized character comes with the offset of the original
+    // character it came from; the later characters of one original
+    // continue its word.
+    let mut sentence = PartialSentence::default();
+    let mut offsets: Vec<usize> = Vec::new();
+    normalization.for_each_char(&raw, |offset, ch| {
+        let start = if offsets.last() == Some(&offset) {
+            Some(false)
+        } else {
+            raw_starts
+                .binary_search_by_key(&offset, |&(o, _)| o)
+                .map_or(Some(false), |i| raw_starts[i].1)
+        };
+        sentence.text.push(ch);
+        sentence.starts.push(start);
+        offsets.push(offset);
+    });
+    if let Some(first) = sentence.starts.first_mut() {
+        *first = Some(true);
+    }
+    for (start, end, tag) in raw_words {
+        let start = offsets.partition_point(|&o| o < start);
+        let end = offsets.partition_point(|&o| o < end);
+        if start < end {
+            sentence.words.push((start, end, tag));
+        }
+    }
+    Ok(sentence)
+}
+
+/// Appends one text character to an untagged partially annotated line,
+/// escaped if it would read as a delimiter or an escape.
+///
+/// # Arguments
+/// * `line` - The line being written.
+/// * `ch` - The character.
+pub(crate) fn push_char(line: &mut String, ch: char) {
+    if matches!(ch, BOUNDARY | NO_BOUNDARY | UNKNOWN | ESCAPE) {
+        line.push(ESCAPE);
+    }
+    line.push(ch);
+}
+
+/// Appends the delimiter annotating the next character.
+///
+/// # Arguments
+/// * `line` - The line being written.
+/// * `start` - Whether a word starts at the next character, or `None` if
+///   the position is unannotated.
+pub(crate) fn push_delimiter(line: &mut String, start: Option<bool>) {
+    line.push(match start {
+        Some(true) => BOUNDARY,
+        Some(false) => NO_BOUNDARY,
+        None => UNKNOWN,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str, tagged: bool) -> Result<PartialSentence> {
+        parse_partial_line(line, tagged, Normalization::None)
+    }
+
+    #[test]
+    fn test_parse_annotations() -> Result<()> {
+        let sentence = parse("コ-ー-ス|が|設 定", false)?;
+        assert_eq!(sentence.text, "コースが設定");
+        assert_eq!(
+            sentence.starts,
+            vec![Some(true), Some(false), Some(false), Some(true), Some(true), None]
+        );
+        assert!(sentence.words.is_empty());
+        assert_eq!(parse("", false)?, PartialSentence::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_escapes() -> Result<()> {
+        let sentence = parse(r"a|\|-\ |\\", false)?;
+        assert_eq!(sentence.text, r"a| \");
+        assert_eq!(sentence.starts, vec![Some(true), Some(true), Some(false), Some(true)]);
+        // `/` is text either way, but needs its delimiters like any other
+        // character; in a tagged line it has to be escaped.
+        assert!(parse("b/c", false).is_err());
+        assert_eq!(parse("b-/-c", false)?.text, "b/c");
+        assert_eq!(parse(r"b-\/-c", true)?.text, "b/c");
+        Ok(())
+    }
+
+    #[test]
+    fn test_written_lines_parse_back() -> Result<()> {
+        let text = r"a| -\b";
+        let starts = [Some(true), None, Some(false), Some(true), None, Some(false)];
+        let mut line = String::new();
+        for (i, (ch, start)) in text.chars().zip(starts).enumerate() {
+            if i > 0 {
+                push_delimiter(&mut line, start);
+            }
+            push_char(&mut line, ch);
+        }
+        let sentence = parse(&line, false)?;
+        assert_eq!(sentence.text, text);
+        assert_eq!(sentence.starts, starts);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_tags() -> Result<()> {
+        let sentence = parse("こ-れ/PRON|は/ADP|テ ス ト", true)?;
+        assert_eq!(sentence.text, "これはテスト");
+        assert_eq!(sentence.words, vec![(0, 2, Upos::PRON), (2, 3, Upos::ADP)]);
+        // An unknown tag falls back to X, as in the POS corpus format.
+        assert_eq!(parse("a/FOO", true)?.words, vec![(0, 1, Upos::X)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        for line in [
+            "|a",
+            "a|",
+            "a||b",
+            "ab",
+            r"a|b\",
+            "a b/NOUN",
+            "a/NOUN-b",
+            "/NOUN|a",
+            "a|b/NOUN c",
+        ] {
+            assert!(
+                matches!(parse(line, true), Err(LitseaError::InvalidData(_))),
+                "accepted {:?}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_normalizes_per_character() -> Result<()> {
+        // NFKC expands ㍻ to 平成: the second character continues the word.
+        let sentence = parse_partial_line("㍻/NOUN|年", true, Normalization::Nfkc)?;
+        assert_eq!(sentence.text, "平成年");
+        assert_eq!(sentence.starts, vec![Some(true), Some(false), Some(true)]);
+        assert_eq!(sentence.words, vec![(0, 2, Upos::NOUN)]);
+        Ok(())
+    }
+}
@@ -0,0 +1,388 @@
+//! Atomic pattern tokens for segmentation.
+//!
+//! Defines [`PatternSet`], a set of regular expressions whose matches are
+//! kept whole by the segmenter (see
+//! [`Segmenter::with_patterns`](crate::segmenter::Segmenter::with_patterns)):
+//! every match comes out as a single token, with a boundary forced at both
+//! ends and every boundary inside it forbidden, while the packed model
+//! still segments the text around it. Boundary models trained on newspaper
+//! text have rarely seen URLs, e-mail addresses, or version numbers and
+//! tend to shred them; a pattern pass fixes that without retraining.
+//!
+//! A set combines [`BuiltinPattern`]s with user-supplied regular
+//! expressions. Matching is leftmost-longest across all of them: scanning
+//! left to right, the earliest match wins, the longest one among matches
+//! starting at the same position, and scanning resumes after it. Matches
+//! therefore never overlap.
+
+use std::fmt;
+use std::io::BufRead;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::error::{LitseaError, Result};
+
+/// Error returned when parsing a [`BuiltinPattern`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unsupported pattern: '{input}'. Supported: url, email, number, hashtag, mention")]
+pub struct ParseBuiltinPatternError {
+    input: String,
+}
+
+impl ParseBuiltinPatternError {
+    /// Returns the string that failed to parse.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+/// A pattern shipped with litsea.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinPattern {
+    /// `http://`, `https://`, and `www.` URLs made of ASCII URL characters;
+    /// trailing sentence punctuation (`.`, `,`, `!`, `?`, ...) is left out.
+    Url,
+    /// E-mail addresses such as `user.name+tag@example.co.jp`.
+    Email,
+    /// Numbers with thousands separators or decimals (`1,234,567`, `3.14`)
+    /// and dotted version numbers (`1.2.3`). Plain digit runs are left to
+    /// the model.
+    Number,
+    /// Hashtags: `#` or `＃` followed by letters, digits, or `_`.
+    Hashtag,
+    /// Mentions: `@` followed by ASCII letters, digits, or `_`.
+    Mention,
+}
+
+impl BuiltinPattern {
+    /// Every built-in pattern, in declaration order.
+    pub const ALL: [BuiltinPattern; 5] = [
+        BuiltinPattern::Url,
+        BuiltinPattern::Email,
+        BuiltinPattern::Number,
+        BuiltinPattern::Hashtag,
+        BuiltinPattern::Mention,
+    ];
+
+    /// Returns the regular expression behind the pattern.
+    #[must_use]
+    pub fn regex(self) -> &'static str {
+        match self {
+            BuiltinPattern::Url => {
+                r"(?:https?://|www\.)[A-Za-z0-9\-._~:/?#\[\]@!$&'*+,;=%]*[A-Za-z0-9\-_~/#\[\]@$&*+=%]"
+            }
+            BuiltinPattern::Email => {
+                r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9\-]+(?:\.[A-Za-z0-9\-]+)*\.[A-Za-z]{2,}"
+            }
+            BuiltinPattern::Number => r"[0-9]+(?:[.,][0-9]+)+",
+            BuiltinPattern::Hashtag => r"[#＃][\p{L}\p{N}_]+",
+            BuiltinPattern::Mention => r"@[A-Za-z0-9_]+",
+        }
+    }
+}
+
+impl fmt::Display for BuiltinPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuiltinPattern::Url => write!(f, "url"),
+            BuiltinPattern::Email => write!(f, "email"),
+            BuiltinPattern::Number => write!(f, "number"),
+            BuiltinPattern::Hashtag => write!(f, "hashtag"),
+            BuiltinPattern::Mention => write!(f, "mention"),
+        }
+    }
+}
+
+impl FromStr for BuiltinPattern {
+    type Err = ParseBuiltinPatternError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "url" => Ok(BuiltinPattern::Url),
+            "email" => Ok(BuiltinPattern::Email),
+            "number" => Ok(BuiltinPattern::Number),
+            "hashtag" => Ok(BuiltinPattern::Hashtag),
+            "mention" => Ok(BuiltinPattern::Mention),
+            _ => Err(ParseBuiltinPatternError {
+                input: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// A set of patterns whose matches segmentation must keep whole.
+#[derive(Debug, Clone, Default)]
+pub struct PatternSet {
+    /// The compiled patterns, built-ins and user regexes alike.
+    regexes: Vec<Regex>,
+}
+
+impl PatternSet {
+    /// Creates an empty pattern set.
+    ///
+    /// # Returns
+    /// A [`PatternSet`] with no patterns; it matches nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        PatternSet {
+            regexes: Vec::new(),
+        }
+    }
+
+    /// Creates a pattern set holding every [`BuiltinPattern`].
+    ///
+    /// # Returns
+    /// A [`PatternSet`] matching URLs, e-mail addresses, numbers,
+    /// hashtags, and mentions.
+    ///
+    /// # Example
+    /// ```
+    /// use litsea::pattern::PatternSet;
+    ///
+    /// let patterns = PatternSet::builtin();
+    /// let text = "詳細はhttps://example.com/docsまで";
+    /// assert_eq!(patterns.find_matches(text), vec![(9, 33)]);
+    /// ```
+    #[must_use]
+    pub fn builtin() -> Self {
+        let mut patterns = Self::new();
+        for builtin in BuiltinPattern::ALL {
+            patterns.insert_builtin(builtin);
+        }
+        patterns
+    }
+
+    /// Adds a built-in pattern.
+    ///
+    /// # Arguments
+    /// * `builtin` - The pattern to add.
+    pub fn insert_builtin(&mut self, builtin: BuiltinPattern) {
+        let regex = Regex::new(builtin.regex()).expect("built-in patterns are valid regexes");
+        self.regexes.push(regex);
+    }
+
+    /// Adds a user-supplied regular expression (in the syntax of the
+    /// [`regex`] crate). Matches of the empty string are ignored.
+    ///
+    /// # Arguments
+    /// * `pattern` - The regular expression to add.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if `pattern` does not compile.
+    pub fn insert_regex(&mut self, pattern: &str) -> Result<()> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| LitseaError::InvalidInput(format!("invalid pattern {pattern:?}: {e}")))?;
+        self.regexes.push(regex);
+        Ok(())
+    }
+
+    /// Returns the number of patterns in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.regexes.len()
+    }
+
+    /// Returns true when the set holds no patterns.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.regexes.is_empty()
+    }
+
+    /// Reads a pattern set from a reader: one regular expression per line
+    /// (see [`insert_regex`](Self::insert_regex)).
+    ///
+    /// Blank lines and lines starting with `#` are skipped. A line is used
+    /// as-is otherwise, since leading or trailing spaces may be part of
+    /// the expression.
+    ///
+    /// # Arguments
+    /// * `reader` - The buffered reader supplying the patterns.
+    ///
+    /// # Returns
+    /// The loaded [`PatternSet`].
+    ///
+    /// # Errors
+    /// Returns an I/O error if reading fails (including invalid UTF-8), or
+    /// [`LitseaError::InvalidInput`] if a line does not compile.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut patterns = Self::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            patterns.insert_regex(&line)?;
+        }
+        Ok(patterns)
+    }
+
+    /// Reads a pattern file: one regular expression per line (see
+    /// [`from_reader`](Self::from_reader)).
+    ///
+    /// # Arguments
+    /// * `path` - The path to the pattern file.
+    ///
+    /// # Returns
+    /// The loaded [`PatternSet`].
+    ///
+    /// # Errors
+    /// Returns an I/O error if the file cannot be opened or read, or
+    /// [`LitseaError::InvalidInput`] if a line does not compile.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Calls `sink(start, end)` for every leftmost-longest match in
+    /// `text`, as half-open byte ranges, in order.
+    ///
+    /// Each pattern's next match is cached and only searched again once
+    /// the scan has moved past its start, so every pattern scans the text
+    /// about once.
+    ///
+    /// # Arguments
+    /// * `text` - The text to scan.
+    /// * `sink` - Receives each matched `(start, end)` range.
+    pub(crate) fn for_each_match(&self, text: &str, mut sink: impl FnMut(usize, usize)) {
+        if self.is_empty() {
+            return;
+        }
+        let mut next: Vec<Option<(usize, usize)>> =
+            self.regexes.iter().map(|regex| find_non_empty(regex, text, 0)).collect();
+        let mut pos = 0;
+        loop {
+            let mut best: Option<(usize, usize)> = None;
+            for (regex, candidate) in self.regexes.iter().zip(next.iter_mut()) {
+                if candidate.is_some_and(|(start, _)| start < pos) {
+                    *candidate = find_non_empty(regex, text, pos);
+                }
+                if let Some((start, end)) = *candidate
+                    && best.is_none_or(|(s, e)| start < s || (start == s && end > e))
+                {
+                    best = Some((start, end));
+                }
+            }
+            let Some((start, end)) = best else {
+                break;
+            };
+            sink(start, end);
+            pos = end;
+        }
+    }
+
+    /// Returns the leftmost-longest matches in `text` as byte ranges.
+    ///
+    /// # Arguments
+    /// * `text` - The text to scan.
+    ///
+    /// # Returns
+    /// The non-overlapping matched `(start, end)` byte ranges, in order.
+    #[must_use]
+    pub fn find_matches(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+        self.for_each_match(text, |start, end| matches.push((start, end)));
+        matches
+    }
+}
+
+impl FromIterator<BuiltinPattern> for PatternSet {
+    fn from_iter<I: IntoIterator<Item = BuiltinPattern>>(iter: I) -> Self {
+        let mut patterns = Self::new();
+        for builtin in iter {
+            patterns.insert_builtin(builtin);
+        }
+        patterns
+    }
+}
+
+/// Returns the first non-empty match of `regex` in `text` starting at or
+/// after byte `from`.
+fn find_non_empty(regex: &Regex, text: &str, from: usize) -> Option<(usize, usize)> {
+    let mut from = from;
+    while from <= text.len() {
+        let found = regex.find_at(text, from)?;
+        if !found.is_empty() {
+            return Some((found.start(), found.end()));
+        }
+        // Step past the empty match to the next character boundary.
+        from = found.end() + text[found.end()..].chars().next()?.len_utf8();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched<'a>(patterns: &PatternSet, text: &'a str) -> Vec<&'a str> {
+        patterns.find_matches(text).into_iter().map(|(s, e)| &text[s..e]).collect()
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for builtin in BuiltinPattern::ALL {
+            assert_eq!(builtin.to_string().parse::<BuiltinPattern>(), Ok(builtin));
+        }
+        let err = "phone".parse::<BuiltinPattern>().unwrap_err();
+        assert_eq!(err.input(), "phone");
+    }
+
+    #[test]
+    fn test_builtin_patterns() {
+        let patterns = PatternSet::builtin();
+        assert_eq!(
+            matched(&patterns, "See https://example.com/a?b=1, or www.example.org."),
+            vec!["https://example.com/a?b=1", "www.example.org"]
+        );
+        assert_eq!(
+            matched(&patterns, "連絡先はuser.name+tag@example.co.jpです"),
+            vec!["user.name+tag@example.co.jp"]
+        );
+        assert_eq!(
+            matched(&patterns, "価格は1,234,567円、v1.2.3で3.14倍、2024年"),
+            vec!["1,234,567", "1.2.3", "3.14"]
+        );
+        assert_eq!(
+            matched(&patterns, "#今日の一枚 を @litsea_dev に"),
+            vec!["#今日の一枚", "@litsea_dev"]
+        );
+    }
+
+    #[test]
+    fn test_longest_match_wins_at_the_same_start() {
+        // The e-mail address starts before its "@example" mention, and the
+        // URL is longer than the user pattern matching its scheme.
+        let mut patterns = PatternSet::builtin();
+        patterns.insert_regex("https").unwrap();
+        assert_eq!(
+            matched(&patterns, "a@example.com https://x.jp"),
+            vec!["a@example.com", "https://x.jp"]
+        );
+    }
+
+    #[test]
+    fn test_user_regexes() {
+        let mut patterns = PatternSet::new();
+        assert!(patterns.is_empty());
+        patterns.insert_regex(r"[A-Z]{2,}-[0-9]+").unwrap();
+        patterns.insert_regex(r"x*").unwrap();
+        assert_eq!(patterns.len(), 2);
+        // "x*" matches the empty string everywhere but only "xx" is kept.
+        assert_eq!(matched(&patterns, "チケットJIRA-123をxx"), vec!["JIRA-123", "xx"]);
+        let err = patterns.insert_regex("(").unwrap_err();
+        assert!(matches!(err, LitseaError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_from_reader_skips_blank_and_comment_lines() {
+        let input = "# ticket ids\n[A-Z]+-[0-9]+\n\n";
+        let patterns = PatternSet::from_reader(input.as_bytes()).unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert!(PatternSet::from_reader("(\n".as_bytes()).is_err());
+    }
+}
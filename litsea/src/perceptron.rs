@@ -9,8 +9,8 @@
 //! [`AdaBoost`](crate::adaboost::AdaBoost) before inference — see
 //! `crate::trainer`'s module docs) and as the stage-2 word tagger (whose
 //! weights back `crate::packed_two_stage::PackedTwoStageModel`). The same
-//! collapse (via `PerceptronTrainer` + `scripts/collapse_binary_perceptron.py`)
-//! produces the bundled AdaBoost-format segmentation models (issue #165).
+//! collapse (via `BoundaryPerceptronTrainer`) produces the bundled
+//! AdaBoost-format segmentation models (issue #165).
 
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
@@ -28,8 +28,13 @@ use std::sync::atomic::{AtomicBool, Ordering};
 // default SipHash.
 use rustc_hash::FxHashMap;
 
+use crate::binary_model::{self, Decoder, Encoder};
+use crate::cutoff::FeatureCutoff;
 use crate::error::{LitseaError, Result};
+use crate::metadata::ModelMetadata;
 use crate::metrics::MulticlassMetrics;
+use crate::progress::Progress;
+use crate::two_stage::ModelKind;
 
 /// Per-feature training state, one entry per class: live weights (`w`),
 /// averaging accumulators (`acc`), and the step at which each weight was
@@ -40,7 +45,7 @@ use crate::metrics::MulticlassMetrics;
 /// stay empty until training first touches the feature — model loading for
 /// inference-only use must not pay for averaging state it never needs
 /// (materializing them eagerly doubled the loaded-model memory footprint).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FeatureSlot {
     w: Vec<f64>,
     acc: Vec<f64>,
@@ -66,6 +71,655 @@ impl FeatureSlot {
             self.ts.resize(self.w.len(), 0);
         }
     }
+
+    /// Updates the weight of one class.
+    /// Catches the class's accumulated weight up to `step` before adding
+    /// `delta`.
+    fn update(&mut self, class_idx: usize, delta: f64, step: usize) {
+        self.ensure_averaging();
+
+        let elapsed = step - self.ts[class_idx];
+        if elapsed > 0 {
+            self.acc[class_idx] += self.w[class_idx] * elapsed as f64;
+        }
+        self.ts[class_idx] = step;
+        self.w[class_idx] += delta;
+    }
+
+    /// Returns the weight of one class as averaging at `step` would make
+    /// it, without catching the slot up.
+    ///
+    /// Follows [`AveragedPerceptron::average_weights`] operation for
+    /// operation, so a dev-set score computed from it is the score of the
+    /// model training would write at that step.
+    fn averaged(&self, class_idx: usize, step: usize) -> f64 {
+        let (mut acc, ts) = if self.acc.is_empty() {
+            (0.0, 0)
+        } else {
+            (self.acc[class_idx], self.ts[class_idx])
+        };
+        let elapsed = step - ts;
+        if elapsed > 0 {
+            acc += self.w[class_idx] * elapsed as f64;
+        }
+        acc / step.max(1) as f64
+    }
+}
+
+/// Training instances, with their features and labels interned.
+///
+/// A corpus repeats each feature across many instances (a large one across
+/// millions), so each feature string is stored once and an instance holds
+/// `u32` ids into it. Training then scores and updates weight slots indexed
+/// by id instead of hashing feature strings.
+#[derive(Debug, Default)]
+struct TrainingSet {
+    /// Per instance: the feature ids (in feature-name order) and the label
+    /// id.
+    instances: Vec<(Vec<u32>, u32)>,
+    /// Feature names, indexed by id.
+    feature_names: Vec<String>,
+    /// Feature name -> id.
+    feature_ids: FxHashMap<String, u32>,
+    /// Gold labels, indexed by id. Kept apart from the class list, whose
+    /// indices shift whenever a class is registered.
+    labels: Vec<String>,
+    /// Per instance, the size of its update step; empty while every
+    /// instance takes the unit step.
+    weights: Vec<f64>,
+}
+
+impl TrainingSet {
+    /// Adds an instance whose features are already sorted by name, with
+    /// the size of its update step.
+    fn push(&mut self, features: Vec<String>, label: String, weight: f64) {
+        let ids = features
+            .into_iter()
+            .map(|feat| match self.feature_ids.get(&feat) {
+                Some(&id) => id,
+                None => {
+                    let id = self.feature_names.len() as u32;
+                    self.feature_ids.insert(feat.clone(), id);
+                    self.feature_names.push(feat);
+                    id
+                }
+            })
+            .collect();
+        let label_id = match self.labels.iter().position(|l| *l == label) {
+            Some(i) => i,
+            None => {
+                self.labels.push(label);
+                self.labels.len() - 1
+            }
+        };
+        if weight != 1.0 && self.weights.is_empty() {
+            self.weights.resize(self.instances.len(), 1.0);
+        }
+        self.instances.push((ids, label_id as u32));
+        if !self.weights.is_empty() {
+            self.weights.push(weight);
+        }
+    }
+
+    /// Returns the size of an instance's update step.
+    fn weight(&self, i: usize) -> f64 {
+        self.weights.get(i).copied().unwrap_or(1.0)
+    }
+
+    /// Returns the number of instances.
+    fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Returns whether there are no instances.
+    fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    /// Drops the features `keep` rejects from every instance.
+    ///
+    /// # Arguments
+    /// * `keep` - Decides from a feature's name and instance count whether
+    ///   it stays.
+    ///
+    /// # Returns
+    /// The number of distinct features dropped.
+    fn retain_features<F>(&mut self, keep: F) -> usize
+    where
+        F: Fn(&str, usize) -> bool,
+    {
+        let mut counts = vec![0usize; self.feature_names.len()];
+        for (ids, _) in &self.instances {
+            for &id in ids {
+                counts[id as usize] += 1;
+            }
+        }
+        let kept: Vec<bool> = self
+            .feature_names
+            .iter()
+            .zip(&counts)
+            .map(|(name, &count)| keep(name, count))
+            .collect();
+        for (ids, _) in &mut self.instances {
+            ids.retain(|&id| kept[id as usize]);
+        }
+        // Ids stay stable; a dropped name is simply never referenced again,
+        // so training never creates a slot for it.
+        kept.iter().filter(|&&k| !k).count()
+    }
+
+    /// Returns the names of an instance's features.
+    fn feature_names<'a>(&'a self, ids: &'a [u32]) -> impl Iterator<Item = &'a str> + 'a {
+        ids.iter().map(|&id| self.feature_names[id as usize].as_str())
+    }
+}
+
+/// SplitMix64, the generator behind the seeded instance shuffle.
+///
+/// Kept here rather than taken from a crate so a seed's shuffle order is
+/// part of this crate's own contract: the same seed and inputs produce the
+/// same model whatever dependency versions a build resolves.
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Returns the next 64 random bits.
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            // Multiply-shift maps 64 random bits onto 0..=i.
+            let j = ((u128::from(self.next_u64()) * (i as u128 + 1)) >> 64) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Returns the index of the highest score; the first one wins a tie.
+fn argmax(scores: &[f64]) -> usize {
+    let mut best = 0;
+    let mut best_score = f64::NEG_INFINITY;
+    for (i, s) in scores.iter().enumerate() {
+        if *s > best_score {
+            best_score = *s;
+            best = i;
+        }
+    }
+    best
+}
+
+/// One shard's pass over its part of an epoch under parameter mixing.
+#[derive(Debug)]
+struct ShardPass {
+    /// The slots the shard updated, by feature id: copies of the shared
+    /// weights it started from, with averaging state local to the shard
+    /// and caught up to its last step.
+    slots: FxHashMap<u32, FeatureSlot>,
+    /// The instances trained on, i.e. the shard's steps.
+    steps: usize,
+    /// The mistakes (weight updates) made.
+    updates: usize,
+    /// `false` if the `running` flag interrupted the pass.
+    completed: bool,
+}
+
+/// Trains one shard for a single pass, starting from the shared weights.
+///
+/// The shared slots are only read; the first update to a feature copies
+/// its weights into the shard. A copied slot's accumulators start at zero
+/// with its update step at zero, so by the end of the pass each holds the
+/// sum of the weights the shard saw at every one of its steps.
+///
+/// # Arguments
+/// * `training` - The training set.
+/// * `shared` - The weights at the start of the epoch, by feature id.
+/// * `truths` - The class index of each label id.
+/// * `order` - The shard's instances, in visiting order.
+/// * `n` - The number of classes.
+/// * `running` - A flag for interrupting the training.
+fn train_shard(
+    training: &TrainingSet,
+    shared: &[Option<FeatureSlot>],
+    truths: &[Option<usize>],
+    order: &[u32],
+    n: usize,
+    running: &AtomicBool,
+) -> ShardPass {
+    let mut slots: FxHashMap<u32, FeatureSlot> = FxHashMap::default();
+    let mut scores: Vec<f64> = vec![0.0; n];
+    let (mut steps, mut updates) = (0usize, 0usize);
+    for &i in order {
+        if !running.load(Ordering::SeqCst) {
+            return ShardPass {
+                slots,
+                steps,
+                updates,
+                completed: false,
+            };
+        }
+        let (features, label) = &training.instances[i as usize];
+
+        scores.fill(0.0);
+        for &id in features {
+            if let Some(slot) = slots.get(&id).or(shared[id as usize].as_ref()) {
+                for (s, w) in scores.iter_mut().zip(slot.w.iter()) {
+                    *s += *w;
+                }
+            }
+        }
+        let guess_idx = argmax(&scores);
+        let Some(truth_idx) = truths[*label as usize] else {
+            continue;
+        };
+        if guess_idx != truth_idx {
+            updates += 1;
+            let weight = training.weight(i as usize);
+            for &id in features {
+                let slot = slots.entry(id).or_insert_with(|| FeatureSlot {
+                    w: shared[id as usize].as_ref().map_or_else(|| vec![0.0; n], |s| s.w.clone()),
+                    acc: Vec::new(),
+                    ts: Vec::new(),
+                });
+                slot.update(truth_idx, weight, steps);
+                slot.update(guess_idx, -weight, steps);
+            }
+        }
+        steps += 1;
+    }
+    for slot in slots.values_mut() {
+        slot.ensure_averaging();
+        for class_idx in 0..n {
+            slot.acc[class_idx] += slot.w[class_idx] * (steps - slot.ts[class_idx]) as f64;
+            slot.ts[class_idx] = steps;
+        }
+    }
+    ShardPass {
+        slots,
+        steps,
+        updates,
+        completed: true,
+    }
+}
+
+/// Trains the rest of an epoch by iterative parameter mixing.
+///
+/// `order` is cut into up to `threads` contiguous shards, each trained on
+/// its own thread from the same starting weights ([`train_shard`]). The
+/// mixed weights are then the uniform average of the shards' final
+/// weights, and every shard's steps are added to the averaging state, so
+/// the averaged model averages the weights seen at every step of every
+/// shard. Shards are merged in a fixed order, so the result depends on the
+/// instances and the thread count, never on thread scheduling.
+///
+/// # Arguments
+/// * `training` - The training set, whose feature ids index `slots`.
+/// * `slots` - The training features' slots, mixed in place.
+/// * `truths` - The class index of each label id.
+/// * `order` - The instances left in the epoch, in visiting order.
+/// * `n` - The number of classes.
+/// * `step` - The step counter at the start of the epoch.
+/// * `threads` - The maximum number of shards.
+/// * `running` - A flag for interrupting the training.
+///
+/// # Returns
+/// The steps taken and the mistakes made, or `None` if `running`
+/// interrupted a shard; the slots are then left untouched, so the epoch
+/// can be run again.
+#[allow(clippy::too_many_arguments)]
+fn mix_epoch(
+    training: &TrainingSet,
+    slots: &mut [Option<FeatureSlot>],
+    truths: &[Option<usize>],
+    order: &[u32],
+    n: usize,
+    step: usize,
+    threads: usize,
+    running: &AtomicBool,
+) -> Option<(usize, usize)> {
+    if order.is_empty() {
+        return Some((0, 0));
+    }
+    let shared: &[Option<FeatureSlot>] = slots;
+    let shards = order.chunks(order.len().div_ceil(threads));
+    // wasm32 has no threads to spawn; running the shards one after another
+    // gives the same model.
+    #[cfg(target_arch = "wasm32")]
+    let passes: Vec<ShardPass> = shards
+        .map(|shard| train_shard(training, shared, truths, shard, n, running))
+        .collect();
+    #[cfg(not(target_arch = "wasm32"))]
+    let passes: Vec<ShardPass> = std::thread::scope(|scope| {
+        let handles: Vec<_> = shards
+            .map(|shard| {
+                scope.spawn(move || train_shard(training, shared, truths, shard, n, running))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+    if passes.iter().any(|pass| !pass.completed) {
+        return None;
+    }
+
+    let mut touched: Vec<u32> = passes.iter().flat_map(|pass| pass.slots.keys().copied()).collect();
+    touched.sort_unstable();
+    touched.dedup();
+    let steps: usize = passes.iter().map(|pass| pass.steps).sum();
+    let shard_count = passes.len() as f64;
+    for id in touched {
+        let slot = slots[id as usize].get_or_insert_with(|| FeatureSlot::new(n));
+        slot.ensure_averaging();
+        for class_idx in 0..n {
+            // Catch up to the start of the epoch, then add every shard's
+            // steps: a shard that never touched the feature held the
+            // starting weight throughout.
+            let start = slot.w[class_idx];
+            slot.acc[class_idx] += start * (step - slot.ts[class_idx]) as f64;
+            let mut mixed = 0.0;
+            for pass in &passes {
+                match pass.slots.get(&id) {
+                    Some(local) => {
+                        slot.acc[class_idx] += local.acc[class_idx];
+                        mixed += local.w[class_idx];
+                    }
+                    None => {
+                        slot.acc[class_idx] += start * pass.steps as f64;
+                        mixed += start;
+                    }
+                }
+            }
+            slot.ts[class_idx] = step + steps;
+            slot.w[class_idx] = mixed / shard_count;
+        }
+    }
+    Some((steps, passes.iter().map(|pass| pass.updates).sum()))
+}
+
+/// The progress of a training run: everything besides the weights that
+/// decides how the run continues, so a checkpointed run picks up exactly
+/// where it stopped.
+#[derive(Debug, Clone, Default)]
+struct RunState {
+    /// Completed epochs.
+    epoch: usize,
+    /// Instances of the epoch in progress already visited, in `order`.
+    position: usize,
+    /// Whether `order` has been drawn for the epoch in progress.
+    shuffled: bool,
+    /// The visiting order of the epoch in progress (training instance
+    /// indices).
+    order: Vec<u32>,
+    /// The shuffle generator; `None` when training in insertion order.
+    rng: Option<SplitMix64>,
+    /// The development accuracy after each completed epoch.
+    curve: Vec<f64>,
+    /// The best development accuracy so far, with the training features'
+    /// slots and the step it was reached at.
+    best: Option<(f64, Vec<Option<FeatureSlot>>, usize)>,
+    /// Completed epochs since `best`.
+    since_best: usize,
+    /// Whether the run is over and its weights already averaged.
+    finished: bool,
+}
+
+/// Receives the checkpoints of a training run.
+pub(crate) type CheckpointSink<'a> = &'a mut dyn FnMut(LearnerState) -> Result<()>;
+
+/// How a training run ended.
+#[derive(Debug)]
+pub(crate) struct TrainingRun {
+    /// The development accuracy (in %) after each completed epoch.
+    pub(crate) curve: Vec<f64>,
+    /// `false` if the `running` flag interrupted the run.
+    pub(crate) completed: bool,
+}
+
+/// The resumable state of one perceptron's training run, the unit a
+/// [`Checkpoint`](crate::checkpoint::Checkpoint) is made of.
+///
+/// Holds every feature slot with its averaging state, so resuming needs
+/// neither the original model nor any replay; `fingerprint` ties the state
+/// to the instances it was trained on.
+#[derive(Debug, Clone)]
+pub(crate) struct LearnerState {
+    /// [`fingerprint`] of the training and development instances.
+    fingerprint: u64,
+    /// The registered classes, sorted.
+    classes: Vec<String>,
+    /// The step counter.
+    step: usize,
+    /// The shuffle seed, recorded again in the resumed model's metadata.
+    seed: Option<u64>,
+    /// Every feature slot, sorted by feature name.
+    slots: Vec<(String, FeatureSlot)>,
+    /// The progress of the run.
+    run: RunState,
+}
+
+impl LearnerState {
+    /// Returns the number of completed epochs.
+    pub(crate) fn epochs_completed(&self) -> usize {
+        self.run.epoch
+    }
+
+    /// Writes the state as binary records.
+    ///
+    /// # Errors
+    /// Returns an I/O error if writing fails.
+    pub(crate) fn write<W: Write>(&self, enc: &mut Encoder<'_, W>) -> Result<()> {
+        enc.u64(self.fingerprint)?;
+        enc.len(self.classes.len())?;
+        for class in &self.classes {
+            enc.str(class)?;
+        }
+        enc.len(self.step)?;
+        enc.bool(self.seed.is_some())?;
+        enc.u64(self.seed.unwrap_or_default())?;
+        enc.len(self.slots.len())?;
+        for (feat, slot) in &self.slots {
+            enc.str(feat)?;
+            write_slot(enc, slot)?;
+        }
+
+        let run = &self.run;
+        enc.bool(run.finished)?;
+        enc.len(run.epoch)?;
+        enc.len(run.position)?;
+        enc.bool(run.shuffled)?;
+        enc.len(run.order.len())?;
+        run.order.iter().try_for_each(|&i| enc.u32(i))?;
+        enc.bool(run.rng.is_some())?;
+        enc.u64(run.rng.as_ref().map_or(0, |rng| rng.0))?;
+        enc.f64s(&run.curve)?;
+        enc.len(run.since_best)?;
+        enc.bool(run.best.is_some())?;
+        if let Some((accuracy, slots, step)) = &run.best {
+            enc.f64(*accuracy)?;
+            enc.len(*step)?;
+            enc.len(slots.len())?;
+            for slot in slots {
+                enc.bool(slot.is_some())?;
+                if let Some(slot) = slot {
+                    write_slot(enc, slot)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a state written by [`write`](Self::write).
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the records are truncated
+    /// or inconsistent: unsorted classes or features, a slot of the wrong
+    /// width, a non-finite weight, an update step past the step counter, or
+    /// a visiting order that is not a permutation.
+    pub(crate) fn read(dec: &mut Decoder<'_>) -> Result<Self> {
+        let fingerprint = dec.u64()?;
+        let mut classes: Vec<String> = Vec::new();
+        for _ in 0..dec.len(4)? {
+            let class = dec.str()?;
+            if classes.last().is_some_and(|prev| prev.as_str() >= class) {
+                return Err(invalid_state(format!("classes are not sorted at '{}'", class)));
+            }
+            classes.push(class.to_string());
+        }
+        let n = classes.len();
+        let step = read_usize(dec)?;
+        let has_seed = dec.bool()?;
+        let seed = dec.u64()?;
+        let mut slots: Vec<(String, FeatureSlot)> = Vec::new();
+        for _ in 0..dec.len(4)? {
+            let feat = dec.str()?;
+            if slots.last().is_some_and(|(prev, _)| prev.as_str() >= feat) {
+                return Err(invalid_state(format!("features are not sorted at '{}'", feat)));
+            }
+            slots.push((feat.to_string(), read_slot(dec, n, step)?));
+        }
+
+        let finished = dec.bool()?;
+        let epoch = read_usize(dec)?;
+        let position = read_usize(dec)?;
+        let shuffled = dec.bool()?;
+        let len = dec.len(4)?;
+        let mut order = Vec::with_capacity(len);
+        let mut seen = vec![false; len];
+        for _ in 0..len {
+            let i = dec.u32()?;
+            if seen.get(i as usize) != Some(&false) {
+                return Err(invalid_state("the visiting order is not a permutation".to_string()));
+            }
+            seen[i as usize] = true;
+            order.push(i);
+        }
+        if position > len {
+            return Err(invalid_state(format!("position {} is past the epoch's end", position)));
+        }
+        let has_rng = dec.bool()?;
+        let rng = dec.u64()?;
+        let curve = dec.f64s()?;
+        let since_best = read_usize(dec)?;
+        let best = if dec.bool()? {
+            let accuracy = dec.f64()?;
+            let best_step = read_usize(dec)?;
+            if best_step > step {
+                return Err(invalid_state("the best epoch is past the step counter".to_string()));
+            }
+            let len = dec.len(1)?;
+            let mut best_slots = Vec::with_capacity(len);
+            for _ in 0..len {
+                best_slots.push(if dec.bool()? {
+                    Some(read_slot(dec, n, best_step)?)
+                } else {
+                    None
+                });
+            }
+            Some((accuracy, best_slots, best_step))
+        } else {
+            None
+        };
+
+        Ok(LearnerState {
+            fingerprint,
+            classes,
+            step,
+            seed: has_seed.then_some(seed),
+            slots,
+            run: RunState {
+                epoch,
+                position,
+                shuffled,
+                order,
+                rng: has_rng.then_some(SplitMix64(rng)),
+                curve,
+                best,
+                since_best,
+                finished,
+            },
+        })
+    }
+}
+
+/// Writes a feature slot: its weights, accumulators, and update steps.
+fn write_slot<W: Write>(enc: &mut Encoder<'_, W>, slot: &FeatureSlot) -> Result<()> {
+    enc.f64s(&slot.w)?;
+    enc.f64s(&slot.acc)?;
+    enc.len(slot.ts.len())?;
+    slot.ts.iter().try_for_each(|&ts| enc.len(ts))?;
+    Ok(())
+}
+
+/// Reads a slot written by [`write_slot`], checking it holds `n` classes
+/// updated no later than `step`.
+fn read_slot(dec: &mut Decoder<'_>, n: usize, step: usize) -> Result<FeatureSlot> {
+    let w = dec.f64s()?;
+    let acc = dec.f64s()?;
+    let mut ts = Vec::with_capacity(acc.len());
+    for _ in 0..dec.len(8)? {
+        ts.push(read_usize(dec)?);
+    }
+    if w.len() != n || !(acc.is_empty() || acc.len() == n) || ts.len() != acc.len() {
+        return Err(invalid_state(format!("a feature slot does not have {} classes", n)));
+    }
+    if w.iter().chain(&acc).any(|v| !v.is_finite()) {
+        return Err(invalid_state("non-finite weight".to_string()));
+    }
+    if ts.iter().any(|&ts| ts > step) {
+        return Err(invalid_state("a weight was updated past the step counter".to_string()));
+    }
+    Ok(FeatureSlot { w, acc, ts })
+}
+
+/// Reads a `u64` record into a `usize`.
+fn read_usize(dec: &mut Decoder<'_>) -> Result<usize> {
+    usize::try_from(dec.u64()?).map_err(|_| invalid_state("counter out of range".to_string()))
+}
+
+/// The error for a malformed training state.
+fn invalid_state(message: String) -> LitseaError {
+    LitseaError::InvalidData(format!("invalid training checkpoint: {}", message))
+}
+
+/// Returns an FNV-1a hash of the training and development instances,
+/// identifying the data a [`LearnerState`] was trained on.
+fn fingerprint(training: &TrainingSet, dev: &TrainingSet) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    let mut feed = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    };
+    for set in [training, dev] {
+        feed(&(set.instances.len() as u64).to_le_bytes());
+        for (ids, label) in &set.instances {
+            feed(&(ids.len() as u64).to_le_bytes());
+            for &id in ids {
+                feed(&id.to_le_bytes());
+            }
+            feed(&label.to_le_bytes());
+        }
+        for weight in &set.weights {
+            feed(&weight.to_le_bytes());
+        }
+        for name in set.feature_names.iter().chain(&set.labels) {
+            feed(&(name.len() as u64).to_le_bytes());
+            feed(name.as_bytes());
+        }
+    }
+    hash
 }
 
 /// Multiclass Averaged Perceptron classifier.
@@ -93,8 +747,22 @@ pub struct AveragedPerceptron {
     step: usize,
     /// Known classes (always kept sorted)
     classes: Vec<String>,
-    /// Training instances: (feature set, gold label)
-    instances: Vec<(Vec<String>, String)>,
+    /// Training instances, interned.
+    instances: TrainingSet,
+    /// Held-out development instances, scored after every epoch when
+    /// present.
+    dev: TrainingSet,
+    /// Seed of the per-epoch instance shuffle; `None` trains in insertion
+    /// order.
+    seed: Option<u64>,
+    /// The number of threads an epoch is trained on; above 1, epochs run
+    /// by parameter mixing.
+    threads: usize,
+    /// The progress of a run restored by [`resume`](Self::resume), picked
+    /// up by the next training call.
+    resumed: Option<RunState>,
+    /// Provenance recorded in the model file.
+    metadata: ModelMetadata,
 }
 
 impl Default for AveragedPerceptron {
@@ -114,10 +782,76 @@ impl AveragedPerceptron {
             slots: FxHashMap::default(),
             step: 0,
             classes: Vec::new(),
-            instances: Vec::new(),
+            instances: TrainingSet::default(),
+            dev: TrainingSet::default(),
+            seed: None,
+            threads: 1,
+            resumed: None,
+            metadata: ModelMetadata::default(),
         }
     }
 
+    /// Returns the provenance this model records (see [`ModelMetadata`]).
+    #[must_use]
+    pub fn metadata(&self) -> &ModelMetadata {
+        &self.metadata
+    }
+
+    /// Sets the provenance this model records. It is written as
+    /// `#key\tvalue` header lines before the class count by
+    /// [`save_model`](Self::save_model) (nothing is written for empty
+    /// metadata).
+    ///
+    /// # Arguments
+    /// * `metadata` - The model's language, templates, training parameters
+    ///   and corpus checksum.
+    pub fn set_metadata(&mut self, metadata: ModelMetadata) {
+        self.metadata = metadata;
+    }
+
+    /// Sets the seed of the per-epoch instance shuffle.
+    ///
+    /// With a seed, [`train`](Self::train) visits the instances in a fresh
+    /// pseudo-random order every epoch, drawn from a generator seeded once
+    /// per training run; the same seed and the same instances always
+    /// produce the same orders, and so a byte-identical model. Without one
+    /// (the default), instances are visited in the order they were added.
+    ///
+    /// # Arguments
+    /// * `seed` - The shuffle seed, or `None` for insertion order.
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Returns the seed of the per-epoch instance shuffle, if any.
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Sets the number of threads each epoch is trained on.
+    ///
+    /// With more than one, [`train`](Self::train) runs every epoch by
+    /// iterative parameter mixing: the epoch's instances (in visiting
+    /// order) are cut into that many contiguous shards, each shard is
+    /// trained on its own thread from the weights the previous epoch left,
+    /// and the shards' weights are averaged. The model is deterministic for
+    /// a given thread count (and seed), but differs from the
+    /// single-threaded one. 0 and 1 both train single-threaded (the
+    /// default).
+    ///
+    /// # Arguments
+    /// * `threads` - The number of threads.
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// Returns the number of threads each epoch is trained on.
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
     /// Registers a class and returns its index.
     /// New classes are inserted in sorted order, and a matching column is
     /// inserted into every existing feature slot.
@@ -156,7 +890,76 @@ impl AveragedPerceptron {
         self.ensure_class(&label);
         let mut feats: Vec<String> = features.into_iter().collect();
         feats.sort_unstable();
-        self.instances.push((feats, label));
+        self.instances.push(feats, label, 1.0);
+    }
+
+    /// Adds a training instance whose update step is scaled by `weight`.
+    ///
+    /// A mistake on the instance moves the weights by `weight` instead of
+    /// 1, so an instance of weight `0.5` counts half as much as one added
+    /// with [`add_instance`](Self::add_instance), and one of weight `0`
+    /// never moves them.
+    ///
+    /// # Arguments
+    /// * `features` - The feature set
+    /// * `label` - The gold label
+    /// * `weight` - The size of the update step, finite and non-negative
+    pub fn add_weighted_instance(&mut self, features: HashSet<String>, label: String, weight: f64) {
+        self.ensure_class(&label);
+        let mut feats: Vec<String> = features.into_iter().collect();
+        feats.sort_unstable();
+        self.instances.push(feats, label, weight);
+    }
+
+    /// Adds a held-out development instance.
+    ///
+    /// Development instances are never trained on. When any are present,
+    /// [`train`](Self::train) scores them after every epoch and keeps the
+    /// averaged weights of the best epoch (see
+    /// [`train_with_dev`](Self::train_with_dev)).
+    ///
+    /// # Arguments
+    /// * `features` - The feature set
+    /// * `label` - The gold label
+    pub fn add_dev_instance(&mut self, features: HashSet<String>, label: String) {
+        let mut feats: Vec<String> = features.into_iter().collect();
+        feats.sort_unstable();
+        self.dev.push(feats, label, 1.0);
+    }
+
+    /// Moves the training instances of `other` into this perceptron's
+    /// development set, so the trainers can load a dev features file with
+    /// the same line parsers as the training one.
+    pub(crate) fn set_dev(&mut self, other: AveragedPerceptron) {
+        self.dev = other.instances;
+    }
+
+    /// Returns the number of development instances.
+    #[must_use]
+    pub fn num_dev_instances(&self) -> usize {
+        self.dev.len()
+    }
+
+    /// Drops the features that occur in fewer training instances than the
+    /// cutoff requires.
+    ///
+    /// Counts are taken over the instances added so far, so call this
+    /// after the last [`add_instance`](Self::add_instance) and before
+    /// [`train`](Self::train). Features that already carry weights (from a
+    /// loaded model) are kept whatever their count.
+    ///
+    /// # Arguments
+    /// * `cutoff` - The minimum counts.
+    ///
+    /// # Returns
+    /// The number of distinct features dropped.
+    pub fn apply_cutoff(&mut self, cutoff: &FeatureCutoff) -> usize {
+        if cutoff.is_noop() {
+            return 0;
+        }
+        let slots = &self.slots;
+        self.instances
+            .retain_features(|name, count| cutoff.keeps(name, count) || slots.contains_key(name))
     }
 
     /// Returns the index of the highest-scoring class for the features,
@@ -180,15 +983,7 @@ impl AveragedPerceptron {
                 }
             }
         }
-        let mut best = 0;
-        let mut best_score = f64::NEG_INFINITY;
-        for (i, s) in scores.iter().enumerate() {
-            if *s > best_score {
-                best_score = *s;
-                best = i;
-            }
-        }
-        Some(best)
+        Some(argmax(scores))
     }
 
     /// Predicts the label for a feature set.
@@ -246,47 +1041,13 @@ impl AveragedPerceptron {
         &self.classes
     }
 
-    /// Updates the weight of a single (feature, class) pair.
-    /// Catches the accumulated weight up to the current step before adding
-    /// `delta`. One hashed lookup, and no allocation when the feature is
-    /// already known (get-then-insert instead of the owned-key entry API).
-    fn update_single(&mut self, feat: &str, class_idx: usize, delta: f64) {
-        let slot = match self.slots.get_mut(feat) {
-            Some(slot) => slot,
-            None => {
-                let n = self.classes.len();
-                self.slots.entry(feat.to_string()).or_insert_with(|| FeatureSlot::new(n))
-            }
-        };
-        slot.ensure_averaging();
-
-        let elapsed = self.step - slot.ts[class_idx];
-        if elapsed > 0 {
-            slot.acc[class_idx] += slot.w[class_idx] * elapsed as f64;
-        }
-        slot.ts[class_idx] = self.step;
-        slot.w[class_idx] += delta;
-    }
-
-    /// Updates the weights for one instance.
-    ///
-    /// When the prediction differs from the gold label:
-    /// - the gold class weights are incremented by 1
-    /// - the predicted class weights are decremented by 1
-    fn update(&mut self, truth_idx: usize, guess_idx: usize, features: &[String]) {
-        for feat in features {
-            self.update_single(feat, truth_idx, 1.0);
-            self.update_single(feat, guess_idx, -1.0);
-        }
-    }
-
     /// Writes the averaged weights into the final model.
     ///
     /// A single pass over the slots: each (feature, class) accumulator is
     /// caught up to the current step and the live weight is replaced by the
     /// average. Pairs are independent, so map iteration order cannot affect
-    /// the result (same math as the previous per-key update_single loop,
-    /// without cloning every key and re-looking each one up).
+    /// the result (same math as a per-key update loop, without cloning
+    /// every key and re-looking each one up).
     fn average_weights(&mut self) {
         let step_now = self.step;
         let step = self.step.max(1) as f64;
@@ -305,52 +1066,409 @@ impl AveragedPerceptron {
 
     /// Trains the model.
     ///
+    /// With development instances (see
+    /// [`add_dev_instance`](Self::add_dev_instance)), this is
+    /// [`train_with_dev`](Self::train_with_dev) without a patience: every
+    /// epoch runs and the best one's weights are kept.
+    ///
     /// # Arguments
     /// * `num_epochs` - The number of epochs
     /// * `running` - A flag for interrupting the training
     pub fn train(&mut self, num_epochs: usize, running: &AtomicBool) {
+        self.train_with_dev(num_epochs, None, running);
+    }
+
+    /// Trains the model, scoring the development set after every epoch.
+    ///
+    /// After each completed epoch the averaged weights are evaluated on the
+    /// development instances; the model keeps those of the epoch with the
+    /// highest accuracy (the earliest on a tie), exactly as if training had
+    /// stopped there. With a `patience`, training stops once that many
+    /// epochs in a row fail to improve on the best. Without development
+    /// instances this is plain training over `num_epochs` epochs.
+    ///
+    /// # Arguments
+    /// * `num_epochs` - The maximum number of epochs.
+    /// * `patience` - The number of epochs without improvement after which
+    ///   training stops, or `None` to run every epoch.
+    /// * `running` - A flag for interrupting the training.
+    ///
+    /// # Returns
+    /// The development accuracy (in %) after each completed epoch; empty
+    /// without development instances.
+    pub fn train_with_dev(
+        &mut self,
+        num_epochs: usize,
+        patience: Option<usize>,
+        running: &AtomicBool,
+    ) -> Vec<f64> {
+        match self.train_checkpointed(num_epochs, patience, running, None, None) {
+            Ok(run) => run.curve,
+            // Only writing a checkpoint can fail, and there is no sink.
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Trains the model like [`train_with_dev`](Self::train_with_dev),
+    /// handing the run's state to a checkpoint sink along the way.
+    ///
+    /// The sink receives the state after every `every` completed epochs and
+    /// once more if `running` interrupts the run (an `every` of 0 only
+    /// writes the latter). The state is taken before the weights are
+    /// averaged, so [`resume`](Self::resume) followed by the same call
+    /// continues the run as if it had never stopped.
+    ///
+    /// # Arguments
+    /// * `num_epochs` - The maximum number of epochs, counting those a
+    ///   resumed run already completed.
+    /// * `patience` - The number of epochs without improvement after which
+    ///   training stops, or `None` to run every epoch.
+    /// * `running` - A flag for interrupting the training.
+    /// * `checkpoint` - The checkpoint interval and sink, if any.
+    /// * `progress` - Receives a report after every epoch, if any.
+    ///
+    /// # Returns
+    /// The development curve and whether the run completed.
+    ///
+    /// # Errors
+    /// Returns the sink's error; training stops at the failed checkpoint,
+    /// with the weights averaged as if interrupted there.
+    pub(crate) fn train_checkpointed(
+        &mut self,
+        num_epochs: usize,
+        patience: Option<usize>,
+        running: &AtomicBool,
+        mut checkpoint: Option<(usize, CheckpointSink<'_>)>,
+        mut progress: Option<&mut Progress<'_>>,
+    ) -> Result<TrainingRun> {
+        let resumed = self.resumed.take();
+        if let Some(run) = resumed.as_ref().filter(|run| run.finished) {
+            return Ok(TrainingRun {
+                curve: run.curve.clone(),
+                completed: true,
+            });
+        }
         if self.instances.is_empty() {
-            return;
+            return Ok(TrainingRun {
+                curve: Vec::new(),
+                completed: true,
+            });
+        }
+
+        // Invariant: instances are non-empty here, and add_instance
+        // registers a class for every instance, so classes cannot be empty;
+        // degrade gracefully instead of panicking if the invariant is ever
+        // broken.
+        let n = self.classes.len();
+        if n == 0 {
+            return Ok(TrainingRun {
+                curve: Vec::new(),
+                completed: true,
+            });
         }
 
         // Temporarily move the instances out to avoid double borrows during
         // training (previously every instance was cloned).
-        let instances = std::mem::take(&mut self.instances);
+        let training = std::mem::take(&mut self.instances);
+        // The slots of the training features, indexed by feature id, so the
+        // epoch loop never hashes a feature string. Slots a loaded model
+        // already holds move in and back out below.
+        let mut slots: Vec<Option<FeatureSlot>> =
+            training.feature_names.iter().map(|feat| self.slots.remove(feat)).collect();
+        // Invariant: add_instance registered every gold class.
+        let truths: Vec<Option<usize>> = training
+            .labels
+            .iter()
+            .map(|label| self.classes.binary_search(label).ok())
+            .collect();
         // Scratch buffer reused across every prediction in the epoch loop.
-        let mut scores: Vec<f64> = Vec::new();
-
-        for _epoch in 0..num_epochs {
+        let mut scores: Vec<f64> = vec![0.0; n];
+        let mut run = resumed.unwrap_or_else(|| RunState {
+            order: (0..training.instances.len() as u32).collect(),
+            rng: self.seed.map(SplitMix64),
+            ..RunState::default()
+        });
+        let mut interrupted = false;
+        let mut error = None;
+
+        while run.epoch < num_epochs {
+            if patience.is_some_and(|patience| run.since_best >= patience) {
+                break;
+            }
             if !running.load(Ordering::SeqCst) {
+                interrupted = true;
                 break;
             }
 
-            for (features, truth) in &instances {
+            // Mistakes of this epoch, for the progress report (a resumed
+            // epoch counts from where it resumed).
+            let (mut visited, mut updates) = (0usize, 0usize);
+
+            // The visiting order, reshuffled every epoch when seeded.
+            if !run.shuffled {
+                if let Some(rng) = &mut run.rng {
+                    rng.shuffle(&mut run.order);
+                }
+                run.shuffled = true;
+            }
+            if self.threads > 1 {
+                // An interrupted mixing epoch is dropped whole and run
+                // again on resume.
+                match mix_epoch(
+                    &training,
+                    &mut slots,
+                    &truths,
+                    &run.order[run.position..],
+                    n,
+                    self.step,
+                    self.threads,
+                    running,
+                ) {
+                    Some((steps, mistakes)) => {
+                        visited += steps;
+                        updates += mistakes;
+                        self.step += steps;
+                        run.position = run.order.len();
+                    }
+                    None => {
+                        interrupted = true;
+                        break;
+                    }
+                }
+            }
+            while let Some(&i) = run.order.get(run.position) {
                 if !running.load(Ordering::SeqCst) {
+                    interrupted = true;
                     break;
                 }
-
-                // Invariant: instances are non-empty here, and add_instance
-                // registers a class for every instance, so classes cannot be
-                // empty; degrade gracefully instead of panicking if the
-                // invariant is ever broken.
-                let Some(guess_idx) = self.predict_idx_into(features.iter(), &mut scores) else {
-                    break;
-                };
-                // Invariant: add_instance registered the gold class.
-                let Ok(truth_idx) = self.classes.binary_search_by(|c| c.as_str().cmp(truth)) else {
+                run.position += 1;
+                visited += 1;
+                let (features, label) = &training.instances[i as usize];
+
+                scores.fill(0.0);
+                for &id in features {
+                    if let Some(slot) = &slots[id as usize] {
+                        for (s, w) in scores.iter_mut().zip(slot.w.iter()) {
+                            *s += *w;
+                        }
+                    }
+                }
+                let guess_idx = argmax(&scores);
+                let Some(truth_idx) = truths[*label as usize] else {
                     continue;
                 };
+                // On a wrong guess, the gold class weights are incremented
+                // by the instance's weight (1 unless added with
+                // add_weighted_instance) and the guessed class weights
+                // decremented by it.
                 if guess_idx != truth_idx {
-                    self.update(truth_idx, guess_idx, features);
+                    updates += 1;
+                    let weight = training.weight(i as usize);
+                    for &id in features {
+                        let slot = slots[id as usize].get_or_insert_with(|| FeatureSlot::new(n));
+                        slot.update(truth_idx, weight, self.step);
+                        slot.update(guess_idx, -weight, self.step);
+                    }
                 }
                 self.step += 1;
             }
+            if interrupted {
+                break;
+            }
+            run.epoch += 1;
+            run.position = 0;
+            run.shuffled = false;
+
+            let mut dev_accuracy = None;
+            if !self.dev.is_empty() {
+                let accuracy = self.dev_accuracy(&training, &slots, &mut scores);
+                dev_accuracy = Some(accuracy);
+                run.curve.push(accuracy);
+                if run.best.as_ref().is_none_or(|(best_accuracy, _, _)| accuracy > *best_accuracy) {
+                    run.best = Some((accuracy, slots.clone(), self.step));
+                    run.since_best = 0;
+                } else {
+                    run.since_best += 1;
+                }
+            }
+            if let Some(progress) = progress.as_deref_mut() {
+                let error_rate = updates as f64 / visited.max(1) as f64 * 100.0;
+                progress.report(run.epoch, error_rate, updates, dev_accuracy);
+            }
+            if let Some((every, save)) = &mut checkpoint {
+                if *every > 0 && run.epoch.is_multiple_of(*every) {
+                    if let Err(e) = save(self.state(&training, &slots, &run)) {
+                        error = Some(e);
+                        break;
+                    }
+                }
+            }
+        }
+        if interrupted {
+            if let Some((_, save)) = &mut checkpoint {
+                if let Err(e) = save(self.state(&training, &slots, &run)) {
+                    error = Some(e);
+                }
+            }
         }
 
-        self.instances = instances;
+        // Keep the weights of the best epoch, exactly as if training had
+        // stopped there.
+        if let Some((_, best_slots, best_step)) = run.best {
+            slots = best_slots;
+            self.step = best_step;
+        }
+        for (feat, slot) in training.feature_names.iter().zip(slots) {
+            if let Some(slot) = slot {
+                self.slots.insert(feat.clone(), slot);
+            }
+        }
+        self.instances = training;
 
         // Write the averaged weights into the final model
         self.average_weights();
+        match error {
+            Some(e) => Err(e),
+            None => Ok(TrainingRun {
+                curve: run.curve,
+                completed: !interrupted,
+            }),
+        }
+    }
+
+    /// Captures the state of a run in progress.
+    ///
+    /// # Arguments
+    /// * `training` - The training set, whose feature ids index `slots`.
+    /// * `slots` - The training features' slots; the other slots are still
+    ///   in `self.slots`.
+    /// * `run` - The progress of the run.
+    fn state(
+        &self,
+        training: &TrainingSet,
+        slots: &[Option<FeatureSlot>],
+        run: &RunState,
+    ) -> LearnerState {
+        let mut all: Vec<(String, FeatureSlot)> = self
+            .slots
+            .iter()
+            .map(|(feat, slot)| (feat.clone(), slot.clone()))
+            .chain(
+                training
+                    .feature_names
+                    .iter()
+                    .zip(slots)
+                    .filter_map(|(feat, slot)| Some((feat.clone(), slot.clone()?))),
+            )
+            .collect();
+        all.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        LearnerState {
+            fingerprint: fingerprint(training, &self.dev),
+            classes: self.classes.clone(),
+            step: self.step,
+            seed: self.seed,
+            slots: all,
+            run: run.clone(),
+        }
+    }
+
+    /// Captures the state of a finished run, so a checkpoint of a later
+    /// stage can carry this one's final weights.
+    ///
+    /// # Arguments
+    /// * `curve` - The development curve the run returned.
+    pub(crate) fn finished_state(&self, curve: Vec<f64>) -> LearnerState {
+        let run = RunState {
+            curve,
+            finished: true,
+            ..RunState::default()
+        };
+        self.state(&self.instances, &[], &run)
+    }
+
+    /// Restores a checkpointed training state, so the next training call
+    /// continues that run.
+    ///
+    /// Call this once every training and development instance is added and
+    /// any cutoff applied: the state must have been trained on exactly the
+    /// same instances.
+    ///
+    /// # Arguments
+    /// * `state` - The state to continue from.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the state was trained on
+    /// different instances or lacks a registered class, or
+    /// [`LitseaError::InvalidData`] if it is inconsistent with them.
+    pub(crate) fn resume(&mut self, state: LearnerState) -> Result<()> {
+        if state.fingerprint != fingerprint(&self.instances, &self.dev) {
+            return Err(LitseaError::InvalidInput(
+                "the checkpoint was written for different training data; resume with the same inputs and options"
+                    .to_string(),
+            ));
+        }
+        if let Some(class) = self.classes.iter().find(|c| state.classes.binary_search(c).is_err()) {
+            return Err(LitseaError::InvalidInput(format!(
+                "the checkpoint has no class '{}'",
+                class
+            )));
+        }
+        let run = &state.run;
+        if !run.finished
+            && (run.order.len() != self.instances.len()
+                || run
+                    .best
+                    .as_ref()
+                    .is_some_and(|(_, slots, _)| slots.len() != self.instances.feature_names.len()))
+        {
+            return Err(invalid_state("it does not match the training instances".to_string()));
+        }
+
+        self.classes = state.classes;
+        self.step = state.step;
+        self.seed = state.seed;
+        self.slots = state.slots.into_iter().collect();
+        self.resumed = Some(state.run);
+        Ok(())
+    }
+
+    /// Scores the development set with the weights averaging would write
+    /// at the current step.
+    ///
+    /// # Arguments
+    /// * `training` - The training set, whose feature ids index `slots`.
+    /// * `slots` - The training features' slots; other features are looked
+    ///   up among the slots of a loaded model.
+    /// * `scores` - Scratch buffer, one entry per class.
+    ///
+    /// # Returns
+    /// The development accuracy in percentage (%).
+    fn dev_accuracy(
+        &self,
+        training: &TrainingSet,
+        slots: &[Option<FeatureSlot>],
+        scores: &mut [f64],
+    ) -> f64 {
+        let mut correct = 0usize;
+        for (features, label) in &self.dev.instances {
+            scores.fill(0.0);
+            for feat in self.dev.feature_names(features) {
+                let slot = match training.feature_ids.get(feat) {
+                    Some(&id) => slots[id as usize].as_ref(),
+                    None => self.slots.get(feat),
+                };
+                if let Some(slot) = slot {
+                    for (class_idx, s) in scores.iter_mut().enumerate() {
+                        *s += slot.averaged(class_idx, self.step);
+                    }
+                }
+            }
+            let truth = self.classes.binary_search(&self.dev.labels[*label as usize]).ok();
+            if truth == Some(argmax(scores)) {
+                correct += 1;
+            }
+        }
+        correct as f64 / self.dev.len().max(1) as f64 * 100.0
     }
 
     /// Saves the model to a file as text (class header + TSV).
@@ -361,8 +1479,10 @@ impl AveragedPerceptron {
     /// (one syscall per line would mean ~half a million syscalls for the
     /// shipped POS models).
     ///
-    /// Format:
+    /// Format (the `#key\tvalue` metadata lines are present only when
+    /// [`metadata`](Self::metadata) records something):
     /// ```text
+    /// #<metadata key>\t<value>
     /// <number of classes>
     /// <class name 1>
     /// <class name 2>
@@ -398,11 +1518,16 @@ impl AveragedPerceptron {
     ///
     /// # Errors
     /// Returns [`LitseaError::InvalidInput`] if no classes are registered
-    /// (an empty model), or an I/O error if writing fails.
+    /// (an empty model) or a metadata value cannot be written as a header
+    /// line, or an I/O error if writing fails.
     pub fn save_model_to_writer<W: Write>(&self, writer: &mut W) -> Result<()> {
         if self.classes.is_empty() {
             return Err(LitseaError::InvalidInput("Cannot save an empty model".to_string()));
         }
+        self.metadata.validate()?;
+        for (key, value) in self.metadata.headers() {
+            writeln!(writer, "#{}\t{}", key, value)?;
+        }
 
         // Header: the number of classes and the class names
         writeln!(writer, "{}", self.classes.len())?;
@@ -425,6 +1550,179 @@ impl AveragedPerceptron {
         Ok(())
     }
 
+    /// Saves the model to a file in the binary model format (see
+    /// [`save_binary_model_to_writer`](Self::save_binary_model_to_writer)).
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file to write the model to.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if no classes are registered
+    /// (an empty model), or an I/O error if the file cannot be created or
+    /// written.
+    pub fn save_binary_model(&self, path: &Path) -> Result<()> {
+        let mut file = io::BufWriter::new(File::create(path)?);
+        self.save_binary_model_to_writer(&mut file)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Writes the model to an arbitrary writer in the binary model format
+    /// (`litsea-binary v1 perceptron`): the metadata as `(key, value)`
+    /// pairs, the class names, then one record
+    /// per feature with a non-zero weight, in sorted feature order, holding
+    /// the feature name and its weight for every class. Weights are stored
+    /// bit for bit, so [`load_model_from_reader`](Self::load_model_from_reader)
+    /// reads back exactly this model. The writer is not flushed.
+    ///
+    /// # Arguments
+    /// * `writer` - The writer receiving the model bytes.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if no classes are registered
+    /// (an empty model), or an I/O error if writing fails.
+    pub fn save_binary_model_to_writer<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let mut enc = Encoder::new(writer, ModelKind::AveragedPerceptron)?;
+        self.write_binary(&mut enc)
+    }
+
+    /// Writes the model's binary payload (see
+    /// [`save_binary_model_to_writer`](Self::save_binary_model_to_writer)).
+    /// Also embedded by the two-stage binary format.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if no classes are registered,
+    /// or an I/O error if writing fails.
+    pub(crate) fn write_binary<W: Write>(&self, enc: &mut Encoder<'_, W>) -> Result<()> {
+        if self.classes.is_empty() {
+            return Err(LitseaError::InvalidInput("Cannot save an empty model".to_string()));
+        }
+        self.metadata.validate()?;
+        let headers = self.metadata.headers();
+        enc.len(headers.len())?;
+        for (key, value) in &headers {
+            enc.str(key)?;
+            enc.str(value)?;
+        }
+        enc.len(self.classes.len())?;
+        for class in &self.classes {
+            enc.str(class)?;
+        }
+        let mut feats: Vec<(&String, &FeatureSlot)> =
+            self.slots.iter().filter(|(_, slot)| slot.w.iter().any(|&w| w != 0.0)).collect();
+        feats.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        enc.len(feats.len())?;
+        for (feat, slot) in feats {
+            enc.str(feat)?;
+            slot.w.iter().try_for_each(|&w| enc.f64(w))?;
+        }
+        Ok(())
+    }
+
+    /// Reads a payload written by [`write_binary`](Self::write_binary) into
+    /// a new model.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the payload is truncated, a
+    /// metadata entry is unknown or invalid, there are no classes, the
+    /// classes or features are not sorted and unique, or a weight is not
+    /// finite.
+    pub(crate) fn read_binary(dec: &mut Decoder<'_>) -> Result<Self> {
+        let mut metadata = ModelMetadata::default();
+        for _ in 0..dec.len(8)? {
+            let key = dec.str()?;
+            let value = dec.str()?;
+            metadata.apply_header(key, value).map_err(LitseaError::InvalidData)?;
+        }
+        let n = dec.len(4)?;
+        let mut classes: Vec<String> = Vec::with_capacity(n);
+        for _ in 0..n {
+            let class = dec.str()?;
+            if classes.last().is_some_and(|prev| prev.as_str() >= class) {
+                return Err(LitseaError::InvalidData(format!(
+                    "binary model classes are not sorted and unique at '{}'",
+                    class
+                )));
+            }
+            classes.push(class.to_string());
+        }
+        if classes.is_empty() {
+            return Err(LitseaError::InvalidData("binary model has no classes".to_string()));
+        }
+        let len = dec.len(4 + 8 * n)?;
+        let mut slots = FxHashMap::with_capacity_and_hasher(len, Default::default());
+        let mut prev: Option<&str> = None;
+        for _ in 0..len {
+            let feat = dec.str()?;
+            if prev.is_some_and(|prev| prev >= feat) {
+                return Err(LitseaError::InvalidData(format!(
+                    "binary model features are not sorted and unique at '{}'",
+                    feat
+                )));
+            }
+            prev = Some(feat);
+            let mut slot = FeatureSlot::new(n);
+            for w in &mut slot.w {
+                *w = dec.f64()?;
+                if !w.is_finite() {
+                    return Err(LitseaError::InvalidData(format!(
+                        "Non-finite weight for feature '{}'",
+                        feat
+                    )));
+                }
+            }
+            slots.insert(feat.to_string(), slot);
+        }
+        Ok(AveragedPerceptron {
+            slots,
+            step: 0,
+            classes,
+            instances: TrainingSet::default(),
+            dev: TrainingSet::default(),
+            seed: None,
+            threads: 1,
+            resumed: None,
+            metadata,
+        })
+    }
+
+    /// Loads a binary model file into this model, merging its classes into
+    /// the registered ones and replacing the weights like the text loader.
+    fn load_binary_model(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut dec = Decoder::new(bytes, ModelKind::AveragedPerceptron)?;
+        let mut loaded = Self::read_binary(&mut dec)?;
+        dec.finish()?;
+        self.metadata = std::mem::take(&mut loaded.metadata);
+        for class in &loaded.classes {
+            self.ensure_class(class);
+        }
+        self.step = 0;
+        if self.classes == loaded.classes {
+            self.slots = loaded.slots;
+            return Ok(());
+        }
+        // Training instances registered extra classes: widen every loaded
+        // row to the merged class list.
+        let n = self.classes.len();
+        let columns: Vec<usize> = loaded
+            .classes
+            .iter()
+            .map(|class| self.classes.binary_search(class).unwrap_or_default())
+            .collect();
+        self.slots = loaded
+            .slots
+            .into_iter()
+            .map(|(feat, slot)| {
+                let mut widened = FeatureSlot::new(n);
+                for (&column, &w) in columns.iter().zip(&slot.w) {
+                    widened.w[column] = w;
+                }
+                (feat, widened)
+            })
+            .collect();
+        Ok(())
+    }
+
     /// Returns the registered class names in their sorted storage order
     /// (the order used for weight-vector indexing and argmax tie-breaking).
     ///
@@ -478,23 +1776,42 @@ impl AveragedPerceptron {
     ///
     /// # Arguments
     /// * `reader` - The buffered reader providing the model content (the
-    ///   text format written by [`save_model`](Self::save_model)).
+    ///   text format written by [`save_model`](Self::save_model), or the
+    ///   binary format written by
+    ///   [`save_binary_model`](Self::save_binary_model), recognized by its
+    ///   magic line).
     ///
     /// # Errors
-    /// Returns [`LitseaError::InvalidData`] if the model file is empty, the
+    /// Returns [`LitseaError::InvalidData`] if the model file is empty, a
+    /// `#key\tvalue` header line is not a valid [`ModelMetadata`] entry, the
     /// class count is not a valid number, the file ends while reading the
     /// class names, a weight line does not have exactly three tab-separated
     /// fields, a weight line names an unknown class, or a weight value is
-    /// unparsable or non-finite. I/O errors from the reader are also
-    /// propagated.
-    pub fn load_model_from_reader<R: BufRead>(&mut self, reader: R) -> Result<()> {
+    /// unparsable or non-finite, or if a binary model is truncated, holds
+    /// another model kind, or is otherwise malformed. I/O errors from the
+    /// reader are also propagated.
+    pub fn load_model_from_reader<R: BufRead>(&mut self, mut reader: R) -> Result<()> {
+        if let Some(bytes) = binary_model::read_if_binary(&mut reader)? {
+            return self.load_binary_model(&bytes);
+        }
         let mut lines = reader.lines();
 
-        // Read the number of classes
-        let num_classes: usize = lines
-            .next()
-            .ok_or_else(|| LitseaError::InvalidData("Empty model file".to_string()))?
-            .map_err(|e| LitseaError::InvalidData(format!("Read error: {}", e)))?
+        // Read the metadata header lines, then the number of classes
+        let mut metadata = ModelMetadata::default();
+        let count_line = loop {
+            let line = lines
+                .next()
+                .ok_or_else(|| LitseaError::InvalidData("Empty model file".to_string()))?
+                .map_err(|e| LitseaError::InvalidData(format!("Read error: {}", e)))?;
+            match line.strip_prefix('#') {
+                Some(header) => {
+                    let (key, value) = header.split_once('\t').unwrap_or((header, ""));
+                    metadata.apply_header(key, value).map_err(LitseaError::InvalidData)?;
+                }
+                None => break line,
+            }
+        };
+        let num_classes: usize = count_line
             .trim()
             .parse()
             .map_err(|e| LitseaError::InvalidData(format!("Invalid class count: {}", e)))?;
@@ -519,6 +1836,7 @@ impl AveragedPerceptron {
         // zeroed accumulators, so replacing the map does exactly that.
         self.slots.clear();
         self.step = 0;
+        self.metadata = metadata;
         let n = self.classes.len();
         for line in lines {
             let line = line?;
@@ -575,18 +1893,20 @@ impl AveragedPerceptron {
         let mut total_correct = 0usize;
         let mut scores: Vec<f64> = Vec::new();
 
-        for (features, truth) in &self.instances {
-            let guess = match self.predict_idx_into(features.iter(), &mut scores) {
+        for (features, label) in &self.instances.instances {
+            let truth = self.instances.labels[*label as usize].as_str();
+            let features = self.instances.feature_names(features);
+            let guess = match self.predict_idx_into(features, &mut scores) {
                 Some(i) => self.classes[i].as_str(),
                 None => "",
             };
 
-            *gold_per_class.entry(truth.clone()).or_insert(0) += 1;
+            *gold_per_class.entry(truth.to_string()).or_insert(0) += 1;
             *predicted_per_class.entry(guess.to_string()).or_insert(0) += 1;
 
             if guess == truth {
                 total_correct += 1;
-                *correct_per_class.entry(truth.clone()).or_insert(0) += 1;
+                *correct_per_class.entry(truth.to_string()).or_insert(0) += 1;
             }
         }
 
@@ -619,6 +1939,8 @@ impl AveragedPerceptron {
             correct_per_class,
             predicted_per_class,
             gold_per_class,
+            features_removed: 0,
+            dev_curve: Vec::new(),
         }
     }
 }
@@ -707,6 +2029,135 @@ mod tests {
         assert_eq!(p.instances.len(), 1);
     }
 
+    #[test]
+    fn test_train_with_dev_reports_each_epoch() {
+        let mut p = AveragedPerceptron::new();
+        let feats = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<HashSet<_>>();
+        p.add_instance(feats(&["f1", "f2"]), "A".to_string());
+        p.add_instance(feats(&["f3", "f4"]), "B".to_string());
+        p.add_dev_instance(feats(&["f1"]), "A".to_string());
+        p.add_dev_instance(feats(&["f4", "f5"]), "B".to_string());
+        assert_eq!(p.num_dev_instances(), 2);
+
+        let running = AtomicBool::new(true);
+        let curve = p.train_with_dev(5, None, &running);
+
+        // Without a patience every epoch runs; the dev set is separable from
+        // the first epoch on, so the first epoch's weights are kept.
+        assert_eq!(curve, vec![100.0; 5]);
+        assert_eq!(p.step, 2);
+        assert_eq!(p.predict(&feats(&["f1"])), "A");
+        assert_eq!(p.predict(&feats(&["f4", "f5"])), "B");
+        // Dev instances are never trained on.
+        assert_eq!(p.instances.len(), 2);
+        assert!(!p.slots.contains_key("f5"));
+    }
+
+    #[test]
+    fn test_seeded_shuffle_order_is_pinned() {
+        // A seed's order is part of the reproducibility contract: changing
+        // the generator would change every seeded model.
+        let mut order: Vec<u32> = (0..10).collect();
+        let mut rng = SplitMix64(42);
+        rng.shuffle(&mut order);
+        assert_eq!(order, vec![8, 3, 6, 5, 4, 0, 9, 2, 1, 7]);
+        rng.shuffle(&mut order);
+        assert_eq!(order, vec![1, 6, 4, 8, 0, 7, 2, 5, 3, 9]);
+    }
+
+    #[test]
+    fn test_parameter_mixing_is_deterministic() -> Result<()> {
+        let learner = |threads: usize| {
+            let mut p = AveragedPerceptron::new();
+            let feats = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+            for i in 0..30 {
+                let label = ["A", "B", "C"][i % 3];
+                let noise = format!("n{}", i % 7);
+                p.add_instance(feats(&[label, &noise, "bias"]), label.to_string());
+            }
+            p.set_threads(threads);
+            p
+        };
+        let train = |threads: usize| -> Result<(AveragedPerceptron, Vec<u8>)> {
+            let mut p = learner(threads);
+            p.train(5, &AtomicBool::new(true));
+            let mut bytes = Vec::new();
+            p.save_model_to_writer(&mut bytes)?;
+            Ok((p, bytes))
+        };
+
+        let (mixed, bytes) = train(4)?;
+        assert_eq!(mixed.threads(), 4);
+        // Every shard's steps count, so the step counter matches a
+        // single-threaded run.
+        assert_eq!(mixed.step, 150);
+        assert_eq!(bytes, train(4)?.1, "two mixed runs disagreed");
+        assert_ne!(bytes, train(1)?.1);
+        for label in ["A", "B", "C"] {
+            let feats = [label, "n0", "bias"].iter().map(|n| n.to_string()).collect();
+            assert_eq!(mixed.predict(&feats), label);
+        }
+        // Zero threads is single-threaded training.
+        assert_eq!(learner(0).threads(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resumed_training_matches_an_uninterrupted_run() -> Result<()> {
+        for threads in [1, 3] {
+            check_resumed_run(threads)?;
+        }
+        Ok(())
+    }
+
+    /// Interrupts a run at its epoch-2 checkpoint and checks that resuming
+    /// it writes the model of the uninterrupted run.
+    fn check_resumed_run(threads: usize) -> Result<()> {
+        let learner = || {
+            let mut p = AveragedPerceptron::new();
+            let feats = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+            for i in 0..12 {
+                let label = ["A", "B", "C"][i % 3];
+                let noise = format!("n{}", i % 5);
+                p.add_instance(feats(&[label, &noise, "bias"]), label.to_string());
+            }
+            p.add_dev_instance(feats(&["A", "n4"]), "A".to_string());
+            p.add_dev_instance(feats(&["C", "n0"]), "C".to_string());
+            p.set_seed(Some(9));
+            p.set_threads(threads);
+            p
+        };
+        let model = |p: &AveragedPerceptron| -> Result<Vec<u8>> {
+            let mut bytes = Vec::new();
+            p.save_model_to_writer(&mut bytes)?;
+            Ok(bytes)
+        };
+
+        let mut reference = learner();
+        let curve = reference.train_with_dev(6, None, &AtomicBool::new(true));
+
+        // Stop at the epoch-2 checkpoint: the interrupted run hands over
+        // its state once more on the way out.
+        let running = AtomicBool::new(true);
+        let mut states = Vec::new();
+        let mut sink = |state: LearnerState| {
+            running.store(false, Ordering::SeqCst);
+            states.push(state);
+            Ok(())
+        };
+        let mut interrupted = learner();
+        let run = interrupted.train_checkpointed(6, None, &running, Some((2, &mut sink)), None)?;
+        assert!(!run.completed);
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[1].epochs_completed(), 2);
+
+        let mut resumed = learner();
+        resumed.resume(states.pop().unwrap())?;
+        assert_eq!(resumed.train_with_dev(6, None, &AtomicBool::new(true)), curve);
+        assert_eq!(model(&resumed)?, model(&reference)?);
+        Ok(())
+    }
+
     #[test]
     fn test_train_multiclass() {
         let mut p = AveragedPerceptron::new();
@@ -832,6 +2283,74 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_metadata_header_round_trip() -> Result<()> {
+        let text = "2\nB\nO\nf1\tB\t0.5\n";
+        let mut p = AveragedPerceptron::new();
+        p.load_model_from_reader(text.as_bytes())?;
+        assert!(p.metadata().is_empty());
+        let mut plain = Vec::new();
+        p.save_model_to_writer(&mut plain)?;
+        assert_eq!(plain, text.as_bytes());
+
+        let metadata = ModelMetadata::new()
+            .with_language(crate::language::Language::Chinese)
+            .with_param("epochs", 3);
+        p.set_metadata(metadata.clone());
+        let mut declared = Vec::new();
+        p.save_model_to_writer(&mut declared)?;
+        let header = "#language\tchinese\n#param.epochs\t3\n";
+        assert_eq!(std::str::from_utf8(&declared).unwrap(), format!("{header}{text}"));
+        assert_eq!(ModelKind::detect_bytes(&declared), ModelKind::AveragedPerceptron);
+        let mut binary = Vec::new();
+        p.save_binary_model_to_writer(&mut binary)?;
+
+        for bytes in [declared.as_slice(), binary.as_slice()] {
+            let mut loaded = AveragedPerceptron::new();
+            loaded.load_model_from_reader(bytes)?;
+            assert_eq!(loaded.metadata(), &metadata);
+            assert_eq!(loaded.classes(), ["B", "O"]);
+        }
+
+        for bad in ["#lang\tchinese\n2\nB\nO\n", "#language\tklingon\n2\nB\nO\n"] {
+            let result = AveragedPerceptron::new().load_model_from_reader(bad.as_bytes());
+            assert!(matches!(result, Err(LitseaError::InvalidData(_))), "{bad:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_model_round_trip() -> Result<()> {
+        let text = "3\nA\nB\nC\nf1\tA\t0.5\nf1\tC\t-0.1\nf2\tB\t2\n";
+        let mut p = AveragedPerceptron::new();
+        p.load_model_from_reader(text.as_bytes())?;
+        let mut binary = Vec::new();
+        p.save_binary_model_to_writer(&mut binary)?;
+        assert!(binary.starts_with(b"litsea-binary v1 perceptron\n"));
+
+        let mut loaded = AveragedPerceptron::new();
+        loaded.load_model_from_reader(binary.as_slice())?;
+        assert_eq!(loaded.classes(), ["A", "B", "C"]);
+        let mut back = Vec::new();
+        loaded.save_model_to_writer(&mut back)?;
+        assert_eq!(std::str::from_utf8(&back).unwrap(), text);
+
+        // Classes registered by training data are merged, as with text.
+        let mut merged = AveragedPerceptron::new();
+        let mut feats = HashSet::new();
+        feats.insert("f1".to_string());
+        merged.add_instance(feats.clone(), "AA".to_string());
+        merged.load_model_from_reader(binary.as_slice())?;
+        assert_eq!(merged.classes(), ["A", "AA", "B", "C"]);
+        assert_eq!(merged.slots["f1"].w, vec![0.5, 0.0, 0.0, -0.1]);
+        assert_eq!(merged.predict(&feats), "A");
+
+        for end in 0..binary.len() {
+            assert!(AveragedPerceptron::new().load_model_from_reader(&binary[..end]).is_err());
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_save_model_empty() {
         let p = AveragedPerceptron::new();
@@ -0,0 +1,164 @@
+//! Training progress reports.
+//!
+//! Defines [`TrainingProgress`], the report the trainers hand their
+//! [`ProgressObserver`] after every AdaBoost boosting iteration and every
+//! perceptron epoch, so a long run is no longer a black box until it
+//! returns its metrics. Attach an observer with `set_progress` on
+//! [`Trainer`](crate::trainer::Trainer),
+//! [`PerceptronTrainer`](crate::trainer::PerceptronTrainer),
+//! [`BoundaryPerceptronTrainer`](crate::trainer::BoundaryPerceptronTrainer),
+//! or [`TwoStageTrainer`](crate::trainer::TwoStageTrainer).
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The model a [`TrainingProgress`] report is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrainingStage {
+    /// AdaBoost segmentation training; one report per boosting iteration.
+    Boosting,
+    /// Generic perceptron training; one report per epoch.
+    Perceptron,
+    /// A boundary perceptron: a segmentation model, or stage 1 of a
+    /// two-stage model; one report per epoch.
+    Boundary,
+    /// Stage 2 of a two-stage model, the word-level tagger; one report per
+    /// epoch.
+    Tagging,
+}
+
+impl TrainingStage {
+    /// Returns the stage's name.
+    ///
+    /// # Returns
+    /// `"boosting"`, `"perceptron"`, `"boundary"`, or `"tagging"`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrainingStage::Boosting => "boosting",
+            TrainingStage::Perceptron => "perceptron",
+            TrainingStage::Boundary => "boundary",
+            TrainingStage::Tagging => "tagging",
+        }
+    }
+}
+
+impl fmt::Display for TrainingStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The progress of a training run after one boosting iteration or
+/// perceptron epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingProgress {
+    /// The model being trained.
+    pub stage: TrainingStage,
+    /// The iteration or epoch just completed, counting from 1 (a resumed
+    /// run continues the count of the run it resumes).
+    pub iteration: usize,
+    /// The configured number of iterations or epochs; training may stop
+    /// earlier.
+    pub total: usize,
+    /// The error rate of the iteration, in percent: for a boosting
+    /// iteration, the weighted error of the weak classifier it selected;
+    /// for a perceptron epoch, the share of instances it mispredicted.
+    pub error_rate: f64,
+    /// The number of updates the iteration made: the training instances
+    /// the selected weak classifier misclassifies, or the mispredicted
+    /// instances a perceptron epoch updated the weights for.
+    pub updates: usize,
+    /// The development-set accuracy after the epoch, in percent, when a
+    /// development set is loaded.
+    pub dev_accuracy: Option<f64>,
+    /// The time since training started.
+    pub elapsed: Duration,
+}
+
+/// Receives the [`TrainingProgress`] reports of a training run.
+///
+/// Implemented for every `FnMut(&TrainingProgress) + Send + Sync` closure;
+/// the bounds keep the trainers holding an observer `Send` and `Sync`. The
+/// trainer calls it on the training thread, between two iterations, so a
+/// slow observer slows training down.
+pub trait ProgressObserver: Send + Sync {
+    /// Handles one report.
+    ///
+    /// # Arguments
+    /// * `progress` - The progress after the iteration just completed.
+    fn on_progress(&mut self, progress: &TrainingProgress);
+}
+
+impl<F: FnMut(&TrainingProgress) + Send + Sync> ProgressObserver for F {
+    fn on_progress(&mut self, progress: &TrainingProgress) {
+        self(progress)
+    }
+}
+
+impl fmt::Debug for dyn ProgressObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressObserver")
+    }
+}
+
+/// Builds the reports of one stage of a run and hands them to its
+/// observer.
+pub(crate) struct Progress<'a> {
+    /// The observer receiving the reports.
+    observer: &'a mut dyn ProgressObserver,
+    /// The model being trained.
+    stage: TrainingStage,
+    /// The configured number of iterations or epochs.
+    total: usize,
+    /// When the run started.
+    start: Instant,
+}
+
+impl<'a> Progress<'a> {
+    /// Creates the reporter of one stage.
+    ///
+    /// # Arguments
+    /// * `observer` - The observer receiving the reports.
+    /// * `stage` - The model being trained.
+    /// * `total` - The configured number of iterations or epochs.
+    /// * `start` - When the run started, shared by the stages of a run.
+    pub(crate) fn new(
+        observer: &'a mut dyn ProgressObserver,
+        stage: TrainingStage,
+        total: usize,
+        start: Instant,
+    ) -> Self {
+        Progress {
+            observer,
+            stage,
+            total,
+            start,
+        }
+    }
+
+    /// Reports a completed iteration.
+    ///
+    /// # Arguments
+    /// * `iteration` - The iteration just completed, counting from 1.
+    /// * `error_rate` - Its error rate, in percent.
+    /// * `updates` - The number of updates it made.
+    /// * `dev_accuracy` - The development-set accuracy, if measured.
+    pub(crate) fn report(
+        &mut self,
+        iteration: usize,
+        error_rate: f64,
+        updates: usize,
+        dev_accuracy: Option<f64>,
+    ) {
+        self.observer.on_progress(&TrainingProgress {
+            stage: self.stage,
+            iteration,
+            total: self.total,
+            error_rate,
+            updates,
+            dev_accuracy,
+            elapsed: self.start.elapsed(),
+        });
+    }
+}
@@ -11,13 +11,31 @@ use std::sync::{PoisonError, RwLock};
 
 use crate::adaboost::AdaBoost;
 use crate::error::{LitseaError, Result};
+use crate::grapheme;
 use crate::language::{Language, OTHER_TYPE_ID};
+use crate::normalize::Normalization;
 use crate::packed_model::{
-    PackedModel, SENTINEL_BASE, Slot, TAG_B, TAG_O, TAG_U, TEMPLATES, templates_for,
+    DICTIONARY_FEATURES, PackedModel, SENTINEL_BASE, Slot, TAG_B, TAG_O, TAG_U, TEMPLATES,
+    dictionary_masks_into, is_tag_dependent_feature, push_dictionary_feature, templates_for,
 };
 use crate::packed_two_stage::PackedTwoStageModel;
+use crate::partial::{self, PartialSentence};
+use crate::pattern::PatternSet;
+use crate::sentence;
 use crate::two_stage::TwoStageLearner;
 use crate::upos::{SegmentLabel, Upos};
+use crate::user_dict::UserDictionary;
+
+/// Boundary constraint ids for [`SegmentBuffer`]'s `constraints` scratch:
+/// the model decides (no dictionary match covers the position), a user
+/// dictionary match starts or ends at it, or it falls inside a match.
+const CONSTRAINT_FREE: u8 = 0;
+const CONSTRAINT_BOUNDARY: u8 = 1;
+const CONSTRAINT_NO_BOUNDARY: u8 = 2;
+
+/// The most passes [`Segmenter::learn`] makes over one sentence: an update
+/// at one position can undo an earlier one through the features they share.
+const MAX_LEARN_PASSES: usize = 10;
 
 /// Reusable scratch and output storage for
 /// [`Segmenter::segment_into`], the allocation-free variant of
@@ -48,8 +66,21 @@ pub struct SegmentBuffer {
     /// Boundary-tag scratch for the sequential pass (unused on the
     /// pointwise fast path, #183).
     tags: Vec<u8>,
+    /// Per-position user-dictionary constraints (`CONSTRAINT_*`); empty
+    /// when the segmenter has no user dictionary.
+    constraints: Vec<u8>,
+    /// Per-character dictionary-match feature masks of the model's
+    /// dictionary (see `packed_model::dictionary_masks_into`); filled only
+    /// for a model with dictionary features.
+    dictionary_masks: Vec<u32>,
+    /// Per-position decision margins, recorded only by
+    /// `Segmenter::segment_with_scores_into`.
+    margins: Vec<f64>,
     /// Output: byte ranges of the segmented tokens, in order.
     ranges: Vec<(usize, usize)>,
+    /// Output: one [`TokenScore`] per range, filled only by
+    /// `Segmenter::segment_with_scores_into`.
+    scores: Vec<TokenScore>,
 }
 
 impl SegmentBuffer {
@@ -65,6 +96,42 @@ impl SegmentBuffer {
     }
 }
 
+/// Boundary confidence for one token, returned by
+/// [`Segmenter::segment_with_scores`].
+///
+/// A margin is the raw score a boundary decision compared against the
+/// threshold, minus that threshold (see [`Segmenter::with_threshold`]): a
+/// position becomes a boundary when its margin is non-negative, so the
+/// closer a margin is to zero, the closer the decision came to going the
+/// other way. A position decided by a user dictionary constraint reports
+/// an infinite margin in the direction it was forced.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TokenScore {
+    /// Margin of the boundary that starts this token (non-negative), or
+    /// `None` for the sentence's first token, which no decision starts.
+    pub boundary: Option<f64>,
+    /// Highest margin among the rejected boundaries inside this token
+    /// (negative) — the split that came closest to happening — or `None`
+    /// for a single-character token.
+    pub inner: Option<f64>,
+}
+
+impl TokenScore {
+    /// Returns the token's confidence: the distance from zero of its
+    /// weakest decision, `min(boundary, -inner)`.
+    ///
+    /// # Returns
+    /// A non-negative value; small values flag tokens worth reviewing. A
+    /// missing side counts as infinitely confident, so a single-character
+    /// first token yields `f64::INFINITY`.
+    #[must_use]
+    pub fn confidence(&self) -> f64 {
+        let boundary = self.boundary.unwrap_or(f64::INFINITY);
+        let inner = self.inner.map_or(f64::INFINITY, |margin| -margin);
+        boundary.min(inner)
+    }
+}
+
 /// Text segmenter supporting two modes: word segmentation via AdaBoost
 /// binary classification, and two-stage word segmentation + POS tagging via
 /// a boundary classifier, lexicon, and word-level tagger (see
@@ -101,6 +168,21 @@ pub struct Segmenter {
     /// compilation, invalidate on mutation) rather than mutating this field
     /// directly.
     two_stage: Option<PackedTwoStageModel>,
+    /// Words that must come out as single tokens, overriding the model's
+    /// boundary decisions for matched spans (see
+    /// [`with_user_dictionary`](Self::with_user_dictionary)).
+    user_dictionary: Option<UserDictionary>,
+    /// Patterns whose matches must come out as single tokens, such as URLs
+    /// and numbers (see [`with_patterns`](Self::with_patterns)).
+    patterns: Option<PatternSet>,
+    /// Offset added to the boundary decision threshold (see
+    /// [`with_threshold`](Self::with_threshold)); 0.0 keeps the model's
+    /// own `score >= 0` rule.
+    threshold: f64,
+    /// Text normalization override (see
+    /// [`with_normalization`](Self::with_normalization)); `None` follows
+    /// the normalization the learner's model declares.
+    normalization: Option<Normalization>,
 }
 
 // Compile-time assertion: parallel batch callers (e.g. the CLI's
@@ -144,24 +226,63 @@ impl Segmenter {
     /// Creates a new instance of [`Segmenter`] with the given AdaBoost
     /// learner (typically one that has loaded a trained model).
     ///
+    /// If the learner was loaded from a binary model carrying packed
+    /// tables compiled for `language`, the segmenter adopts them instead
+    /// of compiling its own.
+    ///
+    /// The language the model records in its metadata, if any, is not
+    /// checked; use [`try_with_learner`](Self::try_with_learner) to reject
+    /// a model trained for another language.
+    ///
     /// # Arguments
     /// * `language` - The language to use for character type classification.
     /// * `learner` - The AdaBoost learner to segment with.
     ///
     /// # Returns
     /// A new Segmenter instance with the specified language and learner.
-    pub fn with_learner(language: Language, learner: AdaBoost) -> Self {
+    pub fn with_learner(language: Language, mut learner: AdaBoost) -> Self {
         // Compile the packed scoring table eagerly so the common
         // load-then-segment path never rebuilds mid-stream.
-        let packed = RwLock::new(Some(PackedModel::build(language, &learner)));
+        let packed = learner
+            .take_compiled(language)
+            .unwrap_or_else(|| PackedModel::build(language, &learner));
+        let packed = RwLock::new(Some(packed));
         Segmenter {
             language,
             learner,
             packed,
             two_stage: None,
+            user_dictionary: None,
+            patterns: None,
+            threshold: 0.0,
+            normalization: None,
         }
     }
 
+    /// Creates a new instance of [`Segmenter`] with the given AdaBoost
+    /// learner, checking that the model was trained for `language`.
+    ///
+    /// Identical to [`with_learner`](Self::with_learner) except that a
+    /// model whose [`metadata`](AdaBoost::metadata) records another
+    /// training language is rejected instead of silently segmenting with
+    /// character types it never saw. A model that records no language is
+    /// accepted.
+    ///
+    /// # Arguments
+    /// * `language` - The language to use for character type classification.
+    /// * `learner` - The AdaBoost learner to segment with.
+    ///
+    /// # Returns
+    /// A new Segmenter instance with the specified language and learner.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::LanguageMismatch`] if the model records a
+    /// different language.
+    pub fn try_with_learner(language: Language, learner: AdaBoost) -> Result<Self> {
+        learner.metadata().check_language(language)?;
+        Ok(Self::with_learner(language, learner))
+    }
+
     /// Creates a new instance of [`Segmenter`] with a two-stage model
     /// (issue #147): the learner's stage-1 boundary classifier becomes the
     /// segmenter's AdaBoost-path learner (so [`segment`](Self::segment)
@@ -172,28 +293,279 @@ impl Segmenter {
     /// # Arguments
     /// * `language` - The language to use for character type classification.
     /// * `learner` - The two-stage learner (typically one that has loaded
-    ///   a `litsea-two-stage v1` model).
+    ///   a `litsea-two-stage v1` model). Packed tables carried by a binary
+    ///   model compiled for `language` are adopted instead of rebuilt.
+    ///
+    /// Like [`with_learner`](Self::with_learner), this does not check the
+    /// model's recorded language (see
+    /// [`try_with_two_stage_learner`](Self::try_with_two_stage_learner)).
     ///
     /// # Returns
     /// A new Segmenter instance configured for two-stage segmentation +
     /// POS tagging.
-    pub fn with_two_stage_learner(language: Language, learner: TwoStageLearner) -> Self {
-        let (stage1, stage2, lexicon, dominance) = learner.into_parts();
+    pub fn with_two_stage_learner(language: Language, mut learner: TwoStageLearner) -> Self {
+        let compiled = learner.take_compiled(language);
+        let (mut stage1, stage2, lexicon, dominance) = learner.into_parts();
         // Compile both packed tables eagerly so the common
         // load-then-segment path never rebuilds mid-stream. The raw stage-2
         // parts are dropped after compilation: the packed model contains
         // everything the tagging path needs, and there is no mutation path
         // that would require rebuilding it (see the `two_stage` field doc).
-        let packed = RwLock::new(Some(PackedModel::build(language, &stage1)));
-        let two_stage = PackedTwoStageModel::build(language, &stage2, &lexicon, dominance);
+        let packed = stage1
+            .take_compiled(language)
+            .unwrap_or_else(|| PackedModel::build(language, &stage1));
+        let packed = RwLock::new(Some(packed));
+        let two_stage = compiled
+            .unwrap_or_else(|| PackedTwoStageModel::build(language, &stage2, &lexicon, dominance));
         Segmenter {
             language,
             learner: stage1,
             packed,
             two_stage: Some(two_stage),
+            user_dictionary: None,
+            patterns: None,
+            threshold: 0.0,
+            normalization: None,
         }
     }
 
+    /// Creates a new instance of [`Segmenter`] with a two-stage model,
+    /// checking that the model was trained for `language`.
+    ///
+    /// Identical to [`with_two_stage_learner`](Self::with_two_stage_learner)
+    /// except that a model whose metadata (that of the two-stage model or
+    /// of its stage-1 classifier) records another training language is
+    /// rejected. A model that records no language is accepted.
+    ///
+    /// # Arguments
+    /// * `language` - The language to use for character type classification.
+    /// * `learner` - The two-stage learner.
+    ///
+    /// # Returns
+    /// A new Segmenter instance configured for two-stage segmentation +
+    /// POS tagging.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::LanguageMismatch`] if the model records a
+    /// different language.
+    pub fn try_with_two_stage_learner(
+        language: Language,
+        learner: TwoStageLearner,
+    ) -> Result<Self> {
+        learner.metadata().check_language(language)?;
+        learner.stage1().metadata().check_language(language)?;
+        Ok(Self::with_two_stage_learner(language, learner))
+    }
+
+    /// Attaches a user dictionary whose words override the model's
+    /// boundary decisions.
+    ///
+    /// Every leftmost-longest match of a dictionary word in the sentence
+    /// (see [`UserDictionary`]) comes out of [`segment`](Self::segment),
+    /// [`segment_into`](Self::segment_into) and
+    /// [`segment_with_pos`](Self::segment_with_pos) as a single token: a
+    /// boundary is forced at both ends of the match and every boundary
+    /// inside it is forbidden, whatever the model scores. Positions outside
+    /// any match are decided by the model as before. For a tag-dependent
+    /// model the constrained decisions also feed the later positions' tag
+    /// features, exactly as if the model had made them.
+    ///
+    /// # Arguments
+    /// * `dictionary` - The words to keep whole; replaces any dictionary
+    ///   attached earlier.
+    ///
+    /// # Returns
+    /// The segmenter with the dictionary attached.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use litsea::adaboost::AdaBoost;
+    /// use litsea::language::Language;
+    /// use litsea::segmenter::Segmenter;
+    /// use litsea::user_dict::UserDictionary;
+    ///
+    /// let model_file =
+    ///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("RWCP.model");
+    /// let mut learner = AdaBoost::new(0.01, 100);
+    /// learner.load_model_from_path(&model_file).unwrap();
+    ///
+    /// let dictionary: UserDictionary = ["テストです"].into_iter().collect();
+    /// let segmenter =
+    ///     Segmenter::with_learner(Language::Japanese, learner).with_user_dictionary(dictionary);
+    /// assert_eq!(segmenter.segment("これはテストです。"), vec!["これ", "は", "テストです", "。"]);
+    /// ```
+    #[must_use]
+    pub fn with_user_dictionary(mut self, dictionary: UserDictionary) -> Self {
+        self.user_dictionary = Some(dictionary);
+        self
+    }
+
+    /// Attaches a pattern set whose matches come out as atomic tokens.
+    ///
+    /// Every leftmost-longest match of the set in the sentence (see
+    /// [`PatternSet`]), e.g. a URL, an e-mail address, or a number like
+    /// `1,234.5`, is kept whole exactly like a user dictionary word: a
+    /// boundary is forced at both ends and every boundary inside is
+    /// forbidden, while the model segments the text around it, so the
+    /// ranges still tile the sentence. Patterns run on the original text,
+    /// before any [`normalization`](Self::normalization). Where a user
+    /// dictionary match overlaps a pattern match, the dictionary wins.
+    ///
+    /// # Arguments
+    /// * `patterns` - The patterns to keep whole; replaces any set
+    ///   attached earlier.
+    ///
+    /// # Returns
+    /// The segmenter with the patterns attached.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use litsea::adaboost::AdaBoost;
+    /// use litsea::language::Language;
+    /// use litsea::pattern::PatternSet;
+    /// use litsea::segmenter::Segmenter;
+    ///
+    /// let model_file =
+    ///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("RWCP.model");
+    /// let mut learner = AdaBoost::new(0.01, 100);
+    /// learner.load_model_from_path(&model_file).unwrap();
+    ///
+    /// let segmenter =
+    ///     Segmenter::with_learner(Language::Japanese, learner).with_patterns(PatternSet::builtin());
+    /// let tokens = segmenter.segment("詳細はhttps://example.com/docsへ");
+    /// assert!(tokens.contains(&"https://example.com/docs".to_string()));
+    /// ```
+    #[must_use]
+    pub fn with_patterns(mut self, patterns: PatternSet) -> Self {
+        self.patterns = Some(patterns);
+        self
+    }
+
+    /// Offsets the boundary decision threshold, making segmentation
+    /// coarser or finer from one model.
+    ///
+    /// A position becomes a boundary when its score is at least
+    /// `threshold` instead of 0: a positive offset keeps only confident
+    /// boundaries (coarser tokens, e.g. for display), a negative one also
+    /// accepts weak ones (finer tokens, e.g. for search indexing). The
+    /// offset applies to [`segment`](Self::segment),
+    /// [`segment_into`](Self::segment_into),
+    /// [`segment_with_scores`](Self::segment_with_scores) (whose margins
+    /// are measured from it), and [`segment_with_pos`](Self::segment_with_pos);
+    /// user dictionary constraints still win.
+    ///
+    /// # Arguments
+    /// * `threshold` - The offset; `0.0` is the model's own decision rule.
+    ///
+    /// # Returns
+    /// The segmenter with the offset applied.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use litsea::adaboost::AdaBoost;
+    /// use litsea::language::Language;
+    /// use litsea::segmenter::Segmenter;
+    ///
+    /// let model_file =
+    ///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("RWCP.model");
+    /// let mut learner = AdaBoost::new(0.01, 100);
+    /// learner.load_model_from_path(&model_file).unwrap();
+    ///
+    /// let segmenter = Segmenter::with_learner(Language::Japanese, learner).with_threshold(1e9);
+    /// assert_eq!(segmenter.segment("これはテストです。"), vec!["これはテストです。"]);
+    /// ```
+    #[must_use]
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the Unicode normalization applied to input before it is
+    /// segmented, overriding the one the model declares (see
+    /// [`AdaBoost::normalization`]).
+    ///
+    /// Segmentation then runs on the normalized text, but every returned
+    /// range still indexes the caller's original string: normalized
+    /// characters map back to the original characters they came from, and
+    /// no boundary is placed inside the expansion of a single original
+    /// character (`㍿` stays one token even though it normalizes to
+    /// `株式会社`). [`segment_with_pos`](Self::segment_with_pos) tags the
+    /// normalized form of each word, and corpus processing
+    /// ([`add_corpus`](Self::add_corpus) and the extractor built on it)
+    /// normalizes every corpus word, so training and inference see the
+    /// same text. User dictionary words are matched against the normalized
+    /// text.
+    ///
+    /// # Arguments
+    /// * `normalization` - The normalization form to apply.
+    ///
+    /// # Returns
+    /// The segmenter with the normalization set.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use litsea::adaboost::AdaBoost;
+    /// use litsea::language::Language;
+    /// use litsea::normalize::Normalization;
+    /// use litsea::segmenter::Segmenter;
+    ///
+    /// let model_file =
+    ///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("RWCP.model");
+    /// let mut learner = AdaBoost::new(0.01, 100);
+    /// learner.load_model_from_path(&model_file).unwrap();
+    ///
+    /// let segmenter = Segmenter::with_learner(Language::Japanese, learner)
+    ///     .with_normalization(Normalization::Nfkc);
+    /// let result = segmenter.segment("これはﾃｽﾄです。");
+    /// assert_eq!(result, vec!["これ", "は", "ﾃｽﾄ", "です", "。"]);
+    /// ```
+    #[must_use]
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Returns the normalization applied to input.
+    ///
+    /// # Returns
+    /// The form set by [`with_normalization`](Self::with_normalization),
+    /// or else the one the learner's model declares
+    /// ([`Normalization::None`] for a model without a declaration).
+    #[must_use]
+    pub fn normalization(&self) -> Normalization {
+        self.normalization.unwrap_or_else(|| self.learner.normalization())
+    }
+
+    /// Returns the boundary decision threshold offset.
+    ///
+    /// # Returns
+    /// The offset set by [`with_threshold`](Self::with_threshold), 0.0 by
+    /// default.
+    #[must_use]
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Returns the attached user dictionary, if any.
+    #[must_use]
+    pub fn user_dictionary(&self) -> Option<&UserDictionary> {
+        self.user_dictionary.as_ref()
+    }
+
+    /// Returns the attached pattern set, if any.
+    #[must_use]
+    pub fn patterns(&self) -> Option<&PatternSet> {
+        self.patterns.as_ref()
+    }
+
     /// Returns the language this segmenter was created for.
     #[must_use]
     pub fn language(&self) -> Language {
@@ -265,6 +637,19 @@ impl Segmenter {
         (chars, types)
     }
 
+    /// Returns the dictionary-match feature masks of a text's characters
+    /// for the string writer (see
+    /// [`write_attributes`](Self::write_attributes)), or an empty vector
+    /// when the model has no dictionary.
+    fn dictionary_masks(&self, text: &str) -> Vec<u32> {
+        let mut masks = Vec::new();
+        if let Some(dictionary) = self.learner.dictionary() {
+            let codes: Vec<u32> = text.chars().map(u32::from).collect();
+            dictionary_masks_into(dictionary, &codes, &mut masks);
+        }
+        masks
+    }
+
     /// Fills `buf`'s context arrays for [`segment_into`](Self::segment_into):
     /// the same sentinel layout as [`packed_context`](Self::packed_context)
     /// for `char_codes` / `type_ids`, plus `char_starts` holding the byte
@@ -272,6 +657,13 @@ impl Segmenter {
     /// covering real characters `r..s` spans bytes
     /// `char_starts[r]..char_starts[s]`). No string slices are stored, which
     /// is what lets the buffer be reused across sentences.
+    ///
+    /// With a [`normalization`](Self::normalization), the codes and types
+    /// describe the normalized text while `char_starts` keeps offsets into
+    /// the original: every character of one normalization chunk gets the
+    /// chunk's start, which is how the output ranges map back (and how
+    /// [`constraints_into`](Self::constraints_into) finds the positions
+    /// inside a chunk).
     fn packed_context_into(&self, text: &str, buf: &mut SegmentBuffer) {
         buf.char_codes.clear();
         buf.type_ids.clear();
@@ -279,11 +671,11 @@ impl Segmenter {
         buf.char_codes
             .extend_from_slice(&[SENTINEL_BASE, SENTINEL_BASE + 1, SENTINEL_BASE + 2]);
         buf.type_ids.extend_from_slice(&[OTHER_TYPE_ID; 3]);
-        for (i, ch) in text.char_indices() {
+        self.normalization().for_each_char(text, |i, ch| {
             buf.char_starts.push(i);
             buf.char_codes.push(u32::from(ch));
             buf.type_ids.push(self.language.char_type_id(ch));
-        }
+        });
         buf.char_starts.push(text.len());
         buf.char_codes.extend_from_slice(&[
             SENTINEL_BASE + 3,
@@ -293,6 +685,78 @@ impl Segmenter {
         buf.type_ids.extend_from_slice(&[OTHER_TYPE_ID; 3]);
     }
 
+    /// Fills `buf.constraints` from the pattern set's matches over the
+    /// original `text` and the user dictionary's matches over the real
+    /// characters in `buf.char_codes` (filled by
+    /// [`packed_context_into`](Self::packed_context_into)), in that order
+    /// so a dictionary edge wins over a pattern, and forbids a
+    /// boundary inside any normalization chunk, which has no original
+    /// offset to map to, and inside any extended grapheme cluster of the
+    /// original `text`, so a base character never loses its combining marks
+    /// and an emoji sequence or flag is never cut. The cluster rule is
+    /// applied last and wins over a dictionary match edge. Left empty when
+    /// none applies, so the decision loops read every position as
+    /// [`CONSTRAINT_FREE`].
+    fn constraints_into(&self, text: &str, buf: &mut SegmentBuffer) {
+        buf.constraints.clear();
+        let n = buf.char_codes.len();
+        // Real character r sits at decision position r + 3; a boundary
+        // there means r starts a word.
+        if let Some(patterns) = &self.patterns {
+            let starts = &buf.char_starts;
+            let real_len = starts.len() - 1;
+            let constraints = &mut buf.constraints;
+            patterns.for_each_match(text, |start, end| {
+                // A byte offset inside a normalization chunk rounds to the
+                // next chunk's first character.
+                let start = starts.partition_point(|&b| b < start);
+                let end = starts.partition_point(|&b| b < end);
+                if start == end {
+                    return;
+                }
+                constraints.resize(n, CONSTRAINT_FREE);
+                constraints[start + 3] = CONSTRAINT_BOUNDARY;
+                constraints[start + 4..end + 3].fill(CONSTRAINT_NO_BOUNDARY);
+                if end < real_len {
+                    constraints[end + 3] = CONSTRAINT_BOUNDARY;
+                }
+            });
+        }
+        if let Some(dictionary) = &self.user_dictionary {
+            let real = &buf.char_codes[3..n - 3];
+            let constraints = &mut buf.constraints;
+            constraints.resize(n, CONSTRAINT_FREE);
+            dictionary.for_each_match(real, |start, end| {
+                constraints[start + 3] = CONSTRAINT_BOUNDARY;
+                constraints[start + 4..end + 3].fill(CONSTRAINT_NO_BOUNDARY);
+                if end < real.len() {
+                    constraints[end + 3] = CONSTRAINT_BOUNDARY;
+                }
+            });
+        }
+        // The final char_starts entry is the text length, never shared.
+        let starts = &buf.char_starts;
+        for r in 1..starts.len() - 1 {
+            if starts[r] == starts[r - 1] {
+                buf.constraints.resize(n, CONSTRAINT_FREE);
+                buf.constraints[r + 3] = CONSTRAINT_NO_BOUNDARY;
+            }
+        }
+        // Offsets arrive in increasing order, so one forward walk over
+        // char_starts finds each continuation's character. A continuation
+        // folded into a normalization chunk has no position of its own.
+        let mut r = 0;
+        grapheme::for_each_continuation(text, |offset| {
+            while buf.char_starts[r] < offset {
+                r += 1;
+            }
+            if buf.char_starts[r] == offset {
+                buf.constraints.resize(n, CONSTRAINT_FREE);
+                buf.constraints[r + 3] = CONSTRAINT_NO_BOUNDARY;
+            }
+        });
+    }
+
     /// Runs `f` with the packed scoring table, rebuilding it first if a
     /// learner mutation invalidated it. The fast path takes only an
     /// uncontended read lock (one per sentence).
@@ -340,8 +804,12 @@ impl Segmenter {
         let mut tags: Vec<&'static str> = vec!["U"; 3];
         let mut labels: Vec<L> = Vec::new();
         let mut text = String::new();
+        let normalization = self.normalization();
 
         for (word, label) in tokens {
+            // Each word is normalized on its own, so the corpus boundaries
+            // survive and every word keeps at least one character.
+            let word = normalization.normalize(word);
             let char_count = word.chars().count();
             if char_count == 0 {
                 continue;
@@ -352,7 +820,7 @@ impl Segmenter {
                 tags.push("O");
                 labels.push(cont_label.clone());
             }
-            text.push_str(word);
+            text.push_str(&word);
         }
 
         if tags.len() < 4 {
@@ -363,10 +831,11 @@ impl Segmenter {
         tags[3] = "U";
 
         let (chars, types) = self.sentence_context(&text);
+        let masks = self.dictionary_masks(&text);
 
         let first = if include_first { 3 } else { 4 };
         for i in first..(chars.len() - 3) {
-            let attrs = self.get_attributes(i, &tags, &chars, &types);
+            let attrs = self.get_attributes(i, &tags, &chars, &types, &masks);
             callback(attrs, labels[i - 3].clone());
         }
     }
@@ -424,6 +893,53 @@ impl Segmenter {
         self.process_tokens(tokens, SegmentLabel::O, true, callback);
     }
 
+    /// Processes a partially annotated sentence, calling the callback for
+    /// each annotated character position with its attributes and whether a
+    /// word starts there.
+    ///
+    /// The attributes are built from the whole sentence, unannotated
+    /// positions included. A row whose tag window (the three previous
+    /// positions) covers an unannotated position gets no tag-dependent
+    /// attributes: its history is unknown, and the `U` tag stands only for
+    /// the sentence start at inference. `include_first` is as in
+    /// [`process_tokens`](Self::process_tokens).
+    pub(crate) fn process_partial<F>(
+        &self,
+        sentence: &PartialSentence,
+        include_first: bool,
+        mut callback: F,
+    ) where
+        F: FnMut(HashSet<String>, bool),
+    {
+        if sentence.starts.is_empty() {
+            return;
+        }
+        let mut tags: Vec<&'static str> = vec!["U"; 3];
+        tags.extend(sentence.starts.iter().map(|start| match start {
+            Some(true) => "B",
+            Some(false) => "O",
+            None => "U",
+        }));
+        tags[3] = "U";
+
+        let (chars, types) = self.sentence_context(&sentence.text);
+        let masks = self.dictionary_masks(&sentence.text);
+
+        let first = if include_first { 3 } else { 4 };
+        for i in first..(chars.len() - 3) {
+            if let Some(start) = sentence.starts[i - 3] {
+                let mut attrs = self.get_attributes(i, &tags, &chars, &types, &masks);
+                // tags[j] is the decision at character j - 3; the first
+                // character's `U` is the real sentence start.
+                let unknown_history = ((i - 3).max(4)..i).any(|j| sentence.starts[j - 3].is_none());
+                if unknown_history {
+                    attrs.retain(|attr| !is_tag_dependent_feature(attr));
+                }
+                callback(attrs, start);
+            }
+        }
+    }
+
     /// Adds a corpus to the segmenter with a custom writer function.
     ///
     /// # Arguments
@@ -555,6 +1071,154 @@ impl Segmenter {
         }
     }
 
+    /// Learns from a corrected segmentation, updating the boundary weights
+    /// in place so the next [`segment`](Self::segment) call already
+    /// reflects the correction.
+    ///
+    /// Every position of the sentence but the first is scored with the
+    /// corrected words as its boundary history, and each one that does not
+    /// score at least `margin` on its correct side (past the
+    /// [`threshold`](Self::threshold) offset) gets a passive-aggressive
+    /// perceptron update (see [`AdaBoost::update`]). Since positions share
+    /// features, the sentence is revisited until no position needs one, up
+    /// to 10 passes, after which `segment` reproduces the corrected words
+    /// unless a user dictionary or pattern overrides them. A pointwise
+    /// (tag-free) model stays pointwise.
+    ///
+    /// The update goes through [`learner_mut`](Self::learner_mut), so the
+    /// packed scoring table is rebuilt; persist the result with the
+    /// learner's [`save_model`](AdaBoost::save_model). For a two-stage
+    /// segmenter only the stage-1 boundary weights change.
+    ///
+    /// # Arguments
+    /// * `sentence` - The sentence, as passed to `segment`.
+    /// * `words` - Its corrected segmentation; the words must spell out
+    ///   `sentence` exactly.
+    /// * `margin` - The score every position must reach, in the units of
+    ///   the model's weights: smaller values change the model less.
+    ///
+    /// # Returns
+    /// The number of updates applied; 0 when the model already segments
+    /// the sentence as corrected, by the margin.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the words do not spell out
+    /// the sentence, `margin` is not positive and finite, or the threshold
+    /// offset is not finite.
+    ///
+    /// # Example
+    /// ```
+    /// use litsea::language::Language;
+    /// use litsea::segmenter::Segmenter;
+    ///
+    /// let mut segmenter = Segmenter::new(Language::Japanese);
+    /// segmenter.learn("テストです", &["テスト", "です"], 1.0).unwrap();
+    /// assert_eq!(segmenter.segment("テストです"), ["テスト", "です"]);
+    /// ```
+    pub fn learn(&mut self, sentence: &str, words: &[&str], margin: f64) -> Result<usize> {
+        if words.concat() != sentence {
+            return Err(LitseaError::InvalidInput(format!(
+                "the corrected words {:?} do not spell out '{}'",
+                words, sentence
+            )));
+        }
+        if !(margin.is_finite() && margin > 0.0) {
+            return Err(LitseaError::InvalidInput(format!(
+                "margin must be positive and finite, got {}",
+                margin
+            )));
+        }
+        let threshold = self.threshold;
+        if !threshold.is_finite() {
+            return Err(LitseaError::InvalidInput(format!(
+                "cannot learn under an infinite threshold offset ({})",
+                threshold
+            )));
+        }
+
+        let tag_free = self.learner.is_tag_free();
+        let mut instances = Vec::new();
+        self.process_tokens(words.iter().map(|&word| (word, 1)), -1, false, |mut attrs, label| {
+            if tag_free {
+                attrs.retain(|a| !is_tag_dependent_feature(a));
+            }
+            instances.push((attrs, label));
+        });
+
+        // Mutate through learner_mut() so the packed scoring table is
+        // invalidated alongside the learner change.
+        let learner = self.learner_mut();
+        let mut updates = 0;
+        for _ in 0..MAX_LEARN_PASSES {
+            let before = updates;
+            for (attrs, label) in &instances {
+                // segment() decides `score - threshold >= 0`.
+                let target = margin + f64::from(*label) * threshold;
+                if learner.update(attrs, *label, target) {
+                    updates += 1;
+                }
+            }
+            if updates == before {
+                break;
+            }
+        }
+        Ok(updates)
+    }
+
+    /// Adds a partially annotated corpus line (KyTea-style) to the
+    /// segmenter with a custom writer function.
+    ///
+    /// Corpus format: the sentence's characters with one delimiter between
+    /// every two of them: `|` (a word starts at the next character), `-`
+    /// (it continues the word), or a space (unannotated); a backslash makes
+    /// the next character part of the text. For example, `コ-ー-ス|が|設 定`
+    /// annotates every position but the one inside `設定`.
+    ///
+    /// # Arguments
+    /// * `corpus` - A partially annotated corpus line.
+    /// * `writer` - A closure that receives each annotated character
+    ///   position's attribute set and boundary label (1 = word start, -1 =
+    ///   continuation).
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the line is malformed: a
+    /// delimiter missing, doubled, or at either end, or a trailing
+    /// backslash.
+    ///
+    /// # Note
+    /// The writer is called only for annotated positions, except the first
+    /// (like [`add_corpus_with_writer`](Self::add_corpus_with_writer)); the
+    /// attributes still see the whole sentence, so an unannotated
+    /// neighbour contributes its characters to the context. The
+    /// tag-dependent templates read `U` where a previous position is
+    /// unannotated.
+    ///
+    /// # Example
+    /// ```
+    /// use litsea::language::Language;
+    /// use litsea::segmenter::Segmenter;
+    ///
+    /// let segmenter = Segmenter::new(Language::Japanese);
+    /// let mut labels = Vec::new();
+    /// segmenter
+    ///     .add_corpus_partial_with_writer("コ-ー-ス|が|設 定", |_, label| labels.push(label))
+    ///     .unwrap();
+    /// assert_eq!(labels, vec![-1, -1, 1, 1]);
+    /// ```
+    pub fn add_corpus_partial_with_writer<F>(&self, corpus: &str, mut writer: F) -> Result<()>
+    where
+        F: FnMut(HashSet<String>, i8),
+    {
+        let sentence = partial::parse_partial_line(corpus, false, self.normalization())?;
+        self.process_partial(&sentence, false, |attrs, start| {
+            writer(attrs, if start { 1 } else { -1 });
+        });
+        Ok(())
+    }
+
     /// Processes a POS-tagged corpus's features with a custom writer.
     ///
     /// # Arguments
@@ -697,15 +1361,259 @@ impl Segmenter {
         sentence: &str,
         buf: &'b mut SegmentBuffer,
     ) -> &'b [(usize, usize)] {
+        self.segment_ranges_into(sentence, buf, false);
+        &buf.ranges
+    }
+
+    /// Segments a sentence and scores each token's boundary decisions, so
+    /// low-confidence segmentations can be flagged for review.
+    ///
+    /// The tokens are exactly those of [`segment`](Self::segment); each is
+    /// paired with the margin of the boundary that started it and the
+    /// strongest boundary rejected inside it (see [`TokenScore`]).
+    ///
+    /// # Arguments
+    /// * `sentence` - The sentence to segment.
+    ///
+    /// # Returns
+    /// The tokens with their scores, in order. Empty for an empty sentence.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use litsea::adaboost::AdaBoost;
+    /// use litsea::language::Language;
+    /// use litsea::segmenter::Segmenter;
+    ///
+    /// let model_file =
+    ///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("RWCP.model");
+    /// let mut learner = AdaBoost::new(0.01, 100);
+    /// learner.load_model_from_path(&model_file).unwrap();
+    /// let segmenter = Segmenter::with_learner(Language::Japanese, learner);
+    ///
+    /// let scored = segmenter.segment_with_scores("これはテストです。");
+    /// assert_eq!(scored[0].0, "これ");
+    /// assert_eq!(scored[0].1.boundary, None);
+    /// assert!(scored[1].1.boundary.is_some_and(|margin| margin >= 0.0));
+    /// ```
+    #[must_use]
+    pub fn segment_with_scores(&self, sentence: &str) -> Vec<(String, TokenScore)> {
+        let mut buf = SegmentBuffer::new();
+        let (ranges, scores) = self.segment_with_scores_into(sentence, &mut buf);
+        ranges
+            .iter()
+            .zip(scores)
+            .map(|(&(start, end), &score)| (sentence[start..end].to_string(), score))
+            .collect()
+    }
+
+    /// Segments a sentence into byte ranges with per-token scores, reusing
+    /// `buf`'s allocations — the allocation-free variant of
+    /// [`segment_with_scores`](Self::segment_with_scores).
+    ///
+    /// # Arguments
+    /// * `sentence` - The sentence to segment.
+    /// * `buf` - The scratch/output buffer to (re)use; cleared and refilled
+    ///   by this call.
+    ///
+    /// # Returns
+    /// The token byte ranges (identical to
+    /// [`segment_into`](Self::segment_into)'s) and one [`TokenScore`] per
+    /// range, both borrowed from `buf`.
+    pub fn segment_with_scores_into<'b>(
+        &self,
+        sentence: &str,
+        buf: &'b mut SegmentBuffer,
+    ) -> (&'b [(usize, usize)], &'b [TokenScore]) {
+        self.segment_ranges_into(sentence, buf, true);
+        buf.scores.clear();
+        if !sentence.is_empty() {
+            // Replays the decisions from their recorded margins: a
+            // non-negative margin is exactly a boundary, so the scores
+            // line up with the ranges one to one.
+            let hi = buf.char_codes.len() - 4;
+            let mut current = TokenScore::default();
+            for &margin in &buf.margins[4..=hi] {
+                if margin >= 0.0 {
+                    buf.scores.push(current);
+                    current = TokenScore {
+                        boundary: Some(margin),
+                        inner: None,
+                    };
+                } else {
+                    current.inner = Some(current.inner.map_or(margin, |inner| inner.max(margin)));
+                }
+            }
+            buf.scores.push(current);
+        }
+        (&buf.ranges, &buf.scores)
+    }
+
+    /// Labels a raw sentence with this segmenter's decisions as a
+    /// partially annotated corpus line (see
+    /// [`add_corpus_partial_with_writer`](Self::add_corpus_partial_with_writer)),
+    /// the pseudo-labels from which a student model is distilled (see
+    /// [`BoundaryPerceptronTrainer::from_teacher`](crate::trainer::BoundaryPerceptronTrainer::from_teacher)).
+    ///
+    /// Each position is annotated as [`segment`](Self::segment) decides it
+    /// when its margin (see [`TokenScore`]) is at least `min_margin` away
+    /// from zero, and left unannotated otherwise, so a student is not
+    /// trained on the decisions the segmenter was unsure of. The line
+    /// spells out the text after [`normalization`](Self::normalization),
+    /// so a student extracting it under the same normalization sees the
+    /// characters this segmenter scored.
+    ///
+    /// # Arguments
+    /// * `sentence` - The raw sentence.
+    /// * `min_margin` - The smallest absolute margin annotated; `0.0`
+    ///   annotates every position.
+    ///
+    /// # Returns
+    /// The partially annotated line; empty for an empty sentence.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use litsea::adaboost::AdaBoost;
+    /// use litsea::language::Language;
+    /// use litsea::segmenter::Segmenter;
+    ///
+    /// let model_file =
+    ///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("japanese.model");
+    /// let mut learner = AdaBoost::new(0.01, 100);
+    /// learner.load_model_from_path(&model_file).unwrap();
+    /// let segmenter = Segmenter::with_learner(Language::Japanese, learner);
+    ///
+    /// assert_eq!(segmenter.pseudo_label("これはテストです。", 0.0), "こ-れ|は|テ-ス-ト|で-す|。");
+    /// assert_eq!(segmenter.pseudo_label("これはテストです。", f64::INFINITY), "こ れ は テ ス ト で す 。");
+    /// ```
+    #[must_use]
+    pub fn pseudo_label(&self, sentence: &str, min_margin: f64) -> String {
+        let mut line = String::new();
+        let mut margins = Vec::new();
+        self.pseudo_label_into(
+            sentence,
+            min_margin,
+            &mut SegmentBuffer::new(),
+            &mut line,
+            &mut margins,
+        );
+        line
+    }
+
+    /// Writes [`pseudo_label`](Self::pseudo_label)'s line into `line`,
+    /// reusing `buf` across sentences, and the absolute margins of its
+    /// decided delimiters, in order, into `margins`.
+    pub(crate) fn pseudo_label_into(
+        &self,
+        sentence: &str,
+        min_margin: f64,
+        buf: &mut SegmentBuffer,
+        line: &mut String,
+        margins: &mut Vec<f64>,
+    ) {
+        line.clear();
+        margins.clear();
+        self.segment_ranges_into(sentence, buf, true);
+        if sentence.is_empty() {
+            return;
+        }
+        // Real character r sits at decision position r + 3.
+        let real = &buf.char_codes[3..buf.char_codes.len() - 3];
+        for (r, &code) in real.iter().enumerate() {
+            if r > 0 {
+                let margin = buf.margins[r + 3];
+                let decided = margin.abs() >= min_margin;
+                if decided {
+                    margins.push(margin.abs());
+                }
+                partial::push_delimiter(line, decided.then_some(margin >= 0.0));
+            }
+            partial::push_char(line, char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+    }
+
+    /// Scores every boundary decision of a sentence through the packed
+    /// path and returns their margins (see [`TokenScore`]), one per gap
+    /// between two characters, in order. Backs
+    /// [`SentenceSelector`](crate::select::SentenceSelector).
+    pub(crate) fn boundary_margins_into<'b>(
+        &self,
+        sentence: &str,
+        buf: &'b mut SegmentBuffer,
+    ) -> &'b [f64] {
+        self.segment_ranges_into(sentence, buf, true);
+        if sentence.is_empty() {
+            return &[];
+        }
+        let hi = buf.char_codes.len() - 4;
+        &buf.margins[4..=hi]
+    }
+
+    /// Segments a whole document: splits it into sentences with
+    /// [`sentence::split`](crate::sentence::split) under this segmenter's
+    /// language, then segments each sentence.
+    ///
+    /// # Arguments
+    /// * `document` - The text to segment; may span many sentences and
+    ///   lines.
+    ///
+    /// # Returns
+    /// One entry per sentence, in order, each holding that sentence's
+    /// tokens as `(start, end)` byte ranges into `document` (not into the
+    /// sentence), so `&document[start..end]` is the token. Whitespace
+    /// between sentences belongs to no token.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use litsea::adaboost::AdaBoost;
+    /// use litsea::language::Language;
+    /// use litsea::segmenter::Segmenter;
+    ///
+    /// let model_file =
+    ///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("RWCP.model");
+    /// let mut learner = AdaBoost::new(0.01, 100);
+    /// learner.load_model_from_path(&model_file).unwrap();
+    /// let segmenter = Segmenter::with_learner(Language::Japanese, learner);
+    ///
+    /// let document = "これはテストです。\nこれもテストです。";
+    /// let sentences = segmenter.segment_document(document);
+    /// assert_eq!(sentences.len(), 2);
+    /// let (start, end) = sentences[1][0];
+    /// assert_eq!(&document[start..end], "これ");
+    /// ```
+    #[must_use]
+    pub fn segment_document(&self, document: &str) -> Vec<Vec<(usize, usize)>> {
+        let mut buf = SegmentBuffer::new();
+        sentence::split(self.language, document)
+            .map(|(start, end)| {
+                self.segment_into(&document[start..end], &mut buf)
+                    .iter()
+                    .map(|&(token_start, token_end)| (start + token_start, start + token_end))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The scoring core shared by [`segment_into`](Self::segment_into) and
+    /// [`segment_with_scores_into`](Self::segment_with_scores_into): fills
+    /// `buf.ranges`, and `buf.margins` when `record_margins` is set.
+    fn segment_ranges_into(&self, sentence: &str, buf: &mut SegmentBuffer, record_margins: bool) {
         buf.ranges.clear();
         if sentence.is_empty() {
-            return &buf.ranges;
+            return;
         }
         self.packed_context_into(sentence, buf);
+        self.constraints_into(sentence, buf);
 
         // The bias is a sum over all model weights; compute it once per
-        // sentence instead of once per character.
-        let bias = self.learner.bias();
+        // sentence instead of once per character. Folding the threshold
+        // offset into it makes every decision `score - threshold >= 0`.
+        let bias = self.learner.bias() - self.threshold;
 
         self.with_packed(|packed| {
             let char_codes = &buf.char_codes;
@@ -797,6 +1705,27 @@ impl Segmenter {
                     }
                 }
             }
+            // Dictionary-match features: the model's word list is matched
+            // against the real characters, and each position adds the
+            // weight of every feature set in its mask, in bit order (the
+            // string writer's emission order).
+            if let Some(dictionary) =
+                self.learner.dictionary().filter(|_| packed.has_dictionary_features())
+            {
+                let masks = &mut buf.dictionary_masks;
+                dictionary_masks_into(dictionary, &char_codes[3..n - 3], masks);
+                for (r, &mask) in masks.iter().enumerate() {
+                    let i = r + 3;
+                    if !(lo..=hi).contains(&i) {
+                        continue;
+                    }
+                    let mut bits = mask;
+                    while bits != 0 {
+                        static_scores[i] += packed.dictionary[bits.trailing_zeros() as usize];
+                        bits &= bits - 1;
+                    }
+                }
+            }
 
             // ---- Sequential pass: only the 16 tag-dependent templates
             // (all dense loads, indexed directly with the mixed-radix
@@ -810,7 +1739,13 @@ impl Segmenter {
             // char_starts[i - 3]; the final word always ends at the last
             // char_starts entry (the sentence length).
             let static_scores = &buf.static_scores;
+            let constraints = &buf.constraints;
             let ranges = &mut buf.ranges;
+            let margins = &mut buf.margins;
+            margins.clear();
+            if record_margins {
+                margins.resize(n, 0.0);
+            }
             let mut word_start = 0usize; // real-character index
             if packed.has_tag_features {
                 let t = type_radix;
@@ -845,7 +1780,11 @@ impl Segmenter {
                         + d[35][((p2 * t + c2) * t + c3) * t + c4]
                         + d[36][((p3 * t + c1) * t + c2) * t + c3]
                         + d[37][((p3 * t + c2) * t + c3) * t + c4];
-                    if score >= 0.0 {
+                    let margin = decision_margin(constraints, i, score);
+                    if record_margins {
+                        margins[i] = margin;
+                    }
+                    if margin >= 0.0 {
                         ranges.push((char_starts[word_start], char_starts[i - 3]));
                         word_start = i - 3;
                         tags.push(TAG_B);
@@ -861,7 +1800,11 @@ impl Segmenter {
                 // branch is equivalence-pinned against segment_reference
                 // by the tag-free differential test.
                 for i in 4..=hi {
-                    if bias + static_scores[i] >= 0.0 {
+                    let margin = decision_margin(constraints, i, bias + static_scores[i]);
+                    if record_margins {
+                        margins[i] = margin;
+                    }
+                    if margin >= 0.0 {
                         ranges.push((char_starts[word_start], char_starts[i - 3]));
                         word_start = i - 3;
                     }
@@ -869,7 +1812,6 @@ impl Segmenter {
             }
             ranges.push((char_starts[word_start], char_starts[n - 6]));
         });
-        &buf.ranges
     }
 
     /// Reference implementation of [`segment`](Self::segment) using the
@@ -883,6 +1825,7 @@ impl Segmenter {
         }
         let learner = &self.learner;
         let (chars, types) = self.sentence_context(sentence);
+        let masks = self.dictionary_masks(sentence);
         let mut tags: Vec<&'static str> = Vec::with_capacity(chars.len());
         tags.extend_from_slice(&["U"; 4]);
         let bias = learner.bias();
@@ -891,7 +1834,7 @@ impl Segmenter {
         let mut word = chars[3].to_string();
         for (i, ch) in chars.iter().enumerate().take(chars.len() - 3).skip(4) {
             let mut score = bias;
-            self.write_attributes(i, &tags, &chars, &types, &mut |attr| {
+            self.write_attributes(i, &tags, &chars, &types, &masks, &mut |attr| {
                 score += learner.weight(attr);
             });
             if score >= 0.0 {
@@ -931,7 +1874,16 @@ impl Segmenter {
         }
         let packed = self.two_stage.as_ref().ok_or(LitseaError::PosLearnerNotSet)?;
         let words = self.segment(sentence);
-        let tags = packed.tag_words(self.language, &words);
+        // The tagger's lexicon and features were built from normalized
+        // words; the returned surfaces stay the caller's originals.
+        let normalization = self.normalization();
+        let tags = if normalization == Normalization::None {
+            packed.tag_words(self.language, &words)
+        } else {
+            let normalized: Vec<String> =
+                words.iter().map(|word| normalization.normalize(word).into_owned()).collect();
+            packed.tag_words(self.language, &normalized)
+        };
         Ok(words.into_iter().zip(tags).collect())
     }
 
@@ -943,9 +1895,10 @@ impl Segmenter {
         tags: &[&'static str],
         chars: &[&str],
         types: &[&'static str],
+        dictionary_masks: &[u32],
     ) -> HashSet<String> {
         let mut attrs = HashSet::with_capacity(48);
-        self.write_attributes(i, tags, chars, types, &mut |attr| {
+        self.write_attributes(i, tags, chars, types, dictionary_masks, &mut |attr| {
             attrs.insert(attr.to_string());
         });
         attrs
@@ -964,10 +1917,11 @@ impl Segmenter {
         tags: &[&'static str],
         chars: &[&str],
         types: &[&'static str],
+        dictionary_masks: &[u32],
         out: &mut Vec<String>,
     ) {
         let mut idx = 0;
-        self.write_attributes(i, tags, chars, types, &mut |attr| {
+        self.write_attributes(i, tags, chars, types, dictionary_masks, &mut |attr| {
             if idx < out.len() {
                 out[idx].clear();
                 out[idx].push_str(attr);
@@ -985,7 +1939,10 @@ impl Segmenter {
     /// `{prefix}:{slot values}` via plain `push_str` (no `core::fmt`), in
     /// table order. The language-specific `WC*` templates are included for
     /// Japanese and Chinese only (Korean's uniform syllable types would make
-    /// them noise), via [`crate::packed_model::templates_for`].
+    /// them noise), via [`crate::packed_model::templates_for`]. The
+    /// dictionary-match features set in `dictionary_masks[i - 3]` follow
+    /// the table's templates, in bit order; `dictionary_masks` is empty
+    /// when the model has no dictionary.
     ///
     /// # Panics
     /// Panics if `i` is less than 3 or if `i + 2` exceeds the length of
@@ -997,6 +1954,7 @@ impl Segmenter {
         tags: &[&'static str],
         chars: &[&str],
         types: &[&'static str],
+        dictionary_masks: &[u32],
         sink: &mut dyn FnMut(&str),
     ) {
         let mut buf = String::with_capacity(32);
@@ -1013,6 +1971,27 @@ impl Segmenter {
             }
             sink(&buf);
         }
+        if let Some(&mask) = dictionary_masks.get(i - 3) {
+            for feature in (0..DICTIONARY_FEATURES).filter(|&f| mask & (1 << f) != 0) {
+                buf.clear();
+                push_dictionary_feature(&mut buf, feature);
+                sink(&buf);
+            }
+        }
+    }
+}
+
+/// Returns the margin of the boundary decision at decision position `i`;
+/// the position is a boundary when the margin is non-negative. A user
+/// dictionary constraint wins over the model with an infinite margin,
+/// otherwise the margin is the model's score. `constraints` is empty
+/// (every position free) when the segmenter has no user dictionary.
+#[inline]
+fn decision_margin(constraints: &[u8], i: usize, score: f64) -> f64 {
+    match constraints.get(i) {
+        Some(&CONSTRAINT_BOUNDARY) => f64::INFINITY,
+        Some(&CONSTRAINT_NO_BOUNDARY) => f64::NEG_INFINITY,
+        _ => score,
     }
 }
 
@@ -1209,7 +2188,7 @@ mod tests {
 
         let types: Vec<&'static str> = vec!["O", "O", "O", "O", "I", "I", "O"];
 
-        let attrs = segmenter.get_attributes(4, &tags, &chars, &types);
+        let attrs = segmenter.get_attributes(4, &tags, &chars, &types, &[]);
         assert!(attrs.contains("UW4:い"));
         assert!(attrs.contains("UC4:I"));
         assert!(attrs.contains("UP3:U"));
@@ -1236,7 +2215,7 @@ mod tests {
         let types: Vec<&'static str> = vec!["O", "P", "A", "N", "I", "H", "K"];
 
         let mut attrs: Vec<String> = Vec::new();
-        segmenter.collect_attributes(4, &tags, &chars, &types, &mut attrs);
+        segmenter.collect_attributes(4, &tags, &chars, &types, &[], &mut attrs);
 
         let expected = [
             "UP1:B",
@@ -1295,7 +2274,7 @@ mod tests {
         let types: Vec<&'static str> = vec!["O", "E", "SN", "SF", "J", "G", "H"];
 
         let mut attrs: Vec<String> = Vec::new();
-        segmenter.collect_attributes(4, &tags, &chars, &types, &mut attrs);
+        segmenter.collect_attributes(4, &tags, &chars, &types, &[], &mut attrs);
 
         let expected = [
             "UP1:B",
@@ -1348,15 +2327,15 @@ mod tests {
         let types: Vec<&'static str> = vec!["O", "O", "O", "O", "I", "I", "O"];
 
         let mut buf: Vec<String> = Vec::new();
-        segmenter.collect_attributes(4, &tags, &chars, &types, &mut buf);
+        segmenter.collect_attributes(4, &tags, &chars, &types, &[], &mut buf);
         assert_eq!(buf.len(), 42);
         // The slice contents must match the HashSet variant.
-        let set = segmenter.get_attributes(4, &tags, &chars, &types);
+        let set = segmenter.get_attributes(4, &tags, &chars, &types, &[]);
         for attr in &buf {
             assert!(set.contains(attr), "missing from set: {}", attr);
         }
         // A second collection reuses the buffer and yields the same result.
-        segmenter.collect_attributes(4, &tags, &chars, &types, &mut buf);
+        segmenter.collect_attributes(4, &tags, &chars, &types, &[], &mut buf);
         assert_eq!(buf.len(), 42);
     }
 
@@ -1368,7 +2347,7 @@ mod tests {
         let chars = vec!["B3", "B2", "B1", "あ", "い", "う", "E1"];
         let types: Vec<&'static str> = vec!["O"; 7];
         // i=2 is out of valid range [3, chars.len()-3); should panic on chars[i-3]
-        let _ = segmenter.get_attributes(2, &tags, &chars, &types);
+        let _ = segmenter.get_attributes(2, &tags, &chars, &types, &[]);
     }
 
     #[test]
@@ -1379,7 +2358,7 @@ mod tests {
         let chars = vec!["B3", "B2", "B1", "あ", "い", "う", "E1"];
         let types: Vec<&'static str> = vec!["O"; 7];
         // i=5 means i+2=7 which exceeds chars.len()=7; should panic on chars[i+2]
-        let _ = segmenter.get_attributes(5, &tags, &chars, &types);
+        let _ = segmenter.get_attributes(5, &tags, &chars, &types, &[]);
     }
 
     #[test]
@@ -1400,7 +2379,7 @@ mod tests {
 
         let types: Vec<&'static str> = vec!["O", "O", "O", "SF", "SF", "SN", "O"];
 
-        let attrs = segmenter.get_attributes(4, &tags, &chars, &types);
+        let attrs = segmenter.get_attributes(4, &tags, &chars, &types, &[]);
         assert!(attrs.contains("UW4:국"));
         assert!(attrs.contains("UC4:SF"));
         // Korean does NOT include WC features
@@ -1679,6 +2658,333 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_user_dictionary_keeps_words_whole() {
+        let sentence = "東京都に住んでいます。";
+        let plain = Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"));
+        let tokens = plain.segment(sentence);
+        assert!(!tokens.contains(&"住んでいます".to_string()));
+
+        // A dictionary word forbids every boundary inside it and forces
+        // one at each end; "には" never matches, so it changes nothing.
+        let dictionary: UserDictionary = ["住んでいます", "には"].into_iter().collect();
+        let constrained =
+            Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"))
+                .with_user_dictionary(dictionary);
+        let tokens = constrained.segment(sentence);
+        assert!(tokens.contains(&"住んでいます".to_string()), "{tokens:?}");
+        assert_eq!(tokens.concat(), sentence);
+        assert_eq!(constrained.user_dictionary().map(UserDictionary::len), Some(2));
+    }
+
+    #[test]
+    fn test_user_dictionary_forces_boundaries_at_match_ends() {
+        // "はテ" straddles a model boundary; the dictionary forces it out
+        // as one token, so "これ" and "スト" are cut at its edges.
+        let dictionary: UserDictionary = ["はテ"].into_iter().collect();
+        let segmenter = Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"))
+            .with_user_dictionary(dictionary);
+        let tokens = segmenter.segment("これはテストです。");
+        assert_eq!(tokens[..2], ["これ", "はテ"]);
+        assert_eq!(tokens.concat(), "これはテストです。");
+    }
+
+    #[test]
+    fn test_user_dictionary_applies_on_pointwise_fast_path() {
+        let dictionary: UserDictionary = ["住んでいます。"].into_iter().collect();
+        let segmenter =
+            Segmenter::with_learner(Language::Japanese, load_adaboost_tag_free("japanese.model"))
+                .with_user_dictionary(dictionary);
+        assert!(segmenter.with_packed(|p| !p.has_tag_features));
+        let tokens = segmenter.segment("東京都に住んでいます。");
+        assert_eq!(tokens.last().map(String::as_str), Some("住んでいます。"));
+    }
+
+    #[test]
+    fn test_patterns_keep_matches_whole() {
+        let sentence =
+            "詳細はhttps://example.com/docs?id=42を、価格は1,234,567円でv1.2.3から3.14倍。";
+        let plain = Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"));
+        let tokens = plain.segment(sentence);
+        assert!(tokens.contains(&"https".to_string()), "{tokens:?}");
+
+        let segmenter =
+            Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"))
+                .with_patterns(PatternSet::builtin());
+        let tokens = segmenter.segment(sentence);
+        for atom in ["https://example.com/docs?id=42", "1,234,567", "1.2.3", "3.14"] {
+            assert!(tokens.contains(&atom.to_string()), "{atom} in {tokens:?}");
+        }
+        assert_eq!(tokens.concat(), sentence);
+        assert_eq!(tokens[..2], ["詳細", "は"]);
+        assert_eq!(segmenter.patterns().map(PatternSet::len), Some(5));
+        assert_segment_into_matches(&segmenter, &[sentence, "https://x.jp", "1.5"]);
+    }
+
+    #[test]
+    fn test_patterns_map_through_normalization() {
+        // Patterns see the original half-width text, and their byte
+        // ranges map onto the normalized characters the model scores.
+        let mut patterns = PatternSet::new();
+        patterns.insert_regex("ﾃｽﾄ[0-9]+").unwrap();
+        let segmenter =
+            Segmenter::with_learner(Language::Japanese, load_adaboost_tag_free("japanese.model"))
+                .with_normalization(Normalization::Nfkc)
+                .with_threshold(f64::NEG_INFINITY)
+                .with_patterns(patterns);
+        assert_eq!(segmenter.segment("ﾃｽﾄ12です"), vec!["ﾃｽﾄ12", "で", "す"]);
+    }
+
+    #[test]
+    fn test_user_dictionary_wins_over_patterns() {
+        let dictionary: UserDictionary = ["v1"].into_iter().collect();
+        let segmenter = Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"))
+            .with_patterns(PatternSet::builtin())
+            .with_user_dictionary(dictionary);
+        let tokens = segmenter.segment("v1.2.3です");
+        assert_eq!(tokens[0], "v1");
+        assert_eq!(tokens.concat(), "v1.2.3です");
+    }
+
+    #[test]
+    fn test_user_dictionary_buffer_reuse_is_stateless() {
+        // Constraints left in a buffer by a dictionary segmenter must not
+        // leak into the next call through a segmenter without one.
+        let dictionary: UserDictionary = ["テストです"].into_iter().collect();
+        let constrained = Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"))
+            .with_user_dictionary(dictionary);
+        let plain = Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"));
+        let sentence = "これはテストです。";
+        let mut buf = SegmentBuffer::new();
+        let _ = constrained.segment_into(sentence, &mut buf);
+        let reused = plain.segment_into(sentence, &mut buf).to_vec();
+        let mut fresh = SegmentBuffer::new();
+        assert_eq!(reused, plain.segment_into(sentence, &mut fresh));
+        assert_segment_into_matches(&constrained, &["これはテストです。", "テストです", "字"]);
+    }
+
+    /// Collects the string-keyed reference score of every decision
+    /// position, in order (the margins `segment_reference` decides on).
+    fn reference_margins(segmenter: &Segmenter, sentence: &str) -> Vec<f64> {
+        let (chars, types) = segmenter.sentence_context(sentence);
+        let masks = segmenter.dictionary_masks(sentence);
+        let mut tags: Vec<&'static str> = vec!["U"; 4];
+        let bias = segmenter.learner.bias();
+        let mut margins = Vec::new();
+        for i in 4..chars.len() - 3 {
+            let mut score = bias;
+            segmenter.write_attributes(i, &tags, &chars, &types, &masks, &mut |attr| {
+                score += segmenter.learner.weight(attr);
+            });
+            tags.push(if score >= 0.0 { "B" } else { "O" });
+            margins.push(score);
+        }
+        margins
+    }
+
+    /// Asserts that `segment_with_scores` returns `segment`'s tokens and
+    /// that each score summarizes the reference margins inside its token.
+    fn assert_scores_match_reference(segmenter: &Segmenter, sentences: &[&str]) {
+        for sentence in sentences {
+            let scored = segmenter.segment_with_scores(sentence);
+            let tokens: Vec<String> = scored.iter().map(|(token, _)| token.clone()).collect();
+            assert_eq!(tokens, segmenter.segment(sentence), "tokens diverged on {sentence:?}");
+
+            let margins = reference_margins(segmenter, sentence);
+            let mut position = 0; // index into margins of the token's first char
+            for (k, (token, score)) in scored.iter().enumerate() {
+                let len = token.chars().count();
+                let close = |a: Option<f64>, b: Option<f64>| match (a, b) {
+                    (Some(a), Some(b)) => (a - b).abs() < 1e-9,
+                    (a, b) => a == b,
+                };
+                let boundary = (k > 0).then(|| margins[position - 1]);
+                let inner = margins[position..position + len - 1].iter().copied().reduce(f64::max);
+                assert!(close(score.boundary, boundary), "{sentence:?} {token:?}: {score:?}");
+                assert!(close(score.inner, inner), "{sentence:?} {token:?}: {score:?}");
+                assert!(score.confidence() >= 0.0);
+                position += len;
+            }
+        }
+    }
+
+    #[test]
+    fn test_segment_with_scores_matches_reference_margins() {
+        let sentences = ["これはテストです。", "東京都に住んでいます。", "字", ""];
+        for model in ["RWCP.model", "japanese.model"] {
+            let segmenter = Segmenter::with_learner(Language::Japanese, load_adaboost(model));
+            assert_scores_match_reference(&segmenter, &sentences);
+            assert_scores_match_reference(&segmenter, &STRESS_SENTENCES);
+        }
+        let tag_free =
+            Segmenter::with_learner(Language::Japanese, load_adaboost_tag_free("japanese.model"));
+        assert_scores_match_reference(&tag_free, &sentences);
+    }
+
+    #[test]
+    fn test_segment_with_scores_reports_constraints_as_infinite() {
+        let dictionary: UserDictionary = ["テストです"].into_iter().collect();
+        let segmenter = Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"))
+            .with_user_dictionary(dictionary);
+        let scored = segmenter.segment_with_scores("これはテストです。");
+        let (token, score) = &scored[2];
+        assert_eq!(token, "テストです");
+        assert_eq!(score.boundary, Some(f64::INFINITY));
+        assert_eq!(score.inner, Some(f64::NEG_INFINITY));
+        assert_eq!(score.confidence(), f64::INFINITY);
+        assert_eq!(scored[3].1.boundary, Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_segment_with_scores_into_buffer_reuse_is_stateless() {
+        let segmenter = Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"));
+        let mut buf = SegmentBuffer::new();
+        let _ = segmenter.segment_with_scores_into("東京都に住んでいます。", &mut buf);
+        let _ = segmenter.segment_into("字", &mut buf);
+        let (ranges, scores) = segmenter.segment_with_scores_into("これはテストです。", &mut buf);
+        let (ranges, scores) = (ranges.to_vec(), scores.to_vec());
+        let mut fresh = SegmentBuffer::new();
+        let (want_ranges, want_scores) =
+            segmenter.segment_with_scores_into("これはテストです。", &mut fresh);
+        assert_eq!(ranges, want_ranges);
+        assert_eq!(scores, want_scores);
+        assert_eq!(ranges.len(), scores.len());
+    }
+
+    #[test]
+    fn test_threshold_offset_moves_the_decision_boundary() {
+        let sentence = "東京都に住んでいます。";
+        let load = || Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"));
+        let base = load();
+        assert_eq!(base.threshold(), 0.0);
+        let scored = base.segment_with_scores(sentence);
+
+        // Raising the threshold just above the weakest accepted boundary
+        // removes exactly that boundary; lowering it just below the
+        // strongest rejected one adds exactly that boundary.
+        let weakest = scored.iter().filter_map(|(_, s)| s.boundary).fold(f64::INFINITY, f64::min);
+        let strongest =
+            scored.iter().filter_map(|(_, s)| s.inner).fold(f64::NEG_INFINITY, f64::max);
+        let coarser = load().with_threshold(weakest + 1e-9).segment(sentence);
+        let finer = load().with_threshold(strongest - 1e-9).segment(sentence);
+        assert_eq!(coarser.len(), scored.len() - 1, "{coarser:?}");
+        assert_eq!(finer.len(), scored.len() + 1, "{finer:?}");
+        assert_eq!(coarser.concat(), sentence);
+        assert_eq!(finer.concat(), sentence);
+    }
+
+    #[test]
+    fn test_normalization_ranges_index_the_original_text() {
+        let plain = Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"));
+        let nfkc = Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"))
+            .with_normalization(Normalization::Nfkc);
+        for sentence in ["これはﾃﾞｰﾀﾍﾞｰｽのﾃｽﾄです。", "ＡＢＣ１２３を買った", "ｶﾞｷﾞｸﾞ"]
+        {
+            let normalized = Normalization::Nfkc.normalize(sentence);
+            let tokens = nfkc.segment(sentence);
+            assert_eq!(tokens.concat(), sentence);
+            // Segmenting the original with normalization decides exactly
+            // what segmenting the normalized text does.
+            let folded: Vec<String> = tokens
+                .iter()
+                .map(|token| Normalization::Nfkc.normalize(token).into_owned())
+                .collect();
+            assert_eq!(folded, plain.segment(&normalized), "{sentence:?}");
+            let scored: Vec<String> =
+                nfkc.segment_with_scores(sentence).into_iter().map(|(token, _)| token).collect();
+            assert_eq!(scored, tokens);
+        }
+    }
+
+    #[test]
+    fn test_normalization_never_splits_one_original_character() {
+        let segmenter =
+            Segmenter::with_learner(Language::Japanese, load_adaboost_tag_free("japanese.model"))
+                .with_normalization(Normalization::Nfkc)
+                .with_threshold(f64::NEG_INFINITY);
+        assert_eq!(segmenter.segment("㍿ﾃﾞ"), vec!["㍿", "ﾃﾞ"]);
+        let scored = segmenter.segment_with_scores("㍿ﾃﾞ");
+        assert_eq!(scored[0].1.inner, Some(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn test_normalization_defaults_to_the_model_declaration() {
+        let mut learner = load_adaboost("RWCP.model");
+        assert_eq!(Segmenter::new(Language::Japanese).normalization(), Normalization::None);
+        learner.set_normalization(Normalization::Nfkc);
+        let segmenter = Segmenter::with_learner(Language::Japanese, learner);
+        assert_eq!(segmenter.normalization(), Normalization::Nfkc);
+        let segmenter = segmenter.with_normalization(Normalization::None);
+        assert_eq!(segmenter.normalization(), Normalization::None);
+    }
+
+    #[test]
+    fn test_boundaries_never_split_a_grapheme_cluster() {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        // With an infinitely low threshold every free position is a
+        // boundary, so the tokens are exactly the grapheme clusters.
+        let sentences = [
+            "e\u{301}tude",
+            "\u{1f469}\u{200d}\u{1f4bb}で作業🇯🇵🇰🇷",
+            "\u{2764}\u{fe0f}と\u{1f44d}\u{1f3fd}",
+            "\u{1100}\u{1161}\u{11a8}\u{1102}\u{1165}",
+            "ｶﾞｯｺｳ",
+        ];
+        for model in [load_adaboost("RWCP.model"), load_adaboost_tag_free("japanese.model")] {
+            let segmenter = Segmenter::with_learner(Language::Japanese, model)
+                .with_threshold(f64::NEG_INFINITY);
+            for sentence in sentences {
+                let clusters: Vec<&str> = sentence.graphemes(true).collect();
+                assert_eq!(segmenter.segment(sentence), clusters, "{sentence:?}");
+            }
+        }
+
+        // A dictionary edge inside a cluster gives way to the cluster.
+        let dictionary: UserDictionary = ["e"].into_iter().collect();
+        let segmenter = Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"))
+            .with_threshold(f64::NEG_INFINITY)
+            .with_user_dictionary(dictionary);
+        assert_eq!(segmenter.segment("e\u{301}x"), vec!["e\u{301}", "x"]);
+    }
+
+    #[test]
+    fn test_threshold_offset_applies_on_pointwise_fast_path() {
+        let segmenter =
+            Segmenter::with_learner(Language::Japanese, load_adaboost_tag_free("japanese.model"));
+        let sentence = "東京都に住んでいます。";
+        let all = segmenter.with_threshold(f64::NEG_INFINITY).segment(sentence);
+        assert_eq!(all.len(), sentence.chars().count());
+        let segmenter =
+            Segmenter::with_learner(Language::Japanese, load_adaboost_tag_free("japanese.model"))
+                .with_threshold(f64::INFINITY);
+        assert_eq!(segmenter.segment(sentence), vec![sentence]);
+    }
+
+    #[test]
+    fn test_segment_document_offsets_match_per_sentence_segmentation() {
+        let segmenter = Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"));
+        let document = "  これはテストです。東京都に住んでいます。\n\n「はい。」と言った。  ";
+        let sentences = segmenter.segment_document(document);
+        let expected: Vec<Vec<String>> = crate::sentence::split(Language::Japanese, document)
+            .map(|(start, end)| segmenter.segment(&document[start..end]))
+            .collect();
+        assert_eq!(sentences.len(), 3);
+        let tokens: Vec<Vec<String>> = sentences
+            .iter()
+            .map(|ranges| ranges.iter().map(|&(s, e)| document[s..e].to_string()).collect())
+            .collect();
+        assert_eq!(tokens, expected);
+        // Within a sentence the ranges tile it; between sentences only
+        // whitespace is skipped.
+        let flat: Vec<(usize, usize)> = sentences.concat();
+        for pair in flat.windows(2) {
+            let gap = &document[pair[0].1..pair[1].0];
+            assert!(gap.trim().is_empty(), "{gap:?}");
+        }
+        assert!(segmenter.segment_document(" \n ").is_empty());
+    }
+
     /// Loads a bundled model with its tag-dependent (`UP*`/`BP*`/`UQ*`/
     /// `BQ*`/`TQ*`) feature lines filtered out, producing a pointwise model
     /// that exercises `segment()`'s tag-free fast path (#183).
@@ -1730,6 +3036,68 @@ mod tests {
         assert!(tagged.with_packed(|p| p.has_tag_features));
     }
 
+    /// Rewrites a bundled model with a dictionary and strong weights for
+    /// its match features: a word boundary before and after every
+    /// dictionary word, none inside it.
+    fn load_adaboost_with_dictionary(model: &str, tag_free: bool) -> AdaBoost {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join(model);
+        let text = std::fs::read_to_string(&path).unwrap();
+        let mut lines: Vec<&str> = text
+            .lines()
+            .filter(|l| !tag_free || !crate::packed_model::is_tag_dependent_feature(l))
+            .collect();
+        let bias = lines.pop().unwrap();
+        let mut rewritten = String::from("#dictionary\t東京都庁\n#dictionary\t可愛い猫\n");
+        for line in lines {
+            rewritten.push_str(line);
+            rewritten.push('\n');
+        }
+        for (kind, weight) in [("DL", 500.0), ("DR", 500.0), ("DI", -500.0)] {
+            for len in 1..=4 {
+                rewritten.push_str(&format!("{kind}:{len}\t{weight}\n"));
+            }
+        }
+        rewritten.push_str(bias);
+        let mut learner = AdaBoost::default();
+        learner.load_model_from_reader(rewritten.as_bytes()).unwrap();
+        learner
+    }
+
+    #[test]
+    fn test_segment_differential_dictionary_features() {
+        // The packed scorer matches the model's dictionary itself; both the
+        // tagged and the pointwise paths, and a model read back from the
+        // binary format (whose packed tables carry no dictionary weights),
+        // must score exactly like the string-keyed reference.
+        let sentences = [
+            "東京都庁に行きます。",
+            "私の可愛い猫は寝ている。",
+            "東京都庁舎と東京都庁",
+            "庁",
+            "これはテストです。",
+        ];
+        for tag_free in [false, true] {
+            let learner = load_adaboost_with_dictionary("japanese.model", tag_free);
+            let mut binary = Vec::new();
+            learner
+                .save_binary_model_to_writer(&mut binary, Some(Language::Japanese))
+                .unwrap();
+            let segmenter = Segmenter::with_learner(Language::Japanese, learner);
+            assert!(segmenter.with_packed(|p| p.has_dictionary_features()));
+            assert_segment_matches_reference(&segmenter, &sentences);
+            assert_segment_matches_reference(&segmenter, &STRESS_SENTENCES);
+            assert!(segmenter.segment("東京都庁に行きます。").contains(&"東京都庁".to_string()));
+
+            let mut loaded = AdaBoost::default();
+            loaded.load_model_from_reader(binary.as_slice()).unwrap();
+            let loaded = Segmenter::with_learner(Language::Japanese, loaded);
+            assert!(loaded.with_packed(|p| p.has_dictionary_features()));
+            for sentence in sentences {
+                assert_eq!(loaded.segment(sentence), segmenter.segment(sentence));
+            }
+        }
+    }
+
     #[test]
     fn test_segment_differential_synthetic_ambiguity_model() {
         // Handpicked sentinel-adjacent features: the packed table must give
@@ -1800,6 +3168,56 @@ mod tests {
         assert_ne!(before, after, "the merged model must actually disagree on this sentence");
     }
 
+    #[test]
+    fn test_learn_applies_a_correction() -> Result<()> {
+        let sentence = "きゃりーぱみゅぱみゅが歌う。";
+        let words = ["きゃりーぱみゅぱみゅ", "が", "歌う", "。"];
+        let mut segmenter =
+            Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"));
+        assert_ne!(segmenter.segment(sentence), words);
+
+        assert!(segmenter.learn(sentence, &words, 1.0)? > 0);
+        assert_eq!(segmenter.segment(sentence), words);
+        assert_segment_matches_reference(&segmenter, &[sentence, "これはテストです。"]);
+        // Learned already: nothing left to update.
+        assert_eq!(segmenter.learn(sentence, &words, 1.0)?, 0);
+
+        // The updated weights persist.
+        let mut text = Vec::new();
+        segmenter.learner().save_model_to_writer(&mut text)?;
+        let mut learner = AdaBoost::default();
+        learner.load_model_from_reader(text.as_slice())?;
+        assert_eq!(Segmenter::with_learner(Language::Japanese, learner).segment(sentence), words);
+
+        // The threshold offset is part of the decision being corrected.
+        let mut coarse =
+            Segmenter::with_learner(Language::Japanese, load_adaboost("japanese.model"))
+                .with_threshold(5.0);
+        coarse.learn(sentence, &words, 1.0)?;
+        assert_eq!(coarse.segment(sentence), words);
+
+        for (words, margin) in
+            [(&["きゃりー", "が"][..], 1.0), (&words[..], 0.0), (&words[..], f64::NAN)]
+        {
+            assert!(matches!(
+                segmenter.learn(sentence, words, margin),
+                Err(LitseaError::InvalidInput(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_learn_keeps_a_pointwise_model_pointwise() -> Result<()> {
+        let mut segmenter =
+            Segmenter::with_learner(Language::Korean, load_adaboost_tag_free("korean.model"));
+        assert!(segmenter.learner().is_tag_free());
+        segmenter.learn("나는고양이다", &["나는", "고양이", "다"], 1.0)?;
+        assert!(segmenter.learner().is_tag_free());
+        assert_eq!(segmenter.segment("나는고양이다"), ["나는", "고양이", "다"]);
+        Ok(())
+    }
+
     #[test]
     fn test_segment_cache_invalidated_by_add_corpus_and_train() {
         // The add_corpus + learner_mut().train() workflow must also
@@ -1845,6 +3263,41 @@ mod tests {
         assert!(!called);
     }
 
+    #[test]
+    fn test_try_with_learner_checks_the_recorded_language() {
+        use crate::metadata::ModelMetadata;
+
+        // A model without a recorded language is accepted for any language.
+        assert!(Segmenter::try_with_learner(Language::Korean, load_adaboost("RWCP.model")).is_ok());
+
+        let tagged = || {
+            let mut learner = load_adaboost("RWCP.model");
+            learner.set_metadata(ModelMetadata::new().with_language(Language::Japanese));
+            learner
+        };
+        let segmenter = Segmenter::try_with_learner(Language::Japanese, tagged()).unwrap();
+        assert_eq!(
+            segmenter.segment("これはテストです。"),
+            Segmenter::with_learner(Language::Japanese, load_adaboost("RWCP.model"))
+                .segment("これはテストです。")
+        );
+        let err = Segmenter::try_with_learner(Language::Korean, tagged()).err().unwrap();
+        assert!(matches!(
+            err,
+            LitseaError::LanguageMismatch {
+                model: Language::Japanese,
+                requested: Language::Korean
+            }
+        ));
+
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models/korean_pos.model");
+        let mut two_stage = TwoStageLearner::new();
+        two_stage.load_model_from_path(&path).unwrap();
+        two_stage.set_metadata(ModelMetadata::new().with_language(Language::Korean));
+        let result = Segmenter::try_with_two_stage_learner(Language::English, two_stage);
+        assert!(matches!(result, Err(LitseaError::LanguageMismatch { .. })));
+    }
+
     #[test]
     #[ignore = "full-corpus sweep (slow with the string-keyed reference); run explicitly with --ignored"]
     fn test_segment_differential_bocchan_full() {
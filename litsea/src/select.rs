@@ -0,0 +1,429 @@
+//! Active-learning sentence selection.
+//!
+//! Annotation effort goes furthest on the sentences the model is least
+//! sure about. A [`SentenceSelector`] segments a raw corpus, scores each
+//! sentence by the uncertainty of its boundary decisions (see
+//! [`Uncertainty`]), and returns the top-K sentences for annotation, most
+//! uncertain first. The margins come from the same packed scoring path as
+//! [`Segmenter::segment_into`], so ranking a corpus costs no more than
+//! segmenting it. With [`with_diversity`](SentenceSelector::with_diversity)
+//! a sentence whose character n-grams are mostly covered by the sentences
+//! already selected is passed over, so the budget is not spent on
+//! near-duplicates.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::segmenter::{SegmentBuffer, Segmenter};
+
+/// Error returned when parsing an [`Uncertainty`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unsupported uncertainty measure: '{input}'. Supported: margin, entropy")]
+pub struct ParseUncertaintyError {
+    input: String,
+}
+
+impl ParseUncertaintyError {
+    /// Returns the string that failed to parse.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+/// How the boundary margins of a sentence are aggregated into its
+/// uncertainty.
+///
+/// A margin is a decision's score minus the threshold (see
+/// [`TokenScore`](crate::segmenter::TokenScore)). Decisions forced by a user
+/// dictionary or pattern have infinite margins and count as certain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Uncertainty {
+    /// The smallest absolute margin in the sentence: the decision that
+    /// came closest to going the other way. Sentences rank by it in
+    /// ascending order, independent of their length.
+    #[default]
+    Margin,
+    /// The summed binary entropy, in bits, of the sentence's decisions,
+    /// each read as a boundary probability `1 / (1 + exp(-margin /
+    /// scale))`. Sentences rank by it in descending order; being a sum, it
+    /// favors longer sentences with several doubtful decisions.
+    Entropy,
+}
+
+impl fmt::Display for Uncertainty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Uncertainty::Margin => write!(f, "margin"),
+            Uncertainty::Entropy => write!(f, "entropy"),
+        }
+    }
+}
+
+impl FromStr for Uncertainty {
+    type Err = ParseUncertaintyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "margin" => Ok(Uncertainty::Margin),
+            "entropy" => Ok(Uncertainty::Entropy),
+            _ => Err(ParseUncertaintyError {
+                input: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// One sentence picked by [`SentenceSelector::select`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelectedSentence {
+    /// Index of the sentence in the slice passed to `select`.
+    pub index: usize,
+    /// The sentence's uncertainty: its smallest absolute margin for
+    /// [`Uncertainty::Margin`], its entropy in bits for
+    /// [`Uncertainty::Entropy`].
+    pub score: f64,
+}
+
+/// Ranks sentences by boundary uncertainty and picks the top-K for
+/// annotation.
+///
+/// # Example
+/// ```
+/// use std::path::PathBuf;
+///
+/// use litsea::adaboost::AdaBoost;
+/// use litsea::language::Language;
+/// use litsea::segmenter::Segmenter;
+/// use litsea::select::{SentenceSelector, Uncertainty};
+///
+/// let model_file =
+///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("japanese.model");
+/// let mut learner = AdaBoost::new(0.01, 100);
+/// learner.load_model_from_path(&model_file).unwrap();
+/// let segmenter = Segmenter::with_learner(Language::Japanese, learner);
+///
+/// let corpus = ["これはテストです。", "きゃりーぱみゅぱみゅが歌う。", "東京都に住んでいます。"];
+/// let selector = SentenceSelector::new().with_uncertainty(Uncertainty::Margin);
+/// let selected = selector.select(&segmenter, &corpus, 2);
+/// assert_eq!(selected.len(), 2);
+/// assert!(selected[0].score <= selected[1].score);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentenceSelector {
+    uncertainty: Uncertainty,
+    scale: f64,
+    /// Character n-gram order and maximum overlap, when diversity is on.
+    diversity: Option<(usize, f64)>,
+}
+
+impl Default for SentenceSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SentenceSelector {
+    /// Creates a selector ranking by [`Uncertainty::Margin`], with an
+    /// entropy scale of 1.0 and no diversity filter.
+    ///
+    /// # Returns
+    /// A new [`SentenceSelector`].
+    #[must_use]
+    pub fn new() -> Self {
+        SentenceSelector {
+            uncertainty: Uncertainty::default(),
+            scale: 1.0,
+            diversity: None,
+        }
+    }
+
+    /// Sets how a sentence's margins are aggregated.
+    ///
+    /// # Arguments
+    /// * `uncertainty` - The measure to rank by.
+    ///
+    /// # Returns
+    /// The selector with the measure set.
+    #[must_use]
+    pub fn with_uncertainty(mut self, uncertainty: Uncertainty) -> Self {
+        self.uncertainty = uncertainty;
+        self
+    }
+
+    /// Sets the margin scale of [`Uncertainty::Entropy`]: a decision whose
+    /// margin is `scale` away from zero has a boundary probability of
+    /// about 0.73 or 0.27. Margins are in the units of the model's
+    /// weights, so a boosted model such as `RWCP.model` wants a scale well
+    /// below the bundled perceptron models' 1.0.
+    ///
+    /// # Arguments
+    /// * `scale` - The scale; non-positive or non-finite values keep 1.0.
+    ///
+    /// # Returns
+    /// The selector with the scale set.
+    #[must_use]
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = if scale.is_finite() && scale > 0.0 { scale } else { 1.0 };
+        self
+    }
+
+    /// Passes over a sentence when more than `max_overlap` of its distinct
+    /// character `order`-grams already occur in the sentences selected
+    /// before it. A sentence shorter than `order` counts as one n-gram.
+    ///
+    /// # Arguments
+    /// * `order` - The n-gram length in characters; 0 is treated as 1.
+    /// * `max_overlap` - The largest covered fraction still accepted,
+    ///   clamped to `0.0..=1.0`; `0.0` takes no sentence sharing an
+    ///   n-gram with an earlier one, and values just below `1.0` only
+    ///   skip sentences entirely covered, such as duplicates.
+    ///
+    /// # Returns
+    /// The selector with the diversity filter enabled.
+    #[must_use]
+    pub fn with_diversity(mut self, order: usize, max_overlap: f64) -> Self {
+        let max_overlap = if max_overlap.is_nan() { 1.0 } else { max_overlap.clamp(0.0, 1.0) };
+        self.diversity = Some((order.max(1), max_overlap));
+        self
+    }
+
+    /// Returns the measure sentences are ranked by.
+    #[must_use]
+    pub fn uncertainty(&self) -> Uncertainty {
+        self.uncertainty
+    }
+
+    /// Scores one sentence.
+    ///
+    /// # Arguments
+    /// * `segmenter` - The segmenter whose decisions are scored.
+    /// * `sentence` - The sentence.
+    /// * `buf` - Scratch storage reused across calls.
+    ///
+    /// # Returns
+    /// The sentence's uncertainty (see [`SelectedSentence::score`]), or
+    /// `None` when it has no decision left to the model: it is empty, a
+    /// single character, or entirely forced by constraints.
+    pub fn score(
+        &self,
+        segmenter: &Segmenter,
+        sentence: &str,
+        buf: &mut SegmentBuffer,
+    ) -> Option<f64> {
+        let mut decided = segmenter
+            .boundary_margins_into(sentence, buf)
+            .iter()
+            .copied()
+            .filter(|margin| margin.is_finite())
+            .peekable();
+        decided.peek()?;
+        Some(match self.uncertainty {
+            Uncertainty::Margin => decided.map(f64::abs).fold(f64::INFINITY, f64::min),
+            Uncertainty::Entropy => decided.map(|margin| binary_entropy(margin / self.scale)).sum(),
+        })
+    }
+
+    /// Ranks `sentences` by uncertainty and returns the `k` to annotate
+    /// first.
+    ///
+    /// Sentences are visited from the most to the least uncertain, ties in
+    /// input order, and taken until `k` are selected; the diversity filter,
+    /// when set, skips a sentence too similar to those already taken.
+    /// Sentences without a model decision (see [`score`](Self::score)) are
+    /// never selected.
+    ///
+    /// # Arguments
+    /// * `segmenter` - The segmenter whose decisions are scored.
+    /// * `sentences` - The raw corpus, one sentence per entry.
+    /// * `k` - The number of sentences to select.
+    ///
+    /// # Returns
+    /// At most `k` selected sentences, most uncertain first.
+    #[must_use]
+    pub fn select<S: AsRef<str>>(
+        &self,
+        segmenter: &Segmenter,
+        sentences: &[S],
+        k: usize,
+    ) -> Vec<SelectedSentence> {
+        let mut buf = SegmentBuffer::new();
+        let mut ranked: Vec<SelectedSentence> = sentences
+            .iter()
+            .enumerate()
+            .filter_map(|(index, sentence)| {
+                let score = self.score(segmenter, sentence.as_ref(), &mut buf)?;
+                Some(SelectedSentence { index, score })
+            })
+            .collect();
+        // Stable, so equal scores keep their input order.
+        match self.uncertainty {
+            Uncertainty::Margin => ranked.sort_by(|a, b| a.score.total_cmp(&b.score)),
+            Uncertainty::Entropy => ranked.sort_by(|a, b| b.score.total_cmp(&a.score)),
+        }
+
+        let Some((order, max_overlap)) = self.diversity else {
+            ranked.truncate(k);
+            return ranked;
+        };
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut selected = Vec::new();
+        for candidate in ranked {
+            if selected.len() == k {
+                break;
+            }
+            let grams = char_ngrams(sentences[candidate.index].as_ref(), order);
+            let covered = grams.iter().filter(|gram| seen.contains(*gram)).count();
+            if covered as f64 > max_overlap * grams.len() as f64 {
+                continue;
+            }
+            seen.extend(grams);
+            selected.push(candidate);
+        }
+        selected
+    }
+}
+
+/// The entropy in bits of a boundary decision whose logistic probability
+/// is `1 / (1 + exp(-x))`.
+fn binary_entropy(x: f64) -> f64 {
+    let p = 1.0 / (1.0 + (-x).exp());
+    [p, 1.0 - p].iter().filter(|&&q| q > 0.0).map(|&q| -q * q.log2()).sum()
+}
+
+/// The distinct character n-grams of `text`, or `text` itself when it is
+/// shorter than `order` characters.
+fn char_ngrams(text: &str, order: usize) -> HashSet<&str> {
+    let starts: Vec<usize> = text.char_indices().map(|(i, _)| i).chain([text.len()]).collect();
+    if starts.len() <= order {
+        return [text].into_iter().collect();
+    }
+    starts.windows(order + 1).map(|w| &text[w[0]..w[order]]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::adaboost::AdaBoost;
+    use crate::language::Language;
+    use crate::user_dict::UserDictionary;
+
+    fn segmenter(model: &str) -> Segmenter {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join(model);
+        let mut learner = AdaBoost::new(0.01, 100);
+        learner.load_model_from_path(&path).unwrap();
+        Segmenter::with_learner(Language::Japanese, learner)
+    }
+
+    const CORPUS: [&str; 5] = [
+        "これはテストです。",
+        "きゃりーぱみゅぱみゅが歌う。",
+        "東京都に住んでいます。",
+        "字",
+        "",
+    ];
+
+    #[test]
+    fn test_uncertainty_parse_and_display() {
+        for uncertainty in [Uncertainty::Margin, Uncertainty::Entropy] {
+            assert_eq!(uncertainty.to_string().parse::<Uncertainty>(), Ok(uncertainty));
+        }
+        assert_eq!("ENTROPY".parse::<Uncertainty>(), Ok(Uncertainty::Entropy));
+        let err = "variance".parse::<Uncertainty>().unwrap_err();
+        assert_eq!(err.input(), "variance");
+    }
+
+    #[test]
+    fn test_margin_score_is_the_weakest_token_confidence() {
+        let segmenter = segmenter("japanese.model");
+        let selector = SentenceSelector::new();
+        let mut buf = SegmentBuffer::new();
+        for sentence in &CORPUS[..3] {
+            let weakest = segmenter
+                .segment_with_scores(sentence)
+                .iter()
+                .map(|(_, score)| score.confidence())
+                .fold(f64::INFINITY, f64::min);
+            let score = selector.score(&segmenter, sentence, &mut buf).unwrap();
+            assert!((score - weakest).abs() < 1e-9, "{sentence:?}: {score} vs {weakest}");
+        }
+        assert_eq!(selector.score(&segmenter, "字", &mut buf), None);
+        assert_eq!(selector.score(&segmenter, "", &mut buf), None);
+    }
+
+    #[test]
+    fn test_select_ranks_and_truncates() {
+        let segmenter = segmenter("japanese.model");
+        let selected = SentenceSelector::new().select(&segmenter, &CORPUS, 10);
+        // The single character and the empty line have nothing to annotate.
+        assert_eq!(selected.len(), 3);
+        assert!(selected.windows(2).all(|w| w[0].score <= w[1].score));
+        assert_eq!(SentenceSelector::new().select(&segmenter, &CORPUS, 1), selected[..1]);
+
+        let entropy = SentenceSelector::new().with_uncertainty(Uncertainty::Entropy);
+        let selected = entropy.select(&segmenter, &CORPUS, 10);
+        assert_eq!(selected.len(), 3);
+        assert!(selected.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(selected.iter().all(|s| s.score > 0.0));
+    }
+
+    #[test]
+    fn test_entropy_scale_sharpens_or_flattens_the_scores() {
+        let segmenter = segmenter("RWCP.model");
+        let mut buf = SegmentBuffer::new();
+        let mut entropy = |scale: f64| {
+            SentenceSelector::new()
+                .with_uncertainty(Uncertainty::Entropy)
+                .with_scale(scale)
+                .score(&segmenter, "これはテストです。", &mut buf)
+                .unwrap()
+        };
+        // Eight decisions, each at most one bit.
+        assert!(entropy(0.1) < entropy(1.0));
+        assert!(entropy(1.0) <= 8.0);
+        assert_eq!(entropy(-1.0), entropy(1.0));
+    }
+
+    #[test]
+    fn test_constrained_decisions_count_as_certain() {
+        let dictionary: UserDictionary = ["これはテストです"].into_iter().collect();
+        let segmenter = segmenter("japanese.model").with_user_dictionary(dictionary);
+        let mut buf = SegmentBuffer::new();
+        // Every gap is inside or at the edge of the dictionary match.
+        assert_eq!(SentenceSelector::new().score(&segmenter, "これはテストです。", &mut buf), None);
+        assert!(
+            SentenceSelector::new()
+                .score(&segmenter, "これはテストです。東京", &mut buf)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_diversity_skips_covered_sentences() {
+        let segmenter = segmenter("japanese.model");
+        let corpus = ["東京都に住んでいます。", "東京都に住んでいます。", "これはテストです。"];
+        let indices = |selected: Vec<SelectedSentence>| {
+            let mut indices: Vec<usize> = selected.iter().map(|s| s.index).collect();
+            indices.sort_unstable();
+            indices
+        };
+        assert_eq!(indices(SentenceSelector::new().select(&segmenter, &corpus, 3)), [0, 1, 2]);
+
+        // The duplicate is fully covered once the first copy is taken.
+        let diverse = SentenceSelector::new().with_diversity(2, 0.5);
+        assert_eq!(indices(diverse.select(&segmenter, &corpus, 3)), [0, 2]);
+        let duplicates_only = SentenceSelector::new().with_diversity(3, 0.99);
+        assert_eq!(indices(duplicates_only.select(&segmenter, &corpus, 3)), [0, 2]);
+    }
+
+    #[test]
+    fn test_char_ngrams() {
+        let grams = char_ngrams("東京東京", 2);
+        assert_eq!(grams, ["東京", "京東"].into_iter().collect());
+        assert_eq!(char_ngrams("字", 3), ["字"].into_iter().collect());
+        assert_eq!(char_ngrams("ab", 2), ["ab"].into_iter().collect());
+    }
+}
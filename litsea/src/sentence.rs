@@ -0,0 +1,345 @@
+//! Sentence splitting for document-level segmentation.
+//!
+//! [`Segmenter::segment`](crate::segmenter::Segmenter::segment) works one
+//! sentence at a time, so a document has to be split into sentences first.
+//! [`split`] does that with per-language rules and yields byte ranges into
+//! the document, which is what
+//! [`Segmenter::segment_document`](crate::segmenter::Segmenter::segment_document)
+//! builds on to report token offsets relative to the whole document.
+//!
+//! The rules, shared by all languages unless noted:
+//!
+//! - Full-width terminators (`。！？｡`) end a sentence wherever they occur;
+//!   ASCII terminators (`.!?`) only when followed by whitespace or the end
+//!   of the text, so `3.14` and `Yahoo!ニュース` stay whole.
+//! - A run of terminators and the closing brackets or quotes right after
+//!   it (`。」`, `?!"`) stays with the sentence it ends.
+//! - Nothing inside brackets (`「」『』（）()【】〔〕〈〉《》“”［］[]`) ends a
+//!   sentence, so a quoted `「こんにちは。太郎です。」` is one unit.
+//! - Japanese: a closing bracket after a terminator does not end the
+//!   sentence when hiragana follows (`「はい。」と言った。` is one
+//!   sentence), since that is a quotation particle continuing it.
+//! - English: a period does not end a sentence after a title or common
+//!   abbreviation (`Dr.`, `Mr.`, `vs.`), a single capital initial
+//!   (`J. Smith`), a dotted abbreviation (`U.S.`, `e.g.`), or when the next
+//!   word starts with a lowercase letter.
+//! - A blank line (paragraph break) always ends a sentence; a single line
+//!   break is ordinary whitespace, so hard-wrapped text is rejoined.
+//!
+//! Character classes come from [`Language::char_type`] (hiragana, Latin
+//! case), so the rules see the same classification as the segmenter.
+
+use std::iter::FusedIterator;
+
+use crate::language::Language;
+
+/// Titles and abbreviations whose period never ends an English sentence
+/// (compared case-insensitively, without the period). Abbreviations that
+/// commonly end sentences (`etc.`, `Inc.`) are deliberately absent.
+const ENGLISH_ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "mt", "rev", "gen", "gov", "sen", "rep",
+    "capt", "col", "lt", "sgt", "vs", "approx", "dept", "fig", "no", "vol", "jan", "feb", "mar",
+    "apr", "jun", "jul", "aug", "sep", "sept", "oct", "nov", "dec",
+];
+
+/// How a terminator character ends a sentence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Terminator {
+    /// Full-width: ends the sentence wherever it occurs.
+    Full,
+    /// ASCII: ends the sentence only before whitespace or the end of text.
+    Spaced,
+}
+
+/// Classifies a sentence-terminating character.
+fn terminator(c: char) -> Option<Terminator> {
+    match c {
+        '。' | '！' | '？' | '｡' => Some(Terminator::Full),
+        '.' | '!' | '?' => Some(Terminator::Spaced),
+        _ => None,
+    }
+}
+
+/// Returns true for an opening bracket or quote (paired with
+/// [`is_closer`]).
+fn is_opener(c: char) -> bool {
+    matches!(c, '「' | '『' | '（' | '(' | '【' | '〔' | '〈' | '《' | '“' | '［' | '[')
+}
+
+/// Returns true for a closing bracket or quote (paired with
+/// [`is_opener`]).
+fn is_closer(c: char) -> bool {
+    matches!(c, '」' | '』' | '）' | ')' | '】' | '〕' | '〉' | '》' | '”' | '］' | ']')
+}
+
+/// Returns true for a quote that may trail a terminator but cannot be
+/// paired: the ASCII quotes read the same opening and closing, and `’`
+/// doubles as an apostrophe.
+fn is_unpaired_quote(c: char) -> bool {
+    matches!(c, '"' | '\'' | '’')
+}
+
+/// Splits `text` into sentences.
+///
+/// # Arguments
+/// * `language` - The language whose rules to apply (see the module docs).
+/// * `text` - The document to split.
+///
+/// # Returns
+/// An iterator over the sentences as `(start, end)` byte ranges into
+/// `text`, in order. Whitespace between sentences is excluded from the
+/// ranges, and a text of only whitespace yields no sentences.
+///
+/// # Example
+/// ```
+/// use litsea::language::Language;
+/// use litsea::sentence;
+///
+/// let text = "彼は「こんにちは。」と言った。今日は晴れです！";
+/// let sentences: Vec<&str> =
+///     sentence::split(Language::Japanese, text).map(|(start, end)| &text[start..end]).collect();
+/// assert_eq!(sentences, vec!["彼は「こんにちは。」と言った。", "今日は晴れです！"]);
+/// ```
+#[must_use]
+pub fn split(language: Language, text: &str) -> Sentences<'_> {
+    Sentences {
+        language,
+        text,
+        pos: 0,
+    }
+}
+
+/// Iterator over the sentences of a text, returned by [`split`].
+#[derive(Debug, Clone)]
+pub struct Sentences<'a> {
+    language: Language,
+    text: &'a str,
+    /// Byte offset where the next sentence search starts.
+    pos: usize,
+}
+
+impl Sentences<'_> {
+    /// Scans forward from `start` (a non-whitespace character) to the byte
+    /// offset where the sentence starting there ends, trailing whitespace
+    /// included.
+    fn sentence_end(&self, start: usize) -> usize {
+        let text = self.text;
+        let mut depth = 0usize;
+        // Line breaks in the current whitespace run; two make a blank line.
+        let mut newlines = 0;
+        // Start of the current whitespace-delimited word.
+        let mut word_start = start;
+        // The first terminator of the pending run and its kind, plus
+        // whether a closing bracket or quote followed it.
+        let mut pending: Option<(usize, Terminator)> = None;
+        let mut after_closer = false;
+
+        for (offset, c) in text[start..].char_indices() {
+            let i = start + offset;
+            if let Some(kind) = terminator(c) {
+                pending = match pending {
+                    Some((at, Terminator::Full)) => Some((at, Terminator::Full)),
+                    Some((at, _)) => Some((at, kind)),
+                    None => Some((i, kind)),
+                };
+                after_closer = false;
+                continue;
+            }
+            if is_closer(c) || is_unpaired_quote(c) {
+                if is_closer(c) {
+                    depth = depth.saturating_sub(1);
+                }
+                after_closer = pending.is_some();
+                continue;
+            }
+            if let Some((at, kind)) = pending.take() {
+                if depth == 0 && self.ends_sentence(kind, at, word_start, after_closer, i, c) {
+                    return i;
+                }
+            }
+            if c == '\n' {
+                newlines += 1;
+                if newlines == 2 {
+                    return i;
+                }
+            } else if !c.is_whitespace() {
+                newlines = 0;
+            }
+            if c.is_whitespace() {
+                word_start = i + c.len_utf8();
+            } else if is_opener(c) {
+                depth += 1;
+            }
+        }
+        text.len()
+    }
+
+    /// Decides whether the terminator run starting at byte `at` ends the
+    /// sentence, given the character `c` at byte `i` that follows it.
+    fn ends_sentence(
+        &self,
+        kind: Terminator,
+        at: usize,
+        word_start: usize,
+        after_closer: bool,
+        i: usize,
+        c: char,
+    ) -> bool {
+        match kind {
+            Terminator::Full => {
+                // 「はい。」と言った: the quotation particle continues it.
+                !(self.language == Language::Japanese
+                    && after_closer
+                    && self.language.char_type(c) == "I")
+            }
+            Terminator::Spaced => {
+                c.is_whitespace()
+                    && !(self.language == Language::English
+                        && self.text[at..].starts_with('.')
+                        && self.is_english_abbreviation(word_start, at, i))
+            }
+        }
+    }
+
+    /// Returns true when the period at byte `at` belongs to an English
+    /// abbreviation rather than ending the sentence; the word it closes
+    /// starts at `word_start`, and the text resumes at `i`.
+    fn is_english_abbreviation(&self, word_start: usize, at: usize, i: usize) -> bool {
+        let language = self.language;
+        let word = self.text[word_start..at].trim_start_matches(|c: char| !c.is_alphanumeric());
+        let mut chars = word.chars();
+        let initial =
+            matches!((chars.next(), chars.next()), (Some(c), None) if language.char_type(c) == "U");
+        let next_is_lowercase = self.text[i..]
+            .trim_start()
+            .chars()
+            .next()
+            .is_some_and(|c| language.char_type(c) == "A" && c.is_lowercase());
+        ENGLISH_ABBREVIATIONS
+            .iter()
+            .any(|abbreviation| abbreviation.eq_ignore_ascii_case(word))
+            || (word.contains('.') && !word.contains(|c: char| c.is_ascii_digit()))
+            || initial
+            || next_is_lowercase
+    }
+}
+
+impl Iterator for Sentences<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.text[self.pos..];
+        let start = self.pos + (rest.len() - rest.trim_start().len());
+        if start == self.text.len() {
+            self.pos = start;
+            return None;
+        }
+        let end = self.sentence_end(start);
+        self.pos = end;
+        Some((start, start + self.text[start..end].trim_end().len()))
+    }
+}
+
+impl FusedIterator for Sentences<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences(language: Language, text: &str) -> Vec<&str> {
+        split(language, text).map(|(start, end)| &text[start..end]).collect()
+    }
+
+    #[test]
+    fn test_japanese_terminators_and_brackets() {
+        assert_eq!(
+            sentences(Language::Japanese, "今日は晴れ。明日は雨！本当？"),
+            vec!["今日は晴れ。", "明日は雨！", "本当？"]
+        );
+        // Terminators inside brackets never split; the quotation particle
+        // after the closing bracket continues the sentence.
+        assert_eq!(
+            sentences(Language::Japanese, "彼は「こんにちは。太郎です。」と言った。次の文。"),
+            vec!["彼は「こんにちは。太郎です。」と言った。", "次の文。"]
+        );
+        // A closing bracket after a terminator stays with its sentence.
+        assert_eq!(
+            sentences(Language::Japanese, "「はい。」次に進む。"),
+            vec!["「はい。」", "次に進む。"]
+        );
+        assert_eq!(sentences(Language::Japanese, "えっ！？そうなの"), vec!["えっ！？", "そうなの"]);
+    }
+
+    #[test]
+    fn test_ascii_terminators_need_whitespace() {
+        assert_eq!(
+            sentences(Language::Japanese, "Yahoo!ニュースで円周率3.14を見た。"),
+            vec!["Yahoo!ニュースで円周率3.14を見た。"]
+        );
+    }
+
+    #[test]
+    fn test_chinese_full_width_punctuation() {
+        assert_eq!(
+            sentences(Language::Chinese, "我喜欢吃中国菜。你呢？太好了！"),
+            vec!["我喜欢吃中国菜。", "你呢？", "太好了！"]
+        );
+        assert_eq!(sentences(Language::Chinese, "“你好。”他说。"), vec!["“你好。”", "他说。"]);
+    }
+
+    #[test]
+    fn test_english_abbreviations_and_initials() {
+        assert_eq!(
+            sentences(
+                Language::English,
+                "Dr. Smith met J. R. Tolkien in the U.S. yesterday. It was fun! Was it? Yes."
+            ),
+            vec![
+                "Dr. Smith met J. R. Tolkien in the U.S. yesterday.",
+                "It was fun!",
+                "Was it?",
+                "Yes."
+            ]
+        );
+        assert_eq!(
+            sentences(Language::English, "He said \"Stop.\" Then he left... and returned."),
+            vec!["He said \"Stop.\"", "Then he left... and returned."]
+        );
+        assert_eq!(
+            sentences(Language::English, "Pi is 3.14. Next (see fig. 2.) is here."),
+            vec!["Pi is 3.14.", "Next (see fig. 2.) is here."]
+        );
+    }
+
+    #[test]
+    fn test_korean_sentences() {
+        assert_eq!(
+            sentences(Language::Korean, "안녕하세요. 반갑습니다! 잘 지내요?"),
+            vec!["안녕하세요.", "반갑습니다!", "잘 지내요?"]
+        );
+    }
+
+    #[test]
+    fn test_paragraph_break_ends_a_sentence() {
+        let text = "This line is\nwrapped\n\nNew paragraph";
+        assert_eq!(
+            sentences(Language::English, text),
+            vec!["This line is\nwrapped", "New paragraph"]
+        );
+        // An unbalanced bracket does not swallow the next paragraph.
+        assert_eq!(
+            sentences(Language::Japanese, "「未完の文。\r\n\r\n次の段落。"),
+            vec!["「未完の文。", "次の段落。"]
+        );
+    }
+
+    #[test]
+    fn test_ranges_exclude_surrounding_whitespace() {
+        let text = "  最初。　二番目。 \n";
+        let ranges: Vec<(usize, usize)> = split(Language::Japanese, text).collect();
+        assert_eq!(ranges, vec![(2, 11), (14, 26)]);
+        assert!(split(Language::Japanese, "").next().is_none());
+        assert!(split(Language::Japanese, " \n\t").next().is_none());
+        assert_eq!(sentences(Language::Japanese, "終わりなし"), vec!["終わりなし"]);
+    }
+}
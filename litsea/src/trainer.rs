@@ -1,22 +1,28 @@
 //! High-level training front-ends.
 //!
 //! Defines [`Trainer`] (AdaBoost word-boundary model), [`PerceptronTrainer`]
-//! (a generic label-agnostic Averaged Perceptron trainer, used as the
-//! training step of the boundary-collapse recipe below), and
-//! [`TwoStageTrainer`] (the two-stage boundary + word-tagger model of issue
-//! #147). `Trainer` and `PerceptronTrainer` each read a single features file
-//! produced by [`Extractor`](crate::extractor::Extractor); `TwoStageTrainer`
-//! reads the three files
+//! (a generic label-agnostic Averaged Perceptron trainer),
+//! [`BoundaryPerceptronTrainer`] (a boundary perceptron saved through the
+//! collapse below), and [`TwoStageTrainer`] (the two-stage boundary +
+//! word-tagger model of issue #147). The first three each read a single
+//! features file produced by [`Extractor`](crate::extractor::Extractor);
+//! `Trainer` and `BoundaryPerceptronTrainer` can instead stream the same
+//! instances straight from a segmented corpus (`from_corpus`), with no
+//! features file in between, and `BoundaryPerceptronTrainer` from a raw
+//! corpus labeled by a teacher model (`from_teacher`, for distillation);
+//! `TwoStageTrainer` reads the three files
 //! [`Extractor::extract_two_stage`](crate::extractor::Extractor::extract_two_stage)
-//! writes from a common prefix. All three optionally load an existing
-//! model, train, save the result, and report training metrics.
+//! writes from a common prefix. All of them train, save the result, and
+//! report training metrics; `Trainer` and `PerceptronTrainer` can also
+//! continue from an existing model.
 //!
 //! # The lossless boundary-perceptron-to-AdaBoost collapse
 //!
 //! `TwoStageTrainer` trains its stage-1 boundary classifier as a 2-class
 //! (`B`/`O`) [`AveragedPerceptron`] but saves it in the [`AdaBoost`] text
 //! format, via the private `collapse_boundary_perceptron` helper in this
-//! module. This is also how the bundled
+//! module. [`BoundaryPerceptronTrainer`] does the same for a plain
+//! segmentation model, and is how the bundled
 //! `models/{japanese,chinese,korean}.model` segmentation models are produced
 //! (issue #165): trained as a 2-class Averaged Perceptron, then losslessly
 //! collapsed to scalar AdaBoost-format weights, rather than trained by
@@ -26,20 +32,20 @@
 //! for any feature set `f` its class scores are `score_B = sum_{feat in f}
 //! w_B[feat]` and `score_O = sum_{feat in f} w_O[feat]`, and therefore
 //! `score_B - score_O = sum_{feat in f} (w_B[feat] - w_O[feat])` exactly.
-//! Writing one line `feat\t(w_B[feat] - w_O[feat])` per feature plus a
-//! literal `"0"` bias line and loading the result with
-//! [`AdaBoost::load_model_from_reader`] reproduces this: the AdaBoost model
-//! format defines `bias()` to equal the written bias line verbatim
-//! (independent of the feature weights), so the collapsed model's decision
-//! rule `score >= 0.0` becomes exactly `score_B >= score_O`. This includes
-//! the tie case (an empty or entirely-unseen feature set): the perceptron's
-//! first-wins argmax picks class index 0, which is `"B"` because classes are
-//! always ordered `["B", "O"]` (`B` sorts first alphabetically), the same
-//! class AdaBoost's `0.0 >= 0.0` picks.
+//! Building an AdaBoost model with one weight `w_B[feat] - w_O[feat]` per
+//! feature and a bias of exactly `0` reproduces this (the bias is set
+//! directly rather than re-derived from a float sum of the weights, whose
+//! last bits would depend on their order), so the collapsed model's
+//! decision rule `score >= 0.0` becomes exactly `score_B >= score_O`. This
+//! includes the tie case (an empty or entirely-unseen feature set): the
+//! perceptron's first-wins argmax picks class index 0, which is `"B"`
+//! because classes are always ordered `["B", "O"]` (`B` sorts first
+//! alphabetically), the same class AdaBoost's `0.0 >= 0.0` picks.
 
 use std::collections::HashSet;
 use std::io::{BufRead, Write};
 use std::sync::atomic::AtomicBool;
+use std::time::Instant;
 
 // The path-based entry points are compiled out on wasm32, which has no
 // filesystem; the in-memory ones below work everywhere.
@@ -47,20 +53,27 @@ use std::sync::atomic::AtomicBool;
 use std::fs::File;
 #[cfg(not(target_arch = "wasm32"))]
 use std::io;
-#[cfg(not(target_arch = "wasm32"))]
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use rustc_hash::FxHashMap;
 
 use crate::adaboost::AdaBoost;
+use crate::checkpoint::Checkpoint;
+use crate::cutoff::FeatureCutoff;
 use crate::error::{LitseaError, Result};
+use crate::extractor::{CorpusFormat, Extractor};
+use crate::metadata::{ModelMetadata, TEMPLATES_FULL, TEMPLATES_TAG_FREE};
 use crate::metrics::{BinaryMetrics, MulticlassMetrics};
-use crate::perceptron::AveragedPerceptron;
-use crate::two_stage::{TwoStageLearner, parse_lexicon};
+use crate::normalize::Normalization;
+use crate::perceptron::{AveragedPerceptron, LearnerState, TrainingRun};
+use crate::progress::{Progress, ProgressObserver, TrainingStage};
+use crate::segmenter::Segmenter;
+use crate::two_stage::{TwoStageFeatureSet, TwoStageLearner, parse_lexicon};
 // Only `TwoStageTrainer::new` needs the path helper.
 #[cfg(not(target_arch = "wasm32"))]
 use crate::two_stage::two_stage_paths;
 use crate::upos::Upos;
+use crate::user_dict::UserDictionary;
 
 /// Trainer struct for managing the AdaBoost training process.
 /// It initializes the AdaBoost learner with the specified parameters (from a
@@ -71,20 +84,65 @@ use crate::upos::Upos;
 pub struct Trainer {
     /// The underlying AdaBoost learner.
     learner: AdaBoost,
+    /// The feature frequency cutoff applied before training.
+    cutoff: FeatureCutoff,
+    /// Receives a report after every boosting iteration.
+    progress: Option<Box<dyn ProgressObserver>>,
 }
 
 /// Generic Averaged Perceptron trainer.
 /// Manages multiclass classification training with the Averaged Perceptron;
 /// labels are treated as opaque strings, so it works for any label space.
-/// Its main use is training the 2-class (`B`/`O`) boundary perceptron that
-/// the collapse recipe (see the module docs) turns into the bundled
-/// AdaBoost-format segmentation models.
+/// For a segmentation model, use [`BoundaryPerceptronTrainer`], which
+/// trains the same learner and saves it collapsed to the AdaBoost format.
 #[derive(Debug)]
 pub struct PerceptronTrainer {
     /// The underlying Averaged Perceptron learner.
     learner: AveragedPerceptron,
     /// The number of training epochs to run.
     num_epochs: usize,
+    /// The feature frequency cutoff applied before training.
+    cutoff: FeatureCutoff,
+    /// Epochs without development-set improvement after which training
+    /// stops.
+    patience: Option<usize>,
+    /// Where and how often training writes checkpoints.
+    checkpoint: Option<CheckpointTarget>,
+    /// The checkpoint training continues from.
+    resume: Option<Checkpoint>,
+    /// Receives a report after every epoch.
+    progress: Option<Box<dyn ProgressObserver>>,
+}
+
+/// Boundary Averaged Perceptron trainer.
+/// Trains a 2-class (`B`/`O`) Averaged Perceptron on ordinary
+/// [`Extractor`](crate::extractor::Extractor) output (`1`/`-1` labels) and
+/// saves it losslessly collapsed (see the module docs) into an
+/// AdaBoost-format segmentation model, the way the bundled models are
+/// trained.
+#[derive(Debug)]
+pub struct BoundaryPerceptronTrainer {
+    /// The underlying 2-class Averaged Perceptron learner.
+    learner: AveragedPerceptron,
+    /// The number of training epochs to run.
+    num_epochs: usize,
+    /// The feature frequency cutoff applied before training.
+    cutoff: FeatureCutoff,
+    /// Epochs without development-set improvement after which training
+    /// stops.
+    patience: Option<usize>,
+    /// Where and how often training writes checkpoints.
+    checkpoint: Option<CheckpointTarget>,
+    /// The checkpoint training continues from.
+    resume: Option<Checkpoint>,
+    /// Receives a report after every epoch.
+    progress: Option<Box<dyn ProgressObserver>>,
+    /// The normalization the collapsed model declares.
+    normalization: Normalization,
+    /// The dictionary-feature word list the collapsed model stores.
+    dictionary: UserDictionary,
+    /// Provenance recorded in the collapsed model's header.
+    metadata: ModelMetadata,
 }
 
 impl Trainer {
@@ -107,7 +165,11 @@ impl Trainer {
         learner.initialize_features(features_path)?;
         learner.initialize_instances(features_path)?;
 
-        Ok(Trainer { learner })
+        Ok(Trainer {
+            learner,
+            cutoff: FeatureCutoff::default(),
+            progress: None,
+        })
     }
 
     /// Creates a `Trainer` from a features file's contents.
@@ -135,7 +197,232 @@ impl Trainer {
         learner.initialize_features_from_str(features)?;
         learner.initialize_instances_from_str(features)?;
 
-        Ok(Trainer { learner })
+        Ok(Trainer {
+            learner,
+            cutoff: FeatureCutoff::default(),
+            progress: None,
+        })
+    }
+
+    /// Creates a `Trainer` straight from a segmented corpus file.
+    ///
+    /// The instances `extractor` would write to a features file are fed to
+    /// the learner as they are extracted, so no features file is written
+    /// or read; the trained model is the one [`new`](Self::new) trains on
+    /// the extracted file.
+    ///
+    /// # Arguments
+    /// * `threshold` - The threshold for the weak classifier's accuracy.
+    /// * `num_iterations` - The maximum number of boosting iterations.
+    /// * `extractor` - The extractor defining the language and
+    ///   normalization of the features.
+    /// * `corpus_path` - The path to the segmented corpus file.
+    /// * `format` - The layout of the corpus lines.
+    /// * `tag_free` - Whether to train on the tag-free template subset.
+    ///
+    /// # Returns
+    /// Returns a new instance of `Trainer`.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the corpus cannot be read, or
+    /// [`LitseaError::InvalidData`] if it yields no features.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_corpus(
+        threshold: f64,
+        num_iterations: usize,
+        extractor: &Extractor,
+        corpus_path: &Path,
+        format: CorpusFormat,
+        tag_free: bool,
+    ) -> Result<Self> {
+        let mut learner = AdaBoost::new(threshold, num_iterations);
+
+        extractor.extract_instances(corpus_path, format, tag_free, |attrs, label| {
+            learner.add_instance(attrs, label)
+        })?;
+        learner.sort_features()?;
+
+        Ok(Trainer {
+            learner,
+            cutoff: FeatureCutoff::default(),
+            progress: None,
+        })
+    }
+
+    /// Creates a `Trainer` straight from an in-memory segmented corpus.
+    ///
+    /// The in-memory counterpart of [`from_corpus`](Self::from_corpus).
+    ///
+    /// # Arguments
+    /// * `threshold` - The threshold for the weak classifier's accuracy.
+    /// * `num_iterations` - The maximum number of boosting iterations.
+    /// * `extractor` - The extractor defining the language and
+    ///   normalization of the features.
+    /// * `corpus` - The corpus contents, one sentence per line.
+    /// * `format` - The layout of the corpus lines.
+    /// * `tag_free` - Whether to train on the tag-free template subset.
+    ///
+    /// # Returns
+    /// Returns a new instance of `Trainer`.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the corpus yields no
+    /// features or a partially annotated line is malformed.
+    pub fn from_corpus_str(
+        threshold: f64,
+        num_iterations: usize,
+        extractor: &Extractor,
+        corpus: &str,
+        format: CorpusFormat,
+        tag_free: bool,
+    ) -> Result<Self> {
+        let mut learner = AdaBoost::new(threshold, num_iterations);
+
+        extractor.extract_instances_from_str(corpus, format, tag_free, |attrs, label| {
+            learner.add_instance(attrs, label)
+        })?;
+        learner.sort_features()?;
+
+        Ok(Trainer {
+            learner,
+            cutoff: FeatureCutoff::default(),
+            progress: None,
+        })
+    }
+
+    /// Declares the Unicode normalization the training features were
+    /// extracted with (see
+    /// [`Extractor::with_normalization`](crate::extractor::Extractor::with_normalization)),
+    /// so the saved model records it and segmenters loading the model apply
+    /// it to their input.
+    ///
+    /// A model loaded afterwards with [`load_model`](Self::load_model)
+    /// replaces the declaration with its own.
+    ///
+    /// # Arguments
+    /// * `normalization` - The normalization form of the training text.
+    ///
+    /// # Returns
+    /// The trainer with the declaration set.
+    #[must_use]
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.learner.set_normalization(normalization);
+        self
+    }
+
+    /// Declares the word list the training features' dictionary features
+    /// were extracted with (see
+    /// [`Extractor::with_dictionary`](crate::extractor::Extractor::with_dictionary)),
+    /// so the saved model stores it and segmenters loading the model match
+    /// it against their input.
+    ///
+    /// A model loaded afterwards with [`load_model`](Self::load_model)
+    /// replaces the word list with its own.
+    ///
+    /// # Arguments
+    /// * `dictionary` - The word list of the training features.
+    ///
+    /// # Returns
+    /// The trainer with the word list set.
+    #[must_use]
+    pub fn with_dictionary(mut self, dictionary: UserDictionary) -> Self {
+        self.learner.set_dictionary(dictionary);
+        self
+    }
+
+    /// Sets the feature frequency cutoff: features occurring in fewer
+    /// training instances than it requires are dropped before training,
+    /// and the number dropped is reported as
+    /// [`BinaryMetrics::features_removed`]. Features carrying weights from
+    /// a model loaded with [`load_model`](Self::load_model) are kept. A
+    /// cutoff other than the default is recorded as the model's
+    /// `min_count` parameter.
+    ///
+    /// # Arguments
+    /// * `cutoff` - The minimum feature counts.
+    ///
+    /// # Returns
+    /// The trainer with the cutoff set.
+    #[must_use]
+    pub fn with_cutoff(mut self, cutoff: FeatureCutoff) -> Self {
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Reports the progress of training to `observer` after every boosting
+    /// iteration (see [`TrainingProgress`](crate::progress::TrainingProgress)),
+    /// or stops reporting with `None`.
+    ///
+    /// # Arguments
+    /// * `observer` - The observer receiving the reports.
+    pub fn set_progress(&mut self, observer: Option<Box<dyn ProgressObserver>>) {
+        self.progress = observer;
+    }
+
+    /// Declares provenance to record in the saved model (see
+    /// [`ModelMetadata`]): typically the language and the corpus checksum.
+    /// Training adds its own parameters (`threshold`, `iterations`) and,
+    /// unless declared here, the template set (`full` or `tag-free`,
+    /// detected from the features).
+    ///
+    /// Like [`with_normalization`](Self::with_normalization), a model
+    /// loaded afterwards with [`load_model`](Self::load_model) replaces the
+    /// declaration with its own.
+    ///
+    /// # Arguments
+    /// * `metadata` - The provenance to record.
+    ///
+    /// # Returns
+    /// The trainer with the metadata set.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: ModelMetadata) -> Self {
+        self.learner.set_metadata(metadata);
+        self
+    }
+
+    /// Returns the provenance the saved model will record (before training
+    /// adds its parameters): the declared metadata, or that of a model
+    /// loaded with [`load_model`](Self::load_model).
+    #[must_use]
+    pub fn metadata(&self) -> &ModelMetadata {
+        self.learner.metadata()
+    }
+
+    /// Runs the boosting iterations, reporting them to the progress
+    /// observer.
+    ///
+    /// # Arguments
+    /// * `running` - A flag for interrupting the training.
+    fn boost(&mut self, running: &AtomicBool) {
+        match self.progress.as_deref_mut() {
+            Some(observer) => {
+                let total = self.learner.num_iterations();
+                let mut progress =
+                    Progress::new(observer, TrainingStage::Boosting, total, Instant::now());
+                self.learner.train_observed(running, Some(&mut progress));
+            }
+            None => self.learner.train(running),
+        }
+    }
+
+    /// Adds the training parameters and, unless declared, the detected
+    /// template set to the model's metadata.
+    fn record_training(&mut self) {
+        let mut metadata = self
+            .learner
+            .metadata()
+            .clone()
+            .with_param("threshold", self.learner.threshold())
+            .with_param("iterations", self.learner.num_iterations());
+        if !self.cutoff.is_noop() {
+            metadata = metadata.with_param("min_count", &self.cutoff);
+        }
+        if metadata.templates().is_none() {
+            let templates =
+                if self.learner.is_tag_free() { TEMPLATES_TAG_FREE } else { TEMPLATES_FULL };
+            metadata = metadata.with_templates(templates);
+        }
+        self.learner.set_metadata(metadata);
     }
 
     /// Load Model from a URI.
@@ -184,12 +471,17 @@ impl Trainer {
     /// Returns an error if the training fails or if the model cannot be saved.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn train(&mut self, running: &AtomicBool, model_path: &Path) -> Result<BinaryMetrics> {
-        self.learner.train(running);
+        let removed = self.learner.apply_cutoff(&self.cutoff);
+        self.boost(running);
+        self.record_training();
 
         // Save the trained model to the specified file
         self.learner.save_model(model_path)?;
 
-        Ok(self.learner.metrics())
+        Ok(BinaryMetrics {
+            features_removed: removed,
+            ..self.learner.metrics()
+        })
     }
 
     /// Trains the model and writes it to a writer.
@@ -214,10 +506,15 @@ impl Trainer {
         running: &AtomicBool,
         writer: &mut W,
     ) -> Result<BinaryMetrics> {
-        self.learner.train(running);
+        let removed = self.learner.apply_cutoff(&self.cutoff);
+        self.boost(running);
+        self.record_training();
         self.learner.save_model_to_writer(writer)?;
 
-        Ok(self.learner.metrics())
+        Ok(BinaryMetrics {
+            features_removed: removed,
+            ..self.learner.metrics()
+        })
     }
 }
 
@@ -242,8 +539,13 @@ impl PerceptronTrainer {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn new(num_epochs: usize, features_path: &Path) -> Result<Self> {
         Ok(PerceptronTrainer {
-            learner: load_perceptron_instances(features_path)?,
+            learner: load_perceptron_instances(features_path, ingest_perceptron_line)?,
             num_epochs,
+            cutoff: FeatureCutoff::default(),
+            patience: None,
+            checkpoint: None,
+            resume: None,
+            progress: None,
         })
     }
 
@@ -262,11 +564,207 @@ impl PerceptronTrainer {
     /// Returns an error if a feature line is missing its label.
     pub fn from_features(num_epochs: usize, features: &str) -> Result<Self> {
         Ok(PerceptronTrainer {
-            learner: parse_perceptron_instances(features)?,
+            learner: parse_perceptron_instances(features, ingest_perceptron_line)?,
             num_epochs,
+            cutoff: FeatureCutoff::default(),
+            patience: None,
+            checkpoint: None,
+            resume: None,
+            progress: None,
         })
     }
 
+    /// Sets the feature frequency cutoff (see [`Trainer::with_cutoff`]);
+    /// the number of features dropped is reported as
+    /// [`MulticlassMetrics::features_removed`].
+    ///
+    /// # Arguments
+    /// * `cutoff` - The minimum feature counts.
+    ///
+    /// # Returns
+    /// The trainer with the cutoff set.
+    #[must_use]
+    pub fn with_cutoff(mut self, cutoff: FeatureCutoff) -> Self {
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Reports the progress of training to `observer` after every epoch
+    /// (see [`TrainingProgress`](crate::progress::TrainingProgress)), or
+    /// stops reporting with `None`.
+    ///
+    /// # Arguments
+    /// * `observer` - The observer receiving the reports.
+    pub fn set_progress(&mut self, observer: Option<Box<dyn ProgressObserver>>) {
+        self.progress = observer;
+    }
+
+    /// Loads a held-out development features file (the same format as
+    /// the training one). After every epoch the model is scored on it, the
+    /// weights of the best epoch are kept, and the per-epoch accuracies are
+    /// reported as [`MulticlassMetrics::dev_curve`] (see
+    /// [`AveragedPerceptron::train_with_dev`]).
+    ///
+    /// # Arguments
+    /// * `dev_path` - The path to the development features file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or a line is missing its
+    /// label.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_dev(&mut self, dev_path: &Path) -> Result<()> {
+        self.learner
+            .set_dev(load_perceptron_instances(dev_path, ingest_perceptron_line)?);
+        Ok(())
+    }
+
+    /// Loads held-out development features from a features file's
+    /// contents.
+    ///
+    /// The in-memory counterpart of [`load_dev`](Self::load_dev).
+    ///
+    /// # Arguments
+    /// * `dev` - The contents of the development features file.
+    ///
+    /// # Errors
+    /// Returns an error if a line is missing its label.
+    pub fn load_dev_from_features(&mut self, dev: &str) -> Result<()> {
+        self.learner.set_dev(parse_perceptron_instances(dev, ingest_perceptron_line)?);
+        Ok(())
+    }
+
+    /// Sets the early-stopping patience: once a development set is loaded,
+    /// training stops after this many epochs in a row without a better
+    /// development accuracy. Without it, every epoch runs (the best
+    /// epoch's weights are still kept).
+    ///
+    /// # Arguments
+    /// * `patience` - The number of epochs without improvement to allow.
+    ///
+    /// # Returns
+    /// The trainer with the patience set.
+    #[must_use]
+    pub fn with_patience(mut self, patience: usize) -> Self {
+        self.patience = Some(patience);
+        self
+    }
+
+    /// Shuffles the training instances every epoch, in an order drawn
+    /// from `seed` (see [`AveragedPerceptron::set_seed`]). The same seed
+    /// and inputs produce a byte-identical model; the seed is recorded as
+    /// the model's `seed` parameter.
+    ///
+    /// # Arguments
+    /// * `seed` - The shuffle seed.
+    ///
+    /// # Returns
+    /// The trainer with the seed set.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.learner.set_seed(Some(seed));
+        self
+    }
+
+    /// Trains every epoch on `threads` threads by iterative parameter
+    /// mixing (see [`AveragedPerceptron::set_threads`]). The model is
+    /// deterministic for a given thread count and seed but differs from
+    /// the single-threaded one, so a count above 1 is recorded as the
+    /// model's `threads` parameter.
+    ///
+    /// # Arguments
+    /// * `threads` - The number of threads; 0 and 1 train single-threaded.
+    ///
+    /// # Returns
+    /// The trainer with the thread count set.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.learner.set_threads(threads);
+        self
+    }
+
+    /// Writes a [`Checkpoint`] of the training run to `path` after every
+    /// `every` completed epochs and when the `running` flag interrupts
+    /// training, so an interrupted run can be continued with
+    /// [`resume`](Self::resume).
+    ///
+    /// Each checkpoint replaces the previous one. It holds the perceptron's
+    /// weights and averaging state, so it is several times the size of the
+    /// model.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the checkpoint file.
+    /// * `every` - The number of epochs between two checkpoints; `0` only
+    ///   writes one when training is interrupted.
+    ///
+    /// # Returns
+    /// The trainer with checkpointing enabled.
+    #[must_use]
+    pub fn with_checkpoint(mut self, path: &Path, every: usize) -> Self {
+        self.checkpoint = Some(CheckpointTarget {
+            path: path.to_path_buf(),
+            every,
+        });
+        self
+    }
+
+    /// Continues an interrupted training run from its checkpoint.
+    ///
+    /// Training then picks up exactly where the checkpointed run stopped,
+    /// and produces the model that run would have. The trainer must be set
+    /// up as that run was: the same features, development set, cutoff, and
+    /// epoch budget (the seed and weights come from the checkpoint).
+    ///
+    /// # Arguments
+    /// * `checkpoint` - A checkpoint written by
+    ///   [`with_checkpoint`](Self::with_checkpoint).
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the checkpoint holds more
+    /// than one stage. A checkpoint of other training data is rejected
+    /// when training starts.
+    pub fn resume(&mut self, checkpoint: Checkpoint) -> Result<()> {
+        self.resume = Some(single_stage(checkpoint)?);
+        Ok(())
+    }
+
+    /// Declares provenance to record in the saved model (see
+    /// [`ModelMetadata`]). Training adds its own `epochs` parameter. A
+    /// model loaded afterwards with [`load_model`](Self::load_model)
+    /// replaces the declaration with its own.
+    ///
+    /// # Arguments
+    /// * `metadata` - The provenance to record.
+    ///
+    /// # Returns
+    /// The trainer with the metadata set.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: ModelMetadata) -> Self {
+        self.learner.set_metadata(metadata);
+        self
+    }
+
+    /// Returns the provenance the saved model will record (see
+    /// [`Trainer::metadata`]).
+    #[must_use]
+    pub fn metadata(&self) -> &ModelMetadata {
+        self.learner.metadata()
+    }
+
+    /// Adds the training parameters to the model's metadata.
+    fn record_training(&mut self) {
+        let mut metadata = self.learner.metadata().clone().with_param("epochs", self.num_epochs);
+        if !self.cutoff.is_noop() {
+            metadata = metadata.with_param("min_count", &self.cutoff);
+        }
+        if let Some(seed) = self.learner.seed() {
+            metadata = metadata.with_param("seed", seed);
+        }
+        if self.learner.threads() > 1 {
+            metadata = metadata.with_param("threads", self.learner.threads());
+        }
+        self.learner.set_metadata(metadata);
+    }
+
     /// Loads an existing model from a URI.
     ///
     /// # Arguments
@@ -308,9 +806,9 @@ impl PerceptronTrainer {
     /// Returns an error if the trained model cannot be saved.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn train(&mut self, running: &AtomicBool, model_path: &Path) -> Result<MulticlassMetrics> {
-        self.learner.train(self.num_epochs, running);
+        let metrics = self.train_learner(running)?;
         self.learner.save_model(model_path)?;
-        Ok(self.learner.metrics())
+        Ok(metrics)
     }
 
     /// Trains the model and writes it to a writer.
@@ -332,65 +830,845 @@ impl PerceptronTrainer {
         running: &AtomicBool,
         writer: &mut W,
     ) -> Result<MulticlassMetrics> {
-        self.learner.train(self.num_epochs, running);
+        let metrics = self.train_learner(running)?;
         self.learner.save_model_to_writer(writer)?;
-        Ok(self.learner.metrics())
+        Ok(metrics)
     }
-}
-
-/// Loads training instances from a features file (`label\tfeature\t...`
-/// rows) into a fresh [`AveragedPerceptron`]. Shared by
-/// [`PerceptronTrainer::new`] and [`TwoStageTrainer::new`], which read the
-/// same row format for different label spaces (boundary `B`/`O` vs. UPOS
-/// tags — the perceptron treats labels as opaque strings either way).
-#[cfg(not(target_arch = "wasm32"))]
-fn load_perceptron_instances(features_path: &Path) -> Result<AveragedPerceptron> {
-    let mut learner = AveragedPerceptron::new();
 
-    let file = File::open(features_path)?;
-    let reader = io::BufReader::new(file);
-
-    // Streamed rather than slurped: a features file for a real corpus can be
-    // hundreds of megabytes.
-    for line in reader.lines() {
-        ingest_perceptron_line(&mut learner, &line?)?;
+    /// Trains the perceptron, without saving it.
+    ///
+    /// Shared by [`train`](Self::train) and
+    /// [`train_to_writer`](Self::train_to_writer).
+    ///
+    /// # Arguments
+    /// * `running` - A flag for interrupting the training.
+    ///
+    /// # Returns
+    /// The perceptron's in-sample metrics.
+    ///
+    /// # Errors
+    /// Returns an error if the resumed checkpoint does not match the
+    /// training data or a checkpoint cannot be written.
+    fn train_learner(&mut self, running: &AtomicBool) -> Result<MulticlassMetrics> {
+        let removed = self.learner.apply_cutoff(&self.cutoff);
+        if let Some(checkpoint) = self.resume.take() {
+            resume_stages(checkpoint, [&mut self.learner])?;
+        }
+        let mut progress = self.progress.as_deref_mut().map(|observer| {
+            Progress::new(observer, TrainingStage::Perceptron, self.num_epochs, Instant::now())
+        });
+        let run = train_perceptron(
+            &mut self.learner,
+            self.num_epochs,
+            self.patience,
+            running,
+            self.checkpoint.as_ref(),
+            None,
+            progress.as_mut(),
+        )?;
+        self.record_training();
+        Ok(MulticlassMetrics {
+            features_removed: removed,
+            dev_curve: run.curve,
+            ..self.learner.metrics()
+        })
     }
-
-    Ok(learner)
 }
 
-/// Parses training instances from a features file's contents into a fresh
-/// [`AveragedPerceptron`].
-///
-/// The in-memory counterpart of `load_perceptron_instances`, shared by
-/// [`PerceptronTrainer::from_features`] and
-/// [`TwoStageTrainer::from_features`].
-///
-/// # Arguments
-/// * `features` - The contents of a features file.
-///
-/// # Returns
-/// The perceptron, loaded with the instances.
-///
-/// # Errors
-/// Returns [`LitseaError::InvalidData`] if a line is missing its label.
-fn parse_perceptron_instances(features: &str) -> Result<AveragedPerceptron> {
-    let mut learner = AveragedPerceptron::new();
+impl BoundaryPerceptronTrainer {
+    /// Creates a `BoundaryPerceptronTrainer` from a features file written
+    /// by [`Extractor::extract`](crate::extractor::Extractor::extract).
+    ///
+    /// # Arguments
+    /// * `num_epochs` - The number of training epochs.
+    /// * `features_path` - The path to the features file.
+    ///
+    /// # Returns
+    /// Returns a new instance of `BoundaryPerceptronTrainer` with the
+    /// training instances loaded from the features file.
+    ///
+    /// # Errors
+    /// Returns an error if the features file cannot be opened or read, or
+    /// if a line carries a label other than `1`/`-1`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(num_epochs: usize, features_path: &Path) -> Result<Self> {
+        let learner = load_perceptron_instances(features_path, ingest_boundary_line)?;
+        Ok(Self::from_learner(num_epochs, learner))
+    }
 
-    for line in features.lines() {
-        ingest_perceptron_line(&mut learner, line)?;
+    /// Creates a `BoundaryPerceptronTrainer` from a features file's
+    /// contents.
+    ///
+    /// The in-memory counterpart of [`new`](Self::new).
+    ///
+    /// # Arguments
+    /// * `num_epochs` - The number of training epochs.
+    /// * `features` - The contents of a features file.
+    ///
+    /// # Returns
+    /// Returns a new instance of `BoundaryPerceptronTrainer`.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if a line carries a label other
+    /// than `1`/`-1`.
+    pub fn from_features(num_epochs: usize, features: &str) -> Result<Self> {
+        let learner = parse_perceptron_instances(features, ingest_boundary_line)?;
+        Ok(Self::from_learner(num_epochs, learner))
     }
 
-    Ok(learner)
-}
+    /// Creates a `BoundaryPerceptronTrainer` straight from a segmented
+    /// corpus file, with no features file in between (see
+    /// [`Trainer::from_corpus`]).
+    ///
+    /// # Arguments
+    /// * `num_epochs` - The number of training epochs.
+    /// * `extractor` - The extractor defining the language and
+    ///   normalization of the features.
+    /// * `corpus_path` - The path to the segmented corpus file.
+    /// * `format` - The layout of the corpus lines.
+    /// * `tag_free` - Whether to train on the tag-free template subset.
+    ///
+    /// # Returns
+    /// Returns a new instance of `BoundaryPerceptronTrainer`.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the corpus cannot be read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_corpus(
+        num_epochs: usize,
+        extractor: &Extractor,
+        corpus_path: &Path,
+        format: CorpusFormat,
+        tag_free: bool,
+    ) -> Result<Self> {
+        let mut learner = AveragedPerceptron::new();
+        extractor.extract_instances(corpus_path, format, tag_free, |attrs, label| {
+            add_boundary_instance(&mut learner, attrs, label)
+        })?;
+        Ok(Self::from_learner(num_epochs, learner))
+    }
 
-/// Adds one `label\tfeature...` line to a perceptron's instance set.
-///
-/// # Arguments
-/// * `learner` - The perceptron to add the instance to.
-/// * `line` - The line to ingest; blank and feature-less lines are skipped.
-///
-/// # Returns
+    /// Creates a `BoundaryPerceptronTrainer` straight from an in-memory
+    /// segmented corpus.
+    ///
+    /// The in-memory counterpart of [`from_corpus`](Self::from_corpus).
+    ///
+    /// # Arguments
+    /// * `num_epochs` - The number of training epochs.
+    /// * `extractor` - The extractor defining the language and
+    ///   normalization of the features.
+    /// * `corpus` - The corpus contents, one sentence per line.
+    /// * `format` - The layout of the corpus lines.
+    /// * `tag_free` - Whether to train on the tag-free template subset.
+    ///
+    /// # Returns
+    /// Returns a new instance of `BoundaryPerceptronTrainer`.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if a partially annotated line is
+    /// malformed.
+    pub fn from_corpus_str(
+        num_epochs: usize,
+        extractor: &Extractor,
+        corpus: &str,
+        format: CorpusFormat,
+        tag_free: bool,
+    ) -> Result<Self> {
+        let mut learner = AveragedPerceptron::new();
+        extractor.extract_instances_from_str(corpus, format, tag_free, |attrs, label| {
+            add_boundary_instance(&mut learner, attrs, label)
+        })?;
+        Ok(Self::from_learner(num_epochs, learner))
+    }
+
+    /// Creates a `BoundaryPerceptronTrainer` that distills a teacher
+    /// segmenter: the instances are extracted from a raw corpus
+    /// pseudo-labeled by the teacher (see
+    /// [`Extractor::extract_distilled_instances`]), so a fast tag-free
+    /// student can learn from far more text than a gold corpus holds.
+    ///
+    /// The trainer declares the extractor's normalization, which should be
+    /// the teacher's.
+    ///
+    /// With `weight_by_margin`, each instance's update step is its teacher
+    /// margin divided by the given scale, clamped to `[0, 1]`: a decision
+    /// the teacher made by the scale or more counts fully, a narrower one
+    /// proportionally less. Without it every kept instance counts fully.
+    ///
+    /// # Arguments
+    /// * `num_epochs` - The number of training epochs.
+    /// * `extractor` - The extractor defining the language and
+    ///   normalization of the features.
+    /// * `teacher` - The segmenter whose decisions become the labels; a
+    ///   tag-dependent or two-stage model.
+    /// * `corpus_path` - The path to the raw corpus file, one sentence per
+    ///   line.
+    /// * `min_margin` - The smallest absolute teacher margin trained on;
+    ///   `0.0` keeps every position.
+    /// * `weight_by_margin` - The teacher margin at which an instance takes
+    ///   a full update step, or `None` to weight every instance equally.
+    /// * `tag_free` - Whether to train on the tag-free template subset.
+    ///
+    /// # Returns
+    /// Returns a new instance of `BoundaryPerceptronTrainer`.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if `weight_by_margin` is not
+    /// positive and finite, an I/O error if the corpus cannot be read, or
+    /// [`LitseaError::InvalidData`] if no teacher decision reaches
+    /// `min_margin`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// use litsea::adaboost::AdaBoost;
+    /// use litsea::extractor::Extractor;
+    /// use litsea::language::Language;
+    /// use litsea::segmenter::Segmenter;
+    /// use litsea::trainer::BoundaryPerceptronTrainer;
+    ///
+    /// let model_file =
+    ///     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join("japanese.model");
+    /// let mut learner = AdaBoost::new(0.01, 100);
+    /// learner.load_model_from_path(&model_file).unwrap();
+    /// let teacher = Segmenter::with_learner(Language::Japanese, learner);
+    ///
+    /// let extractor =
+    ///     Extractor::new(Language::Japanese).with_normalization(teacher.normalization());
+    /// let mut trainer = BoundaryPerceptronTrainer::from_teacher_str(
+    ///     5,
+    ///     &extractor,
+    ///     &teacher,
+    ///     "これはテストです。\n",
+    ///     0.0,
+    ///     None,
+    ///     true,
+    /// )
+    /// .unwrap();
+    /// let mut student = Vec::new();
+    /// trainer.train_to_writer(&AtomicBool::new(true), &mut student).unwrap();
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_teacher(
+        num_epochs: usize,
+        extractor: &Extractor,
+        teacher: &Segmenter,
+        corpus_path: &Path,
+        min_margin: f64,
+        weight_by_margin: Option<f64>,
+        tag_free: bool,
+    ) -> Result<Self> {
+        Self::from_distilled(num_epochs, extractor, min_margin, weight_by_margin, |sink| {
+            extractor.extract_distilled_instances(teacher, corpus_path, min_margin, tag_free, sink)
+        })
+    }
+
+    /// Creates a `BoundaryPerceptronTrainer` that distills a teacher
+    /// segmenter on an in-memory raw corpus.
+    ///
+    /// The in-memory counterpart of [`from_teacher`](Self::from_teacher).
+    ///
+    /// # Arguments
+    /// * `num_epochs` - The number of training epochs.
+    /// * `extractor` - The extractor defining the language and
+    ///   normalization of the features.
+    /// * `teacher` - The segmenter whose decisions become the labels.
+    /// * `corpus` - The raw corpus contents, one sentence per line.
+    /// * `min_margin` - The smallest absolute teacher margin trained on;
+    ///   `0.0` keeps every position.
+    /// * `weight_by_margin` - The teacher margin at which an instance takes
+    ///   a full update step, or `None` to weight every instance equally.
+    /// * `tag_free` - Whether to train on the tag-free template subset.
+    ///
+    /// # Returns
+    /// Returns a new instance of `BoundaryPerceptronTrainer`.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if `weight_by_margin` is not
+    /// positive and finite, or [`LitseaError::InvalidData`] if no teacher
+    /// decision reaches `min_margin`.
+    pub fn from_teacher_str(
+        num_epochs: usize,
+        extractor: &Extractor,
+        teacher: &Segmenter,
+        corpus: &str,
+        min_margin: f64,
+        weight_by_margin: Option<f64>,
+        tag_free: bool,
+    ) -> Result<Self> {
+        Self::from_distilled(num_epochs, extractor, min_margin, weight_by_margin, |sink| {
+            extractor
+                .extract_distilled_instances_from_str(teacher, corpus, min_margin, tag_free, sink)
+        })
+    }
+
+    /// Builds a trainer from the instances `extract` feeds to its sink (one
+    /// of the distilled extractors), shared by
+    /// [`from_teacher`](Self::from_teacher) and
+    /// [`from_teacher_str`](Self::from_teacher_str).
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] for a bad `weight_by_margin`,
+    /// the error of `extract`, or [`LitseaError::InvalidData`] if it
+    /// yields no instance.
+    fn from_distilled(
+        num_epochs: usize,
+        extractor: &Extractor,
+        min_margin: f64,
+        weight_by_margin: Option<f64>,
+        extract: impl FnOnce(&mut dyn FnMut(HashSet<String>, i8, f64)) -> Result<()>,
+    ) -> Result<Self> {
+        if let Some(scale) = weight_by_margin {
+            if !(scale.is_finite() && scale > 0.0) {
+                return Err(LitseaError::InvalidInput(format!(
+                    "the margin weighting scale must be positive and finite, got {scale}"
+                )));
+            }
+        }
+        let mut learner = AveragedPerceptron::new();
+        let mut count = 0;
+        extract(&mut |attrs, label, margin| {
+            count += 1;
+            let weight = weight_by_margin.map_or(1.0, |scale| (margin / scale).clamp(0.0, 1.0));
+            add_weighted_boundary_instance(&mut learner, attrs, label, weight);
+        })?;
+        if count == 0 {
+            return Err(LitseaError::InvalidData(format!(
+                "no teacher decision reaches the minimum margin {min_margin}"
+            )));
+        }
+        Ok(Self::from_learner(num_epochs, learner).with_normalization(extractor.normalization()))
+    }
+
+    /// Wraps a loaded learner with the default declarations.
+    fn from_learner(num_epochs: usize, learner: AveragedPerceptron) -> Self {
+        BoundaryPerceptronTrainer {
+            learner,
+            num_epochs,
+            cutoff: FeatureCutoff::default(),
+            patience: None,
+            checkpoint: None,
+            resume: None,
+            progress: None,
+            normalization: Normalization::None,
+            dictionary: UserDictionary::new(),
+            metadata: ModelMetadata::default(),
+        }
+    }
+
+    /// Declares the Unicode normalization the training features were
+    /// extracted with (see [`Trainer::with_normalization`]).
+    ///
+    /// # Arguments
+    /// * `normalization` - The normalization form of the training text.
+    ///
+    /// # Returns
+    /// The trainer with the declaration set.
+    #[must_use]
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Declares the word list the training features' dictionary features
+    /// were extracted with (see [`Trainer::with_dictionary`]).
+    ///
+    /// # Arguments
+    /// * `dictionary` - The word list of the training features.
+    ///
+    /// # Returns
+    /// The trainer with the word list set.
+    #[must_use]
+    pub fn with_dictionary(mut self, dictionary: UserDictionary) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
+
+    /// Sets the feature frequency cutoff (see [`Trainer::with_cutoff`]);
+    /// the number of features dropped is reported as
+    /// [`MulticlassMetrics::features_removed`].
+    ///
+    /// # Arguments
+    /// * `cutoff` - The minimum feature counts.
+    ///
+    /// # Returns
+    /// The trainer with the cutoff set.
+    #[must_use]
+    pub fn with_cutoff(mut self, cutoff: FeatureCutoff) -> Self {
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Reports the progress of training to `observer` after every epoch
+    /// (see [`TrainingProgress`](crate::progress::TrainingProgress)), or
+    /// stops reporting with `None`.
+    ///
+    /// # Arguments
+    /// * `observer` - The observer receiving the reports.
+    pub fn set_progress(&mut self, observer: Option<Box<dyn ProgressObserver>>) {
+        self.progress = observer;
+    }
+
+    /// Loads a held-out development features file: ordinary `extract`
+    /// output, like the training one (see [`PerceptronTrainer::load_dev`]).
+    ///
+    /// # Arguments
+    /// * `dev_path` - The path to the development features file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or a line carries a
+    /// non-boundary label.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_dev(&mut self, dev_path: &Path) -> Result<()> {
+        self.learner.set_dev(load_perceptron_instances(dev_path, ingest_boundary_line)?);
+        Ok(())
+    }
+
+    /// Loads held-out development features from a features file's
+    /// contents.
+    ///
+    /// The in-memory counterpart of [`load_dev`](Self::load_dev).
+    ///
+    /// # Arguments
+    /// * `dev` - The contents of the development features file.
+    ///
+    /// # Errors
+    /// Returns an error if a line carries a non-boundary label.
+    pub fn load_dev_from_features(&mut self, dev: &str) -> Result<()> {
+        self.learner.set_dev(parse_perceptron_instances(dev, ingest_boundary_line)?);
+        Ok(())
+    }
+
+    /// Loads a held-out gold corpus as the development set, extracting
+    /// its instances the way [`from_corpus`](Self::from_corpus) extracts
+    /// the training ones.
+    ///
+    /// # Arguments
+    /// * `extractor` - The extractor the training instances came from.
+    /// * `corpus_path` - The path to the segmented development corpus.
+    /// * `format` - The layout of the corpus lines.
+    /// * `tag_free` - Whether the training uses the tag-free templates.
+    ///
+    /// # Errors
+    /// Returns an I/O error if the corpus cannot be read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_dev_corpus(
+        &mut self,
+        extractor: &Extractor,
+        corpus_path: &Path,
+        format: CorpusFormat,
+        tag_free: bool,
+    ) -> Result<()> {
+        let mut dev = AveragedPerceptron::new();
+        extractor.extract_instances(corpus_path, format, tag_free, |attrs, label| {
+            add_boundary_instance(&mut dev, attrs, label)
+        })?;
+        self.learner.set_dev(dev);
+        Ok(())
+    }
+
+    /// Loads an in-memory gold corpus as the development set.
+    ///
+    /// The in-memory counterpart of [`load_dev_corpus`](Self::load_dev_corpus).
+    ///
+    /// # Arguments
+    /// * `extractor` - The extractor the training instances came from.
+    /// * `corpus` - The development corpus, one sentence per line.
+    /// * `format` - The layout of the corpus lines.
+    /// * `tag_free` - Whether the training uses the tag-free templates.
+    ///
+    /// # Returns
+    /// Returns a Result indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if a partially annotated line is
+    /// malformed.
+    pub fn load_dev_corpus_str(
+        &mut self,
+        extractor: &Extractor,
+        corpus: &str,
+        format: CorpusFormat,
+        tag_free: bool,
+    ) -> Result<()> {
+        let mut dev = AveragedPerceptron::new();
+        extractor.extract_instances_from_str(corpus, format, tag_free, |attrs, label| {
+            add_boundary_instance(&mut dev, attrs, label)
+        })?;
+        self.learner.set_dev(dev);
+        Ok(())
+    }
+
+    /// Sets the early-stopping patience: once a development set is loaded,
+    /// training stops after this many epochs in a row without a better
+    /// development accuracy. Without it, every epoch runs (the best
+    /// epoch's weights are still kept).
+    ///
+    /// # Arguments
+    /// * `patience` - The number of epochs without improvement to allow.
+    ///
+    /// # Returns
+    /// The trainer with the patience set.
+    #[must_use]
+    pub fn with_patience(mut self, patience: usize) -> Self {
+        self.patience = Some(patience);
+        self
+    }
+
+    /// Shuffles the training instances every epoch, in an order drawn
+    /// from `seed` (see [`AveragedPerceptron::set_seed`]). The same seed
+    /// and inputs produce a byte-identical model; the seed is recorded as
+    /// the model's `seed` parameter.
+    ///
+    /// # Arguments
+    /// * `seed` - The shuffle seed.
+    ///
+    /// # Returns
+    /// The trainer with the seed set.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.learner.set_seed(Some(seed));
+        self
+    }
+
+    /// Trains every epoch on `threads` threads by iterative parameter
+    /// mixing (see [`AveragedPerceptron::set_threads`]). The model is
+    /// deterministic for a given thread count and seed but differs from
+    /// the single-threaded one, so a count above 1 is recorded as the
+    /// model's `threads` parameter.
+    ///
+    /// # Arguments
+    /// * `threads` - The number of threads; 0 and 1 train single-threaded.
+    ///
+    /// # Returns
+    /// The trainer with the thread count set.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.learner.set_threads(threads);
+        self
+    }
+
+    /// Writes a [`Checkpoint`] of the training run to `path` after every
+    /// `every` completed epochs and when the `running` flag interrupts
+    /// training, so an interrupted run can be continued with
+    /// [`resume`](Self::resume).
+    ///
+    /// Each checkpoint replaces the previous one. It holds the perceptron's
+    /// weights and averaging state, so it is several times the size of the
+    /// model.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the checkpoint file.
+    /// * `every` - The number of epochs between two checkpoints; `0` only
+    ///   writes one when training is interrupted.
+    ///
+    /// # Returns
+    /// The trainer with checkpointing enabled.
+    #[must_use]
+    pub fn with_checkpoint(mut self, path: &Path, every: usize) -> Self {
+        self.checkpoint = Some(CheckpointTarget {
+            path: path.to_path_buf(),
+            every,
+        });
+        self
+    }
+
+    /// Continues an interrupted training run from its checkpoint.
+    ///
+    /// Training then picks up exactly where the checkpointed run stopped,
+    /// and produces the model that run would have. The trainer must be set
+    /// up as that run was: the same features, development set, cutoff, and
+    /// epoch budget (the seed and weights come from the checkpoint).
+    ///
+    /// # Arguments
+    /// * `checkpoint` - A checkpoint written by
+    ///   [`with_checkpoint`](Self::with_checkpoint).
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the checkpoint holds more
+    /// than one stage. A checkpoint of other training data is rejected
+    /// when training starts.
+    pub fn resume(&mut self, checkpoint: Checkpoint) -> Result<()> {
+        self.resume = Some(single_stage(checkpoint)?);
+        Ok(())
+    }
+
+    /// Declares provenance to record in the saved model (see
+    /// [`Trainer::with_metadata`]). Training adds its own `epochs`
+    /// parameter and, unless declared, the detected template set.
+    ///
+    /// # Arguments
+    /// * `metadata` - The provenance to record.
+    ///
+    /// # Returns
+    /// The trainer with the metadata set.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: ModelMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the provenance the saved model will record (see
+    /// [`Trainer::metadata`]).
+    #[must_use]
+    pub fn metadata(&self) -> &ModelMetadata {
+        &self.metadata
+    }
+
+    /// Trains the perceptron and saves it collapsed to the AdaBoost format.
+    ///
+    /// # Arguments
+    /// * `running` - A flag for interrupting the training.
+    /// * `model_path` - The path to save the model to.
+    ///
+    /// # Returns
+    /// Returns the [`MulticlassMetrics`] of the perceptron measured on the
+    /// training data, over its 2 boundary classes. The collapsed model
+    /// makes the same decisions.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the training data lacks
+    /// either boundary class, or an error if the model cannot be saved.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn train(&mut self, running: &AtomicBool, model_path: &Path) -> Result<MulticlassMetrics> {
+        let (model, metrics) = self.train_into_model(running)?;
+        model.save_model(model_path)?;
+        Ok(metrics)
+    }
+
+    /// Trains the perceptron and writes it, collapsed, to a writer.
+    ///
+    /// The in-memory counterpart of [`train`](Self::train).
+    ///
+    /// # Arguments
+    /// * `running` - A flag for interrupting the training.
+    /// * `writer` - Where to write the collapsed model.
+    ///
+    /// # Returns
+    /// Returns the [`MulticlassMetrics`] of the perceptron measured on the
+    /// training data.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the training data lacks
+    /// either boundary class, or an error if the model cannot be written.
+    pub fn train_to_writer<W: Write>(
+        &mut self,
+        running: &AtomicBool,
+        writer: &mut W,
+    ) -> Result<MulticlassMetrics> {
+        let (model, metrics) = self.train_into_model(running)?;
+        model.save_model_to_writer(writer)?;
+        Ok(metrics)
+    }
+
+    /// Trains the perceptron and collapses it, without saving.
+    ///
+    /// Shared by [`train`](Self::train) and
+    /// [`train_to_writer`](Self::train_to_writer).
+    ///
+    /// # Arguments
+    /// * `running` - A flag for interrupting the training.
+    ///
+    /// # Returns
+    /// The collapsed model, with its declarations set, and the
+    /// perceptron's in-sample metrics.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidData`] if the perceptron has no `B` or
+    /// no `O` class.
+    fn train_into_model(&mut self, running: &AtomicBool) -> Result<(AdaBoost, MulticlassMetrics)> {
+        let removed = self.learner.apply_cutoff(&self.cutoff);
+        if let Some(checkpoint) = self.resume.take() {
+            resume_stages(checkpoint, [&mut self.learner])?;
+        }
+        let mut progress = self.progress.as_deref_mut().map(|observer| {
+            Progress::new(observer, TrainingStage::Boundary, self.num_epochs, Instant::now())
+        });
+        let run = train_perceptron(
+            &mut self.learner,
+            self.num_epochs,
+            self.patience,
+            running,
+            self.checkpoint.as_ref(),
+            None,
+            progress.as_mut(),
+        )?;
+
+        let mut model = collapse_boundary_perceptron(&self.learner)?;
+        let mut metadata = self.metadata.clone().with_param("epochs", self.num_epochs);
+        if !self.cutoff.is_noop() {
+            metadata = metadata.with_param("min_count", &self.cutoff);
+        }
+        if let Some(seed) = self.learner.seed() {
+            metadata = metadata.with_param("seed", seed);
+        }
+        if self.learner.threads() > 1 {
+            metadata = metadata.with_param("threads", self.learner.threads());
+        }
+        if metadata.templates().is_none() {
+            let templates = if model.is_tag_free() { TEMPLATES_TAG_FREE } else { TEMPLATES_FULL };
+            metadata = metadata.with_templates(templates);
+        }
+        model.set_normalization(self.normalization);
+        model.set_dictionary(self.dictionary.clone());
+        model.set_metadata(metadata);
+
+        let metrics = MulticlassMetrics {
+            features_removed: removed,
+            dev_curve: run.curve,
+            ..self.learner.metrics()
+        };
+        Ok((model, metrics))
+    }
+}
+
+/// Where a perceptron trainer writes its checkpoints, and how often.
+#[derive(Debug, Clone)]
+struct CheckpointTarget {
+    /// The checkpoint file, replaced on every write.
+    path: PathBuf,
+    /// Completed epochs between two checkpoints; `0` writes one only when
+    /// training is interrupted.
+    every: usize,
+}
+
+/// Trains one perceptron, writing its checkpoints to `target`.
+///
+/// # Arguments
+/// * `learner` - The perceptron to train.
+/// * `num_epochs` - The epoch budget.
+/// * `patience` - The development-set patience.
+/// * `running` - A flag for interrupting the training.
+/// * `target` - Where to write checkpoints, if anywhere.
+/// * `finished` - The state of an earlier, finished stage, written ahead
+///   of this one's in every checkpoint.
+/// * `progress` - Receives a report after every epoch, if any.
+///
+/// # Returns
+/// How the run ended.
+///
+/// # Errors
+/// Returns an I/O error if a checkpoint cannot be written.
+fn train_perceptron(
+    learner: &mut AveragedPerceptron,
+    num_epochs: usize,
+    patience: Option<usize>,
+    running: &AtomicBool,
+    target: Option<&CheckpointTarget>,
+    finished: Option<&LearnerState>,
+    progress: Option<&mut Progress<'_>>,
+) -> Result<TrainingRun> {
+    let Some(target) = target else {
+        return learner.train_checkpointed(num_epochs, patience, running, None, progress);
+    };
+    let mut save = |state: LearnerState| {
+        let stages = finished.cloned().into_iter().chain([state]).collect();
+        Checkpoint::new(stages).save(&target.path)
+    };
+    learner.train_checkpointed(
+        num_epochs,
+        patience,
+        running,
+        Some((target.every, &mut save)),
+        progress,
+    )
+}
+
+/// Checks that a checkpoint holds a single stage, the only kind a
+/// one-model trainer resumes.
+///
+/// # Errors
+/// Returns [`LitseaError::InvalidInput`] for a checkpoint of more stages.
+fn single_stage(checkpoint: Checkpoint) -> Result<Checkpoint> {
+    if checkpoint.num_stages() != 1 {
+        return Err(LitseaError::InvalidInput(format!(
+            "the checkpoint holds {} stages; resume it with the two-stage trainer",
+            checkpoint.num_stages()
+        )));
+    }
+    Ok(checkpoint)
+}
+
+/// Restores each checkpointed stage state into its learner, in order.
+///
+/// # Errors
+/// Returns an error if a state does not match its learner's training data.
+fn resume_stages<const N: usize>(
+    checkpoint: Checkpoint,
+    learners: [&mut AveragedPerceptron; N],
+) -> Result<()> {
+    for (learner, state) in learners.into_iter().zip(checkpoint.into_stages()) {
+        learner.resume(state)?;
+    }
+    Ok(())
+}
+
+/// A function adding one features-file line to a perceptron's instance set:
+/// [`ingest_perceptron_line`] keeps labels as they are,
+/// [`ingest_boundary_line`] maps the `extract` labels to `B`/`O`.
+type IngestLine = fn(&mut AveragedPerceptron, &str) -> Result<()>;
+
+/// Loads training instances from a features file (`label\tfeature\t...`
+/// rows) into a fresh [`AveragedPerceptron`]. Shared by
+/// [`PerceptronTrainer::new`], [`BoundaryPerceptronTrainer::new`], and
+/// [`TwoStageTrainer::new`], which read the same row format for different
+/// label spaces (boundary `B`/`O` vs. UPOS tags — the perceptron treats
+/// labels as opaque strings either way).
+#[cfg(not(target_arch = "wasm32"))]
+fn load_perceptron_instances(
+    features_path: &Path,
+    ingest: IngestLine,
+) -> Result<AveragedPerceptron> {
+    let mut learner = AveragedPerceptron::new();
+
+    let file = File::open(features_path)?;
+    let reader = io::BufReader::new(file);
+
+    // Streamed rather than slurped: a features file for a real corpus can be
+    // hundreds of megabytes.
+    for line in reader.lines() {
+        ingest(&mut learner, &line?)?;
+    }
+
+    Ok(learner)
+}
+
+/// Parses training instances from a features file's contents into a fresh
+/// [`AveragedPerceptron`].
+///
+/// The in-memory counterpart of `load_perceptron_instances`, shared by
+/// [`PerceptronTrainer::from_features`],
+/// [`BoundaryPerceptronTrainer::from_features`], and
+/// [`TwoStageTrainer::from_features`].
+///
+/// # Arguments
+/// * `features` - The contents of a features file.
+/// * `ingest` - How each line is added to the instance set.
+///
+/// # Returns
+/// The perceptron, loaded with the instances.
+///
+/// # Errors
+/// Returns [`LitseaError::InvalidData`] if a line is missing its label (or,
+/// with [`ingest_boundary_line`], carries a non-boundary label).
+fn parse_perceptron_instances(features: &str, ingest: IngestLine) -> Result<AveragedPerceptron> {
+    let mut learner = AveragedPerceptron::new();
+
+    for line in features.lines() {
+        ingest(&mut learner, line)?;
+    }
+
+    Ok(learner)
+}
+
+/// Adds one `label\tfeature...` line to a perceptron's instance set.
+///
+/// # Arguments
+/// * `learner` - The perceptron to add the instance to.
+/// * `line` - The line to ingest; blank and feature-less lines are skipped.
+///
+/// # Returns
 /// A result indicating success or failure.
 ///
 /// # Errors
@@ -416,22 +1694,95 @@ fn ingest_perceptron_line(learner: &mut AveragedPerceptron, line: &str) -> Resul
     Ok(())
 }
 
+/// Adds one line of ordinary `extract` output (`1`/`-1` labels) to a
+/// boundary perceptron's instance set, as a `B`/`O` instance.
+///
+/// Lines already labelled `B`/`O` (the old `sed`-relabelled recipe input)
+/// are accepted as they are.
+///
+/// # Arguments
+/// * `learner` - The perceptron to add the instance to.
+/// * `line` - The line to ingest; blank and feature-less lines are skipped.
+///
+/// # Returns
+/// A result indicating success or failure.
+///
+/// # Errors
+/// Returns [`LitseaError::InvalidData`] if the label is not a boundary
+/// label.
+fn ingest_boundary_line(learner: &mut AveragedPerceptron, line: &str) -> Result<()> {
+    if line.is_empty() {
+        return Ok(());
+    }
+    let (label, features) = line.split_once('\t').unwrap_or((line, ""));
+    let label = match label {
+        "1" | "B" => "B",
+        "-1" | "O" => "O",
+        other => {
+            return Err(LitseaError::InvalidData(format!(
+                "non-boundary label in feature line; expected '1'/'-1', found {:?}",
+                other
+            )));
+        }
+    };
+    let features: HashSet<String> =
+        features.split('\t').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect();
+    if features.is_empty() {
+        return Ok(());
+    }
+    learner.add_instance(features, label.to_string());
+
+    Ok(())
+}
+
+/// Adds one extracted boundary instance to a boundary perceptron's
+/// instance set, skipping it if it has no features (as
+/// [`ingest_boundary_line`] skips a feature-less line).
+///
+/// # Arguments
+/// * `learner` - The perceptron to add the instance to.
+/// * `attributes` - The instance's features.
+/// * `label` - The extracted label (1 = word start, -1 = continuation).
+fn add_boundary_instance(learner: &mut AveragedPerceptron, attributes: HashSet<String>, label: i8) {
+    add_weighted_boundary_instance(learner, attributes, label, 1.0);
+}
+
+/// [`add_boundary_instance`] with the update step scaled by `weight` (see
+/// [`AveragedPerceptron::add_weighted_instance`]).
+///
+/// # Arguments
+/// * `learner` - The perceptron to add the instance to.
+/// * `attributes` - The instance's features.
+/// * `label` - The extracted label (1 = word start, -1 = continuation).
+/// * `weight` - The size of the instance's update step.
+fn add_weighted_boundary_instance(
+    learner: &mut AveragedPerceptron,
+    attributes: HashSet<String>,
+    label: i8,
+    weight: f64,
+) {
+    if attributes.is_empty() {
+        return;
+    }
+    let label = if label > 0 { "B" } else { "O" };
+    learner.add_weighted_instance(attributes, label.to_string(), weight);
+}
+
 /// Collapses a 2-class boundary Averaged Perceptron (classes `B`/`O`,
-/// produced by [`TwoStageTrainer`]) into scalar per-feature weights in the
-/// existing AdaBoost model format.
+/// produced by [`BoundaryPerceptronTrainer`] or [`TwoStageTrainer`]) into
+/// scalar per-feature weights in the existing AdaBoost model format.
 ///
 /// The perceptron scores a position purely as `sum(matched-feature
 /// weights)` per class (there is no perceptron-level bias term), so
-/// `score_B - score_O = sum(matched (w_B[f] - w_O[f]))` exactly. Writing
-/// `feat\t(w_B[f] - w_O[f])` lines plus a literal `0` bias line and parsing
-/// them with [`AdaBoost::load_model_from_reader`] reproduces this: the
-/// model format defines `bias()` to equal the written bias line verbatim
-/// regardless of the feature weights (the algebraic inverse of
-/// `save_model`'s bias computation), so the collapsed model's decision
-/// `score >= 0.0` becomes exactly `score_B >= score_O` — including the tie
-/// case, which both the perceptron's first-wins rule (`"B" < "O"`
-/// alphabetically, so `B` is class index 0) and this comparison resolve to
-/// `B`.
+/// `score_B - score_O = sum(matched (w_B[f] - w_O[f]))` exactly. Building
+/// the model from the `w_B[f] - w_O[f]` weights with a bias of exactly `0`
+/// ([`AdaBoost::from_weights`], which takes the bias as given instead of
+/// re-deriving it from a float sum of the weights) reproduces this: the
+/// collapsed model's decision `score >= 0.0` becomes exactly
+/// `score_B >= score_O` — including the tie case, which both the
+/// perceptron's first-wins rule (`"B" < "O"` alphabetically, so `B` is
+/// class index 0) and this comparison resolve to `B`. The result does not
+/// depend on the iteration order of the perceptron's weights.
 fn collapse_boundary_perceptron(stage1: &AveragedPerceptron) -> Result<AdaBoost> {
     let classes = stage1.class_names();
     let b = classes.iter().position(|c| c == "B").ok_or_else(|| {
@@ -441,21 +1792,10 @@ fn collapse_boundary_perceptron(stage1: &AveragedPerceptron) -> Result<AdaBoost>
         LitseaError::InvalidData("stage-1 boundary model has no 'O' class".to_string())
     })?;
 
-    let mut text = String::new();
-    for (feat, weights) in stage1.feature_class_weights() {
-        let w = weights[b] - weights[o];
-        if w != 0.0 {
-            text.push_str(feat);
-            text.push('\t');
-            text.push_str(&w.to_string());
-            text.push('\n');
-        }
-    }
-    text.push_str("0\n");
-
-    let mut adaboost = AdaBoost::default();
-    adaboost.load_model_from_reader(text.as_bytes())?;
-    Ok(adaboost)
+    let weights = stage1
+        .feature_class_weights()
+        .map(|(feat, weights)| (feat.to_string(), weights[b] - weights[o]));
+    Ok(AdaBoost::from_weights(weights, 0.0))
 }
 
 /// In-sample training metrics of a [`TwoStageTrainer::train`] run: one
@@ -485,8 +1825,26 @@ pub struct TwoStageTrainer {
     lexicon: FxHashMap<String, Vec<(Upos, u32)>>,
     /// The number of training epochs for both stages.
     num_epochs: usize,
+    /// The feature frequency cutoff applied to both stages before training.
+    cutoff: FeatureCutoff,
+    /// Epochs without development-set improvement after which a stage
+    /// stops training.
+    patience: Option<usize>,
+    /// Where and how often training writes checkpoints.
+    checkpoint: Option<CheckpointTarget>,
+    /// The checkpoint training continues from.
+    resume: Option<Checkpoint>,
+    /// Receives a report after every epoch.
+    progress: Option<Box<dyn ProgressObserver>>,
     /// The classifier-skip dominance threshold of the assembled model.
     dominance: f64,
+    /// The normalization the assembled model declares for its stage 1.
+    normalization: Normalization,
+    /// The dictionary-feature word list the assembled model's stage 1
+    /// stores.
+    dictionary: UserDictionary,
+    /// Provenance recorded in the assembled model's `[params]` section.
+    metadata: ModelMetadata,
 }
 
 impl TwoStageTrainer {
@@ -513,8 +1871,8 @@ impl TwoStageTrainer {
     pub fn new(num_epochs: usize, dominance: f64, features_prefix: &Path) -> Result<Self> {
         let (stage1_path, stage2_path, lexicon_path) = two_stage_paths(features_prefix);
 
-        let stage1 = load_perceptron_instances(&stage1_path)?;
-        let stage2 = load_perceptron_instances(&stage2_path)?;
+        let stage1 = load_perceptron_instances(&stage1_path, ingest_perceptron_line)?;
+        let stage2 = load_perceptron_instances(&stage2_path, ingest_perceptron_line)?;
 
         let lexicon_file = File::open(&lexicon_path)?;
         let lines: io::Result<Vec<String>> = io::BufReader::new(lexicon_file).lines().collect();
@@ -551,8 +1909,8 @@ impl TwoStageTrainer {
         stage2: &str,
         lexicon: &str,
     ) -> Result<Self> {
-        let stage1 = parse_perceptron_instances(stage1)?;
-        let stage2 = parse_perceptron_instances(stage2)?;
+        let stage1 = parse_perceptron_instances(stage1, ingest_perceptron_line)?;
+        let stage2 = parse_perceptron_instances(stage2, ingest_perceptron_line)?;
         let lexicon_lines: Vec<String> = lexicon.lines().map(|l| l.to_string()).collect();
         let lexicon = parse_lexicon(&lexicon_lines)?;
 
@@ -605,10 +1963,222 @@ impl TwoStageTrainer {
             stage2,
             lexicon,
             num_epochs,
+            cutoff: FeatureCutoff::default(),
+            patience: None,
+            checkpoint: None,
+            resume: None,
+            progress: None,
             dominance,
+            normalization: Normalization::None,
+            dictionary: UserDictionary::new(),
+            metadata: ModelMetadata::default(),
         })
     }
 
+    /// Declares the Unicode normalization the training features were
+    /// extracted with, recorded in the assembled model's stage-1 section
+    /// (see [`Trainer::with_normalization`]).
+    ///
+    /// # Arguments
+    /// * `normalization` - The normalization form of the training text.
+    ///
+    /// # Returns
+    /// The trainer with the declaration set.
+    #[must_use]
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Declares the word list the stage-1 features' dictionary features
+    /// were extracted with, stored in the assembled model's stage-1 section
+    /// (see [`Trainer::with_dictionary`]).
+    ///
+    /// # Arguments
+    /// * `dictionary` - The word list of the stage-1 features.
+    ///
+    /// # Returns
+    /// The trainer with the word list set.
+    #[must_use]
+    pub fn with_dictionary(mut self, dictionary: UserDictionary) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
+
+    /// Sets the feature frequency cutoff (see [`Trainer::with_cutoff`]),
+    /// applied to each stage's features separately; the numbers dropped
+    /// are reported in each stage's
+    /// [`MulticlassMetrics::features_removed`].
+    ///
+    /// # Arguments
+    /// * `cutoff` - The minimum feature counts.
+    ///
+    /// # Returns
+    /// The trainer with the cutoff set.
+    #[must_use]
+    pub fn with_cutoff(mut self, cutoff: FeatureCutoff) -> Self {
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Reports the progress of training to `observer` after every epoch of
+    /// either stage (see
+    /// [`TrainingProgress`](crate::progress::TrainingProgress); stage 1
+    /// reports as [`TrainingStage::Boundary`], stage 2 as
+    /// [`TrainingStage::Tagging`]), or stops reporting with `None`.
+    ///
+    /// # Arguments
+    /// * `observer` - The observer receiving the reports.
+    pub fn set_progress(&mut self, observer: Option<Box<dyn ProgressObserver>>) {
+        self.progress = observer;
+    }
+
+    /// Loads held-out development features for both stages, from the
+    /// `{prefix}.stage1` and `{prefix}.stage2` files
+    /// [`Extractor::extract_two_stage`](crate::extractor::Extractor::extract_two_stage)
+    /// writes for a development corpus (its lexicon is not used). Each
+    /// stage keeps the weights of its best epoch and reports its curve in
+    /// its [`MulticlassMetrics::dev_curve`].
+    ///
+    /// # Arguments
+    /// * `dev_prefix` - The prefix the development features were written
+    ///   to.
+    ///
+    /// # Errors
+    /// Returns an error if either file cannot be read or a line is missing
+    /// its label.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_dev(&mut self, dev_prefix: &Path) -> Result<()> {
+        let (stage1_path, stage2_path, _) = two_stage_paths(dev_prefix);
+        self.stage1
+            .set_dev(load_perceptron_instances(&stage1_path, ingest_perceptron_line)?);
+        self.stage2
+            .set_dev(load_perceptron_instances(&stage2_path, ingest_perceptron_line)?);
+        Ok(())
+    }
+
+    /// Loads held-out development features for both stages from their
+    /// contents.
+    ///
+    /// The in-memory counterpart of [`load_dev`](Self::load_dev).
+    ///
+    /// # Arguments
+    /// * `stage1` - The contents of the stage-1 development features.
+    /// * `stage2` - The contents of the stage-2 development features.
+    ///
+    /// # Errors
+    /// Returns an error if a line is missing its label.
+    pub fn load_dev_from_features(&mut self, stage1: &str, stage2: &str) -> Result<()> {
+        self.stage1.set_dev(parse_perceptron_instances(stage1, ingest_perceptron_line)?);
+        self.stage2.set_dev(parse_perceptron_instances(stage2, ingest_perceptron_line)?);
+        Ok(())
+    }
+
+    /// Sets the early-stopping patience of both stages (see
+    /// [`PerceptronTrainer::with_patience`]).
+    ///
+    /// # Arguments
+    /// * `patience` - The number of epochs without improvement to allow.
+    ///
+    /// # Returns
+    /// The trainer with the patience set.
+    #[must_use]
+    pub fn with_patience(mut self, patience: usize) -> Self {
+        self.patience = Some(patience);
+        self
+    }
+
+    /// Shuffles each stage's training instances every epoch, in an order
+    /// drawn from `seed` (see [`PerceptronTrainer::with_seed`]).
+    ///
+    /// # Arguments
+    /// * `seed` - The shuffle seed.
+    ///
+    /// # Returns
+    /// The trainer with the seed set.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.stage1.set_seed(Some(seed));
+        self.stage2.set_seed(Some(seed));
+        self
+    }
+
+    /// Trains every epoch of both stages on `threads` threads by iterative
+    /// parameter mixing (see [`PerceptronTrainer::with_threads`]).
+    ///
+    /// # Arguments
+    /// * `threads` - The number of threads; 0 and 1 train single-threaded.
+    ///
+    /// # Returns
+    /// The trainer with the thread count set.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.stage1.set_threads(threads);
+        self.stage2.set_threads(threads);
+        self
+    }
+
+    /// Writes a [`Checkpoint`] of the training run to `path` after every
+    /// `every` completed epochs of either stage and when the `running` flag
+    /// interrupts training (see [`PerceptronTrainer::with_checkpoint`]).
+    /// Once stage 1 has finished, its checkpoints carry its final weights
+    /// along with the state of stage 2.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the checkpoint file.
+    /// * `every` - The number of epochs between two checkpoints; `0` only
+    ///   writes one when training is interrupted.
+    ///
+    /// # Returns
+    /// The trainer with checkpointing enabled.
+    #[must_use]
+    pub fn with_checkpoint(mut self, path: &Path, every: usize) -> Self {
+        self.checkpoint = Some(CheckpointTarget {
+            path: path.to_path_buf(),
+            every,
+        });
+        self
+    }
+
+    /// Continues an interrupted training run from its checkpoint (see
+    /// [`PerceptronTrainer::resume`]): a finished stage 1 is taken as it
+    /// is, and the stage in progress continues where it stopped.
+    ///
+    /// # Arguments
+    /// * `checkpoint` - A checkpoint written by
+    ///   [`with_checkpoint`](Self::with_checkpoint).
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the checkpoint holds more
+    /// than two stages. A checkpoint of other training data is rejected
+    /// when training starts.
+    pub fn resume(&mut self, checkpoint: Checkpoint) -> Result<()> {
+        if checkpoint.num_stages() > 2 {
+            return Err(LitseaError::InvalidInput(format!(
+                "a two-stage run has 2 stages, but the checkpoint holds {}",
+                checkpoint.num_stages()
+            )));
+        }
+        self.resume = Some(checkpoint);
+        Ok(())
+    }
+
+    /// Declares provenance to record in the assembled model (see
+    /// [`Trainer::with_metadata`]). Training adds its own `epochs`
+    /// parameter and, unless declared here, the
+    /// [`TwoStageFeatureSet`] detected from the stage-2 features.
+    ///
+    /// # Arguments
+    /// * `metadata` - The provenance to record.
+    ///
+    /// # Returns
+    /// The trainer with the metadata set.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: ModelMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     /// Trains both stages and assembles + saves a `litsea-two-stage v1`
     /// model.
     ///
@@ -684,18 +2254,90 @@ impl TwoStageTrainer {
         mut self,
         running: &AtomicBool,
     ) -> Result<(TwoStageLearner, TwoStageMetrics)> {
-        self.stage1.train(self.num_epochs, running);
-        self.stage2.train(self.num_epochs, running);
-        let stage1_metrics = self.stage1.metrics();
-        let stage2_metrics = self.stage2.metrics();
+        let stage1_removed = self.stage1.apply_cutoff(&self.cutoff);
+        let stage2_removed = self.stage2.apply_cutoff(&self.cutoff);
+        if let Some(checkpoint) = self.resume.take() {
+            resume_stages(checkpoint, [&mut self.stage1, &mut self.stage2])?;
+        }
+        // Both stages report the time since stage 1 started.
+        let start = self.progress.as_ref().map(|_| Instant::now());
+        let mut progress = self.progress.as_deref_mut().zip(start).map(|(observer, start)| {
+            Progress::new(observer, TrainingStage::Boundary, self.num_epochs, start)
+        });
+        let stage1_run = train_perceptron(
+            &mut self.stage1,
+            self.num_epochs,
+            self.patience,
+            running,
+            self.checkpoint.as_ref(),
+            None,
+            progress.as_mut(),
+        )?;
+        let mut progress = self.progress.as_deref_mut().zip(start).map(|(observer, start)| {
+            Progress::new(observer, TrainingStage::Tagging, self.num_epochs, start)
+        });
+        // Stage-2 checkpoints carry the finished stage 1. An interrupted
+        // stage 1 has already written its own, which stage 2 (cut short by
+        // the same flag) must not overwrite.
+        let stage2_run = if stage1_run.completed {
+            let stage1_state = self.stage1.finished_state(stage1_run.curve.clone());
+            train_perceptron(
+                &mut self.stage2,
+                self.num_epochs,
+                self.patience,
+                running,
+                self.checkpoint.as_ref(),
+                Some(&stage1_state),
+                progress.as_mut(),
+            )?
+        } else {
+            train_perceptron(
+                &mut self.stage2,
+                self.num_epochs,
+                self.patience,
+                running,
+                None,
+                None,
+                progress.as_mut(),
+            )?
+        };
+        let stage1_metrics = MulticlassMetrics {
+            features_removed: stage1_removed,
+            dev_curve: stage1_run.curve,
+            ..self.stage1.metrics()
+        };
+        let stage2_metrics = MulticlassMetrics {
+            features_removed: stage2_removed,
+            dev_curve: stage2_run.curve,
+            ..self.stage2.metrics()
+        };
+
+        let mut metadata = self.metadata.with_param("epochs", self.num_epochs);
+        if !self.cutoff.is_noop() {
+            metadata = metadata.with_param("min_count", &self.cutoff);
+        }
+        if let Some(seed) = self.stage1.seed() {
+            metadata = metadata.with_param("seed", seed);
+        }
+        if self.stage1.threads() > 1 {
+            metadata = metadata.with_param("threads", self.stage1.threads());
+        }
+        if metadata.templates().is_none() {
+            let feature_set =
+                TwoStageFeatureSet::detect(self.stage2.feature_class_weights().map(|(f, _)| f));
+            metadata = metadata.with_templates(feature_set.to_string());
+        }
 
-        let stage1_adaboost = collapse_boundary_perceptron(&self.stage1)?;
-        let learner = TwoStageLearner::from_parts(
+        let mut stage1_adaboost = collapse_boundary_perceptron(&self.stage1)?;
+        stage1_adaboost.set_normalization(self.normalization);
+        stage1_adaboost.set_dictionary(self.dictionary);
+        let mut learner = TwoStageLearner::from_parts(
             stage1_adaboost,
             self.stage2,
             self.lexicon,
             self.dominance,
         )?;
+        learner.set_metadata(metadata);
 
         Ok((
             learner,
@@ -713,10 +2355,12 @@ mod tests {
 
     use std::io::Write;
     use std::sync::atomic::AtomicBool;
+    use std::sync::{Arc, Mutex};
 
     use tempfile::NamedTempFile;
 
     use crate::metrics::BinaryMetrics;
+    use crate::progress::TrainingProgress;
 
     // Helper: create a dummy features file.
     // This file should contain at least one line for initialize_features and initialize_instances.
@@ -1149,81 +2793,265 @@ mod tests {
         let extractor = Extractor::new(Language::Japanese);
         let feature_set = TwoStageFeatureSet::Fast;
 
-        // Path route.
-        extractor.extract_two_stage(&corpus_path, &prefix, feature_set)?;
+        // Path route.
+        extractor.extract_two_stage(&corpus_path, &prefix, feature_set)?;
+        let running = AtomicBool::new(true);
+        TwoStageTrainer::new(3, 0.99, &prefix)?.train(&running, &model_path)?;
+        let path_stage1 = fs::read_to_string(dir.path().join("features.stage1"))?;
+        let path_stage2 = fs::read_to_string(dir.path().join("features.stage2"))?;
+        let path_lexicon = fs::read_to_string(dir.path().join("features.lexicon"))?;
+        let path_model = fs::read_to_string(&model_path)?;
+
+        // In-memory route.
+        let (mut stage1, mut stage2, mut lexicon) = (Vec::new(), Vec::new(), Vec::new());
+        extractor.extract_two_stage_to_writers(
+            &corpus,
+            &mut stage1,
+            &mut stage2,
+            &mut lexicon,
+            feature_set,
+        )?;
+        let stage1 = String::from_utf8(stage1).expect("stage1 is UTF-8");
+        let stage2 = String::from_utf8(stage2).expect("stage2 is UTF-8");
+        let lexicon = String::from_utf8(lexicon).expect("lexicon is UTF-8");
+
+        assert_eq!(stage1, path_stage1, "stage-1 features diverged");
+        assert_eq!(stage2, path_stage2, "stage-2 features diverged");
+        assert_eq!(lexicon, path_lexicon, "lexicon diverged");
+
+        let mut memory_model = Vec::new();
+        TwoStageTrainer::from_features(3, 0.99, &stage1, &stage2, &lexicon)?
+            .train_to_writer(&running, &mut memory_model)?;
+        let memory_model = String::from_utf8(memory_model).expect("model is UTF-8");
+
+        assert_eq!(memory_model, path_model, "trained models diverged");
+
+        Ok(())
+    }
+
+    /// The in-memory two-stage model must load back as a working segmenter.
+    #[test]
+    fn test_in_memory_two_stage_round_trip() -> Result<()> {
+        use crate::extractor::Extractor;
+        use crate::language::Language;
+        use crate::segmenter::Segmenter;
+        use crate::two_stage::{TwoStageFeatureSet, TwoStageLearner};
+
+        let corpus = sample_pos_corpus();
+        let extractor = Extractor::new(Language::Japanese);
+
+        let (mut stage1, mut stage2, mut lexicon) = (Vec::new(), Vec::new(), Vec::new());
+        extractor.extract_two_stage_to_writers(
+            &corpus,
+            &mut stage1,
+            &mut stage2,
+            &mut lexicon,
+            TwoStageFeatureSet::Fast,
+        )?;
+
+        let mut model = Vec::new();
+        let metrics = TwoStageTrainer::from_features(
+            3,
+            0.99,
+            &String::from_utf8(stage1).expect("stage1 is UTF-8"),
+            &String::from_utf8(stage2).expect("stage2 is UTF-8"),
+            &String::from_utf8(lexicon).expect("lexicon is UTF-8"),
+        )?
+        .train_to_writer(&AtomicBool::new(true), &mut model)?;
+
+        assert!(metrics.stage1.num_instances > 0);
+        assert!(metrics.stage2.num_instances > 0);
+
+        let mut learner = TwoStageLearner::new();
+        learner.load_model_from_reader(model.as_slice())?;
+        let segmenter = Segmenter::with_two_stage_learner(Language::Japanese, learner);
+        let tokens = segmenter.segment_with_pos("これはテストです。")?;
+
+        assert!(!tokens.is_empty());
+
+        Ok(())
+    }
+
+    /// A declared normalization travels from the trainer into the saved
+    /// model, and a segmenter loading it applies it by default.
+    #[test]
+    fn test_trained_models_declare_their_normalization() -> Result<()> {
+        use crate::extractor::Extractor;
+        use crate::language::Language;
+        use crate::segmenter::Segmenter;
+        use crate::two_stage::{TwoStageFeatureSet, TwoStageLearner};
+
+        let extractor = Extractor::new(Language::Japanese).with_normalization(Normalization::Nfkc);
+        let running = AtomicBool::new(true);
+
+        let mut features = Vec::new();
+        extractor.extract_to_writer(&sample_corpus(), &mut features)?;
+        let mut model = Vec::new();
+        Trainer::from_features(0.01, 20, &String::from_utf8(features).expect("UTF-8"))?
+            .with_normalization(Normalization::Nfkc)
+            .train_to_writer(&running, &mut model)?;
+        assert!(model.starts_with(b"#normalization\tnfkc\n"));
+        let mut learner = AdaBoost::default();
+        learner.load_model_from_reader(model.as_slice())?;
+        let segmenter = Segmenter::with_learner(Language::Japanese, learner);
+        assert_eq!(segmenter.normalization(), Normalization::Nfkc);
+
+        let (mut stage1, mut stage2, mut lexicon) = (Vec::new(), Vec::new(), Vec::new());
+        extractor.extract_two_stage_to_writers(
+            &sample_pos_corpus(),
+            &mut stage1,
+            &mut stage2,
+            &mut lexicon,
+            TwoStageFeatureSet::Fast,
+        )?;
+        let mut model = Vec::new();
+        TwoStageTrainer::from_features(
+            3,
+            0.99,
+            &String::from_utf8(stage1).expect("stage1 is UTF-8"),
+            &String::from_utf8(stage2).expect("stage2 is UTF-8"),
+            &String::from_utf8(lexicon).expect("lexicon is UTF-8"),
+        )?
+        .with_normalization(Normalization::Nfkc)
+        .train_to_writer(&running, &mut model)?;
+        let mut learner = TwoStageLearner::new();
+        learner.load_model_from_reader(model.as_slice())?;
+        assert_eq!(learner.stage1().normalization(), Normalization::Nfkc);
+        let segmenter = Segmenter::with_two_stage_learner(Language::Japanese, learner);
+        let tokens = segmenter.segment_with_pos("これはﾃｽﾄです。")?;
+        let surfaces: String = tokens.iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(surfaces, "これはﾃｽﾄです。");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trained_models_store_their_dictionary() -> Result<()> {
+        use crate::extractor::Extractor;
+        use crate::language::Language;
+        use crate::segmenter::Segmenter;
+        use crate::two_stage::{TwoStageFeatureSet, TwoStageLearner};
+
+        let mut dictionary = UserDictionary::new();
+        dictionary.insert("神奈川");
+        dictionary.insert("引っ越し");
+        let words = Some(dictionary.words());
+        let extractor = Extractor::new(Language::Japanese).with_dictionary(dictionary.clone());
         let running = AtomicBool::new(true);
-        TwoStageTrainer::new(3, 0.99, &prefix)?.train(&running, &model_path)?;
-        let path_stage1 = fs::read_to_string(dir.path().join("features.stage1"))?;
-        let path_stage2 = fs::read_to_string(dir.path().join("features.stage2"))?;
-        let path_lexicon = fs::read_to_string(dir.path().join("features.lexicon"))?;
-        let path_model = fs::read_to_string(&model_path)?;
+        let mut features = Vec::new();
+        extractor.extract_to_writer(&sample_corpus(), &mut features)?;
+        let features = String::from_utf8(features).expect("UTF-8");
+
+        let mut model = Vec::new();
+        Trainer::from_features(0.01, 20, &features)?
+            .with_dictionary(dictionary.clone())
+            .train_to_writer(&running, &mut model)?;
+        let mut learner = AdaBoost::default();
+        learner.load_model_from_reader(model.as_slice())?;
+        assert_eq!(learner.dictionary().map(UserDictionary::words), words);
+
+        let mut model = Vec::new();
+        BoundaryPerceptronTrainer::from_features(3, &features)?
+            .with_dictionary(dictionary.clone())
+            .train_to_writer(&running, &mut model)?;
+        let mut learner = AdaBoost::default();
+        learner.load_model_from_reader(model.as_slice())?;
+        assert_eq!(learner.dictionary().map(UserDictionary::words), words);
+        let learned = ["DL:3", "DR:4", "DI:3", "DI:4"].map(|f| learner.weight(f));
+        assert!(learned.iter().any(|&w| w != 0.0), "{learned:?}");
+        let segmenter = Segmenter::with_learner(Language::Japanese, learner);
+        assert_eq!(
+            segmenter.segment("東京都から神奈川県へ引っ越した").join(" "),
+            "東京 都 から 神奈川 県 へ 引っ越し た"
+        );
 
-        // In-memory route.
         let (mut stage1, mut stage2, mut lexicon) = (Vec::new(), Vec::new(), Vec::new());
         extractor.extract_two_stage_to_writers(
-            &corpus,
+            &sample_pos_corpus(),
             &mut stage1,
             &mut stage2,
             &mut lexicon,
-            feature_set,
+            TwoStageFeatureSet::Fast,
         )?;
-        let stage1 = String::from_utf8(stage1).expect("stage1 is UTF-8");
-        let stage2 = String::from_utf8(stage2).expect("stage2 is UTF-8");
-        let lexicon = String::from_utf8(lexicon).expect("lexicon is UTF-8");
-
-        assert_eq!(stage1, path_stage1, "stage-1 features diverged");
-        assert_eq!(stage2, path_stage2, "stage-2 features diverged");
-        assert_eq!(lexicon, path_lexicon, "lexicon diverged");
-
-        let mut memory_model = Vec::new();
-        TwoStageTrainer::from_features(3, 0.99, &stage1, &stage2, &lexicon)?
-            .train_to_writer(&running, &mut memory_model)?;
-        let memory_model = String::from_utf8(memory_model).expect("model is UTF-8");
-
-        assert_eq!(memory_model, path_model, "trained models diverged");
+        let mut model = Vec::new();
+        TwoStageTrainer::from_features(
+            3,
+            0.99,
+            &String::from_utf8(stage1).expect("stage1 is UTF-8"),
+            &String::from_utf8(stage2).expect("stage2 is UTF-8"),
+            &String::from_utf8(lexicon).expect("lexicon is UTF-8"),
+        )?
+        .with_dictionary(dictionary)
+        .train_to_writer(&running, &mut model)?;
+        let mut learner = TwoStageLearner::new();
+        learner.load_model_from_reader(model.as_slice())?;
+        assert_eq!(learner.stage1().dictionary().map(UserDictionary::words), words);
 
         Ok(())
     }
 
-    /// The in-memory two-stage model must load back as a working segmenter.
     #[test]
-    fn test_in_memory_two_stage_round_trip() -> Result<()> {
+    fn test_trained_models_record_their_metadata() -> Result<()> {
         use crate::extractor::Extractor;
         use crate::language::Language;
         use crate::segmenter::Segmenter;
-        use crate::two_stage::{TwoStageFeatureSet, TwoStageLearner};
+        use crate::two_stage::TwoStageLearner;
 
-        let corpus = sample_pos_corpus();
         let extractor = Extractor::new(Language::Japanese);
+        let running = AtomicBool::new(true);
+        let declared = ModelMetadata::new()
+            .with_language(Language::Japanese)
+            .with_corpus_checksum("fnv1a64:0000000000000000");
+
+        let mut features = Vec::new();
+        extractor.extract_tag_free_to_writer(&sample_corpus(), &mut features)?;
+        let mut model = Vec::new();
+        Trainer::from_features(0.01, 20, &String::from_utf8(features).expect("UTF-8"))?
+            .with_metadata(declared.clone())
+            .train_to_writer(&running, &mut model)?;
+        let mut learner = AdaBoost::default();
+        learner.load_model_from_reader(model.as_slice())?;
+        let metadata = learner.metadata();
+        assert_eq!(metadata.language(), Some(Language::Japanese));
+        assert_eq!(metadata.corpus_checksum(), Some("fnv1a64:0000000000000000"));
+        assert_eq!(metadata.templates(), Some(TEMPLATES_TAG_FREE));
+        assert_eq!(metadata.param("iterations"), Some("20"));
+        assert_eq!(metadata.param("threshold"), Some("0.01"));
+        assert!(Segmenter::try_with_learner(Language::Korean, learner).is_err());
+
+        let mut model = Vec::new();
+        PerceptronTrainer::from_features(2, "B\tf1\nO\tf2\n")?
+            .with_metadata(declared.clone())
+            .train_to_writer(&running, &mut model)?;
+        let mut learner = AveragedPerceptron::new();
+        learner.load_model_from_reader(model.as_slice())?;
+        assert_eq!(learner.metadata(), &declared.clone().with_param("epochs", 2));
 
         let (mut stage1, mut stage2, mut lexicon) = (Vec::new(), Vec::new(), Vec::new());
         extractor.extract_two_stage_to_writers(
-            &corpus,
+            &sample_pos_corpus(),
             &mut stage1,
             &mut stage2,
             &mut lexicon,
-            TwoStageFeatureSet::Fast,
+            TwoStageFeatureSet::Balanced,
         )?;
-
         let mut model = Vec::new();
-        let metrics = TwoStageTrainer::from_features(
+        TwoStageTrainer::from_features(
             3,
             0.99,
             &String::from_utf8(stage1).expect("stage1 is UTF-8"),
             &String::from_utf8(stage2).expect("stage2 is UTF-8"),
             &String::from_utf8(lexicon).expect("lexicon is UTF-8"),
         )?
-        .train_to_writer(&AtomicBool::new(true), &mut model)?;
-
-        assert!(metrics.stage1.num_instances > 0);
-        assert!(metrics.stage2.num_instances > 0);
-
+        .with_metadata(declared)
+        .train_to_writer(&running, &mut model)?;
         let mut learner = TwoStageLearner::new();
         learner.load_model_from_reader(model.as_slice())?;
-        let segmenter = Segmenter::with_two_stage_learner(Language::Japanese, learner);
-        let tokens = segmenter.segment_with_pos("これはテストです。")?;
-
-        assert!(!tokens.is_empty());
+        assert_eq!(learner.metadata().language(), Some(Language::Japanese));
+        assert_eq!(learner.metadata().templates(), Some("balanced"));
+        assert_eq!(learner.metadata().param("epochs"), Some("3"));
+        assert!(learner.stage1().metadata().is_empty());
+        assert!(Segmenter::try_with_two_stage_learner(Language::Japanese, learner).is_ok());
 
         Ok(())
     }
@@ -1290,6 +3118,57 @@ mod tests {
         Ok(())
     }
 
+    /// A two-stage run resumes from a checkpoint of either stage.
+    #[test]
+    fn test_two_stage_resumes_from_checkpoint() -> Result<()> {
+        use crate::language::Language;
+
+        let corpus = sample_pos_corpus();
+        let (mut stage1, mut stage2, mut lexicon) = (Vec::new(), Vec::new(), Vec::new());
+        Extractor::new(Language::Japanese).extract_two_stage_to_writers(
+            &corpus,
+            &mut stage1,
+            &mut stage2,
+            &mut lexicon,
+            TwoStageFeatureSet::Fast,
+        )?;
+        let stage1 = String::from_utf8(stage1).expect("stage1 is UTF-8");
+        let stage2 = String::from_utf8(stage2).expect("stage2 is UTF-8");
+        let lexicon = String::from_utf8(lexicon).expect("lexicon is UTF-8");
+        let trainer = || TwoStageTrainer::from_features(3, 0.99, &stage1, &stage2, &lexicon);
+        let dir = tempfile::tempdir()?;
+        let checkpoint = dir.path().join("train.checkpoint");
+        let running = AtomicBool::new(true);
+
+        let mut reference = Vec::new();
+        trainer()?
+            .with_checkpoint(&checkpoint, 2)
+            .train_to_writer(&running, &mut reference)?;
+        let saved = Checkpoint::load(&checkpoint)?;
+        assert_eq!((saved.num_stages(), saved.epochs_completed()), (2, 2));
+        let mut resumed = trainer()?;
+        resumed.resume(saved)?;
+        let mut model = Vec::new();
+        resumed.train_to_writer(&running, &mut model)?;
+        assert_eq!(model, reference, "resuming stage 2 changed the model");
+
+        // Interrupted before the first epoch: only stage 1 is checkpointed,
+        // and there is no model to save.
+        let interrupted = trainer()?
+            .with_checkpoint(&checkpoint, 2)
+            .train_to_writer(&AtomicBool::new(false), &mut Vec::new());
+        assert!(interrupted.is_err());
+        let saved = Checkpoint::load(&checkpoint)?;
+        assert_eq!((saved.num_stages(), saved.epochs_completed()), (1, 0));
+        let mut resumed = trainer()?;
+        resumed.resume(saved)?;
+        let mut model = Vec::new();
+        resumed.train_to_writer(&running, &mut model)?;
+        assert_eq!(model, reference, "resuming stage 1 changed the model");
+
+        Ok(())
+    }
+
     /// The same guarantee for the plain perceptron trainer, which the
     /// bundled segmentation models' collapse recipe goes through.
     #[test]
@@ -1308,4 +3187,470 @@ mod tests {
 
         Ok(())
     }
+
+    /// A seeded shuffle keeps the guarantee: the same seed and inputs give
+    /// a byte-identical model, while the seed does change the visiting
+    /// order (and is recorded).
+    #[test]
+    fn test_seeded_training_is_reproducible() -> Result<()> {
+        use crate::language::Language;
+
+        let mut features = Vec::new();
+        Extractor::new(Language::Japanese).extract_to_writer(&sample_corpus(), &mut features)?;
+        let features = String::from_utf8(features).expect("features are UTF-8");
+        let running = AtomicBool::new(true);
+
+        let train = |seed: Option<u64>| -> Result<Vec<u8>> {
+            let mut trainer = PerceptronTrainer::from_features(3, &features)?;
+            if let Some(seed) = seed {
+                trainer = trainer.with_seed(seed);
+            }
+            let mut model = Vec::new();
+            trainer.train_to_writer(&running, &mut model)?;
+            Ok(model)
+        };
+        let seeded = train(Some(42))?;
+        assert_eq!(seeded, train(Some(42))?, "two seeded runs disagreed");
+        assert!(String::from_utf8_lossy(&seeded).contains("#param.seed\t42\n"));
+
+        let weights = |model: &[u8]| -> Vec<String> {
+            String::from_utf8_lossy(model)
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        };
+        assert_ne!(weights(&seeded), weights(&train(None)?));
+        assert_ne!(weights(&seeded), weights(&train(Some(7))?));
+
+        Ok(())
+    }
+
+    /// Parameter-mixing training is deterministic for a thread count and
+    /// records it, in both the plain and the two-stage trainer.
+    #[test]
+    fn test_multithreaded_training_is_reproducible() -> Result<()> {
+        use crate::language::Language;
+
+        let corpus = sample_pos_corpus();
+        let (mut stage1, mut stage2, mut lexicon) = (Vec::new(), Vec::new(), Vec::new());
+        Extractor::new(Language::Japanese).extract_two_stage_to_writers(
+            &corpus,
+            &mut stage1,
+            &mut stage2,
+            &mut lexicon,
+            TwoStageFeatureSet::Fast,
+        )?;
+        let stage1 = String::from_utf8(stage1).expect("stage1 is UTF-8");
+        let stage2 = String::from_utf8(stage2).expect("stage2 is UTF-8");
+        let lexicon = String::from_utf8(lexicon).expect("lexicon is UTF-8");
+        let running = AtomicBool::new(true);
+
+        let train = |threads: usize| -> Result<Vec<u8>> {
+            let mut model = Vec::new();
+            PerceptronTrainer::from_features(3, &stage2)?
+                .with_seed(3)
+                .with_threads(threads)
+                .train_to_writer(&running, &mut model)?;
+            Ok(model)
+        };
+        let mixed = train(3)?;
+        assert_eq!(mixed, train(3)?, "two multithreaded runs disagreed");
+        assert!(String::from_utf8_lossy(&mixed).contains("#param.threads\t3\n"));
+        // A single thread is plain training and records nothing.
+        let single = train(1)?;
+        assert!(!String::from_utf8_lossy(&single).contains("#param.threads"));
+        assert_ne!(mixed, single);
+
+        let mut models = Vec::new();
+        for _ in 0..2 {
+            let mut model = Vec::new();
+            TwoStageTrainer::from_features(3, 0.99, &stage1, &stage2, &lexicon)?
+                .with_threads(2)
+                .train_to_writer(&running, &mut model)?;
+            models.push(model);
+        }
+        assert_eq!(models[0], models[1], "two multithreaded runs disagreed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resumed_training_matches_an_uninterrupted_run() -> Result<()> {
+        use crate::language::Language;
+
+        let mut features = Vec::new();
+        Extractor::new(Language::Japanese).extract_to_writer(&sample_corpus(), &mut features)?;
+        let features = String::from_utf8(features).expect("features are UTF-8");
+        let dir = tempfile::tempdir()?;
+        let checkpoint = dir.path().join("train.checkpoint");
+        let running = AtomicBool::new(true);
+
+        // Three epochs with a checkpoint every two leave the epoch-2 state.
+        let mut reference = Vec::new();
+        PerceptronTrainer::from_features(3, &features)?
+            .with_seed(5)
+            .with_checkpoint(&checkpoint, 2)
+            .train_to_writer(&running, &mut reference)?;
+        let saved = Checkpoint::load(&checkpoint)?;
+        assert_eq!((saved.num_stages(), saved.epochs_completed()), (1, 2));
+
+        // The seed comes back with the checkpoint.
+        let mut trainer = PerceptronTrainer::from_features(3, &features)?;
+        trainer.resume(saved)?;
+        let mut resumed = Vec::new();
+        trainer.train_to_writer(&running, &mut resumed)?;
+        assert_eq!(resumed, reference);
+
+        let mut trainer = PerceptronTrainer::from_features(3, "B\tf1\nO\tf2\n")?;
+        trainer.resume(Checkpoint::load(&checkpoint)?)?;
+        assert!(matches!(
+            trainer.train_to_writer(&running, &mut Vec::new()),
+            Err(LitseaError::InvalidInput(_))
+        ));
+
+        Ok(())
+    }
+
+    /// `BoundaryPerceptronTrainer` must write the same weights as the
+    /// manual recipe it replaces: relabel the extract output to `B`/`O`,
+    /// train a generic perceptron, then collapse it.
+    #[test]
+    fn test_boundary_perceptron_trainer_matches_manual_recipe() -> Result<()> {
+        use crate::extractor::Extractor;
+        use crate::language::Language;
+
+        let mut features = Vec::new();
+        Extractor::new(Language::Japanese).extract_to_writer(&sample_corpus(), &mut features)?;
+        let features = String::from_utf8(features).expect("features are UTF-8");
+        let relabelled: String = features
+            .lines()
+            .map(|line| match line.split_once('\t') {
+                Some(("1", rest)) => format!("B\t{rest}\n"),
+                Some(("-1", rest)) => format!("O\t{rest}\n"),
+                _ => panic!("unexpected features line: {line:?}"),
+            })
+            .collect();
+        let running = AtomicBool::new(true);
+
+        let mut model = Vec::new();
+        let metrics = BoundaryPerceptronTrainer::from_features(5, &features)?
+            .train_to_writer(&running, &mut model)?;
+        assert_eq!(metrics.num_instances, features.lines().count());
+
+        let mut recipe = PerceptronTrainer::from_features(5, &relabelled)?;
+        recipe.train_to_writer(&running, &mut Vec::new())?;
+        let mut expected = Vec::new();
+        collapse_boundary_perceptron(&recipe.learner)?.save_model_to_writer(&mut expected)?;
+
+        let weights = |model: &[u8]| -> Vec<String> {
+            String::from_utf8_lossy(model)
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        };
+        assert_eq!(weights(&model), weights(&expected));
+
+        let mut learner = AdaBoost::default();
+        learner.load_model_from_reader(model.as_slice())?;
+        assert_eq!(learner.metadata().param("epochs"), Some("5"));
+        assert_eq!(learner.metadata().templates(), Some(TEMPLATES_FULL));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_boundary_perceptron_trainer_rejects_non_boundary_labels() {
+        let err = BoundaryPerceptronTrainer::from_features(1, "NOUN\tf1\n").unwrap_err();
+        assert!(matches!(err, LitseaError::InvalidData(_)), "{err:?}");
+
+        // Only one boundary class: nothing to collapse against.
+        let mut trainer =
+            BoundaryPerceptronTrainer::from_features(1, "1\tf1\n1\tf2\n").expect("valid labels");
+        let running = AtomicBool::new(true);
+        assert!(trainer.train_to_writer(&running, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_from_corpus_matches_features_route() -> Result<()> {
+        use crate::language::Language;
+
+        let extractor = Extractor::new(Language::Japanese);
+        let corpus = sample_corpus();
+        let running = AtomicBool::new(true);
+
+        let mut features = Vec::new();
+        extractor.extract_to_writer(&corpus, &mut features)?;
+        let features = String::from_utf8(features).expect("features are UTF-8");
+        let mut expected = Vec::new();
+        Trainer::from_features(0.001, 50, &features)?.train_to_writer(&running, &mut expected)?;
+        let mut actual = Vec::new();
+        Trainer::from_corpus_str(0.001, 50, &extractor, &corpus, CorpusFormat::Space, false)?
+            .train_to_writer(&running, &mut actual)?;
+        assert_eq!(actual, expected);
+
+        let mut features = Vec::new();
+        extractor.extract_tag_free_to_writer(&corpus, &mut features)?;
+        let features = String::from_utf8(features).expect("features are UTF-8");
+        let mut expected = Vec::new();
+        BoundaryPerceptronTrainer::from_features(3, &features)?
+            .train_to_writer(&running, &mut expected)?;
+        let mut actual = Vec::new();
+        BoundaryPerceptronTrainer::from_corpus_str(
+            3,
+            &extractor,
+            &corpus,
+            CorpusFormat::Space,
+            true,
+        )?
+        .train_to_writer(&running, &mut actual)?;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_distillation_weights_by_margin() -> Result<()> {
+        use crate::adaboost::AdaBoost;
+        use crate::language::Language;
+        use crate::segmenter::Segmenter;
+
+        let model_file =
+            std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models/japanese.model");
+        let mut learner = AdaBoost::new(0.01, 100);
+        learner.load_model_from_path(&model_file)?;
+        let teacher = Segmenter::with_learner(Language::Japanese, learner);
+        let extractor = Extractor::new(Language::Japanese);
+        let raw = sample_corpus().replace(' ', "");
+        let running = AtomicBool::new(true);
+        let distill = |weight_by_margin| -> Result<Vec<u8>> {
+            let mut model = Vec::new();
+            BoundaryPerceptronTrainer::from_teacher_str(
+                3,
+                &extractor,
+                &teacher,
+                &raw,
+                0.0,
+                weight_by_margin,
+                true,
+            )?
+            .train_to_writer(&running, &mut model)?;
+            Ok(model)
+        };
+
+        // Steps scaled by the teacher's margins move the weights
+        // differently from unit steps.
+        let unweighted = distill(None)?;
+        assert_ne!(distill(Some(1000.0))?, unweighted);
+        // A scale every margin exceeds gives every instance the unit step.
+        assert_eq!(distill(Some(f64::MIN_POSITIVE))?, unweighted);
+
+        for scale in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(distill(Some(scale)), Err(LitseaError::InvalidInput(_))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_cutoff_drops_rare_features() -> Result<()> {
+        use crate::language::Language;
+
+        let extractor = Extractor::new(Language::Japanese);
+        // The sample sentences repeat; one more, seen once, brings rare
+        // features.
+        let corpus = sample_corpus() + "富士 山 に 登る\n";
+        let mut features = Vec::new();
+        extractor.extract_to_writer(&corpus, &mut features)?;
+        let features = String::from_utf8(features).expect("features are UTF-8");
+        let running = AtomicBool::new(true);
+
+        // Count each feature once per instance, the way the trainers do.
+        let mut counts: FxHashMap<&str, usize> = FxHashMap::default();
+        for line in features.lines() {
+            let row: HashSet<&str> = line.split('\t').skip(1).collect();
+            for feature in row {
+                *counts.entry(feature).or_default() += 1;
+            }
+        }
+        let singletons = counts.values().filter(|&&c| c < 2).count();
+        let rare_uw4 = counts.iter().filter(|&(f, &c)| f.starts_with("UW4:") && c < 3).count();
+        assert!(singletons > 0 && rare_uw4 > 0);
+
+        // A cutoff of 1 keeps everything: the model is unchanged.
+        let mut expected = Vec::new();
+        let metrics = Trainer::from_features(0.001, 50, &features)?
+            .train_to_writer(&running, &mut expected)?;
+        assert_eq!(metrics.features_removed, 0);
+        let mut actual = Vec::new();
+        Trainer::from_features(0.001, 50, &features)?
+            .with_cutoff(FeatureCutoff::new(1))
+            .train_to_writer(&running, &mut actual)?;
+        assert_eq!(actual, expected);
+
+        let mut model = Vec::new();
+        let metrics = Trainer::from_features(0.001, 50, &features)?
+            .with_cutoff(FeatureCutoff::new(2))
+            .train_to_writer(&running, &mut model)?;
+        assert_eq!(metrics.features_removed, singletons);
+        let mut learner = AdaBoost::new(0.001, 50);
+        learner.load_model_from_reader(model.as_slice())?;
+        assert_eq!(learner.metadata().param("min_count"), Some("2"));
+
+        let cutoff = FeatureCutoff::new(1).with_template("UW4", 3);
+        let metrics = PerceptronTrainer::from_features(3, &features)?
+            .with_cutoff(cutoff.clone())
+            .train_to_writer(&running, &mut Vec::new())?;
+        assert_eq!(metrics.features_removed, rare_uw4);
+        let metrics = BoundaryPerceptronTrainer::from_features(3, &features)?
+            .with_cutoff(cutoff)
+            .train_to_writer(&running, &mut Vec::new())?;
+        assert_eq!(metrics.features_removed, rare_uw4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dev_early_stopping_keeps_the_best_epoch() -> Result<()> {
+        use crate::language::Language;
+
+        let extractor = Extractor::new(Language::Japanese);
+        let mut features = Vec::new();
+        extractor.extract_tag_free_to_writer(&sample_corpus(), &mut features)?;
+        let features = String::from_utf8(features).expect("features are UTF-8");
+        let dev = "富士 山 に 登る\n東京 は 客 が 多い\n";
+        let running = AtomicBool::new(true);
+        let epochs = 8;
+
+        let mut trainer = BoundaryPerceptronTrainer::from_features(epochs, &features)?;
+        trainer.load_dev_corpus_str(&extractor, dev, CorpusFormat::Space, true)?;
+        let mut model = Vec::new();
+        let metrics = trainer.with_patience(2).train_to_writer(&running, &mut model)?;
+
+        let curve = metrics.dev_curve;
+        assert!(!curve.is_empty() && curve.len() <= epochs);
+        let best = curve.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let best_epoch = curve.iter().position(|&a| a == best).expect("a best epoch") + 1;
+        // Stopping early means the last `patience` epochs did not improve.
+        if curve.len() < epochs {
+            assert_eq!(curve.len(), best_epoch + 2);
+        }
+
+        // The kept weights are those of a run stopped at the best epoch;
+        // only the recorded epoch budget differs.
+        let mut expected = Vec::new();
+        BoundaryPerceptronTrainer::from_features(best_epoch, &features)?
+            .train_to_writer(&running, &mut expected)?;
+        let weights = |model: &[u8]| -> Vec<String> {
+            String::from_utf8_lossy(model)
+                .lines()
+                .filter(|line| !line.starts_with("#param.epochs"))
+                .map(str::to_string)
+                .collect()
+        };
+        assert_eq!(weights(&model), weights(&expected));
+
+        // Without a dev set there is no curve.
+        let metrics = BoundaryPerceptronTrainer::from_features(epochs, &features)?
+            .with_patience(2)
+            .train_to_writer(&running, &mut Vec::new())?;
+        assert!(metrics.dev_curve.is_empty());
+
+        Ok(())
+    }
+
+    /// Collects the reports of a training run.
+    fn progress_recorder() -> (Arc<Mutex<Vec<TrainingProgress>>>, Box<dyn ProgressObserver>) {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reports);
+        let observer = move |progress: &TrainingProgress| {
+            sink.lock().unwrap().push(progress.clone());
+        };
+        (reports, Box::new(observer))
+    }
+
+    /// Every boosting iteration and perceptron epoch is reported, in order;
+    /// a perceptron epoch's updates are its mispredicted instances.
+    #[test]
+    fn test_progress_reports_every_iteration_and_epoch() -> Result<()> {
+        use crate::language::Language;
+
+        let mut features = Vec::new();
+        Extractor::new(Language::Japanese).extract_to_writer(&sample_corpus(), &mut features)?;
+        let features = String::from_utf8(features).expect("features are UTF-8");
+        let num_instances = features.lines().filter(|line| !line.is_empty()).count();
+        let running = AtomicBool::new(true);
+
+        let (reports, observer) = progress_recorder();
+        let mut trainer = Trainer::from_features(0.0, 5, &features)?;
+        trainer.set_progress(Some(observer));
+        trainer.train_to_writer(&running, &mut Vec::new())?;
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 5);
+        for (i, report) in reports.iter().enumerate() {
+            assert_eq!(report.stage, TrainingStage::Boosting);
+            assert_eq!((report.iteration, report.total), (i + 1, 5));
+            assert!((0.0..=100.0).contains(&report.error_rate), "{:?}", report);
+            assert!(report.updates <= num_instances);
+            assert_eq!(report.dev_accuracy, None);
+        }
+
+        let (reports, observer) = progress_recorder();
+        let mut trainer = PerceptronTrainer::from_features(4, &features)?;
+        trainer.load_dev_from_features(&features)?;
+        trainer.set_progress(Some(observer));
+        let metrics = trainer.train_to_writer(&running, &mut Vec::new())?;
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 4);
+        for (i, report) in reports.iter().enumerate() {
+            assert_eq!(report.stage, TrainingStage::Perceptron);
+            assert_eq!((report.iteration, report.total), (i + 1, 4));
+            let error_rate = report.updates as f64 / num_instances as f64 * 100.0;
+            assert_eq!(report.error_rate, error_rate);
+            assert_eq!(report.dev_accuracy, Some(metrics.dev_curve[i]));
+        }
+        assert!(reports[0].updates > reports[3].updates);
+        assert!(reports.windows(2).all(|pair| pair[0].elapsed <= pair[1].elapsed));
+
+        Ok(())
+    }
+
+    /// A two-stage run reports stage 1 as `Boundary`, then stage 2 as
+    /// `Tagging`.
+    #[test]
+    fn test_two_stage_progress_reports_both_stages() -> Result<()> {
+        use crate::language::Language;
+
+        let (mut stage1, mut stage2, mut lexicon) = (Vec::new(), Vec::new(), Vec::new());
+        Extractor::new(Language::Japanese).extract_two_stage_to_writers(
+            &sample_pos_corpus(),
+            &mut stage1,
+            &mut stage2,
+            &mut lexicon,
+            TwoStageFeatureSet::Fast,
+        )?;
+        let mut trainer = TwoStageTrainer::from_features(
+            2,
+            0.99,
+            std::str::from_utf8(&stage1).expect("stage1 is UTF-8"),
+            std::str::from_utf8(&stage2).expect("stage2 is UTF-8"),
+            std::str::from_utf8(&lexicon).expect("lexicon is UTF-8"),
+        )?;
+        let (reports, observer) = progress_recorder();
+        trainer.set_progress(Some(observer));
+        trainer.train_to_writer(&AtomicBool::new(true), &mut Vec::new())?;
+
+        let stages: Vec<(TrainingStage, usize)> =
+            reports.lock().unwrap().iter().map(|r| (r.stage, r.iteration)).collect();
+        assert_eq!(
+            stages,
+            [
+                (TrainingStage::Boundary, 1),
+                (TrainingStage::Boundary, 2),
+                (TrainingStage::Tagging, 1),
+                (TrainingStage::Tagging, 2),
+            ]
+        );
+        Ok(())
+    }
 }
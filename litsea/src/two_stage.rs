@@ -18,6 +18,7 @@
 //! litsea-two-stage v1
 //! [params]                    <- optional
 //! dominance\t0.99
+//! language\tjapanese          <- optional metadata lines
 //! [stage1]
 //! <AdaBoost model format: "feature\tweight" lines + one bias line>
 //! [lexicon]
@@ -38,6 +39,9 @@
 //!   its training occurrences is tagged without invoking the stage-2
 //!   classifier. It defaults to [`DEFAULT_DOMINANCE`] when the `[params]`
 //!   section is absent.
+//! - The other `[params]` keys are the model's [`ModelMetadata`]
+//!   (language, templates, training parameters, corpus checksum); they are
+//!   written only when recorded.
 //!
 //! Section markers cannot collide with content lines: every weight and
 //! lexicon line contains a tab, and the only single-token content lines are
@@ -49,6 +53,10 @@
 //! nor a class count), so the format is purely additive: old files keep
 //! loading with their loaders, and those loaders reject two-stage files
 //! with `InvalidData`.
+//!
+//! The same three parts can also be stored in the binary model container
+//! (`litsea-binary v1 two-stage`, see [`crate::binary_model`]), which
+//! [`TwoStageLearner::load_model_from_reader`] accepts as well.
 
 use std::fmt;
 use std::fs::File;
@@ -61,8 +69,12 @@ use std::str::FromStr;
 
 use rustc_hash::FxHashMap;
 
-use crate::adaboost::AdaBoost;
+use crate::adaboost::{AdaBoost, PruneStrategy};
+use crate::binary_model::{self, Decoder, Encoder};
 use crate::error::{LitseaError, Result};
+use crate::language::Language;
+use crate::metadata::ModelMetadata;
+use crate::packed_two_stage::{PackedTwoStageModel, read_upos};
 use crate::perceptron::AveragedPerceptron;
 use crate::upos::Upos;
 
@@ -114,24 +126,32 @@ pub enum ModelKind {
 
 impl ModelKind {
     /// Detects the model kind from file content by inspecting the first
-    /// line: a `litsea-two-stage` magic line means [`ModelKind::TwoStage`],
-    /// a bare integer (the class-count header) means
-    /// [`ModelKind::AveragedPerceptron`], and anything else is assumed to
-    /// be [`ModelKind::AdaBoost`].
+    /// line: a `litsea-binary` magic line means the kind it names (see
+    /// [`crate::binary_model`]), a `litsea-two-stage` magic line means
+    /// [`ModelKind::TwoStage`], a bare integer (the class-count header)
+    /// means [`ModelKind::AveragedPerceptron`], and anything else is
+    /// assumed to be [`ModelKind::AdaBoost`]. Leading `#key\tvalue` header
+    /// lines (see [`crate::metadata`]) are skipped, so the first line
+    /// inspected is the first content line. The binary magic line is
+    /// ASCII; [`detect_bytes`](Self::detect_bytes) decodes only as much of
+    /// the file as detection needs.
     ///
     /// Detection is a dispatch heuristic, not a validation: the matching
     /// loader still fully validates the content and reports malformed files
     /// as `InvalidData`.
     ///
     /// # Arguments
-    /// * `content` - The model file content (or at least its first line).
+    /// * `content` - The model file content (or at least its header lines
+    ///   and first content line).
     ///
     /// # Returns
     /// The detected [`ModelKind`].
     #[must_use]
     pub fn detect(content: &str) -> Self {
-        let first = content.lines().next().unwrap_or("");
-        if first.starts_with(MAGIC_PREFIX) {
+        let first = content.lines().find(|line| !line.starts_with('#')).unwrap_or("");
+        if let Some(kind) = binary_model::kind_from_magic(first) {
+            kind
+        } else if first.starts_with(MAGIC_PREFIX) {
             ModelKind::TwoStage
         } else if first.trim().parse::<usize>().is_ok() {
             ModelKind::AveragedPerceptron
@@ -139,6 +159,27 @@ impl ModelKind {
             ModelKind::AdaBoost
         }
     }
+
+    /// Detects the model kind from raw file bytes, like
+    /// [`detect`](Self::detect), decoding only the header lines and the
+    /// first content line (lossily: detection only needs ASCII).
+    ///
+    /// # Arguments
+    /// * `content` - The model file bytes, text or binary.
+    ///
+    /// # Returns
+    /// The detected [`ModelKind`].
+    #[must_use]
+    pub fn detect_bytes(content: &[u8]) -> Self {
+        let mut end = 0;
+        for line in content.split_inclusive(|&b| b == b'\n') {
+            end += line.len();
+            if !line.starts_with(b"#") {
+                break;
+            }
+        }
+        Self::detect(&String::from_utf8_lossy(&content[..end]))
+    }
 }
 
 /// Lexicon entry type: the UPOS tags observed for one surface, with their
@@ -200,6 +241,12 @@ pub struct TwoStageLearner {
     lexicon: FxHashMap<String, LexiconEntry>,
     /// Classifier-skip dominance threshold, in `(0.5, 1.0]`.
     dominance: f64,
+    /// Provenance recorded in the `[params]` section.
+    metadata: ModelMetadata,
+    /// Packed tagging tables read from a binary model file, and the
+    /// language they were compiled for; handed over to the first segmenter
+    /// built for that language. Stage 1 carries its own.
+    compiled: Option<(Language, PackedTwoStageModel)>,
 }
 
 impl Default for TwoStageLearner {
@@ -224,6 +271,8 @@ impl TwoStageLearner {
             stage2: AveragedPerceptron::new(),
             lexicon: FxHashMap::default(),
             dominance: DEFAULT_DOMINANCE,
+            metadata: ModelMetadata::default(),
+            compiled: None,
         }
     }
 
@@ -318,6 +367,8 @@ impl TwoStageLearner {
             stage2,
             lexicon: map,
             dominance,
+            metadata: ModelMetadata::default(),
+            compiled: None,
         })
     }
 
@@ -333,6 +384,27 @@ impl TwoStageLearner {
         (self.stage1, self.stage2, self.lexicon, self.dominance)
     }
 
+    /// Returns the language of the packed tagging tables read from a binary
+    /// model, or `None` if the model carried none (see
+    /// [`AdaBoost::compiled_language`]).
+    #[must_use]
+    pub fn compiled_language(&self) -> Option<Language> {
+        self.compiled.as_ref().map(|(language, _)| *language)
+    }
+
+    /// Takes the packed tagging tables read from a binary model, if they
+    /// were compiled for `language`. Crate-private: used by the segmenter
+    /// runtime before [`into_parts`](Self::into_parts).
+    ///
+    /// # Returns
+    /// The precompiled tables, or `None` if the caller must build them.
+    pub(crate) fn take_compiled(&mut self, language: Language) -> Option<PackedTwoStageModel> {
+        match self.compiled.take() {
+            Some((compiled_for, packed)) if compiled_for == language => Some(packed),
+            _ => None,
+        }
+    }
+
     /// Returns the stage-1 boundary classifier.
     #[must_use]
     pub fn stage1(&self) -> &AdaBoost {
@@ -351,6 +423,23 @@ impl TwoStageLearner {
         self.dominance
     }
 
+    /// Returns the provenance this model records (see [`ModelMetadata`]).
+    #[must_use]
+    pub fn metadata(&self) -> &ModelMetadata {
+        &self.metadata
+    }
+
+    /// Sets the provenance this model records. It is written as extra
+    /// `key\tvalue` lines of the `[params]` section by
+    /// [`save_model`](Self::save_model).
+    ///
+    /// # Arguments
+    /// * `metadata` - The model's language, templates, training parameters
+    ///   and corpus checksum.
+    pub fn set_metadata(&mut self, metadata: ModelMetadata) {
+        self.metadata = metadata;
+    }
+
     /// Returns the number of surfaces in the lexicon.
     #[must_use]
     pub fn lexicon_len(&self) -> usize {
@@ -371,6 +460,26 @@ impl TwoStageLearner {
         self.lexicon.get(surface).map(Vec::as_slice)
     }
 
+    /// Prunes the stage-1 boundary classifier (see [`AdaBoost::prune`]).
+    /// The stage-2 tagger and the lexicon are left untouched.
+    ///
+    /// # Arguments
+    /// * `strategy` - Which stage-1 features to keep.
+    ///
+    /// # Returns
+    /// The number of stage-1 features kept.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] for an invalid strategy.
+    pub fn prune_stage1(&mut self, strategy: PruneStrategy) -> Result<usize> {
+        self.stage1.prune(strategy)
+    }
+
+    /// Returns the whole candidate-tag lexicon (used by [`crate::inspect`]).
+    pub(crate) fn lexicon(&self) -> &FxHashMap<String, LexiconEntry> {
+        &self.lexicon
+    }
+
     /// Saves the model to a file in the `litsea-two-stage v1` format.
     ///
     /// # Arguments
@@ -399,17 +508,22 @@ impl TwoStageLearner {
     /// * `writer` - The writer receiving the model text.
     ///
     /// # Errors
-    /// Returns [`LitseaError::InvalidInput`] if the lexicon is empty or
+    /// Returns [`LitseaError::InvalidInput`] if the lexicon is empty,
     /// either embedded learner is empty (their writers reject empty
-    /// models), or an I/O error if writing fails.
+    /// models), or a metadata value cannot be written on one line, or an
+    /// I/O error if writing fails.
     pub fn save_model_to_writer<W: Write>(&self, writer: &mut W) -> Result<()> {
         if self.lexicon.is_empty() {
             return Err(LitseaError::InvalidInput("Cannot save an empty model".to_string()));
         }
+        self.metadata.validate()?;
 
         writeln!(writer, "{}", MAGIC)?;
         writeln!(writer, "{}", SECTION_PARAMS)?;
         writeln!(writer, "dominance\t{}", self.dominance)?;
+        for (key, value) in self.metadata.headers() {
+            writeln!(writer, "{}\t{}", key, value)?;
+        }
         writeln!(writer, "{}", SECTION_STAGE1)?;
         self.stage1.save_model_to_writer(writer)?;
         writeln!(writer, "{}", SECTION_LEXICON)?;
@@ -419,6 +533,137 @@ impl TwoStageLearner {
         Ok(())
     }
 
+    /// Saves the model to a file in the binary model format (see
+    /// [`save_binary_model_to_writer`](Self::save_binary_model_to_writer)).
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file to write the model to.
+    /// * `language` - The language to compile packed tables for, or `None`
+    ///   to store the model parts only.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if any part is empty, or an
+    /// I/O error if the file cannot be created or written.
+    pub fn save_binary_model(&self, path: &Path, language: Option<Language>) -> Result<()> {
+        let mut file = std::io::BufWriter::new(File::create(path)?);
+        self.save_binary_model_to_writer(&mut file, language)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Writes the model to an arbitrary writer in the binary model format
+    /// (`litsea-binary v1 two-stage`).
+    ///
+    /// The payload holds the dominance threshold, the metadata as
+    /// `(key, value)` pairs, the stage-1 payload (see
+    /// [`AdaBoost::save_binary_model_to_writer`]), the lexicon sorted by
+    /// surface with tags stored by their [`Upos::ALL`] index, the stage-2
+    /// payload (see [`AveragedPerceptron::save_binary_model_to_writer`]),
+    /// and, with a `language`, the packed tagging tables compiled for it.
+    /// Loading it back yields exactly this model, and a segmenter built
+    /// for `language` from it skips compiling both packed tables. The
+    /// writer is not flushed.
+    ///
+    /// # Arguments
+    /// * `writer` - The writer receiving the model bytes.
+    /// * `language` - The language to compile packed tables for, or `None`
+    ///   to store the model parts only.
+    ///
+    /// # Errors
+    /// Returns [`LitseaError::InvalidInput`] if the lexicon is empty or
+    /// either embedded learner is empty, or an I/O error if writing fails.
+    pub fn save_binary_model_to_writer<W: Write>(
+        &self,
+        writer: &mut W,
+        language: Option<Language>,
+    ) -> Result<()> {
+        if self.lexicon.is_empty() {
+            return Err(LitseaError::InvalidInput("Cannot save an empty model".to_string()));
+        }
+
+        self.metadata.validate()?;
+        let mut enc = Encoder::new(writer, ModelKind::TwoStage)?;
+        enc.f64(self.dominance)?;
+        let headers = self.metadata.headers();
+        enc.len(headers.len())?;
+        for (key, value) in &headers {
+            enc.str(key)?;
+            enc.str(value)?;
+        }
+        self.stage1.write_binary(&mut enc, language)?;
+        let mut surfaces: Vec<&String> = self.lexicon.keys().collect();
+        surfaces.sort_unstable();
+        enc.len(surfaces.len())?;
+        for surface in surfaces {
+            let entry = &self.lexicon[surface];
+            enc.str(surface)?;
+            enc.len(entry.len())?;
+            for &(tag, count) in entry {
+                enc.u8(tag.index())?;
+                enc.u32(count)?;
+            }
+        }
+        self.stage2.write_binary(&mut enc)?;
+        enc.language(language)?;
+        if let Some(language) = language {
+            match &self.compiled {
+                Some((compiled_for, packed)) if *compiled_for == language => {
+                    packed.write_binary(&mut enc)?;
+                }
+                _ => PackedTwoStageModel::build(
+                    language,
+                    &self.stage2,
+                    &self.lexicon,
+                    self.dominance,
+                )
+                .write_binary(&mut enc)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads a binary model file, validating the parts like
+    /// [`from_parts`](Self::from_parts).
+    fn load_binary_model(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut dec = Decoder::new(bytes, ModelKind::TwoStage)?;
+        let dominance = dec.f64()?;
+        let mut metadata = ModelMetadata::default();
+        for _ in 0..dec.len(8)? {
+            let key = dec.str()?;
+            let value = dec.str()?;
+            metadata.apply_header(key, value).map_err(LitseaError::InvalidData)?;
+        }
+        let stage1 = AdaBoost::read_binary(&mut dec)?;
+        let len = dec.len(4 + 8)?;
+        let mut lexicon: Vec<(String, LexiconEntry)> = Vec::with_capacity(len);
+        for _ in 0..len {
+            let surface = dec.str()?.to_string();
+            let count = dec.len(1 + 4)?;
+            let entry = (0..count)
+                .map(|_| Ok((read_upos(&mut dec)?, dec.u32()?)))
+                .collect::<Result<LexiconEntry>>()?;
+            lexicon.push((surface, entry));
+        }
+        let stage2 = AveragedPerceptron::read_binary(&mut dec)?;
+        let compiled = match dec.language()? {
+            Some(language) => {
+                Some((language, PackedTwoStageModel::read_binary(language, &mut dec)?))
+            }
+            None => None,
+        };
+        dec.finish()?;
+
+        let mut learner =
+            Self::from_parts(stage1, stage2, lexicon, dominance).map_err(|e| match e {
+                LitseaError::InvalidInput(message) => LitseaError::InvalidData(message),
+                e => e,
+            })?;
+        learner.metadata = metadata;
+        learner.compiled = compiled;
+        *self = learner;
+        Ok(())
+    }
+
     /// Loads a model from a URI.
     ///
     /// The URI can be a file path, a `file://` path, or an `http(s)://` URL
@@ -459,16 +704,24 @@ impl TwoStageLearner {
     ///
     /// # Arguments
     /// * `reader` - The buffered reader providing the model content (the
-    ///   format written by [`save_model`](Self::save_model)).
+    ///   format written by [`save_model`](Self::save_model), or the binary
+    ///   format written by [`save_binary_model`](Self::save_binary_model),
+    ///   recognized by its magic line).
     ///
     /// # Errors
     /// Returns [`LitseaError::InvalidData`] if the magic line is missing or
     /// names an unsupported version, sections are missing, duplicated, out
     /// of order, or malformed (including the embedded stage-1/stage-2
     /// content, reported with the section name), the lexicon is empty or
-    /// violates the format, or a parameter is unknown or out of range.
+    /// violates the format, or a parameter is unknown or out of range. A
+    /// binary model is rejected when it is truncated, holds another model
+    /// kind, or its parts fail the [`from_parts`](Self::from_parts) checks.
     /// I/O errors from the reader are also propagated.
-    pub fn load_model_from_reader<R: BufRead>(&mut self, reader: R) -> Result<()> {
+    pub fn load_model_from_reader<R: BufRead>(&mut self, mut reader: R) -> Result<()> {
+        if let Some(bytes) = binary_model::read_if_binary(&mut reader)? {
+            return self.load_binary_model(&bytes);
+        }
+
         /// The section currently being collected.
         #[derive(PartialEq, Clone, Copy)]
         enum Section {
@@ -547,7 +800,7 @@ impl TwoStageLearner {
             ));
         }
 
-        let dominance = parse_params(&params_lines)?;
+        let (dominance, metadata) = parse_params(&params_lines)?;
         let mut stage1 = AdaBoost::default();
         stage1
             .load_model_from_reader(stage1_lines.join("\n").as_bytes())
@@ -570,10 +823,25 @@ impl TwoStageLearner {
         self.stage2 = stage2;
         self.lexicon = lexicon;
         self.dominance = dominance;
+        self.metadata = metadata;
+        self.compiled = None;
         Ok(())
     }
 }
 
+/// Returns whether a surface with this lexicon entry is tagged without the
+/// classifier: it has a single observed tag, or its most frequent tag
+/// covers at least the `dominance` fraction of its occurrences. Shared by
+/// the packed tagger and [`crate::inspect`].
+///
+/// # Arguments
+/// * `entry` - The lexicon entry, most frequent tag first.
+/// * `dominance` - The classifier-skip threshold in `(0.5, 1.0]`.
+pub(crate) fn is_dominance_fixed(entry: &[(Upos, u32)], dominance: f64) -> bool {
+    let total: u32 = entry.iter().map(|(_, count)| count).sum();
+    entry.len() == 1 || f64::from(entry[0].1) / f64::from(total) >= dominance
+}
+
 /// Sorts a lexicon entry into the canonical order: count descending, ties
 /// by tag name ascending. Shared by the model loader/builder and the
 /// two-stage feature extractor.
@@ -593,13 +861,16 @@ fn in_section(section: &str, e: LitseaError) -> LitseaError {
 }
 
 /// Parses the `[params]` section lines and returns the dominance threshold
-/// (the default when the section carries no `dominance` line).
+/// (the default when the section carries no `dominance` line) and the
+/// metadata recorded by the other keys.
 ///
 /// # Errors
 /// Returns [`LitseaError::InvalidData`] on an unknown key, a duplicate
-/// `dominance` line, or a value that is unparsable or outside `(0.5, 1.0]`.
-fn parse_params(lines: &[String]) -> Result<f64> {
+/// key, an invalid metadata value, or a dominance value that is
+/// unparsable or outside `(0.5, 1.0]`.
+fn parse_params(lines: &[String]) -> Result<(f64, ModelMetadata)> {
     let mut dominance: Option<f64> = None;
+    let mut metadata = ModelMetadata::default();
     for line in lines {
         let Some((key, value)) = line.split_once('\t') else {
             return Err(LitseaError::InvalidData(format!(
@@ -629,15 +900,24 @@ fn parse_params(lines: &[String]) -> Result<f64> {
                 }
                 dominance = Some(v);
             }
-            _ => {
-                return Err(LitseaError::InvalidData(format!(
-                    "{} section: unknown parameter '{}'",
-                    SECTION_PARAMS, key
-                )));
-            }
+            _ => match metadata.set_header(key, value) {
+                Ok(true) => {}
+                Ok(false) => {
+                    return Err(LitseaError::InvalidData(format!(
+                        "{} section: unknown parameter '{}'",
+                        SECTION_PARAMS, key
+                    )));
+                }
+                Err(e) => {
+                    return Err(LitseaError::InvalidData(format!(
+                        "{} section: invalid '{}': {}",
+                        SECTION_PARAMS, key, e
+                    )));
+                }
+            },
         }
     }
-    Ok(dominance.unwrap_or(DEFAULT_DOMINANCE))
+    Ok((dominance.unwrap_or(DEFAULT_DOMINANCE), metadata))
 }
 
 /// Parses the `[lexicon]` section lines into the in-memory lexicon,
@@ -790,6 +1070,30 @@ impl FromStr for TwoStageFeatureSet {
 }
 
 impl TwoStageFeatureSet {
+    /// Returns the smallest feature set that includes the template of
+    /// every given stage-2 feature, i.e. the set the features were most
+    /// likely extracted with. Used to record the template set in a
+    /// trained model's metadata.
+    ///
+    /// # Arguments
+    /// * `features` - Stage-2 feature strings (`prefix:payload`); strings
+    ///   that match no word template are ignored.
+    pub(crate) fn detect<'a>(features: impl IntoIterator<Item = &'a str>) -> Self {
+        use crate::word_features::WORD_TEMPLATE_PREFIXES;
+
+        let mut used = [false; WORD_TEMPLATE_PREFIXES.len()];
+        for feature in features {
+            let prefix = feature.split_once(':').map_or(feature, |(prefix, _)| prefix);
+            if let Some(tid) = WORD_TEMPLATE_PREFIXES.iter().position(|p| *p == prefix) {
+                used[tid] = true;
+            }
+        }
+        [TwoStageFeatureSet::Fast, TwoStageFeatureSet::Balanced]
+            .into_iter()
+            .find(|set| (0..used.len()).all(|tid| !used[tid] || set.includes(tid)))
+            .unwrap_or(TwoStageFeatureSet::Full)
+    }
+
     /// Returns whether the word template with this id is part of the set.
     pub(crate) fn includes(self, template_id: usize) -> bool {
         use crate::word_features::{
@@ -902,6 +1206,73 @@ mod tests {
         assert_eq!(ModelKind::detect(STAGE2), ModelKind::AveragedPerceptron);
         assert_eq!(ModelKind::detect(STAGE1), ModelKind::AdaBoost);
         assert_eq!(ModelKind::detect(""), ModelKind::AdaBoost);
+        assert_eq!(ModelKind::detect("litsea-binary v1 adaboost\n"), ModelKind::AdaBoost);
+        assert_eq!(
+            ModelKind::detect("litsea-binary v1 perceptron\n"),
+            ModelKind::AveragedPerceptron
+        );
+        assert_eq!(ModelKind::detect("litsea-binary v1 two-stage\n"), ModelKind::TwoStage);
+        // Metadata header lines are skipped.
+        assert_eq!(
+            ModelKind::detect("#language\tkorean\n2\nB\nO\n"),
+            ModelKind::AveragedPerceptron
+        );
+        assert_eq!(ModelKind::detect("#normalization\tnfkc\nfeat\t0.5\n"), ModelKind::AdaBoost);
+        assert_eq!(
+            ModelKind::detect_bytes(b"#language\tkorean\n2\nB\nO\n\xff"),
+            ModelKind::AveragedPerceptron
+        );
+        assert_eq!(
+            ModelKind::detect_bytes(b"litsea-binary v1 two-stage\n\xff"),
+            ModelKind::TwoStage
+        );
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let learner = load(&valid_model()).unwrap();
+        let mut text = Vec::new();
+        learner.save_model_to_writer(&mut text).unwrap();
+
+        for language in [None, Some(Language::English)] {
+            let mut binary = Vec::new();
+            learner.save_binary_model_to_writer(&mut binary, language).unwrap();
+            let head =
+                std::str::from_utf8(&binary[..binary.iter().position(|&b| b == b'\n').unwrap()])
+                    .unwrap();
+            assert_eq!(head, "litsea-binary v1 two-stage");
+            assert_eq!(ModelKind::detect(head), ModelKind::TwoStage);
+
+            let mut loaded = TwoStageLearner::new();
+            loaded.load_model_from_reader(binary.as_slice()).unwrap();
+            assert_eq!(loaded.compiled.is_some(), language.is_some());
+            assert_eq!(
+                loaded.stage1.take_compiled(Language::English).is_some(),
+                language.is_some()
+            );
+            let mut back = Vec::new();
+            loaded.save_model_to_writer(&mut back).unwrap();
+            assert_eq!(back, text, "text -> binary -> text must be lossless");
+        }
+    }
+
+    #[test]
+    fn test_binary_load_validates_parts() {
+        let learner = load(&valid_model()).unwrap();
+        let mut binary = Vec::new();
+        learner.save_binary_model_to_writer(&mut binary, None).unwrap();
+        for end in 0..binary.len() {
+            let mut loaded = load(&valid_model()).unwrap();
+            assert!(loaded.load_model_from_reader(&binary[..end]).is_err());
+            // The learner is not modified on error.
+            assert_eq!(loaded.lexicon_len(), 2);
+        }
+        // The dominance threshold leads the payload; out of range is invalid
+        // data, not invalid input.
+        let start = b"litsea-binary v1 two-stage\n".len();
+        binary[start..start + 8].copy_from_slice(&0.25f64.to_le_bytes());
+        let err = TwoStageLearner::new().load_model_from_reader(binary.as_slice()).unwrap_err();
+        assert!(matches!(err, LitseaError::InvalidData(_)), "{err}");
     }
 
     #[test]
@@ -973,6 +1344,34 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_metadata_round_trip() {
+        let mut learner = load(&valid_model()).unwrap();
+        assert!(learner.metadata().is_empty());
+        let metadata = ModelMetadata::new()
+            .with_language(Language::English)
+            .with_templates(TwoStageFeatureSet::Fast.to_string())
+            .with_param("epochs", 5);
+        learner.set_metadata(metadata.clone());
+
+        let mut text = Vec::new();
+        learner.save_model_to_writer(&mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert!(text.starts_with(
+            "litsea-two-stage v1\n[params]\ndominance\t0.9\nlanguage\tenglish\n\
+             templates\tfast\nparam.epochs\t5\n[stage1]\n"
+        ));
+        let mut binary = Vec::new();
+        learner.save_binary_model_to_writer(&mut binary, None).unwrap();
+
+        for bytes in [text.as_bytes(), binary.as_slice()] {
+            let mut loaded = TwoStageLearner::new();
+            loaded.load_model_from_reader(bytes).unwrap();
+            assert_eq!(loaded.metadata(), &metadata);
+            assert_eq!(loaded.dominance(), 0.9);
+        }
+    }
+
     #[test]
     fn test_load_rejects_bad_params() {
         for (params, expect) in [
@@ -981,6 +1380,8 @@ mod tests {
             ("dominance\tabc", "invalid dominance"),
             ("dominance\t0.9\ndominance\t0.9", "duplicate"),
             ("verbosity\thigh", "unknown parameter"),
+            ("language\tklingon", "invalid 'language'"),
+            ("language\tja\nlanguage\tja", "duplicate"),
             ("dominance 0.9", "expected 'key"),
         ] {
             let text = model_text(Some(params), STAGE1, "run\tVERB:1", STAGE2);
@@ -1235,6 +1636,18 @@ mod tests {
         assert!(TwoStageFeatureSet::Full.includes(T_LB));
     }
 
+    #[test]
+    fn test_feature_set_detect_picks_the_smallest_set() {
+        assert_eq!(
+            TwoStageFeatureSet::detect(["WS:run", "WL:3", "P2:ru"]),
+            TwoStageFeatureSet::Fast
+        );
+        assert_eq!(TwoStageFeatureSet::detect(["WS:run", "FC:r"]), TwoStageFeatureSet::Balanced);
+        assert_eq!(TwoStageFeatureSet::detect(["WS:run", "L2:the"]), TwoStageFeatureSet::Full);
+        // Strings outside the word templates are ignored.
+        assert_eq!(TwoStageFeatureSet::detect(["UW4:x", "WS"]), TwoStageFeatureSet::Fast);
+    }
+
     #[test]
     fn test_load_rejects_joint_perceptron_model() {
         // A standalone Averaged Perceptron file (bare class-count first
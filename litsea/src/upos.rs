@@ -71,6 +71,18 @@ impl Upos {
         Upos::VERB,
         Upos::X,
     ];
+
+    /// Returns the position of this tag in [`Upos::ALL`], which is how
+    /// binary model files store it.
+    pub(crate) fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the tag at `index` in [`Upos::ALL`], or `None` if the index
+    /// is out of range.
+    pub(crate) fn from_index(index: u8) -> Option<Upos> {
+        Upos::ALL.get(usize::from(index)).copied()
+    }
 }
 
 impl fmt::Display for Upos {
@@ -228,6 +240,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_upos_index_matches_all_order() {
+        for (i, &pos) in Upos::ALL.iter().enumerate() {
+            assert_eq!(usize::from(pos.index()), i);
+            assert_eq!(Upos::from_index(pos.index()), Some(pos));
+        }
+        assert_eq!(Upos::from_index(Upos::ALL.len() as u8), None);
+    }
+
     #[test]
     fn test_parse_upos_error_message() {
         // #128: message identical to the former String error.
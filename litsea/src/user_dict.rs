@@ -0,0 +1,334 @@
+//! User dictionary constraints for segmentation.
+//!
+//! Defines [`UserDictionary`], a word list that overrides the packed
+//! model's boundary decisions for matched spans (see
+//! [`Segmenter::with_user_dictionary`](crate::segmenter::Segmenter::with_user_dictionary)):
+//! every matched word comes out as a single token, with a boundary forced
+//! at both ends and every boundary inside it forbidden. This is the lever
+//! for product names and domain terms the model was never trained on,
+//! without retraining or editing weights.
+//!
+//! Matching is leftmost-longest over the sentence's characters: scanning
+//! left to right, the longest dictionary word starting at the current
+//! character wins, and scanning resumes after it. Matches therefore never
+//! overlap, so the constraints they produce never conflict.
+//!
+//! The same word list can instead be stored in a model as the source of
+//! its dictionary-match features (see
+//! [`AdaBoost::set_dictionary`](crate::adaboost::AdaBoost::set_dictionary)),
+//! where every occurrence of every word counts, overlapping or not.
+
+use std::io::BufRead;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+
+use crate::error::Result;
+
+/// A set of words that segmentation must keep whole.
+///
+/// Stored as a character trie keyed by code point, so matching walks the
+/// same `u32` char codes the packed scorer already holds in its
+/// [`SegmentBuffer`](crate::segmenter::SegmentBuffer) and never touches
+/// the sentence's bytes.
+#[derive(Debug, Clone)]
+pub struct UserDictionary {
+    /// Trie edges: `(node, char code) -> child node`. Node 0 is the root.
+    edges: FxHashMap<(u32, u32), u32>,
+    /// Whether each node ends a dictionary word, indexed by node id.
+    terminal: Vec<bool>,
+    /// Number of distinct words inserted.
+    len: usize,
+}
+
+impl Default for UserDictionary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UserDictionary {
+    /// Creates an empty dictionary.
+    ///
+    /// # Returns
+    /// A [`UserDictionary`] with no words; it matches nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        UserDictionary {
+            edges: FxHashMap::default(),
+            terminal: vec![false],
+            len: 0,
+        }
+    }
+
+    /// Adds a word to the dictionary.
+    ///
+    /// Empty words are ignored, and adding a word twice is a no-op.
+    ///
+    /// # Arguments
+    /// * `word` - The word to keep whole.
+    pub fn insert(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+        let mut node = 0u32;
+        for ch in word.chars() {
+            let next = self.terminal.len() as u32;
+            let child = *self.edges.entry((node, u32::from(ch))).or_insert(next);
+            if child == next {
+                self.terminal.push(false);
+            }
+            node = child;
+        }
+        if !self.terminal[node as usize] {
+            self.terminal[node as usize] = true;
+            self.len += 1;
+        }
+    }
+
+    /// Returns the number of distinct words in the dictionary.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true when the dictionary holds no words.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads a dictionary from a reader: one word per line.
+    ///
+    /// Surrounding whitespace is trimmed and blank lines are skipped; a
+    /// word may still contain inner spaces (e.g. `New York` for English).
+    ///
+    /// # Arguments
+    /// * `reader` - The buffered reader supplying the word list.
+    ///
+    /// # Returns
+    /// The loaded [`UserDictionary`].
+    ///
+    /// # Errors
+    /// Returns an I/O error if reading fails (including invalid UTF-8).
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut dictionary = Self::new();
+        for line in reader.lines() {
+            dictionary.insert(line?.trim());
+        }
+        Ok(dictionary)
+    }
+
+    /// Reads a dictionary file: one word per line (see
+    /// [`from_reader`](Self::from_reader)).
+    ///
+    /// # Arguments
+    /// * `path` - The path to the word list.
+    ///
+    /// # Returns
+    /// The loaded [`UserDictionary`].
+    ///
+    /// # Errors
+    /// Returns an I/O error if the file cannot be opened or read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Returns the dictionary's words in sorted order.
+    ///
+    /// # Returns
+    /// Every distinct word inserted, sorted by code point.
+    #[must_use]
+    pub fn words(&self) -> Vec<String> {
+        let mut children: Vec<Vec<(u32, u32)>> = vec![Vec::new(); self.terminal.len()];
+        for (&(node, code), &child) in &self.edges {
+            children[node as usize].push((code, child));
+        }
+        let mut words = Vec::with_capacity(self.len);
+        let mut word = Vec::new();
+        self.collect_words(0, &mut children, &mut word, &mut words);
+        words
+    }
+
+    /// Depth-first walk behind [`words`](Self::words): visits the children
+    /// of `node` in code-point order, so the words come out sorted.
+    fn collect_words(
+        &self,
+        node: u32,
+        children: &mut [Vec<(u32, u32)>],
+        word: &mut Vec<char>,
+        words: &mut Vec<String>,
+    ) {
+        if self.terminal[node as usize] {
+            words.push(word.iter().collect());
+        }
+        let mut edges = std::mem::take(&mut children[node as usize]);
+        edges.sort_unstable();
+        for (code, child) in edges {
+            // Every code was inserted from a `char`.
+            word.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            self.collect_words(child, children, word, words);
+            word.pop();
+        }
+    }
+
+    /// Calls `sink(len)` with the length, in characters, of every
+    /// dictionary word that starts at `codes[0]`, shortest first.
+    ///
+    /// # Arguments
+    /// * `codes` - The characters from the match start on, as code points.
+    /// * `sink` - Receives each matched word's length.
+    pub(crate) fn for_each_prefix(&self, codes: &[u32], mut sink: impl FnMut(usize)) {
+        let mut node = 0u32;
+        for (k, &code) in codes.iter().enumerate() {
+            let Some(&next) = self.edges.get(&(node, code)) else {
+                return;
+            };
+            node = next;
+            if self.terminal[node as usize] {
+                sink(k + 1);
+            }
+        }
+    }
+
+    /// Returns the length, in characters, of the longest dictionary word
+    /// that starts at `codes[0]`, or 0 when none does.
+    fn longest_prefix(&self, codes: &[u32]) -> usize {
+        let mut node = 0u32;
+        let mut longest = 0;
+        for (k, &code) in codes.iter().enumerate() {
+            let Some(&next) = self.edges.get(&(node, code)) else {
+                break;
+            };
+            node = next;
+            if self.terminal[node as usize] {
+                longest = k + 1;
+            }
+        }
+        longest
+    }
+
+    /// Calls `sink(start, end)` for every leftmost-longest match in
+    /// `codes`, as half-open character-index ranges, in order.
+    ///
+    /// # Arguments
+    /// * `codes` - The sentence's characters as code points.
+    /// * `sink` - Receives each matched `(start, end)` range.
+    pub(crate) fn for_each_match(&self, codes: &[u32], mut sink: impl FnMut(usize, usize)) {
+        if self.is_empty() {
+            return;
+        }
+        let mut start = 0;
+        while start < codes.len() {
+            let len = self.longest_prefix(&codes[start..]);
+            if len == 0 {
+                start += 1;
+            } else {
+                sink(start, start + len);
+                start += len;
+            }
+        }
+    }
+
+    /// Returns the leftmost-longest matches in `text` as byte ranges.
+    ///
+    /// # Arguments
+    /// * `text` - The text to scan.
+    ///
+    /// # Returns
+    /// The non-overlapping matched `(start, end)` byte ranges, in order.
+    #[must_use]
+    pub fn find_matches(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut starts: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        starts.push(text.len());
+        let codes: Vec<u32> = text.chars().map(u32::from).collect();
+        let mut matches = Vec::new();
+        self.for_each_match(&codes, |s, e| matches.push((starts[s], starts[e])));
+        matches
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for UserDictionary {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut dictionary = Self::new();
+        for word in iter {
+            dictionary.insert(word.as_ref());
+        }
+        dictionary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_len() {
+        let mut dictionary = UserDictionary::new();
+        assert!(dictionary.is_empty());
+        dictionary.insert("東京");
+        dictionary.insert("東京都");
+        dictionary.insert("東京");
+        dictionary.insert("");
+        assert_eq!(dictionary.len(), 2);
+    }
+
+    #[test]
+    fn test_find_matches_is_leftmost_longest() {
+        let dictionary: UserDictionary = ["東京", "東京都", "京都", "都庁"].into_iter().collect();
+        // "東京都" wins over "東京" at position 0, and scanning resumes
+        // after it, so the overlapping "京都" and "都庁" never match.
+        let text = "東京都庁";
+        let matches = dictionary.find_matches(text);
+        assert_eq!(matches, vec![(0, 9)]);
+        assert_eq!(&text[matches[0].0..matches[0].1], "東京都");
+    }
+
+    #[test]
+    fn test_find_matches_skips_unmatched_characters() {
+        let dictionary: UserDictionary = ["Litsea", "Rust"].into_iter().collect();
+        let text = "LitseaはRustで書かれた";
+        let words: Vec<&str> =
+            dictionary.find_matches(text).into_iter().map(|(s, e)| &text[s..e]).collect();
+        assert_eq!(words, vec!["Litsea", "Rust"]);
+    }
+
+    #[test]
+    fn test_find_matches_backtracks_to_shorter_word() {
+        // "abcd" is a trie path but not a word; "ab" must still match.
+        let dictionary: UserDictionary = ["ab", "abcde"].into_iter().collect();
+        assert_eq!(dictionary.find_matches("abcdx"), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_from_reader_trims_and_skips_blank_lines() {
+        let dictionary =
+            UserDictionary::from_reader("  東京都 \n\nNew York\r\n".as_bytes()).unwrap();
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary.find_matches("New York"), vec![(0, 8)]);
+    }
+
+    #[test]
+    fn test_words_come_out_sorted() {
+        let dictionary: UserDictionary = ["東京都", "京都", "東京", "ab"].into_iter().collect();
+        assert_eq!(dictionary.words(), vec!["ab", "京都", "東京", "東京都"]);
+        assert!(UserDictionary::new().words().is_empty());
+    }
+
+    #[test]
+    fn test_for_each_prefix_reports_every_word() {
+        let dictionary: UserDictionary = ["東", "東京", "東京都庁", "京都"].into_iter().collect();
+        let codes: Vec<u32> = "東京都庁舎".chars().map(u32::from).collect();
+        let mut lens = Vec::new();
+        dictionary.for_each_prefix(&codes, |len| lens.push(len));
+        assert_eq!(lens, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_empty_dictionary_matches_nothing() {
+        assert!(UserDictionary::new().find_matches("テスト").is_empty());
+    }
+}
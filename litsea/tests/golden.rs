@@ -15,6 +15,7 @@ use litsea::language::Language;
 use litsea::segmenter::Segmenter;
 use litsea::two_stage::TwoStageLearner;
 use litsea::upos::Upos;
+use unicode_segmentation::UnicodeSegmentation;
 
 fn model_path(name: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../models").join(name)
@@ -420,6 +421,72 @@ fn golden_segment_with_pos_english_two_stage() {
     assert!(segmenter.segment_with_pos("").expect("two-stage learner is set").is_empty());
 }
 
+// ---------------------------------------------------------------------------
+// Grapheme clusters: no bundled model may cut a user-perceived character.
+// Chat-style text mixing emoji ZWJ sequences, flags, variation selectors,
+// skin-tone modifiers, combining marks, and conjoining Hangul jamo.
+// ---------------------------------------------------------------------------
+
+const GRAPHEME_CORPUS: &[&str] = &[
+    "今日は\u{1f469}\u{200d}\u{1f4bb}で在宅勤務\u{1f3e0}です。",
+    "家族\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}\u{200d}\u{1f466}で旅行\u{2708}\u{fe0f}に行きました！",
+    "日本\u{1f1ef}\u{1f1f5}対韓国\u{1f1f0}\u{1f1f7}の試合\u{26bd}",
+    "いいね\u{1f44d}\u{1f3fd}\u{1f44d}\u{1f3ff}ありがとう\u{2764}\u{fe0f}",
+    "葛\u{e0100}飾区と辻\u{fe00}堂",
+    "か\u{3099}っこう",
+    "\u{1112}\u{1161}\u{11ab}\u{1100}\u{1173}\u{11af} 입니다 \u{1f600}",
+    "I \u{2764}\u{fe0f} caf\u{e9} and cafe\u{301} \u{1f3f3}\u{fe0f}\u{200d}\u{1f308}!",
+    "欢迎\u{1f1e8}\u{1f1f3}朋友\u{1f91d}\u{1f3fb}来玩",
+];
+
+/// Asserts that the tokens cover `sentence` and start only at grapheme
+/// cluster boundaries.
+fn assert_clusters_whole<S: AsRef<str>>(sentence: &str, tokens: &[S], model: &str) {
+    let mut offset = 0;
+    let starts: Vec<usize> = sentence.grapheme_indices(true).map(|(i, _)| i).collect();
+    for token in tokens {
+        assert!(
+            starts.contains(&offset),
+            "{model} split a grapheme cluster at byte {offset} of {sentence:?}: {:?}",
+            tokens.iter().map(AsRef::as_ref).collect::<Vec<_>>()
+        );
+        offset += token.as_ref().len();
+    }
+    assert_eq!(offset, sentence.len(), "{model} tokens do not cover {sentence:?}");
+}
+
+#[test]
+fn golden_grapheme_clusters_stay_whole() {
+    let models = [
+        (Language::Japanese, "japanese.model"),
+        (Language::Japanese, "RWCP.model"),
+        (Language::Japanese, "JEITA_Genpaku_ChaSen_IPAdic.model"),
+        (Language::Chinese, "chinese.model"),
+        (Language::Korean, "korean.model"),
+        (Language::English, "english.model"),
+    ];
+    for (language, model) in models {
+        // The lowest threshold proposes a boundary before every char, so
+        // only the cluster constraints keep the output whole.
+        for threshold in [0.0, f64::NEG_INFINITY] {
+            let segmenter = adaboost_segmenter(language, model).with_threshold(threshold);
+            for sentence in GRAPHEME_CORPUS {
+                assert_clusters_whole(sentence, &segmenter.segment(sentence), model);
+            }
+        }
+    }
+    for (language, model) in
+        [(Language::Korean, "korean_pos.model"), (Language::English, "english_pos.model")]
+    {
+        let segmenter = two_stage_segmenter(language, model).with_threshold(f64::NEG_INFINITY);
+        for sentence in GRAPHEME_CORPUS {
+            let tagged = segmenter.segment_with_pos(sentence).expect("two-stage learner is set");
+            let words: Vec<String> = tagged.into_iter().map(|(word, _)| word).collect();
+            assert_clusters_whole(sentence, &words, model);
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Model file round-trip: load -> save -> load must preserve predictions.
 // Guards the on-disk model format compatibility across refactoring.
@@ -474,3 +541,88 @@ fn roundtrip_two_stage_model() {
         );
     }
 }
+
+// ---------------------------------------------------------------------------
+// Binary model form: text -> binary must preserve predictions, whether the
+// segmenter adopts the stored packed tables or compiles its own, and
+// binary -> text must reproduce the text form exactly.
+// ---------------------------------------------------------------------------
+
+const BINARY_CORPUS: &[&str] = &[
+    "これはテストです。",
+    "価格は1000円です。",
+    "我们今天去北京。",
+    "안녕하세요. 반갑습니다.",
+    "The quick brown fox jumps over the lazy dog.",
+];
+
+#[test]
+fn roundtrip_binary_adaboost_models() {
+    let models = [
+        (Language::Japanese, "japanese.model"),
+        (Language::Japanese, "RWCP.model"),
+        (Language::Japanese, "JEITA_Genpaku_ChaSen_IPAdic.model"),
+        (Language::Chinese, "chinese.model"),
+        (Language::Korean, "korean.model"),
+        (Language::English, "english.model"),
+    ];
+    for (language, model) in models {
+        let mut original = AdaBoost::new(0.01, 100);
+        original.load_model_from_path(&model_path(model)).unwrap();
+        let mut text = Vec::new();
+        original.save_model_to_writer(&mut text).unwrap();
+        let seg_original = Segmenter::with_learner(language, original);
+
+        for compiled_for in [Some(language), None] {
+            let mut binary = Vec::new();
+            seg_original
+                .learner()
+                .save_binary_model_to_writer(&mut binary, compiled_for)
+                .unwrap();
+            let mut reloaded = AdaBoost::new(0.01, 100);
+            reloaded.load_model_from_reader(binary.as_slice()).unwrap();
+            let mut back = Vec::new();
+            reloaded.save_model_to_writer(&mut back).unwrap();
+            assert!(back == text, "{model}: binary -> text diverged from the text form");
+
+            let seg_reloaded = Segmenter::with_learner(language, reloaded);
+            for s in BINARY_CORPUS {
+                assert_eq!(
+                    seg_original.segment_with_scores(s),
+                    seg_reloaded.segment_with_scores(s),
+                    "{model} ({compiled_for:?}) diverged on {s:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn roundtrip_binary_two_stage_models() {
+    for (language, model) in
+        [(Language::Korean, "korean_pos.model"), (Language::English, "english_pos.model")]
+    {
+        let mut original = TwoStageLearner::new();
+        original.load_model_from_path(&model_path(model)).unwrap();
+        let mut text = Vec::new();
+        original.save_model_to_writer(&mut text).unwrap();
+        let mut binary = Vec::new();
+        original.save_binary_model_to_writer(&mut binary, Some(language)).unwrap();
+
+        let mut reloaded = TwoStageLearner::new();
+        reloaded.load_model_from_reader(binary.as_slice()).unwrap();
+        let mut back = Vec::new();
+        reloaded.save_model_to_writer(&mut back).unwrap();
+        assert!(back == text, "{model}: binary -> text diverged from the text form");
+
+        let seg_original = Segmenter::with_two_stage_learner(language, original);
+        let seg_reloaded = Segmenter::with_two_stage_learner(language, reloaded);
+        for s in BINARY_CORPUS {
+            assert_eq!(
+                seg_original.segment_with_pos(s).expect("two-stage learner is set"),
+                seg_reloaded.segment_with_pos(s).expect("two-stage learner is set"),
+                "{model} diverged on {s:?}"
+            );
+        }
+    }
+}